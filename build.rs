@@ -17,5 +17,56 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             &["proto"],
         )?;
 
+    emit_build_metadata();
+
     Ok(())
 }
+
+/// Expose git commit and build time to the crate for GET /version
+///
+/// `TEI_MANAGER_GIT_SHA` and `SOURCE_DATE_EPOCH` take precedence so builds
+/// outside a git checkout (e.g. Docker) can still report them.
+fn emit_build_metadata() {
+    use std::path::Path;
+    use std::process::Command;
+
+    println!("cargo:rerun-if-env-changed=TEI_MANAGER_GIT_SHA");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+
+    // Pick up new commits and checkouts; skipped outside a git checkout, where a
+    // missing path would make cargo rerun this script on every build
+    let head = Path::new(".git/HEAD");
+    if head.exists() {
+        println!("cargo:rerun-if-changed=.git/HEAD");
+        if let Some(branch) = std::fs::read_to_string(head)
+            .ok()
+            .and_then(|head| head.strip_prefix("ref: ").map(|r| r.trim().to_string()))
+        {
+            let branch_ref = Path::new(".git").join(branch);
+            if branch_ref.exists() {
+                println!("cargo:rerun-if-changed={}", branch_ref.display());
+            }
+        }
+    }
+
+    let git_sha = std::env::var("TEI_MANAGER_GIT_SHA").ok().or_else(|| {
+        Command::new("git")
+            .args(["rev-parse", "--short=12", "HEAD"])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    });
+    println!(
+        "cargo:rustc-env=TEI_MANAGER_GIT_SHA={}",
+        git_sha.as_deref().unwrap_or("unknown")
+    );
+
+    let build_epoch = std::env::var("SOURCE_DATE_EPOCH").ok().unwrap_or_else(|| {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs().to_string())
+            .unwrap_or_default()
+    });
+    println!("cargo:rustc-env=TEI_MANAGER_BUILD_EPOCH={}", build_epoch);
+}
@@ -1,17 +1,46 @@
 //! API request handlers
 
 use super::models::{
-    AddModelRequest, CreateInstanceRequest, HealthResponse, InstanceInfo, LogsResponse, ModelInfo,
+    AddModelRequest, ClearLogsResponse, CreateInstanceRequest, HealthCheckReport, HealthResponse,
+    HealthSummary, InstanceCommand, InstanceDebugInfo, InstanceDescription, InstanceHealth,
+    InstanceInfo, InstancesExport, InstancesHealthResponse, JsonlEmbedLine, JsonlEmbedResult,
+    LogsResponse, MaintenanceRequest, MaintenanceResponse, ModelInfo, OpenAiEmbedding,
+    OpenAiEmbeddingRequest, OpenAiEmbeddingResponse, OpenAiEmbeddingVector, OpenAiUsage,
+    PreloadModelsRequest, RegistryDump, VersionResponse, WsEmbedRequest, WsEmbedResponse,
 };
 use super::routes::AppState;
+use crate::audit::{AuditAction, AuditEntry};
+use crate::auth::Principal;
 use crate::config::InstanceConfig;
+use crate::config::ManagerConfig;
 use crate::error::TeiError;
+use crate::grpc::forward::{ForwardHeaders, backend_request};
+use crate::grpc::pool::BackendClients;
+use crate::instance::TeiInstance;
+use crate::models::preload::PreloadModelStatus;
+use crate::models::{ModelAvailability, PreloadJob};
+use crate::registry::{PortUsage, RoutingTable};
 use axum::{
-    Json,
-    extract::{Path, Query, State},
-    http::StatusCode,
+    Extension, Json, RequestExt,
+    body::{Body, Bytes, HttpBody},
+    extract::{
+        Path, Query, Request, State, WebSocketUpgrade,
+        ws::{CloseFrame, Message, WebSocket, close_code},
+    },
+    http::{HeaderMap, StatusCode, header},
+    response::{
+        IntoResponse, Response,
+        sse::{Event, KeepAlive, Sse},
+    },
 };
+use futures::{Stream, StreamExt};
 use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tonic::metadata::MetadataMap;
+
+/// How long `model_hub_check` waits for the Hub before starting anyway
+const HUB_CHECK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
 
 /// GET /health - Manager health check
 pub async fn health() -> (StatusCode, Json<HealthResponse>) {
@@ -24,11 +53,116 @@ pub async fn health() -> (StatusCode, Json<HealthResponse>) {
     )
 }
 
+/// GET /health/instances - Health of every instance plus a summary by status
+pub async fn instances_health(State(state): State<AppState>) -> Json<InstancesHealthResponse> {
+    let instances = state.registry.list().await;
+
+    let mut health: Vec<InstanceHealth> =
+        futures::future::join_all(instances.iter().map(|i| InstanceHealth::from_instance(i))).await;
+    health.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Json(InstancesHealthResponse {
+        summary: HealthSummary::from_instances(&health),
+        instances: health,
+    })
+}
+
+/// GET /version - Build metadata and detected GPUs
+pub async fn version() -> Json<VersionResponse> {
+    Json(VersionResponse::current())
+}
+
 /// GET /metrics - Prometheus metrics
 pub async fn metrics(State(state): State<AppState>) -> String {
     state.prometheus_handle.render()
 }
 
+/// GET /config - Effective configuration with secrets redacted
+pub async fn get_config(State(state): State<AppState>) -> Json<ManagerConfig> {
+    Json(state.config.redacted())
+}
+
+#[derive(Deserialize)]
+pub struct EventsQuery {
+    /// Recent events to send before live ones (capped by `event_history_size`)
+    #[serde(default)]
+    pub replay: usize,
+}
+
+/// GET /events - Instance lifecycle events as server-sent events
+///
+/// Each event is one JSON [`InstanceEvent`](crate::registry::InstanceEvent). With
+/// `?replay=N`, up to N recent events are sent first; events a slow client falls
+/// behind on are skipped.
+pub async fn events(
+    State(state): State<AppState>,
+    Query(params): Query<EventsQuery>,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    let (replayed, live) = state.registry.subscribe_events_with_replay(params.replay);
+    let live = tokio_stream::wrappers::BroadcastStream::new(live)
+        .filter_map(|event| async move { event.ok() });
+    let events = futures::stream::iter(replayed)
+        .chain(live)
+        .map(|event| Event::default().json_data(event));
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+/// GET /debug/access-log/stream - Live gRPC access log as server-sent events (only with debug_endpoints)
+///
+/// Each event is one JSON [`AccessLogEntry`](crate::grpc::access_log::AccessLogEntry);
+/// entries a slow client falls behind on are skipped.
+pub async fn debug_access_log_stream(
+    State(state): State<AppState>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, TeiError> {
+    let access_log = state
+        .access_log
+        .as_ref()
+        .ok_or_else(|| TeiError::InvalidConfig {
+            message: "gRPC access log is not enabled".to_string(),
+        })?;
+    let entries = tokio_stream::wrappers::BroadcastStream::new(access_log.subscribe())
+        .filter_map(|entry| async move { entry.ok() })
+        .map(|entry| Event::default().json_data(entry));
+    Ok(Sse::new(entries).keep_alive(KeepAlive::default()))
+}
+
+/// GET /ports - Ports held by instances and free ports left in the allocation range
+pub async fn ports(State(state): State<AppState>) -> Json<PortUsage> {
+    Json(state.registry.port_usage().await)
+}
+
+/// GET /routing - Instances reachable by index and by model id, with their share of model traffic
+pub async fn routing(State(state): State<AppState>) -> Json<RoutingTable> {
+    Json(
+        state
+            .registry
+            .routing_table(state.config.model_routing_strategy)
+            .await,
+    )
+}
+
+/// GET /debug/registry - Registry and port allocator internals (only with debug_endpoints)
+pub async fn debug_registry(State(state): State<AppState>) -> Json<RegistryDump> {
+    let allocator = state.registry.allocator_state().await;
+
+    let mut instances = state.registry.list().await;
+    instances.sort_by(|a, b| a.config.name.cmp(&b.config.name));
+    let instances = futures::future::join_all(
+        instances
+            .iter()
+            .map(|i| InstanceDebugInfo::from_instance(i)),
+    )
+    .await;
+
+    Json(RegistryDump {
+        generated_at: chrono::Utc::now(),
+        maintenance: state.registry.maintenance_enabled(),
+        max_instances: state.config.max_instances,
+        allocator,
+        instances,
+    })
+}
+
 /// GET /instances - List all instances
 pub async fn list_instances(
     State(state): State<AppState>,
@@ -47,19 +181,46 @@ pub async fn list_instances(
 /// POST /instances - Create and start a new instance
 pub async fn create_instance(
     State(state): State<AppState>,
+    principal: Option<Extension<Principal>>,
     Json(req): Json<CreateInstanceRequest>,
 ) -> Result<(StatusCode, Json<InstanceInfo>), TeiError> {
-    // Validate gpu_id if provided
-    if let Some(gpu_id) = req.gpu_id {
-        let gpu_info = crate::gpu::get_or_init();
-        if !gpu_info.is_valid_gpu_id(gpu_id) {
-            return Err(TeiError::InvalidGpuId {
-                id: gpu_id,
-                reason: format!("Available GPUs: {:?}", gpu_info.indices),
+    check_model_allowed(&state, &req.model_id)?;
+
+    if req.cpu_only && req.gpu_id.is_some() {
+        return Err(TeiError::ValidationError {
+            message: "cpu_only cannot be combined with gpu_id".to_string(),
+        });
+    }
+
+    if let Some(dep) = req.depends_on.iter().find(|d| **d == req.name) {
+        return Err(TeiError::ValidationError {
+            message: format!("Instance '{}' cannot depend on itself", dep),
+        });
+    }
+    for dep in &req.depends_on {
+        if state.registry.get(dep).await.is_none() {
+            return Err(TeiError::ValidationError {
+                message: format!("depends_on references unknown instance '{}'", dep),
             });
         }
     }
 
+    // Validate gpu_id if provided
+    if let Some(gpu_id) = req.gpu_id {
+        state
+            .registry
+            .gpu_info()
+            .check_gpu_id(gpu_id, state.config.allow_gpu_without_smi)?;
+    }
+
+    // Downloads go to the manager's cache, which this instance would not read
+    if req.download_if_missing && req.hf_home.is_some() {
+        return Err(TeiError::ValidationError {
+            message: "download_if_missing cannot be combined with hf_home".to_string(),
+        });
+    }
+    let download_if_missing = req.download_if_missing;
+
     let config = InstanceConfig {
         name: req.name,
         model_id: req.model_id.clone(),
@@ -67,50 +228,74 @@ pub async fn create_instance(
         max_batch_tokens: req.max_batch_tokens.unwrap_or(16384),
         max_concurrent_requests: req.max_concurrent_requests.unwrap_or(512),
         pooling: req.pooling,
+        dtype: req.dtype,
+        revision: req.revision,
         gpu_id: req.gpu_id,
+        cpu_only: req.cpu_only,
+        gpu_memory_fraction: req.gpu_memory_fraction,
+        gpu_memory_bytes: req.gpu_memory_bytes,
         prometheus_port: req.prometheus_port,
         startup_timeout_secs: req.startup_timeout_secs,
+        grpc_max_message_size_mb: req.grpc_max_message_size_mb,
+        pre_stop_command: req.pre_stop_command,
+        log_level: req.log_level,
         extra_args: req.extra_args.unwrap_or_default(),
+        env: req.env.unwrap_or_default(),
+        env_file: req.env_file,
+        hf_home: req.hf_home,
+        depends_on: req.depends_on,
+        metric_labels: req.metric_labels,
         created_at: Some(chrono::Utc::now()),
     };
 
-    let instance = state
+    // An earlier instance may still name this one in its depends_on
+    if !config.depends_on.is_empty() {
+        let mut configs: Vec<InstanceConfig> = state
+            .registry
+            .list()
+            .await
+            .iter()
+            .map(|i| i.config.clone())
+            .collect();
+        configs.push(config.clone());
+        crate::config::dependency_order(&configs).map_err(|e| TeiError::ValidationError {
+            message: e.to_string(),
+        })?;
+    }
+
+    // Fail on a taken name or port before a potentially long download
+    state
         .registry
-        .add(config)
+        .check_admission(&config)
         .await
         .map_err(|e| TeiError::ValidationError {
             message: e.to_string(),
         })?;
 
+    if download_if_missing {
+        ensure_model_cached(&state, &config.model_id, config.revision.as_deref()).await?;
+    } else {
+        let cache_dir = crate::models::cache_dir_for(config.hf_home.as_deref());
+        check_model_exists(&state, &cache_dir, &config.model_id).await?;
+    }
+
+    // The registry also refuses a GPU that instances already fill
+    let instance = state.registry.add(config).await.map_err(|e| {
+        match e.downcast::<crate::gpu::GpuError>() {
+            Ok(gpu) => TeiError::from(gpu),
+            Err(e) => TeiError::ValidationError {
+                message: e.to_string(),
+            },
+        }
+    })?;
+
     instance
         .start(state.registry.tei_binary_path())
         .await
-        .map_err(|e| TeiError::Internal {
-            message: e.to_string(),
-        })?;
-
-    // Wait for instance to be ready (poll every 500ms, timeout after 5 minutes)
-    // This runs in background so API returns immediately with "starting" status
-    let instance_clone = instance.clone();
-    tokio::spawn(async move {
-        use crate::health::GrpcHealthChecker;
-        use std::time::Duration;
+        .map_err(start_error)?;
 
-        if let Err(e) = GrpcHealthChecker::wait_for_ready(
-            &instance_clone,
-            Duration::from_secs(300), // 5 minute timeout for model download
-            Duration::from_millis(500),
-        )
-        .await
-        {
-            tracing::error!(
-                instance = %instance_clone.config.name,
-                error = %e,
-                "Instance failed to become ready"
-            );
-            *instance_clone.status.write().await = crate::instance::InstanceStatus::Failed;
-        }
-    });
+    // Readiness is awaited in the background, so the API returns with "starting"
+    watch_readiness(&state, instance.clone());
 
     // Save state asynchronously
     let state_manager = state.state_manager.clone();
@@ -120,6 +305,14 @@ pub async fn create_instance(
         }
     });
 
+    audit(
+        &state,
+        AuditAction::Create,
+        &instance.config.name,
+        principal,
+    )
+    .await;
+
     // Record metrics
     crate::metrics::record_instance_created(&instance.config.name, &req.model_id);
     crate::metrics::update_instance_count(state.registry.count().await);
@@ -129,6 +322,378 @@ pub async fn create_instance(
     Ok((StatusCode::CREATED, Json(info)))
 }
 
+/// Reject a model outside the manager's `allowed_models`
+fn check_model_allowed(state: &AppState, model_id: &str) -> Result<(), TeiError> {
+    if state.config.model_allowed(model_id) {
+        return Ok(());
+    }
+    Err(TeiError::Forbidden {
+        reason: format!("Model '{}' is not in allowed_models", model_id),
+    })
+}
+
+/// Download `model_id` (at `revision`, if given) into the cache unless it is already there
+///
+/// Runs as a tracked preload job, so a large download shows up under
+/// GET /models/preload/{job_id} while the create request waits.
+async fn ensure_model_cached(
+    state: &AppState,
+    model_id: &str,
+    revision: Option<&str>,
+) -> Result<(), TeiError> {
+    let job = state
+        .preload_tracker
+        .run(vec![model_id.to_string()], revision.map(str::to_string))
+        .await;
+
+    match job.models.into_iter().next() {
+        Some(progress) if progress.status == PreloadModelStatus::Failed => {
+            Err(TeiError::ModelDownloadFailed {
+                model_id: model_id.to_string(),
+                reason: progress.error.unwrap_or_default(),
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Fail fast on a model that is neither cached nor on the Hub
+///
+/// TEI would otherwise only give up once the startup timeout runs out. A no-op
+/// unless `model_hub_check` is enabled; an unreachable Hub lets the start go ahead.
+async fn check_model_exists(
+    state: &AppState,
+    cache_dir: &std::path::Path,
+    model_id: &str,
+) -> Result<(), TeiError> {
+    if !state.config.model_hub_check {
+        return Ok(());
+    }
+
+    match crate::models::check_model_availability(
+        cache_dir,
+        model_id,
+        state.config.model_hub_endpoint.as_deref(),
+        HUB_CHECK_TIMEOUT,
+    )
+    .await
+    {
+        ModelAvailability::Cached => Ok(()),
+        ModelAvailability::OnHub => {
+            tracing::info!(
+                model_id,
+                "Model is not cached; TEI will download it from the Hub on start"
+            );
+            Ok(())
+        }
+        ModelAvailability::NotFound => Err(TeiError::ModelNotFound {
+            model_id: model_id.to_string(),
+        }),
+        ModelAvailability::Unknown(reason) => {
+            tracing::warn!(
+                model_id,
+                reason = %reason,
+                "Could not check the Hub for an uncached model; starting anyway"
+            );
+            Ok(())
+        }
+    }
+}
+
+/// GET /instances/export - Configs of all instances, for POST /instances/import
+pub async fn export_instances(State(state): State<AppState>) -> Json<InstancesExport> {
+    let mut instances: Vec<InstanceConfig> = state
+        .registry
+        .list()
+        .await
+        .iter()
+        .map(|i| i.config.redacted())
+        .collect();
+    instances.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Json(InstancesExport { instances })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImportQuery {
+    /// Replace existing instances of the same name instead of failing
+    #[serde(default)]
+    pub overwrite: bool,
+}
+
+/// POST /instances/import - Create and start every instance of an export
+///
+/// All or nothing: the set is checked against the registry before anything
+/// changes, and if an instance still fails to be added or started, the imported
+/// instances are removed and the replaced ones put back. Instances with
+/// `depends_on` start in the background once their dependencies are ready.
+pub async fn import_instances(
+    State(state): State<AppState>,
+    Query(params): Query<ImportQuery>,
+    principal: Option<Extension<Principal>>,
+    Json(export): Json<InstancesExport>,
+) -> Result<(StatusCode, Json<Vec<InstanceInfo>>), TeiError> {
+    let invalid = |e: anyhow::Error| TeiError::ValidationError {
+        message: e.to_string(),
+    };
+
+    let mut names = HashSet::new();
+    for config in &export.instances {
+        if !names.insert(config.name.as_str()) {
+            return Err(TeiError::ValidationError {
+                message: format!("Instance '{}' appears more than once", config.name),
+            });
+        }
+        if let Some(gpu_id) = config.gpu_id {
+            state
+                .registry
+                .gpu_info()
+                .check_gpu_id(gpu_id, state.config.allow_gpu_without_smi)?;
+        }
+    }
+
+    // Checked as `Registry::add` will, with the manager's defaults and the cached
+    // model family, so nothing is replaced for a set that cannot be added
+    let registry = state.registry.clone();
+    let mut checked = export.instances.clone();
+    tokio::task::spawn_blocking(move || {
+        checked
+            .iter_mut()
+            .try_for_each(|config| registry.apply_launch_defaults(config))
+    })
+    .await
+    .map_err(|e| TeiError::Internal {
+        message: e.to_string(),
+    })?
+    .map_err(invalid)?;
+
+    let (replaced, kept): (Vec<_>, Vec<_>) = state
+        .registry
+        .list()
+        .await
+        .into_iter()
+        .partition(|i| names.contains(i.config.name.as_str()));
+    if !params.overwrite
+        && let Some(existing) = replaced.first()
+    {
+        return Err(TeiError::InstanceExists {
+            name: existing.config.name.clone(),
+        });
+    }
+
+    for config in &export.instances {
+        check_model_allowed(&state, &config.model_id)?;
+    }
+
+    // Dependencies must resolve against the registry as it will be after the import
+    let mut configs: Vec<InstanceConfig> = kept.iter().map(|i| i.config.clone()).collect();
+    configs.extend(export.instances.iter().cloned());
+    for config in &export.instances {
+        if let Some(dep) = config
+            .depends_on
+            .iter()
+            .find(|d| !configs.iter().any(|c| &c.name == *d))
+        {
+            return Err(TeiError::ValidationError {
+                message: format!(
+                    "Instance '{}' depends on unknown instance '{}'",
+                    config.name, dep
+                ),
+            });
+        }
+    }
+    crate::config::dependency_order(&configs).map_err(invalid)?;
+
+    // Fixed ports must not collide with each other or with the instances kept
+    let mut ports: HashMap<u16, &str> = HashMap::new();
+    for config in &configs {
+        let config_ports = [Some(config.port), config.prometheus_port];
+        for port in config_ports.into_iter().flatten().filter(|p| *p != 0) {
+            if let Some(owner) = ports.insert(port, &config.name) {
+                return Err(TeiError::PortConflict {
+                    port,
+                    instance: owner.to_string(),
+                });
+            }
+        }
+    }
+
+    // Footprint estimates read the models' config.json
+    let headroom = state
+        .config
+        .gpu_memory_headroom_mb
+        .saturating_mul(1024 * 1024);
+    let (imported_configs, placed) = (export.instances.clone(), configs.clone());
+    let gpu_info = state.registry.gpu_info().clone();
+    tokio::task::spawn_blocking(move || {
+        imported_configs.iter().try_for_each(|config| {
+            gpu_info.check_placement(config, &placed, headroom, crate::gpu::estimate_footprint)
+        })
+    })
+    .await
+    .map_err(|e| TeiError::Internal {
+        message: e.to_string(),
+    })??;
+
+    let mut previous = Vec::with_capacity(replaced.len());
+    for instance in &replaced {
+        let was_running = matches!(
+            *instance.status.read().await,
+            crate::instance::InstanceStatus::Starting | crate::instance::InstanceStatus::Running
+        );
+        state
+            .registry
+            .remove(&instance.config.name)
+            .await
+            .map_err(|e| TeiError::Internal {
+                message: e.to_string(),
+            })?;
+        previous.push((instance.config.clone(), was_running));
+    }
+
+    let order = crate::config::dependency_order(&export.instances).map_err(invalid)?;
+    let mut imported: Vec<Arc<TeiInstance>> = Vec::with_capacity(order.len());
+    let applied: anyhow::Result<()> = async {
+        for config in &order {
+            let instance = state.registry.add(config.clone()).await?;
+            imported.push(instance.clone());
+            if config.depends_on.is_empty() {
+                instance.start(state.registry.tei_binary_path()).await?;
+            }
+        }
+        Ok(())
+    }
+    .await;
+
+    if let Err(e) = applied {
+        tracing::error!(error = %e, "Import failed, rolling back");
+        rollback_import(&state, &imported, previous).await;
+        return Err(invalid(e));
+    }
+
+    for instance in &imported {
+        if instance.config.depends_on.is_empty() {
+            watch_readiness(&state, instance.clone());
+        }
+    }
+
+    // Dependents wait for their dependencies, like a state restore
+    let dependents: Vec<Arc<TeiInstance>> = imported
+        .iter()
+        .filter(|i| !i.config.depends_on.is_empty())
+        .cloned()
+        .collect();
+    if !dependents.is_empty() {
+        let state = state.clone();
+        tokio::spawn(async move {
+            let timeout = std::time::Duration::from_secs(state.config.startup_timeout_secs);
+            for instance in dependents {
+                let name = &instance.config.name;
+                if let Err(e) = state
+                    .registry
+                    .wait_for_dependencies(&instance.config, timeout)
+                    .await
+                {
+                    tracing::error!(instance = %name, error = %e, "Not starting imported instance");
+                } else if let Err(e) = instance.start(state.registry.tei_binary_path()).await {
+                    tracing::error!(instance = %name, error = %e, "Failed to start imported instance");
+                } else {
+                    watch_readiness(&state, instance.clone());
+                }
+            }
+        });
+    }
+
+    let state_manager = state.state_manager.clone();
+    tokio::spawn(async move {
+        if let Err(e) = state_manager.save().await {
+            tracing::error!(error = %e, "Failed to save state");
+        }
+    });
+
+    let mut infos = Vec::with_capacity(imported.len());
+    for instance in &imported {
+        audit(
+            &state,
+            AuditAction::Create,
+            &instance.config.name,
+            principal.clone(),
+        )
+        .await;
+        crate::metrics::record_instance_created(&instance.config.name, &instance.config.model_id);
+        infos.push(InstanceInfo::from_instance(instance).await);
+    }
+    crate::metrics::update_instance_count(state.registry.count().await);
+
+    tracing::info!(
+        imported = imported.len(),
+        replaced = replaced.len(),
+        "Instances imported"
+    );
+
+    Ok((StatusCode::CREATED, Json(infos)))
+}
+
+/// Undo a failed import: drop what was imported and re-add the replaced instances
+async fn rollback_import(
+    state: &AppState,
+    imported: &[Arc<TeiInstance>],
+    replaced: Vec<(InstanceConfig, bool)>,
+) {
+    for instance in imported.iter().rev() {
+        if let Err(e) = state.registry.remove(&instance.config.name).await {
+            tracing::error!(instance = %instance.config.name, error = %e, "Failed to remove imported instance");
+        }
+    }
+
+    for (config, was_running) in replaced {
+        let name = config.name.clone();
+        match state.registry.add(config).await {
+            Ok(instance) if was_running => {
+                if let Err(e) = instance.start(state.registry.tei_binary_path()).await {
+                    tracing::error!(instance = %name, error = %e, "Failed to restart replaced instance");
+                } else {
+                    watch_readiness(state, instance);
+                }
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::error!(instance = %name, error = %e, "Failed to restore replaced instance");
+            }
+        }
+    }
+}
+
+/// Mark `instance` Failed in the background if it does not become ready within
+/// its startup timeout
+fn watch_readiness(state: &AppState, instance: Arc<TeiInstance>) {
+    let health_checker = state.registry.health_checker();
+    let timeout = instance
+        .config
+        .startup_timeout(std::time::Duration::from_secs(
+            state.config.startup_timeout_secs,
+        ));
+    tokio::spawn(async move {
+        if let Err(e) = crate::health::wait_for_ready_with(
+            health_checker.as_ref(),
+            &instance,
+            timeout,
+            std::time::Duration::from_millis(500),
+        )
+        .await
+        {
+            tracing::error!(
+                instance = %instance.config.name,
+                error = %e,
+                "Instance failed to become ready"
+            );
+            instance
+                .set_status(crate::instance::InstanceStatus::Failed)
+                .await;
+        }
+    });
+}
+
 /// GET /instances/:name - Get instance details
 pub async fn get_instance(
     State(state): State<AppState>,
@@ -149,6 +714,7 @@ pub async fn get_instance(
 pub async fn delete_instance(
     State(state): State<AppState>,
     Path(name): Path<String>,
+    principal: Option<Extension<Principal>>,
 ) -> Result<StatusCode, TeiError> {
     state
         .registry
@@ -162,8 +728,11 @@ pub async fn delete_instance(
         let _ = state_manager.save().await;
     });
 
+    audit(&state, AuditAction::Delete, &name, principal).await;
+
     // Record metrics
     crate::metrics::record_instance_deleted(&name);
+    crate::metrics::remove_instance_labels(&name);
     crate::metrics::update_instance_count(state.registry.count().await);
 
     Ok(StatusCode::NO_CONTENT)
@@ -173,6 +742,7 @@ pub async fn delete_instance(
 pub async fn start_instance(
     State(state): State<AppState>,
     Path(name): Path<String>,
+    principal: Option<Extension<Principal>>,
 ) -> Result<Json<InstanceInfo>, TeiError> {
     let instance = state
         .registry
@@ -180,34 +750,22 @@ pub async fn start_instance(
         .await
         .ok_or_else(|| TeiError::InstanceNotFound { name: name.clone() })?;
 
+    check_model_exists(
+        &state,
+        &instance.config.model_cache_dir(),
+        &instance.config.model_id,
+    )
+    .await?;
+
     instance
         .start(state.registry.tei_binary_path())
         .await
-        .map_err(|e| TeiError::Internal {
-            message: e.to_string(),
-        })?;
+        .map_err(start_error)?;
 
-    // Wait for instance to be ready in background
-    let instance_clone = instance.clone();
-    tokio::spawn(async move {
-        use crate::health::GrpcHealthChecker;
-        use std::time::Duration;
+    audit(&state, AuditAction::Start, &name, principal).await;
 
-        if let Err(e) = GrpcHealthChecker::wait_for_ready(
-            &instance_clone,
-            Duration::from_secs(300),
-            Duration::from_millis(500),
-        )
-        .await
-        {
-            tracing::error!(
-                instance = %instance_clone.config.name,
-                error = %e,
-                "Instance failed to become ready"
-            );
-            *instance_clone.status.write().await = crate::instance::InstanceStatus::Failed;
-        }
-    });
+    // Wait for instance to be ready in background
+    watch_readiness(&state, instance.clone());
 
     let info = InstanceInfo::from_instance(&instance).await;
 
@@ -218,6 +776,7 @@ pub async fn start_instance(
 pub async fn stop_instance(
     State(state): State<AppState>,
     Path(name): Path<String>,
+    principal: Option<Extension<Principal>>,
 ) -> Result<Json<InstanceInfo>, TeiError> {
     let instance = state
         .registry
@@ -229,34 +788,252 @@ pub async fn stop_instance(
         message: e.to_string(),
     })?;
 
+    audit(&state, AuditAction::Stop, &name, principal).await;
+
     let info = InstanceInfo::from_instance(&instance).await;
 
     Ok(Json(info))
 }
 
-/// POST /instances/:name/restart - Restart an instance
+/// POST /instances/:name/unquarantine - Clear a quarantine, leaving the instance stopped
+///
+/// Returns 400 if the instance is not quarantined. Start it again via `/start`.
+pub async fn unquarantine_instance(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    principal: Option<Extension<Principal>>,
+) -> Result<Json<InstanceInfo>, TeiError> {
+    let instance = state
+        .registry
+        .get(&name)
+        .await
+        .ok_or_else(|| TeiError::InstanceNotFound { name: name.clone() })?;
+
+    instance.unquarantine().await.map_err(start_error)?;
+
+    audit(&state, AuditAction::Unquarantine, &name, principal).await;
+
+    Ok(Json(InstanceInfo::from_instance(&instance).await))
+}
+
+/// POST /instances/:name/restart - Graceful restart that waits for readiness
+///
+/// Counted in `manual_restart_count`, separately from health-driven restarts.
+/// Returns 504 if the instance is not ready within its startup timeout.
 pub async fn restart_instance(
     State(state): State<AppState>,
     Path(name): Path<String>,
+    principal: Option<Extension<Principal>>,
 ) -> Result<Json<InstanceInfo>, TeiError> {
+    use std::time::Duration;
+
     let instance = state
         .registry
         .get(&name)
         .await
         .ok_or_else(|| TeiError::InstanceNotFound { name: name.clone() })?;
 
+    tracing::info!(instance = %name, "Graceful restart requested");
+
+    instance.stop().await.map_err(|e| TeiError::Internal {
+        message: e.to_string(),
+    })?;
+    // Same pause as a health-driven restart
+    tokio::time::sleep(crate::instance::RESTART_DELAY).await;
+
     instance
-        .restart(state.registry.tei_binary_path())
+        .start(state.registry.tei_binary_path())
         .await
-        .map_err(|e| TeiError::Internal {
-            message: e.to_string(),
-        })?;
+        .map_err(start_error)?;
+
+    instance.stats.write().await.manual_restart_count += 1;
+    audit(&state, AuditAction::Restart, &name, principal).await;
+
+    let timeout = instance
+        .config
+        .startup_timeout(Duration::from_secs(state.config.startup_timeout_secs));
+
+    // On timeout the instance keeps starting in the background; the health
+    // monitor takes over once it becomes ready
+    crate::health::wait_for_ready_with(
+        state.registry.health_checker().as_ref(),
+        &instance,
+        timeout,
+        Duration::from_millis(500),
+    )
+    .await
+    .map_err(|e| TeiError::Timeout {
+        message: e.to_string(),
+    })?;
 
     let info = InstanceInfo::from_instance(&instance).await;
 
     Ok(Json(info))
 }
 
+/// Map an instance start failure, keeping typed errors such as a missing TEI binary
+fn start_error(e: anyhow::Error) -> TeiError {
+    e.downcast::<TeiError>()
+        .unwrap_or_else(|e| TeiError::Internal {
+            message: e.to_string(),
+        })
+}
+
+/// Record a completed mutating action in the audit trail, if one is configured
+///
+/// The action has already happened, so a failing sink is logged rather than
+/// turned into an error response.
+async fn audit(
+    state: &AppState,
+    action: AuditAction,
+    instance: &str,
+    principal: Option<Extension<Principal>>,
+) {
+    let Some(sink) = &state.audit_sink else {
+        return;
+    };
+    let entry = AuditEntry::now(action, instance, principal.map(|Extension(p)| p.0));
+    if let Err(e) = sink.record(&entry).await {
+        tracing::error!(error = %e, action = ?action, instance = %instance, "Failed to write audit entry");
+    }
+}
+
+fn maintenance_action(enabled: bool) -> AuditAction {
+    if enabled {
+        AuditAction::MaintenanceOn
+    } else {
+        AuditAction::MaintenanceOff
+    }
+}
+
+fn save_state_in_background(state: &AppState) {
+    let state_manager = state.state_manager.clone();
+    tokio::spawn(async move {
+        if let Err(e) = state_manager.save().await {
+            tracing::error!(error = %e, "Failed to save state");
+        }
+    });
+}
+
+/// GET /admin/maintenance - Global and per-instance maintenance state
+pub async fn get_maintenance(State(state): State<AppState>) -> Json<MaintenanceResponse> {
+    Json(maintenance_state(&state).await)
+}
+
+/// POST /admin/maintenance - Enable or disable global maintenance mode
+///
+/// While enabled the health monitor keeps checking instances and recording
+/// failures, but never restarts them.
+///
+/// The change is persisted to the state file and audited against instance `*`.
+pub async fn set_maintenance(
+    State(state): State<AppState>,
+    principal: Option<Extension<Principal>>,
+    Json(req): Json<MaintenanceRequest>,
+) -> Json<MaintenanceResponse> {
+    state.registry.set_maintenance(req.enabled);
+    tracing::warn!(enabled = req.enabled, "Global maintenance mode changed");
+    save_state_in_background(&state);
+    audit(&state, maintenance_action(req.enabled), "*", principal).await;
+    Json(maintenance_state(&state).await)
+}
+
+/// POST /admin/healthcheck - Health check every instance now, concurrently
+///
+/// Results are only reported: failure counts, status and restarts stay with the
+/// health monitor's own checks.
+pub async fn check_all_instances(State(state): State<AppState>) -> Json<Vec<HealthCheckReport>> {
+    let checker = state.registry.health_checker();
+    let instances = state.registry.list().await;
+
+    let mut reports = futures::future::join_all(instances.iter().map(|instance| {
+        let checker = checker.clone();
+        async move {
+            let result = checker.check(instance).await;
+            HealthCheckReport {
+                name: instance.config.name.clone(),
+                healthy: result.healthy,
+                reason: result.reason,
+            }
+        }
+    }))
+    .await;
+    reports.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Json(reports)
+}
+
+/// POST /instances/:name/maintenance - Enable or disable maintenance mode for one instance
+pub async fn set_instance_maintenance(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    principal: Option<Extension<Principal>>,
+    Json(req): Json<MaintenanceRequest>,
+) -> Result<Json<InstanceInfo>, TeiError> {
+    let instance = state
+        .registry
+        .get(&name)
+        .await
+        .ok_or_else(|| TeiError::InstanceNotFound { name: name.clone() })?;
+
+    instance.set_maintenance(req.enabled);
+    tracing::warn!(instance = %name, enabled = req.enabled, "Instance maintenance mode changed");
+    save_state_in_background(&state);
+    audit(&state, maintenance_action(req.enabled), &name, principal).await;
+
+    Ok(Json(InstanceInfo::from_instance(&instance).await))
+}
+
+/// POST /instances/:name/pause - Stop routing new requests to an instance
+pub async fn pause_instance(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<Json<InstanceInfo>, TeiError> {
+    set_instance_paused(&state, name, true).await
+}
+
+/// POST /instances/:name/resume - Route requests to a paused instance again
+pub async fn resume_instance(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<Json<InstanceInfo>, TeiError> {
+    set_instance_paused(&state, name, false).await
+}
+
+async fn set_instance_paused(
+    state: &AppState,
+    name: String,
+    paused: bool,
+) -> Result<Json<InstanceInfo>, TeiError> {
+    let instance = state
+        .registry
+        .get(&name)
+        .await
+        .ok_or_else(|| TeiError::InstanceNotFound { name: name.clone() })?;
+
+    instance.set_paused(paused);
+    tracing::info!(instance = %name, paused, "Instance routing paused state changed");
+
+    Ok(Json(InstanceInfo::from_instance(&instance).await))
+}
+
+async fn maintenance_state(state: &AppState) -> MaintenanceResponse {
+    let mut instances: Vec<String> = state
+        .registry
+        .list()
+        .await
+        .iter()
+        .filter(|i| i.in_maintenance())
+        .map(|i| i.config.name.clone())
+        .collect();
+    instances.sort();
+
+    MaintenanceResponse {
+        enabled: state.registry.maintenance_enabled(),
+        instances,
+    }
+}
+
 /// Query parameters for log slicing
 #[derive(Debug, Deserialize)]
 pub struct LogsQuery {
@@ -264,23 +1041,93 @@ pub struct LogsQuery {
     pub end: Option<i32>,
 }
 
+/// POST /instances/{name}/logs/clear - Truncate an instance's log file in place
+///
+/// The running process keeps its (append-mode) file descriptor, so later
+/// output lands at the start of the emptied file.
+pub async fn clear_logs(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<Json<ClearLogsResponse>, TeiError> {
+    if state.registry.get(&name).await.is_none() {
+        return Err(TeiError::InstanceNotFound { name });
+    }
+
+    let log_path = state.registry.instance_log_path(&name);
+    let size_bytes = match tokio::fs::OpenOptions::new()
+        .write(true)
+        .open(&log_path)
+        .await
+    {
+        Ok(file) => {
+            let truncate = async {
+                file.set_len(0).await?;
+                Ok::<_, std::io::Error>(file.metadata().await?.len())
+            };
+            truncate.await.map_err(|e| TeiError::IoError {
+                message: format!("Failed to truncate log file: {}", e),
+            })?
+        }
+        // Never started, so there is nothing to clear
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => 0,
+        Err(e) => {
+            return Err(TeiError::IoError {
+                message: format!("Failed to open log file: {}", e),
+            });
+        }
+    };
+
+    tracing::info!(instance = %name, "Instance log cleared");
+    Ok(Json(ClearLogsResponse {
+        instance: name,
+        size_bytes,
+    }))
+}
+
+/// How long `GET /instances/{name}/metrics` waits for the instance's metrics endpoint
+const INSTANCE_METRICS_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// GET /instances/{name}/metrics - The instance's own TEI Prometheus metrics
+///
+/// Proxied from its `prometheus_port`, with `instance="name"` added to every sample.
+pub async fn instance_metrics(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<Response, TeiError> {
+    let instance = state
+        .registry
+        .get(&name)
+        .await
+        .ok_or_else(|| TeiError::InstanceNotFound { name: name.clone() })?;
+
+    let port = instance
+        .config
+        .prometheus_port
+        .filter(|port| *port != 0)
+        .ok_or_else(|| TeiError::BackendUnavailable {
+            message: format!("Prometheus metrics are disabled for instance '{}'", name),
+        })?;
+
+    let text = crate::metrics::scrape_instance_metrics(port, INSTANCE_METRICS_TIMEOUT)
+        .await
+        .map_err(|e| TeiError::BackendUnavailable {
+            message: format!("Failed to scrape metrics for instance '{}': {}", name, e),
+        })?;
+
+    Ok((
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        crate::metrics::relabel_instance_metrics(&text, &name),
+    )
+        .into_response())
+}
+
 /// GET /instances/{name}/logs - Get instance logs with Python-style slicing
 pub async fn get_logs(
+    State(state): State<AppState>,
     Path(name): Path<String>,
     Query(params): Query<LogsQuery>,
 ) -> Result<Json<LogsResponse>, TeiError> {
-    // Use same log directory resolution as spawn
-    let log_dir_path =
-        std::env::var("TEI_MANAGER_LOG_DIR").unwrap_or_else(|_| "/data/logs".to_string());
-
-    let log_dir = std::path::Path::new(&log_dir_path);
-
-    // Check fallback location if primary doesn't exist
-    let log_path = if !log_dir.exists() {
-        std::path::Path::new("/tmp/tei-manager/logs").join(format!("{}.log", name))
-    } else {
-        log_dir.join(format!("{}.log", name))
-    };
+    let log_path = state.registry.instance_log_path(&name);
 
     if !log_path.exists() {
         return Err(TeiError::InstanceNotFound { name });
@@ -338,17 +1185,615 @@ pub async fn get_logs(
     }))
 }
 
+/// Log lines included by /describe unless `log_lines` is given
+const DESCRIBE_LOG_LINES: usize = 50;
+
+#[derive(Deserialize)]
+pub struct DescribeQuery {
+    pub log_lines: Option<usize>,
+}
+
+/// GET /instances/{name}/describe - Config, status, stats, command line and log tail
+pub async fn describe_instance(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    Query(params): Query<DescribeQuery>,
+) -> Result<Json<InstanceDescription>, TeiError> {
+    let instance = state
+        .registry
+        .get(&name)
+        .await
+        .ok_or_else(|| TeiError::InstanceNotFound { name: name.clone() })?;
+
+    // No log file yet just means the instance has never been started
+    let log_lines = params.log_lines.unwrap_or(DESCRIBE_LOG_LINES);
+    let log_tail = read_log_tail(&instance.log_path(), log_lines)
+        .await
+        .unwrap_or_default();
+
+    Ok(Json(
+        InstanceDescription::from_instance(&instance, state.registry.tei_binary_path(), log_tail)
+            .await,
+    ))
+}
+
+/// Last `lines` lines of a log file, read backwards from the end in chunks
+///
+/// Only as much of the file as the tail needs is read, so large logs cost the
+/// same as small ones.
+async fn read_log_tail(path: &std::path::Path, lines: usize) -> std::io::Result<Vec<String>> {
+    use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+    const CHUNK: u64 = 8 * 1024;
+
+    if lines == 0 {
+        return Ok(Vec::new());
+    }
+
+    let mut file = tokio::fs::File::open(path).await?;
+    let mut pos = file.metadata().await?.len();
+    let mut buf: Vec<u8> = Vec::new();
+    let mut newlines = 0;
+
+    // One newline more than requested guarantees `lines` complete lines
+    while pos > 0 && newlines <= lines {
+        let len = CHUNK.min(pos);
+        pos -= len;
+        let mut chunk = vec![0; len as usize];
+        file.seek(std::io::SeekFrom::Start(pos)).await?;
+        file.read_exact(&mut chunk).await?;
+        newlines += chunk.iter().filter(|&&b| b == b'\n').count();
+        chunk.extend_from_slice(&buf);
+        buf = chunk;
+    }
+
+    let content = String::from_utf8_lossy(&buf);
+    let all: Vec<&str> = content.lines().collect();
+    Ok(all[all.len().saturating_sub(lines)..]
+        .iter()
+        .map(|line| line.to_string())
+        .collect())
+}
+
+/// GET /instances/{name}/command - Command line the instance is launched with
+pub async fn get_instance_command(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<Json<InstanceCommand>, TeiError> {
+    let instance = state
+        .registry
+        .get(&name)
+        .await
+        .ok_or_else(|| TeiError::InstanceNotFound { name: name.clone() })?;
+
+    Ok(Json(InstanceCommand::from_instance(
+        &instance,
+        state.registry.tei_binary_path(),
+    )))
+}
+
+/// Maximum embed calls in flight per NDJSON batch request
+const JSONL_EMBED_CONCURRENCY: usize = 16;
+
+/// POST /instances/{name}/embed/jsonl - Batch embed an NDJSON body
+///
+/// Each input line is `{"id": ..., "text": ...}`, optionally with `"dimensions"` to
+/// truncate a Matryoshka embedding and `"truncate": true` to cut texts longer than
+/// the model's max input length. Lines are embedded with bounded
+/// concurrency and streamed back as `{"id": ..., "embedding": [...]}` in input order.
+/// Input is only read as fast as output is consumed, so large bodies are never buffered
+/// in full. Per-line failures are reported inline as `{"id": ..., "error": "..."}`.
+///
+/// Bodies larger than `http_max_embed_body_bytes` are rejected with 413 when the size
+/// is declared up front, otherwise reading stops with an inline error at the limit.
+/// A single line is never buffered past the same limit.
+pub async fn embed_jsonl(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    request: Request,
+) -> Result<Response, TeiError> {
+    let limit = state.config.http_max_embed_body_bytes;
+    // The size hint reflects Content-Length when the client declared one
+    if HttpBody::size_hint(request.body()).lower() > limit as u64 {
+        return Err(TeiError::PayloadTooLarge { limit });
+    }
+    let metadata = forwarded_metadata(&state, request.headers());
+    let body = request.with_limited_body().into_body();
+
+    let clients = backend_clients(&state, &name).await?;
+    let request_timeout = crate::config::request_timeout(state.config.grpc_request_timeout_secs);
+    let model_id = match state.registry.get(&name).await {
+        Some(instance) => instance.config.model_id.clone(),
+        None => return Err(TeiError::InstanceNotFound { name }),
+    };
+    let native_dimension = state.embedding_dimensions.embedding_dimension(&model_id);
+
+    let results = ndjson_lines(body.into_data_stream(), limit)
+        .map(move |line| {
+            let mut client = clients.embed.clone();
+            let inflight = clients.track_request();
+            let metadata = metadata.clone();
+            let model_id = model_id.clone();
+            async move {
+                let _inflight = inflight;
+                let line = match line {
+                    Ok(line) => line,
+                    Err(error) => return JsonlEmbedResult::error(serde_json::Value::Null, error),
+                };
+                if let Err(error) =
+                    crate::models::check_dimensions(&model_id, line.dimensions, native_dimension)
+                {
+                    return JsonlEmbedResult::error(line.id, error);
+                }
+                let request = crate::grpc::proto::tei::v1::EmbedRequest {
+                    inputs: line.text,
+                    truncate: line.truncate,
+                    normalize: None,
+                    truncation_direction: 0,
+                    prompt_name: None,
+                    dimensions: line.dimensions,
+                };
+                let request = backend_request(&metadata, request);
+                match with_request_timeout(request_timeout, client.embed(request)).await {
+                    Ok(Ok(response)) => JsonlEmbedResult {
+                        id: line.id,
+                        embedding: Some(response.into_inner().embeddings),
+                        error: None,
+                    },
+                    Ok(Err(status)) => {
+                        JsonlEmbedResult::error(line.id, status.message().to_string())
+                    }
+                    Err(_) => {
+                        JsonlEmbedResult::error(line.id, "Backend request timed out".to_string())
+                    }
+                }
+            }
+        })
+        .buffered(JSONL_EMBED_CONCURRENCY)
+        .map(|result| {
+            let mut bytes = serde_json::to_vec(&result).unwrap_or_default();
+            bytes.push(b'\n');
+            Ok::<_, std::convert::Infallible>(Bytes::from(bytes))
+        });
+
+    Ok((
+        [(header::CONTENT_TYPE, "application/x-ndjson")],
+        Body::from_stream(results),
+    )
+        .into_response())
+}
+
+/// POST /v1/embeddings - OpenAI-compatible embeddings
+///
+/// Routes by `model` like the multiplexer's model routing, embeds each input on
+/// the chosen instance and answers in the OpenAI response shape, so OpenAI client
+/// code can point at the manager unchanged. Any failed input fails the request.
+pub async fn openai_embeddings(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<OpenAiEmbeddingRequest>,
+) -> Result<Json<OpenAiEmbeddingResponse>, TeiError> {
+    let base64 = match request.encoding_format.as_deref() {
+        None | Some("float") => false,
+        Some("base64") => true,
+        Some(other) => {
+            return Err(TeiError::ValidationError {
+                message: format!(
+                    "encoding_format must be \"float\" or \"base64\", got \"{}\"",
+                    other
+                ),
+            });
+        }
+    };
+    let texts = request.input.into_texts();
+    if texts.is_empty() {
+        return Err(TeiError::ValidationError {
+            message: "input must not be empty".to_string(),
+        });
+    }
+    crate::models::check_dimensions(
+        &request.model,
+        request.dimensions,
+        state
+            .embedding_dimensions
+            .embedding_dimension(&request.model),
+    )
+    .map_err(|message| TeiError::ValidationError { message })?;
+
+    let name = state
+        .backend_pool
+        .resolve_model(&request.model)
+        .await
+        .map_err(|status| match status.code() {
+            tonic::Code::NotFound => TeiError::ModelNotFound {
+                model_id: request.model.clone(),
+            },
+            _ => TeiError::BackendUnavailable {
+                message: status.message().to_string(),
+            },
+        })?;
+    let clients = backend_clients(&state, &name).await?;
+    let metadata = forwarded_metadata(&state, &headers);
+    let request_timeout = crate::config::request_timeout(state.config.grpc_request_timeout_secs);
+
+    let responses: Vec<_> = futures::stream::iter(texts)
+        .map(|text| {
+            let mut client = clients.embed.clone();
+            let inflight = clients.track_request();
+            let request = backend_request(
+                &metadata,
+                crate::grpc::proto::tei::v1::EmbedRequest {
+                    inputs: text,
+                    truncate: false,
+                    normalize: None,
+                    truncation_direction: 0,
+                    prompt_name: None,
+                    dimensions: request.dimensions,
+                },
+            );
+            async move {
+                let _inflight = inflight;
+                match with_request_timeout(request_timeout, client.embed(request)).await {
+                    Ok(Ok(response)) => Ok(response.into_inner()),
+                    Ok(Err(status)) if status.code() == tonic::Code::InvalidArgument => {
+                        Err(TeiError::ValidationError {
+                            message: status.message().to_string(),
+                        })
+                    }
+                    Ok(Err(status)) => Err(TeiError::BackendUnavailable {
+                        message: status.message().to_string(),
+                    }),
+                    Err(_) => Err(TeiError::Timeout {
+                        message: "Backend request timed out".to_string(),
+                    }),
+                }
+            }
+        })
+        .buffered(JSONL_EMBED_CONCURRENCY)
+        .collect()
+        .await;
+
+    let mut usage = OpenAiUsage::default();
+    let mut data = Vec::with_capacity(responses.len());
+    for (index, response) in responses.into_iter().enumerate() {
+        let response = response?;
+        let tokens = response.metadata.map_or(0, |m| m.compute_tokens);
+        usage.prompt_tokens += tokens;
+        usage.total_tokens += tokens;
+        let embedding = if base64 {
+            use base64::Engine;
+            let bytes: Vec<u8> = response
+                .embeddings
+                .iter()
+                .flat_map(|value| value.to_le_bytes())
+                .collect();
+            OpenAiEmbeddingVector::Base64(base64::engine::general_purpose::STANDARD.encode(bytes))
+        } else {
+            OpenAiEmbeddingVector::Float(response.embeddings)
+        };
+        data.push(OpenAiEmbedding {
+            object: "embedding".to_string(),
+            embedding,
+            index,
+        });
+    }
+
+    Ok(Json(OpenAiEmbeddingResponse {
+        object: "list".to_string(),
+        data,
+        model: request.model,
+        usage,
+    }))
+}
+
+/// Allowlisted (`forward_headers`) request headers to pass on to the backend
+fn forwarded_metadata(state: &AppState, headers: &HeaderMap) -> MetadataMap {
+    // Names were checked by ManagerConfig::validate
+    ForwardHeaders::new(&state.config.forward_headers)
+        .unwrap_or_default()
+        .from_http(headers)
+}
+
+/// Backend clients for instance `name`, as an API error if it can't be reached
+async fn backend_clients(state: &AppState, name: &str) -> Result<BackendClients, TeiError> {
+    state
+        .backend_pool
+        .get_clients(name)
+        .await
+        .map_err(|status| match status.code() {
+            tonic::Code::NotFound => TeiError::InstanceNotFound {
+                name: name.to_string(),
+            },
+            _ => TeiError::BackendUnavailable {
+                message: status.message().to_string(),
+            },
+        })
+}
+
+/// Await `fut`, giving up after `request_timeout` when one is configured
+async fn with_request_timeout<F: std::future::Future>(
+    request_timeout: Option<std::time::Duration>,
+    fut: F,
+) -> Result<F::Output, tokio::time::error::Elapsed> {
+    match request_timeout {
+        Some(duration) => tokio::time::timeout(duration, fut).await,
+        None => Ok(fut.await),
+    }
+}
+
+/// Texts buffered between a WebSocket client and the backend stream
+const WS_EMBED_BUFFER: usize = 32;
+
+/// GET /instances/{name}/embed/ws - Embed texts over a WebSocket
+///
+/// The client sends `{"text": ...}` text messages (optionally with `"dimensions"`)
+/// and receives `{"embedding": [...]}` for each, in order, over the backend's EmbedStream. Reading from the socket stops
+/// while the backend falls behind, so a fast client is slowed down rather than buffered.
+/// Closing the socket ends the stream once pending embeddings are delivered; an invalid
+/// message or backend error closes it with code 1007 or 1011, and a client that sends
+/// nothing for `http_ws_idle_timeout_secs` is closed with 1001.
+pub async fn embed_ws(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    headers: HeaderMap,
+    ws: WebSocketUpgrade,
+) -> Result<Response, TeiError> {
+    let clients = backend_clients(&state, &name).await?;
+    let metadata = forwarded_metadata(&state, &headers);
+    let model_id = match state.registry.get(&name).await {
+        Some(instance) => instance.config.model_id.clone(),
+        None => return Err(TeiError::InstanceNotFound { name }),
+    };
+    let native_dimension = state.embedding_dimensions.embedding_dimension(&model_id);
+    let idle_timeout = (state.config.http_ws_idle_timeout_secs > 0)
+        .then(|| std::time::Duration::from_secs(state.config.http_ws_idle_timeout_secs));
+
+    Ok(ws
+        .max_message_size(state.config.http_max_embed_body_bytes)
+        .on_upgrade(move |socket| {
+            embed_ws_session(
+                socket,
+                clients,
+                metadata,
+                model_id,
+                native_dimension,
+                idle_timeout,
+            )
+        }))
+}
+
+/// Relay one WebSocket connection through the backend's EmbedStream
+async fn embed_ws_session(
+    socket: WebSocket,
+    clients: BackendClients,
+    metadata: MetadataMap,
+    model_id: String,
+    native_dimension: Option<u32>,
+    idle_timeout: Option<std::time::Duration>,
+) {
+    use futures::SinkExt;
+
+    let (mut sink, mut source) = socket.split();
+    let (tx, rx) = tokio::sync::mpsc::channel(WS_EMBED_BUFFER);
+
+    // Reads client messages into the request stream; returns why it stopped, if abnormal
+    let reader = tokio::spawn(async move {
+        loop {
+            let message = match idle_timeout {
+                Some(idle_timeout) => match tokio::time::timeout(idle_timeout, source.next()).await
+                {
+                    Ok(message) => message,
+                    Err(_) => {
+                        return Some(ws_close(
+                            close_code::AWAY,
+                            &format!("No message for {}s", idle_timeout.as_secs()),
+                        ));
+                    }
+                },
+                None => source.next().await,
+            };
+            // Stream end is a normal close
+            let text = match message? {
+                Ok(Message::Text(text)) => text,
+                Ok(Message::Binary(_)) => {
+                    return Some(ws_close(
+                        close_code::UNSUPPORTED,
+                        "Binary messages are not supported",
+                    ));
+                }
+                Ok(Message::Close(_)) | Err(_) => return None,
+                // Pings are answered by the socket itself
+                Ok(_) => continue,
+            };
+            let request = match serde_json::from_str::<WsEmbedRequest>(&text) {
+                Ok(request) => request,
+                Err(e) => {
+                    return Some(ws_close(
+                        close_code::INVALID,
+                        &format!("Invalid message: {}", e),
+                    ));
+                }
+            };
+            if let Err(e) =
+                crate::models::check_dimensions(&model_id, request.dimensions, native_dimension)
+            {
+                return Some(ws_close(close_code::INVALID, &e));
+            }
+            let request = crate::grpc::proto::tei::v1::EmbedRequest {
+                inputs: request.text,
+                truncate: false,
+                normalize: None,
+                truncation_direction: 0,
+                prompt_name: None,
+                dimensions: request.dimensions,
+            };
+            if tx.send(request).await.is_err() {
+                return None;
+            }
+        }
+    });
+
+    // The whole session counts as one request, like a multiplexer stream
+    let _inflight = clients.track_request();
+    let mut client = clients.embed;
+    let close = match client
+        .embed_stream(backend_request(
+            &metadata,
+            tokio_stream::wrappers::ReceiverStream::new(rx),
+        ))
+        .await
+    {
+        Ok(response) => {
+            let mut responses = response.into_inner();
+            loop {
+                match responses.next().await {
+                    Some(Ok(response)) => {
+                        let message = match serde_json::to_string(&WsEmbedResponse {
+                            embedding: response.embeddings,
+                        }) {
+                            Ok(message) => message,
+                            Err(e) => {
+                                break ws_close(
+                                    close_code::ERROR,
+                                    &format!("Failed to encode response: {}", e),
+                                );
+                            }
+                        };
+                        if sink.send(Message::Text(message.into())).await.is_err() {
+                            // Client is gone, nothing left to close
+                            reader.abort();
+                            return;
+                        }
+                    }
+                    Some(Err(status)) => break ws_close(close_code::ERROR, status.message()),
+                    None => break ws_close(close_code::NORMAL, ""),
+                }
+            }
+        }
+        Err(status) => ws_close(close_code::ERROR, status.message()),
+    };
+
+    // The reader has usually finished by now; its reason takes precedence
+    reader.abort();
+    let close = match reader.await {
+        Ok(Some(reader_close)) => reader_close,
+        _ => close,
+    };
+    let _ = sink.send(Message::Close(Some(close))).await;
+}
+
+/// Close frame with `reason` cut to the 123 bytes a frame can carry
+fn ws_close(code: u16, reason: &str) -> CloseFrame {
+    let mut end = reason.len().min(123);
+    while !reason.is_char_boundary(end) {
+        end -= 1;
+    }
+    CloseFrame {
+        code,
+        reason: reason[..end].into(),
+    }
+}
+
+/// Split a byte stream into parsed NDJSON lines, skipping blank lines
+///
+/// A line longer than `max_line_bytes` ends the stream with an error rather than
+/// growing the buffer without bound.
+fn ndjson_lines<S>(
+    body: S,
+    max_line_bytes: usize,
+) -> impl Stream<Item = Result<JsonlEmbedLine, String>> + Send
+where
+    S: Stream<Item = Result<Bytes, axum::Error>> + Send + 'static,
+{
+    async_stream::stream! {
+        let mut body = std::pin::pin!(body);
+        let mut buffer: Vec<u8> = Vec::new();
+        let mut line_number = 0usize;
+
+        loop {
+            let (chunk, done) = match body.next().await {
+                Some(Ok(chunk)) => (chunk, false),
+                Some(Err(e)) => {
+                    yield Err(format!("Failed to read request body: {}", e));
+                    return;
+                }
+                None => (Bytes::new(), true),
+            };
+            buffer.extend_from_slice(&chunk);
+
+            // At end of body, the remainder is the final (unterminated) line
+            let mut lines: Vec<Vec<u8>> = Vec::new();
+            while let Some(pos) = buffer.iter().position(|b| *b == b'\n') {
+                lines.push(buffer.drain(..=pos).collect());
+            }
+            if done && !buffer.is_empty() {
+                lines.push(std::mem::take(&mut buffer));
+            }
+            let overlong = buffer.len() > max_line_bytes;
+
+            for raw in lines {
+                line_number += 1;
+                let raw = raw.trim_ascii();
+                if raw.is_empty() {
+                    continue;
+                }
+                yield serde_json::from_slice::<JsonlEmbedLine>(raw)
+                    .map_err(|e| format!("Invalid JSON on line {}: {}", line_number, e));
+            }
+
+            if overlong {
+                yield Err(format!(
+                    "Line {} exceeds the {} byte limit",
+                    line_number + 1,
+                    max_line_bytes
+                ));
+                return;
+            }
+            if done {
+                return;
+            }
+        }
+    }
+}
+
 // ============================================================================
 // Model Management Handlers
 // ============================================================================
 
 /// GET /models - List all known models
 pub async fn list_models(State(state): State<AppState>) -> Result<Json<Vec<ModelInfo>>, TeiError> {
+    // Pick up configured models and anything cached since startup
+    state
+        .model_registry
+        .sync_known_models(state.config.models.as_deref().unwrap_or_default())
+        .await;
+
     let entries = state.model_registry.list().await;
-    let models: Vec<ModelInfo> = entries.into_iter().map(ModelInfo::from).collect();
+    let mut models: Vec<ModelInfo> = entries.into_iter().map(ModelInfo::from).collect();
+    annotate_model_usage(&state, &mut models).await;
     Ok(Json(models))
 }
 
+/// Fill in whether each model is configured and which instances serve it
+async fn annotate_model_usage(state: &AppState, models: &mut [ModelInfo]) {
+    let mut serving: HashMap<String, Vec<String>> = HashMap::new();
+    for instance in state.registry.list().await {
+        serving
+            .entry(instance.config.model_id.clone())
+            .or_default()
+            .push(instance.config.name.clone());
+    }
+
+    let configured = state.config.models.as_deref().unwrap_or_default();
+    for model in models {
+        model.configured = configured.contains(&model.model_id);
+        if let Some(mut names) = serving.remove(&model.model_id) {
+            names.sort();
+            model.instances = names;
+        }
+    }
+}
+
 /// GET /models/{model_id} - Get model details
 ///
 /// Note: model_id should be URL-encoded (e.g., "BAAI%2Fbge-small-en-v1.5")
@@ -371,7 +1816,9 @@ pub async fn get_model(
             model_id: model_id.clone(),
         })?;
 
-    Ok(Json(ModelInfo::from(entry)))
+    let mut model = ModelInfo::from(entry);
+    annotate_model_usage(&state, std::slice::from_mut(&mut model)).await;
+    Ok(Json(model))
 }
 
 /// POST /models - Add a model to the registry
@@ -431,7 +1878,11 @@ pub async fn download_model(
         .await;
 
     // Download using hf-hub crate
-    if let Err(e) = crate::models::download_model(&model_id).await {
+    let options = crate::models::DownloadOptions {
+        retry: crate::models::DownloadRetryConfig::from_config(&state.config),
+        ..Default::default()
+    };
+    if let Err(e) = crate::models::download_model_with_options(&model_id, &options).await {
         // Reset status on failure
         state
             .model_registry
@@ -455,6 +1906,51 @@ pub async fn download_model(
     Ok(Json(ModelInfo::from(entry)))
 }
 
+/// POST /models/preload - Start background downloads for a batch of models
+///
+/// Returns immediately with a job that can be polled via GET /models/preload/{job_id}.
+/// Models already in the HF cache are skipped.
+pub async fn preload_models(
+    State(state): State<AppState>,
+    Json(req): Json<PreloadModelsRequest>,
+) -> Result<(StatusCode, Json<PreloadJob>), TeiError> {
+    if req.model_ids.is_empty() {
+        return Err(TeiError::ValidationError {
+            message: "model_ids cannot be empty".to_string(),
+        });
+    }
+    if req.model_ids.iter().any(|id| id.is_empty()) {
+        return Err(TeiError::ValidationError {
+            message: "model_ids cannot contain empty values".to_string(),
+        });
+    }
+
+    // Register models so they show up in GET /models
+    for model_id in &req.model_ids {
+        if !state.model_registry.contains(model_id).await {
+            state.model_registry.add_model(model_id.clone()).await;
+        }
+    }
+
+    let job = state.preload_tracker.start(req.model_ids).await;
+
+    Ok((StatusCode::ACCEPTED, Json(job)))
+}
+
+/// GET /models/preload/{job_id} - Get preload job status
+pub async fn get_preload_job(
+    State(state): State<AppState>,
+    Path(job_id): Path<String>,
+) -> Result<Json<PreloadJob>, TeiError> {
+    let job = state
+        .preload_tracker
+        .get(&job_id)
+        .await
+        .ok_or(TeiError::PreloadJobNotFound { job_id })?;
+
+    Ok(Json(job))
+}
+
 /// POST /models/{model_id}/load - Smoke test model loading
 pub async fn load_model(
     State(state): State<AppState>,
@@ -523,3 +2019,57 @@ pub async fn load_model(
 
     Ok(Json(ModelInfo::from(entry)))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_ndjson_line_capped() {
+        let chunks = vec![
+            Ok(Bytes::from_static(
+                b"{\"id\": 1, \"text\": \"a\"}\n{\"id\": 2, ",
+            )),
+            Ok(Bytes::from_static(b"\"text\": \"this line never ends")),
+            Ok(Bytes::from_static(b" and keeps going\"}\n")),
+        ];
+        let lines: Vec<_> = ndjson_lines(futures::stream::iter(chunks), 32)
+            .collect()
+            .await;
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].as_ref().unwrap().id, 1);
+        let error = lines[1].as_ref().unwrap_err();
+        assert!(
+            error.contains("Line 2 exceeds the 32 byte limit"),
+            "{}",
+            error
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_log_tail_spans_chunks() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("instance.log");
+        let content: String = (0..5000).map(|i| format!("line {}\n", i)).collect();
+        std::fs::write(&path, content).unwrap();
+
+        let tail = read_log_tail(&path, 3).await.unwrap();
+        assert_eq!(tail, vec!["line 4997", "line 4998", "line 4999"]);
+
+        let tail = read_log_tail(&path, 2000).await.unwrap();
+        assert_eq!(tail.len(), 2000);
+        assert_eq!(tail[0], "line 3000");
+
+        let tail = read_log_tail(&path, 10_000).await.unwrap();
+        assert_eq!(tail.len(), 5000);
+        assert_eq!(tail[0], "line 0");
+
+        assert!(read_log_tail(&path, 0).await.unwrap().is_empty());
+        assert!(
+            read_log_tail(&dir.path().join("missing.log"), 5)
+                .await
+                .is_err()
+        );
+    }
+}
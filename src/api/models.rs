@@ -1,6 +1,8 @@
 //! API request and response models
 
-use crate::instance::{InstanceStatus, TeiInstance};
+use crate::config::{Dtype, InstanceConfig, Pooling};
+use crate::instance::{InstanceStats, InstanceStatus, TeiInstance};
+use crate::registry::PortAllocatorState;
 use serde::{Deserialize, Serialize};
 
 /// Health check response
@@ -28,12 +30,33 @@ pub struct CreateInstanceRequest {
     #[serde(default)]
     pub max_concurrent_requests: Option<u32>,
 
+    /// Pooling strategy: "mean", "cls", "splade" or "last-token"
     #[serde(default)]
-    pub pooling: Option<String>,
+    pub pooling: Option<Pooling>,
+
+    /// Weight precision: "float16", "float32" or "bfloat16"
+    #[serde(default)]
+    pub dtype: Option<Dtype>,
+
+    /// Model revision (commit hash, branch or tag) to pin
+    #[serde(default)]
+    pub revision: Option<String>,
 
     #[serde(default)]
     pub gpu_id: Option<u32>,
 
+    /// Run on CPU with an empty CUDA_VISIBLE_DEVICES (cannot be combined with gpu_id)
+    #[serde(default)]
+    pub cpu_only: bool,
+
+    /// Share of GPU memory past which the allocator reclaims cached blocks, in (0, 1] (not a cap)
+    #[serde(default)]
+    pub gpu_memory_fraction: Option<f32>,
+
+    /// GPU memory in bytes to reserve on gpu_id (estimated from the model if omitted)
+    #[serde(default)]
+    pub gpu_memory_bytes: Option<u64>,
+
     #[serde(default)]
     pub prometheus_port: Option<u16>,
 
@@ -43,8 +66,48 @@ pub struct CreateInstanceRequest {
     #[serde(default)]
     pub startup_timeout_secs: Option<u64>,
 
+    /// Override gRPC max message size for this instance's backend connection (MB)
+    /// If not provided, uses global grpc_max_message_size_mb from manager config
+    #[serde(default)]
+    pub grpc_max_message_size_mb: Option<usize>,
+
+    /// Shell command run before this instance is stopped
+    /// If not provided, uses global pre_stop_command from manager config
+    #[serde(default)]
+    pub pre_stop_command: Option<String>,
+
+    /// RUST_LOG for the TEI process (default: manager's instance_log_level)
+    #[serde(default)]
+    pub log_level: Option<String>,
+
     #[serde(default)]
     pub extra_args: Option<Vec<String>>,
+
+    /// Environment for the TEI process; use `${VAR}` references for secrets
+    /// so they are resolved at start time instead of being persisted
+    #[serde(default)]
+    pub env: Option<std::collections::BTreeMap<String, String>>,
+
+    /// Dotenv-style file read when the instance starts
+    #[serde(default)]
+    pub env_file: Option<std::path::PathBuf>,
+
+    /// HuggingFace home holding this instance's models, instead of the manager's cache
+    #[serde(default)]
+    pub hf_home: Option<std::path::PathBuf>,
+
+    /// Existing instances that must be Running before this one starts
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+
+    /// Constant labels added to this instance's metrics, over the manager's
+    #[serde(default)]
+    pub metric_labels: std::collections::BTreeMap<String, String>,
+
+    /// Download the model into the HF cache before starting if it isn't there,
+    /// failing the request if the download fails
+    #[serde(default)]
+    pub download_if_missing: bool,
 }
 
 /// Instance information response
@@ -58,10 +121,27 @@ pub struct InstanceInfo {
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
     pub uptime_secs: Option<u64>,
     pub restarts: u32,
+    pub manual_restart_count: u32,
     pub health_check_failures: u32,
     pub last_health_check: Option<chrono::DateTime<chrono::Utc>>,
     pub gpu_id: Option<u32>,
+    /// Launched with an empty CUDA_VISIBLE_DEVICES
+    pub cpu_only: bool,
+    pub gpu_memory_fraction: Option<f32>,
+    pub gpu_memory_bytes: Option<u64>,
     pub prometheus_port: Option<u16>,
+    /// Health-driven restarts are paused for this instance
+    pub maintenance: bool,
+    /// The multiplexer routes no new requests to this instance
+    pub paused: bool,
+    /// Whether the Prometheus endpoint answered the last probe (None until probed)
+    pub metrics_reachable: Option<bool>,
+    /// Requests currently being forwarded to this instance
+    pub inflight: usize,
+    /// Instances started (and ready) before this one
+    pub depends_on: Vec<String>,
+    /// Constant labels on this instance's metrics (excluding the manager's)
+    pub metric_labels: std::collections::BTreeMap<String, String>,
 }
 
 impl InstanceInfo {
@@ -84,14 +164,233 @@ impl InstanceInfo {
             created_at: instance.config.created_at,
             uptime_secs,
             restarts: stats.restarts,
+            manual_restart_count: stats.manual_restart_count,
             health_check_failures: stats.health_check_failures,
             last_health_check: stats.last_health_check,
             gpu_id: instance.config.gpu_id,
+            cpu_only: instance.config.cpu_only,
+            gpu_memory_fraction: instance.config.gpu_memory_fraction,
+            gpu_memory_bytes: instance.config.gpu_memory_bytes,
             prometheus_port: instance.config.prometheus_port,
+            maintenance: instance.in_maintenance(),
+            paused: instance.is_paused(),
+            metrics_reachable: stats.metrics_reachable,
+            inflight: instance.inflight().get(),
+            depends_on: instance.config.depends_on.clone(),
+            metric_labels: instance.config.metric_labels.clone(),
+        }
+    }
+}
+
+/// Everything known about one instance, for troubleshooting
+#[derive(Debug, Serialize)]
+pub struct InstanceDescription {
+    /// Instance config with secret args and env values redacted
+    pub config: InstanceConfig,
+    pub status: InstanceStatus,
+    pub pid: Option<u32>,
+    pub maintenance: bool,
+    pub paused: bool,
+    /// Counters plus the last health check and metrics probe results
+    pub stats: InstanceStats,
+    /// CUDA_VISIBLE_DEVICES given to the process (None = inherited from the manager)
+    pub cuda_visible_devices: Option<String>,
+    /// GPUs detected via nvidia-smi
+    pub gpu_count: usize,
+    /// Command line the process is started with, secrets redacted and
+    /// `${VAR}` references left unresolved
+    pub command: Vec<String>,
+    /// Last lines of the instance log (empty before the first start)
+    pub log_tail: Vec<String>,
+}
+
+impl InstanceDescription {
+    /// Describe `instance` as started with `tei_binary_path`
+    pub async fn from_instance(
+        instance: &TeiInstance,
+        tei_binary_path: &str,
+        log_tail: Vec<String>,
+    ) -> Self {
+        let config = instance.config.redacted();
+        let spawn_config = instance.launch_preview(tei_binary_path);
+
+        Self {
+            status: *instance.status.read().await,
+            pid: instance.pid().await,
+            maintenance: instance.in_maintenance(),
+            paused: instance.is_paused(),
+            stats: instance.stats.read().await.clone(),
+            cuda_visible_devices: spawn_config.cuda_visible_devices(),
+            gpu_count: crate::gpu::get_or_init().count(),
+            command: spawn_config.command_line(),
+            log_tail,
+            config,
+        }
+    }
+}
+
+/// Command line an instance is (or would be) launched with
+#[derive(Debug, Serialize)]
+pub struct InstanceCommand {
+    pub name: String,
+    /// Binary followed by its arguments, secrets redacted and `${VAR}`
+    /// references left unresolved
+    pub command: Vec<String>,
+    /// CUDA_VISIBLE_DEVICES given to the process (None = inherited from the manager)
+    pub cuda_visible_devices: Option<String>,
+    /// Extra environment for the process, redacted like `command`
+    pub env: std::collections::BTreeMap<String, String>,
+    /// File the rest of the environment is read from at start, not expanded here
+    pub env_file: Option<std::path::PathBuf>,
+}
+
+impl InstanceCommand {
+    /// Command for `instance` as started with `tei_binary_path`
+    pub fn from_instance(instance: &TeiInstance, tei_binary_path: &str) -> Self {
+        let spawn_config = instance.launch_preview(tei_binary_path);
+
+        Self {
+            name: instance.config.name.clone(),
+            command: spawn_config.command_line(),
+            cuda_visible_devices: spawn_config.cuda_visible_devices(),
+            env: spawn_config.env.into_iter().collect(),
+            env_file: instance.config.env_file.clone(),
+        }
+    }
+}
+
+/// Internal state of one instance in the registry dump
+#[derive(Debug, Serialize)]
+pub struct InstanceDebugInfo {
+    /// Instance config with secret args and env values redacted
+    pub config: InstanceConfig,
+    pub status: InstanceStatus,
+    pub pid: Option<u32>,
+    pub maintenance: bool,
+    pub paused: bool,
+    pub inflight: usize,
+    pub stats: InstanceStats,
+}
+
+impl InstanceDebugInfo {
+    pub async fn from_instance(instance: &TeiInstance) -> Self {
+        Self {
+            config: instance.config.redacted(),
+            status: *instance.status.read().await,
+            pid: instance.pid().await,
+            maintenance: instance.in_maintenance(),
+            paused: instance.is_paused(),
+            inflight: instance.inflight().get(),
+            stats: instance.stats.read().await.clone(),
+        }
+    }
+}
+
+/// Configs of every instance (GET /instances/export, POST /instances/import)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InstancesExport {
+    /// Sorted by name on export; inline secrets are redacted like GET /config
+    pub instances: Vec<InstanceConfig>,
+}
+
+/// Snapshot of registry internals for support bundles (GET /debug/registry)
+#[derive(Debug, Serialize)]
+pub struct RegistryDump {
+    pub generated_at: chrono::DateTime<chrono::Utc>,
+    /// Global maintenance mode
+    pub maintenance: bool,
+    pub max_instances: Option<usize>,
+    /// Port allocation cursors and the ports held by instances
+    pub allocator: PortAllocatorState,
+    /// Sorted by name
+    pub instances: Vec<InstanceDebugInfo>,
+}
+
+/// Request to enable or disable maintenance mode
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MaintenanceRequest {
+    pub enabled: bool,
+}
+
+/// Current maintenance state
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MaintenanceResponse {
+    /// Global maintenance mode: no instance is restarted by the health monitor
+    pub enabled: bool,
+    /// Instances with per-instance maintenance enabled, sorted by name
+    pub instances: Vec<String>,
+}
+
+/// Health of a single instance in the fleet summary
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InstanceHealth {
+    pub name: String,
+    pub status: InstanceStatus,
+    /// Running with no outstanding health check failures
+    pub healthy: bool,
+    pub last_health_check: Option<chrono::DateTime<chrono::Utc>>,
+    pub consecutive_failures: u32,
+    /// Prometheus endpoint reachability; a warning only, it does not affect `healthy`
+    pub metrics_reachable: Option<bool>,
+}
+
+impl InstanceHealth {
+    /// Create InstanceHealth from TeiInstance
+    pub async fn from_instance(instance: &TeiInstance) -> Self {
+        let status = *instance.status.read().await;
+        let stats = instance.stats.read().await;
+
+        Self {
+            name: instance.config.name.clone(),
+            status,
+            healthy: status == InstanceStatus::Running && stats.health_check_failures == 0,
+            last_health_check: stats.last_health_check,
+            consecutive_failures: stats.health_check_failures,
+            metrics_reachable: stats.metrics_reachable,
         }
     }
 }
 
+/// Outcome of one instance's on-demand health check
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HealthCheckReport {
+    pub name: String,
+    pub healthy: bool,
+    /// Why the check failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Instance counts by status
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct HealthSummary {
+    pub total: usize,
+    pub running: usize,
+    pub failed: usize,
+    pub starting: usize,
+}
+
+impl HealthSummary {
+    /// Count instances by status
+    pub fn from_instances(instances: &[InstanceHealth]) -> Self {
+        let count = |status| instances.iter().filter(|i| i.status == status).count();
+
+        Self {
+            total: instances.len(),
+            running: count(InstanceStatus::Running),
+            failed: count(InstanceStatus::Failed),
+            starting: count(InstanceStatus::Starting),
+        }
+    }
+}
+
+/// Fleet health response
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InstancesHealthResponse {
+    pub summary: HealthSummary,
+    pub instances: Vec<InstanceHealth>,
+}
+
 /// Log file response with Python-style slicing
 #[derive(Debug, Serialize, Deserialize)]
 pub struct LogsResponse {
@@ -101,6 +400,14 @@ pub struct LogsResponse {
     pub total_lines: usize,
 }
 
+/// Result of truncating an instance's log file
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ClearLogsResponse {
+    pub instance: String,
+    /// Size of the log file after truncation (0 unless the process wrote in between)
+    pub size_bytes: u64,
+}
+
 // ============================================================================
 // Model Management Types
 // ============================================================================
@@ -133,6 +440,12 @@ pub struct ModelInfo {
     pub metadata: Option<HfModelMetadata>,
     /// When this model was added to registry
     pub added_at: chrono::DateTime<chrono::Utc>,
+    /// Whether the model is listed in the manager config's `models`
+    #[serde(default)]
+    pub configured: bool,
+    /// Names of instances currently serving this model
+    #[serde(default)]
+    pub instances: Vec<String>,
 }
 
 impl From<ModelEntry> for ModelInfo {
@@ -150,6 +463,8 @@ impl From<ModelEntry> for ModelInfo {
             verification_error: entry.verification_error,
             metadata: entry.metadata,
             added_at: entry.added_at,
+            configured: false,
+            instances: Vec::new(),
         }
     }
 }
@@ -160,3 +475,165 @@ pub struct AddModelRequest {
     /// HuggingFace model ID (e.g., "BAAI/bge-small-en-v1.5")
     pub model_id: String,
 }
+
+/// Request to preload models into the HF cache
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PreloadModelsRequest {
+    /// HuggingFace model IDs to download
+    pub model_ids: Vec<String>,
+}
+
+/// Build and runtime environment information
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VersionResponse {
+    /// Crate version
+    pub version: String,
+    /// Git commit the binary was built from ("unknown" outside a checkout)
+    pub git_commit: String,
+    /// When the binary was built
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub build_timestamp: Option<chrono::DateTime<chrono::Utc>>,
+    /// Number of GPUs detected via nvidia-smi
+    pub gpu_count: usize,
+    /// CUDA_VISIBLE_DEVICES of the manager process, if set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cuda_visible_devices: Option<String>,
+}
+
+impl VersionResponse {
+    /// Metadata for the running binary
+    pub fn current() -> Self {
+        let build_timestamp = env!("TEI_MANAGER_BUILD_EPOCH")
+            .parse::<i64>()
+            .ok()
+            .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0));
+
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            git_commit: env!("TEI_MANAGER_GIT_SHA").to_string(),
+            build_timestamp,
+            gpu_count: crate::gpu::get_or_init().count(),
+            cuda_visible_devices: std::env::var("CUDA_VISIBLE_DEVICES").ok(),
+        }
+    }
+}
+
+/// One input line of an NDJSON batch embedding request
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JsonlEmbedLine {
+    /// Caller-chosen identifier, echoed back unchanged
+    pub id: serde_json::Value,
+    /// Text to embed
+    pub text: String,
+    /// Truncate the embedding to this many dimensions (Matryoshka models)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dimensions: Option<u32>,
+    /// Cut texts longer than the model's max input length instead of failing the line
+    #[serde(default)]
+    pub truncate: bool,
+}
+
+/// One output line of an NDJSON batch embedding response
+///
+/// Exactly one of `embedding` or `error` is set.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JsonlEmbedResult {
+    /// Identifier from the input line (null if the line could not be parsed)
+    pub id: serde_json::Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<Vec<f32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl JsonlEmbedResult {
+    /// Result line reporting a failure for `id`
+    pub fn error(id: serde_json::Value, error: String) -> Self {
+        Self {
+            id,
+            embedding: None,
+            error: Some(error),
+        }
+    }
+}
+
+/// Text message sent by a WebSocket embed client
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WsEmbedRequest {
+    /// Text to embed
+    pub text: String,
+    /// Truncate the embedding to this many dimensions (Matryoshka models)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dimensions: Option<u32>,
+}
+
+/// Text message answering one [`WsEmbedRequest`], in request order
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WsEmbedResponse {
+    pub embedding: Vec<f32>,
+}
+
+/// Body of the OpenAI-compatible `POST /v1/embeddings`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OpenAiEmbeddingRequest {
+    /// Model id, routed to a running instance serving it
+    pub model: String,
+    pub input: OpenAiEmbeddingInput,
+    /// "float" (default) or "base64" (little-endian f32 bytes)
+    #[serde(default)]
+    pub encoding_format: Option<String>,
+    /// Truncate embeddings to this many dimensions (Matryoshka models)
+    #[serde(default)]
+    pub dimensions: Option<u32>,
+}
+
+/// A single text or a batch of texts
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum OpenAiEmbeddingInput {
+    Single(String),
+    Batch(Vec<String>),
+}
+
+impl OpenAiEmbeddingInput {
+    pub fn into_texts(self) -> Vec<String> {
+        match self {
+            Self::Single(text) => vec![text],
+            Self::Batch(texts) => texts,
+        }
+    }
+}
+
+/// OpenAI-shaped embeddings response
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OpenAiEmbeddingResponse {
+    /// Always "list"
+    pub object: String,
+    pub data: Vec<OpenAiEmbedding>,
+    pub model: String,
+    pub usage: OpenAiUsage,
+}
+
+/// One embedding of an [`OpenAiEmbeddingResponse`], in input order
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OpenAiEmbedding {
+    /// Always "embedding"
+    pub object: String,
+    pub embedding: OpenAiEmbeddingVector,
+    pub index: usize,
+}
+
+/// Embedding values as floats, or base64 when requested
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum OpenAiEmbeddingVector {
+    Float(Vec<f32>),
+    Base64(String),
+}
+
+/// Tokens the backend reported computing
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct OpenAiUsage {
+    pub prompt_tokens: u32,
+    pub total_tokens: u32,
+}
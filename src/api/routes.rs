@@ -1,11 +1,20 @@
 //! API route definitions
 
+use crate::audit::AuditSink;
 use crate::auth::AuthManager;
-use crate::models::{ModelLoader, ModelRegistry};
+use crate::config::{ErrorFormat, ManagerConfig};
+use crate::error::ProblemDetails;
+use crate::grpc::access_log::AccessLog;
+use crate::grpc::pool::BackendPool;
+use crate::models::{EmbeddingDimensionSource, ModelLoader, ModelRegistry, PreloadTracker};
 use crate::registry::Registry;
 use crate::state::StateManager;
 use axum::{
     Router,
+    extract::{DefaultBodyLimit, Request},
+    http::{StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
     routing::{delete, get, post},
 };
 use std::sync::Arc;
@@ -25,23 +34,57 @@ pub struct AppState {
     pub require_cert_headers: bool,
     pub model_registry: Arc<ModelRegistry>,
     pub model_loader: Arc<ModelLoader>,
+    pub preload_tracker: Arc<PreloadTracker>,
+    /// Effective configuration (file + env overrides), served redacted by GET /config
+    pub config: Arc<ManagerConfig>,
+    /// Backend connections for HTTP endpoints that forward to instances
+    pub backend_pool: BackendPool,
+    /// Audit trail for mutating instance actions (None = disabled)
+    pub audit_sink: Option<Arc<dyn AuditSink>>,
+    /// gRPC access log streamed by /debug/access-log/stream (None = disabled)
+    pub access_log: Option<Arc<AccessLog>>,
+    /// Native embedding dimension per model, for validating requested `dimensions`
+    pub embedding_dimensions: Arc<dyn EmbeddingDimensionSource>,
 }
 
 /// Create the main API router
 pub fn create_router(state: AppState) -> Router {
     let auth_manager = state.auth_manager.clone();
     let require_cert_headers = state.require_cert_headers;
+    let max_body_bytes = state.config.http_max_body_bytes;
+    let max_embed_body_bytes = state.config.http_max_embed_body_bytes;
+    let debug_endpoints = state.config.debug_endpoints;
+    let error_format = state.config.error_format;
 
     let mut router = Router::new()
         // Health and status (always public)
         .route("/health", get(handlers::health))
-        .route("/metrics", get(handlers::metrics));
+        .route("/metrics", get(handlers::metrics))
+        .route("/version", get(handlers::version));
 
     // Protected routes - require auth if enabled
     let protected_routes = Router::new()
+        // Effective configuration (secrets redacted)
+        .route("/config", get(handlers::get_config))
+        // Fleet health summary
+        .route("/health/instances", get(handlers::instances_health))
+        // Live instance lifecycle events, optionally replaying recent ones
+        .route("/events", get(handlers::events))
+        // Port allocation range usage
+        .route("/ports", get(handlers::ports))
+        .route("/routing", get(handlers::routing))
+        // Maintenance mode (pauses health-driven restarts)
+        .route(
+            "/admin/maintenance",
+            get(handlers::get_maintenance).post(handlers::set_maintenance),
+        )
+        // On-demand health check of every instance
+        .route("/admin/healthcheck", post(handlers::check_all_instances))
         // Instance management (no PATCH - delete and recreate instead)
         .route("/instances", get(handlers::list_instances))
         .route("/instances", post(handlers::create_instance))
+        .route("/instances/export", get(handlers::export_instances))
+        .route("/instances/import", post(handlers::import_instances))
         .route("/instances/{name}", get(handlers::get_instance))
         .route("/instances/{name}", delete(handlers::delete_instance))
         // Instance lifecycle
@@ -51,11 +94,46 @@ pub fn create_router(state: AppState) -> Router {
             "/instances/{name}/restart",
             post(handlers::restart_instance),
         )
+        .route("/instances/{name}/pause", post(handlers::pause_instance))
+        .route("/instances/{name}/resume", post(handlers::resume_instance))
+        .route(
+            "/instances/{name}/unquarantine",
+            post(handlers::unquarantine_instance),
+        )
+        .route(
+            "/instances/{name}/maintenance",
+            post(handlers::set_instance_maintenance),
+        )
         // Instance logs
         .route("/instances/{name}/logs", get(handlers::get_logs))
+        .route("/instances/{name}/logs/clear", post(handlers::clear_logs))
+        .route(
+            "/instances/{name}/describe",
+            get(handlers::describe_instance),
+        )
+        .route(
+            "/instances/{name}/command",
+            get(handlers::get_instance_command),
+        )
+        // Scoped Prometheus metrics of one instance
+        .route("/instances/{name}/metrics", get(handlers::instance_metrics))
+        // Batch embedding (data plane, with its own body limit)
+        .route(
+            "/instances/{name}/embed/jsonl",
+            post(handlers::embed_jsonl).layer(DefaultBodyLimit::max(max_embed_body_bytes)),
+        )
+        // Streaming embedding over WebSocket (data plane)
+        .route("/instances/{name}/embed/ws", get(handlers::embed_ws))
+        // OpenAI-compatible embeddings, routed by model (data plane)
+        .route(
+            "/v1/embeddings",
+            post(handlers::openai_embeddings).layer(DefaultBodyLimit::max(max_embed_body_bytes)),
+        )
         // Model management
         .route("/models", get(handlers::list_models))
         .route("/models", post(handlers::add_model))
+        .route("/models/preload", post(handlers::preload_models))
+        .route("/models/preload/{job_id}", get(handlers::get_preload_job))
         .route("/models/{model_id}", get(handlers::get_model))
         .route(
             "/models/{model_id}/download",
@@ -63,6 +141,18 @@ pub fn create_router(state: AppState) -> Router {
         )
         .route("/models/{model_id}/load", post(handlers::load_model));
 
+    // Internal state dumps, only when explicitly enabled
+    let protected_routes = if debug_endpoints {
+        protected_routes
+            .route("/debug/registry", get(handlers::debug_registry))
+            .route(
+                "/debug/access-log/stream",
+                get(handlers::debug_access_log_stream),
+            )
+    } else {
+        protected_routes
+    };
+
     // Add auth middleware to protected routes if auth is enabled
     let protected_routes = if let Some(auth) = auth_manager {
         tracing::info!(
@@ -98,10 +188,47 @@ pub fn create_router(state: AppState) -> Router {
     router.with_state(state).layer(
         ServiceBuilder::new()
             .layer(TraceLayer::new_for_http())
-            .layer(CorsLayer::permissive()),
+            .layer(CorsLayer::permissive())
+            .layer(axum::middleware::from_fn(move |req, next| {
+                format_errors(error_format, req, next)
+            }))
+            .layer(axum::middleware::map_response(move |response| {
+                payload_too_large_as_json(response, max_body_bytes)
+            }))
+            .layer(DefaultBodyLimit::max(max_body_bytes)),
     )
 }
 
+/// Replace axum's plain-text body limit rejection with the standard error JSON
+///
+/// Handlers that enforce their own limit already respond with JSON and pass through.
+async fn payload_too_large_as_json(response: Response, limit: usize) -> Response {
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .is_some_and(|v| v.as_bytes().starts_with(b"application/json"));
+
+    if response.status() == StatusCode::PAYLOAD_TOO_LARGE && !is_json {
+        crate::error::TeiError::PayloadTooLarge { limit }.into_response()
+    } else {
+        response
+    }
+}
+
+/// Rewrite error responses as problem+json documents when configured
+async fn format_errors(format: ErrorFormat, req: Request, next: Next) -> Response {
+    if format == ErrorFormat::Simple {
+        return next.run(req).await;
+    }
+
+    let path = req.uri().path().to_string();
+    let mut response = next.run(req).await;
+    match response.extensions_mut().remove::<ProblemDetails>() {
+        Some(problem) => problem.with_instance(path).into_response(),
+        None => response,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -144,8 +271,13 @@ mod tests {
         let prometheus_handle = get_prometheus_handle();
         let model_registry = Arc::new(crate::models::ModelRegistry::new());
         let model_loader = Arc::new(crate::models::ModelLoader::new());
+        let preload_tracker = Arc::new(crate::models::PreloadTracker::new());
 
         AppState {
+            backend_pool: BackendPool::new(registry.clone()),
+            audit_sink: None,
+            access_log: None,
+            embedding_dimensions: Arc::new(crate::models::CachedEmbeddingDimensions::default()),
             registry,
             state_manager,
             prometheus_handle,
@@ -153,6 +285,8 @@ mod tests {
             require_cert_headers: false,
             model_registry,
             model_loader,
+            preload_tracker,
+            config: Arc::new(ManagerConfig::default()),
         }
     }
 
@@ -256,6 +390,39 @@ mod tests {
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
     }
 
+    #[tokio::test]
+    async fn test_problem_json_error_format() {
+        let mut state = create_test_state();
+        state.config = Arc::new(ManagerConfig {
+            error_format: ErrorFormat::Problem,
+            ..Default::default()
+        });
+        let app = create_router(state);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/instances/nonexistent")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            crate::error::PROBLEM_JSON
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["type"], "urn:tei-manager:error:instance-not-found");
+        assert_eq!(body["status"], 404);
+        assert_eq!(body["instance"], "/instances/nonexistent");
+    }
+
     #[tokio::test]
     async fn test_app_state_clone() {
         let state = create_test_state();
@@ -308,4 +475,42 @@ mod tests {
         // With empty providers and no cert header, defaults to passing (native TLS assumption)
         assert_eq!(response.status(), StatusCode::OK);
     }
+
+    #[tokio::test]
+    async fn test_config_endpoint_requires_auth() {
+        let mut state = create_test_state_with_auth();
+        state.require_cert_headers = true;
+        let app = create_router(state);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/config")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_instances_health_requires_auth() {
+        let mut state = create_test_state_with_auth();
+        state.require_cert_headers = true;
+        let app = create_router(state);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/health/instances")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
 }
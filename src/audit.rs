@@ -0,0 +1,119 @@
+//! Audit trail for mutating API actions
+//!
+//! Each successful create/start/stop/restart/delete/unquarantine and maintenance toggle
+//! is recorded with the authenticated principal. Sinks are pluggable via [`AuditSink`].
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+/// Mutating action performed through the API
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    Create,
+    Start,
+    Stop,
+    Restart,
+    Delete,
+    Unquarantine,
+    MaintenanceOn,
+    MaintenanceOff,
+}
+
+/// One audit record
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub action: AuditAction,
+    pub instance: String,
+    /// Authenticated principal, or None when auth is disabled or did not identify the caller
+    pub principal: Option<String>,
+}
+
+impl AuditEntry {
+    /// Entry for an action performed now
+    pub fn now(action: AuditAction, instance: &str, principal: Option<String>) -> Self {
+        Self {
+            timestamp: chrono::Utc::now(),
+            action,
+            instance: instance.to_string(),
+            principal,
+        }
+    }
+}
+
+/// Destination for audit entries
+#[async_trait]
+pub trait AuditSink: Send + Sync {
+    async fn record(&self, entry: &AuditEntry) -> Result<()>;
+}
+
+/// Appends entries to a file as JSON lines
+pub struct FileAuditSink {
+    path: PathBuf,
+    // Serializes writers so concurrent entries never interleave
+    write_lock: Mutex<()>,
+}
+
+impl FileAuditSink {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            write_lock: Mutex::new(()),
+        }
+    }
+}
+
+#[async_trait]
+impl AuditSink for FileAuditSink {
+    async fn record(&self, entry: &AuditEntry) -> Result<()> {
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+
+        let _guard = self.write_lock.lock().await;
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .with_context(|| format!("Failed to open audit log {:?}", self.path))?;
+        file.write_all(&line)
+            .await
+            .with_context(|| format!("Failed to write audit log {:?}", self.path))?;
+        file.flush().await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_file_sink_appends_json_lines() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("audit.log");
+        let sink = FileAuditSink::new(path.clone());
+
+        let create = AuditEntry::now(AuditAction::Create, "bge", Some("CN=alice".to_string()));
+        let delete = AuditEntry::now(AuditAction::Delete, "bge", None);
+        sink.record(&create).await.unwrap();
+        sink.record(&delete).await.unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let entries: Vec<AuditEntry> = content
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(entries, vec![create, delete]);
+
+        let raw: serde_json::Value = serde_json::from_str(content.lines().next().unwrap()).unwrap();
+        assert_eq!(raw["action"], "create");
+        assert_eq!(raw["instance"], "bge");
+        assert_eq!(raw["principal"], "CN=alice");
+    }
+}
@@ -91,6 +91,10 @@ pub struct AuthResult {
     pub metadata: HashMap<String, String>,
 }
 
+/// Authenticated identity, added to request extensions by the HTTP auth middleware
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Principal(pub String);
+
 /// Authentication provider trait
 #[async_trait]
 pub trait AuthProvider: Send + Sync {
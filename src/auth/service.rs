@@ -1,6 +1,6 @@
 //! Authentication service middleware for Axum
 
-use super::{AuthError, AuthManager, AuthRequest, Protocol, TlsInfo};
+use super::{AuthError, AuthManager, AuthRequest, Principal, Protocol, TlsInfo};
 use axum::{
     extract::Request,
     http::{HeaderMap, StatusCode},
@@ -91,7 +91,7 @@ pub async fn auth_middleware(
 pub async fn auth_middleware_with_options(
     auth_manager: Arc<AuthManager>,
     require_cert_headers: bool,
-    request: Request,
+    mut request: Request,
     next: Next,
 ) -> Result<Response, AuthError> {
     // Extract headers and peer address
@@ -140,7 +140,9 @@ pub async fn auth_middleware_with_options(
     // Authenticate
     match auth_manager.authenticate(&auth_request).await {
         Ok(result) if result.authenticated => {
-            // TODO: Add principal to request extensions for downstream handlers
+            if let Some(principal) = result.principal {
+                request.extensions_mut().insert(Principal(principal));
+            }
             Ok(next.run(request).await)
         }
         Ok(_) => Err(AuthError::Unauthorized("Authentication failed".to_string())),
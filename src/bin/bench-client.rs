@@ -14,7 +14,8 @@ use tokio::sync::Semaphore;
 use tonic::transport::{Certificate, Channel, ClientTlsConfig, Identity};
 
 use tei_manager::grpc::proto::multiplexer::v1::{
-    EmbedArrowRequest, EmbedRequest, Target, tei_multiplexer_client::TeiMultiplexerClient,
+    ArrowCompression, EmbedArrowRequest, EmbedRequest, Target,
+    tei_multiplexer_client::TeiMultiplexerClient,
 };
 use tei_manager::grpc::proto::tei::v1 as tei;
 
@@ -26,6 +27,30 @@ enum BenchMode {
     Arrow,
 }
 
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Compression {
+    /// LZ4 frame (the multiplexer's default)
+    Lz4,
+    /// Zstandard, better ratios on text-heavy batches
+    Zstd,
+}
+
+impl Compression {
+    fn ipc(self) -> arrow::ipc::CompressionType {
+        match self {
+            Self::Lz4 => arrow::ipc::CompressionType::LZ4_FRAME,
+            Self::Zstd => arrow::ipc::CompressionType::ZSTD,
+        }
+    }
+
+    fn proto(self) -> ArrowCompression {
+        match self {
+            Self::Lz4 => ArrowCompression::Lz4Frame,
+            Self::Zstd => ArrowCompression::Zstd,
+        }
+    }
+}
+
 #[derive(Parser, Debug)]
 #[clap(
     name = "tei-bench-client",
@@ -75,6 +100,10 @@ struct Args {
     /// Max message size in MB (default: 100, Arrow mode only)
     #[clap(long, default_value = "100")]
     max_message_size_mb: usize,
+
+    /// Arrow IPC compression of requests and responses (Arrow mode only)
+    #[clap(long, value_enum, default_value = "lz4")]
+    compression: Compression,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -285,6 +314,7 @@ async fn benchmark_arrow(
     texts: Vec<String>,
     batch_size: usize,
     noop: bool,
+    compression: Compression,
 ) -> Result<BenchmarkResult> {
     let total_texts = texts.len();
     let start = Instant::now();
@@ -301,14 +331,13 @@ async fn benchmark_arrow(
         let schema = Arc::new(Schema::new(vec![Field::new("text", DataType::Utf8, false)]));
         let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(text_array) as ArrayRef])?;
 
-        // Serialize to Arrow IPC with LZ4 compression
+        // Serialize to compressed Arrow IPC
         let mut arrow_ipc = Vec::new();
         {
-            use arrow::ipc::CompressionType;
             use arrow::ipc::writer::IpcWriteOptions;
 
-            let write_options = IpcWriteOptions::default()
-                .try_with_compression(Some(CompressionType::LZ4_FRAME))?;
+            let write_options =
+                IpcWriteOptions::default().try_with_compression(Some(compression.ipc()))?;
 
             let mut writer =
                 StreamWriter::try_new_with_options(&mut arrow_ipc, &schema, write_options)?;
@@ -329,6 +358,10 @@ async fn benchmark_arrow(
             truncate: true,
             normalize: true,
             noop,
+            truncation_direction: 0,
+            compression: compression.proto() as i32,
+            dimensions: None,
+            noop_mode: 0,
         };
 
         match client.embed_arrow(request).await {
@@ -419,6 +452,7 @@ async fn main() -> Result<()> {
                 texts,
                 args.batch_size,
                 args.noop,
+                args.compression,
             )
             .await?
         }
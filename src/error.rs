@@ -6,7 +6,7 @@
 
 use axum::{
     Json,
-    http::StatusCode,
+    http::{StatusCode, header},
     response::{IntoResponse, Response},
 };
 use serde::Serialize;
@@ -44,6 +44,10 @@ pub enum TeiError {
     #[error("Model '{model_id}' is already {operation}")]
     ModelBusy { model_id: String, operation: String },
 
+    /// Preload job not found
+    #[error("Preload job '{job_id}' not found")]
+    PreloadJobNotFound { job_id: String },
+
     /// Instance with the given name already exists
     #[error("Instance '{name}' already exists")]
     InstanceExists { name: String },
@@ -79,6 +83,18 @@ pub enum TeiError {
     #[error("Invalid GPU ID {id}: {reason}")]
     InvalidGpuId { id: u32, reason: String },
 
+    /// Instance's GPU memory reservation does not fit on its GPU
+    #[error("GPU {id} has {available} bytes of memory unreserved, instance needs {required} bytes")]
+    GpuMemoryExceeded {
+        id: u32,
+        required: u64,
+        available: u64,
+    },
+
+    /// GPU ID cannot be checked because GPU detection failed
+    #[error("Cannot validate GPU ID {id}: {reason} (set allow_gpu_without_smi to trust it)")]
+    GpuDetectionUnavailable { id: u32, reason: String },
+
     /// Invalid instance name
     #[error("Invalid instance name '{name}': {reason}")]
     InvalidInstanceName { name: String, reason: String },
@@ -109,6 +125,13 @@ pub enum TeiError {
     #[error("Missing required field: {field}")]
     MissingField { field: String },
 
+    /// Request body exceeds the configured size limit
+    #[error(
+        "Request body exceeds limit of {limit} bytes; send smaller batches or raise \
+         http_max_body_bytes (http_max_embed_body_bytes for embed endpoints)"
+    )]
+    PayloadTooLarge { limit: usize },
+
     // ========================================================================
     // External Service Errors (5xx)
     // ========================================================================
@@ -130,6 +153,12 @@ pub enum TeiError {
     /// I/O error
     #[error("I/O error: {message}")]
     IoError { message: String },
+
+    /// Configured TEI binary could not be executed
+    #[error(
+        "TEI binary '{path}' not found or not executable; set tei_binary_path (or TEI_BINARY_PATH) to the text-embeddings-router location"
+    )]
+    TeiBinaryNotFound { path: String },
 }
 
 impl TeiError {
@@ -138,17 +167,21 @@ impl TeiError {
     pub fn status_code(&self) -> StatusCode {
         match self {
             // 404 Not Found
-            Self::InstanceNotFound { .. } | Self::ModelNotFound { .. } => StatusCode::NOT_FOUND,
+            Self::InstanceNotFound { .. }
+            | Self::ModelNotFound { .. }
+            | Self::PreloadJobNotFound { .. } => StatusCode::NOT_FOUND,
 
             // 409 Conflict
-            Self::InstanceExists { .. } | Self::PortConflict { .. } | Self::ModelBusy { .. } => {
-                StatusCode::CONFLICT
-            }
+            Self::InstanceExists { .. }
+            | Self::PortConflict { .. }
+            | Self::ModelBusy { .. }
+            | Self::GpuMemoryExceeded { .. } => StatusCode::CONFLICT,
 
             // 400 Bad Request
             Self::InvalidConfig { .. }
             | Self::InvalidPort { .. }
             | Self::InvalidGpuId { .. }
+            | Self::GpuDetectionUnavailable { .. }
             | Self::InvalidInstanceName { .. }
             | Self::ValidationError { .. }
             | Self::MissingField { .. }
@@ -160,6 +193,9 @@ impl TeiError {
             // 403 Forbidden
             Self::Forbidden { .. } => StatusCode::FORBIDDEN,
 
+            // 413 Payload Too Large
+            Self::PayloadTooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE,
+
             // 422 Unprocessable Entity
             Self::MaxInstancesReached { .. } | Self::PortAllocationFailed { .. } => {
                 StatusCode::UNPROCESSABLE_ENTITY
@@ -175,7 +211,8 @@ impl TeiError {
             Self::Internal { .. }
             | Self::IoError { .. }
             | Self::ModelDownloadFailed { .. }
-            | Self::ModelLoadFailed { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+            | Self::ModelLoadFailed { .. }
+            | Self::TeiBinaryNotFound { .. } => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
 
@@ -188,6 +225,7 @@ impl TeiError {
             Self::ModelDownloadFailed { .. } => "MODEL_DOWNLOAD_FAILED",
             Self::ModelLoadFailed { .. } => "MODEL_LOAD_FAILED",
             Self::ModelBusy { .. } => "MODEL_BUSY",
+            Self::PreloadJobNotFound { .. } => "PRELOAD_JOB_NOT_FOUND",
             Self::InstanceExists { .. } => "INSTANCE_EXISTS",
             Self::PortConflict { .. } => "PORT_CONFLICT",
             Self::MaxInstancesReached { .. } => "MAX_INSTANCES_REACHED",
@@ -195,16 +233,20 @@ impl TeiError {
             Self::InvalidConfig { .. } => "INVALID_CONFIG",
             Self::InvalidPort { .. } => "INVALID_PORT",
             Self::InvalidGpuId { .. } => "INVALID_GPU_ID",
+            Self::GpuDetectionUnavailable { .. } => "GPU_DETECTION_UNAVAILABLE",
+            Self::GpuMemoryExceeded { .. } => "GPU_MEMORY_EXCEEDED",
             Self::InvalidInstanceName { .. } => "INVALID_INSTANCE_NAME",
             Self::PortAllocationFailed { .. } => "PORT_ALLOCATION_FAILED",
             Self::Unauthenticated { .. } => "UNAUTHENTICATED",
             Self::Forbidden { .. } => "FORBIDDEN",
             Self::ValidationError { .. } => "VALIDATION_ERROR",
             Self::MissingField { .. } => "MISSING_FIELD",
+            Self::PayloadTooLarge { .. } => "PAYLOAD_TOO_LARGE",
             Self::BackendUnavailable { .. } => "BACKEND_UNAVAILABLE",
             Self::Timeout { .. } => "TIMEOUT",
             Self::Internal { .. } => "INTERNAL_ERROR",
             Self::IoError { .. } => "IO_ERROR",
+            Self::TeiBinaryNotFound { .. } => "TEI_BINARY_NOT_FOUND",
         }
     }
 
@@ -241,6 +283,29 @@ impl From<anyhow::Error> for TeiError {
     }
 }
 
+impl From<crate::gpu::GpuError> for TeiError {
+    fn from(err: crate::gpu::GpuError) -> Self {
+        match err {
+            crate::gpu::GpuError::DetectionUnavailable { id, reason } => {
+                Self::GpuDetectionUnavailable { id, reason }
+            }
+            crate::gpu::GpuError::OutOfRange { id, available } => Self::InvalidGpuId {
+                id,
+                reason: format!("Available GPUs: {:?}", available),
+            },
+            crate::gpu::GpuError::InsufficientMemory {
+                id,
+                required,
+                available,
+            } => Self::GpuMemoryExceeded {
+                id,
+                required,
+                available,
+            },
+        }
+    }
+}
+
 // ============================================================================
 // HTTP Response conversion
 // ============================================================================
@@ -269,13 +334,67 @@ impl IntoResponse for TeiError {
             tracing::debug!(error = %message, code = %code, "Client error");
         }
 
+        let problem = ProblemDetails::new(&self);
         let body = Json(ErrorResponse {
             error: message,
             code,
             timestamp: chrono::Utc::now(),
         });
 
-        (status, body).into_response()
+        // Kept for the problem+json layer, which knows the request path
+        let mut response = (status, body).into_response();
+        response.extensions_mut().insert(problem);
+        response
+    }
+}
+
+/// Content type of [`ProblemDetails`] responses
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+/// RFC 7807 problem document, sent instead of [`ErrorResponse`] with
+/// `error_format = "problem"`
+#[derive(Debug, Clone, Serialize)]
+pub struct ProblemDetails {
+    /// URN identifying the error type, derived from the error code
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    /// Reason phrase of the status code
+    pub title: &'static str,
+    pub status: u16,
+    /// Human-readable error message
+    pub detail: String,
+    /// Path of the request that failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+}
+
+impl ProblemDetails {
+    /// Problem document for `err`, without an `instance`
+    pub fn new(err: &TeiError) -> Self {
+        let status = err.status_code();
+        Self {
+            problem_type: format!(
+                "urn:tei-manager:error:{}",
+                err.error_code().to_ascii_lowercase().replace('_', "-")
+            ),
+            title: status.canonical_reason().unwrap_or("Unknown"),
+            status: status.as_u16(),
+            detail: err.to_string(),
+            instance: None,
+        }
+    }
+
+    /// Set the request path the problem occurred on
+    pub fn with_instance(mut self, instance: impl Into<String>) -> Self {
+        self.instance = Some(instance.into());
+        self
+    }
+}
+
+impl IntoResponse for ProblemDetails {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        (status, [(header::CONTENT_TYPE, PROBLEM_JSON)], Json(self)).into_response()
     }
 }
 
@@ -284,17 +403,21 @@ impl IntoResponse for TeiError {
 // ============================================================================
 
 impl From<TeiError> for tonic::Status {
+    /// The status carries an `ErrorInfo` detail with the HTTP error code as its
+    /// reason, see [`crate::grpc::status`]
     fn from(err: TeiError) -> Self {
+        use tonic::Code;
+
         let message = err.to_string();
-        match err {
-            TeiError::InstanceNotFound { .. } | TeiError::ModelNotFound { .. } => {
-                tonic::Status::not_found(message)
-            }
+        let code = match &err {
+            TeiError::InstanceNotFound { .. }
+            | TeiError::ModelNotFound { .. }
+            | TeiError::PreloadJobNotFound { .. } => Code::NotFound,
             TeiError::InstanceExists { .. }
             | TeiError::PortConflict { .. }
-            | TeiError::ModelBusy { .. } => tonic::Status::already_exists(message),
+            | TeiError::ModelBusy { .. } => Code::AlreadyExists,
             TeiError::ModelDownloadFailed { .. } | TeiError::ModelLoadFailed { .. } => {
-                tonic::Status::internal(message)
+                Code::Internal
             }
             TeiError::InvalidConfig { .. }
             | TeiError::InvalidPort { .. }
@@ -302,18 +425,31 @@ impl From<TeiError> for tonic::Status {
             | TeiError::InvalidInstanceName { .. }
             | TeiError::ValidationError { .. }
             | TeiError::MissingField { .. }
-            | TeiError::InvalidInstanceState { .. } => tonic::Status::invalid_argument(message),
-            TeiError::Unauthenticated { .. } => tonic::Status::unauthenticated(message),
-            TeiError::Forbidden { .. } => tonic::Status::permission_denied(message),
-            TeiError::MaxInstancesReached { .. } | TeiError::PortAllocationFailed { .. } => {
-                tonic::Status::resource_exhausted(message)
+            | TeiError::InvalidInstanceState { .. } => Code::InvalidArgument,
+            TeiError::Unauthenticated { .. } => Code::Unauthenticated,
+            TeiError::Forbidden { .. } => Code::PermissionDenied,
+            TeiError::MaxInstancesReached { .. }
+            | TeiError::PortAllocationFailed { .. }
+            | TeiError::GpuMemoryExceeded { .. }
+            | TeiError::PayloadTooLarge { .. } => Code::ResourceExhausted,
+            TeiError::BackendUnavailable { .. } => Code::Unavailable,
+            TeiError::Timeout { .. } => Code::DeadlineExceeded,
+            TeiError::TeiBinaryNotFound { .. } | TeiError::GpuDetectionUnavailable { .. } => {
+                Code::FailedPrecondition
             }
-            TeiError::BackendUnavailable { .. } => tonic::Status::unavailable(message),
-            TeiError::Timeout { .. } => tonic::Status::deadline_exceeded(message),
-            TeiError::Internal { .. } | TeiError::IoError { .. } => {
-                tonic::Status::internal(message)
-            }
-        }
+            TeiError::Internal { .. } | TeiError::IoError { .. } => Code::Internal,
+        };
+        let metadata: &[(&str, &str)] = match &err {
+            TeiError::InstanceNotFound { name }
+            | TeiError::InstanceExists { name }
+            | TeiError::InvalidInstanceState { name, .. } => &[("instance", name)],
+            TeiError::ModelNotFound { model_id }
+            | TeiError::ModelDownloadFailed { model_id, .. }
+            | TeiError::ModelLoadFailed { model_id, .. }
+            | TeiError::ModelBusy { model_id, .. } => &[("model", model_id)],
+            _ => &[],
+        };
+        crate::grpc::status::error_status(code, message, err.error_code(), metadata)
     }
 }
 
@@ -392,6 +528,11 @@ mod tests {
             StatusCode::SERVICE_UNAVAILABLE
         );
 
+        assert_eq!(
+            TeiError::PayloadTooLarge { limit: 1024 }.status_code(),
+            StatusCode::PAYLOAD_TOO_LARGE
+        );
+
         assert_eq!(
             TeiError::Timeout {
                 message: "test".into()
@@ -427,6 +568,24 @@ mod tests {
             .error_code(),
             "PORT_CONFLICT"
         );
+
+        assert_eq!(
+            TeiError::from(crate::gpu::GpuError::DetectionUnavailable {
+                id: 0,
+                reason: "nvidia-smi not found".into()
+            })
+            .error_code(),
+            "GPU_DETECTION_UNAVAILABLE"
+        );
+
+        assert_eq!(
+            TeiError::from(crate::gpu::GpuError::OutOfRange {
+                id: 2,
+                available: vec![0, 1]
+            })
+            .error_code(),
+            "INVALID_GPU_ID"
+        );
     }
 
     #[test]
@@ -485,6 +644,73 @@ mod tests {
         assert!(matches!(tei_err, TeiError::IoError { .. }));
     }
 
+    async fn body_json(response: Response) -> serde_json::Value {
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_simple_error_format() {
+        let response = TeiError::InstanceNotFound {
+            name: "missing".into(),
+        }
+        .into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+        let body = body_json(response).await;
+        assert_eq!(body["error"], "Instance 'missing' not found");
+        assert_eq!(body["code"], "INSTANCE_NOT_FOUND");
+        assert!(body["timestamp"].is_string());
+
+        let response = TeiError::Internal {
+            message: "boom".into(),
+        }
+        .into_response();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        let body = body_json(response).await;
+        assert_eq!(body["error"], "Internal error: boom");
+        assert_eq!(body["code"], "INTERNAL_ERROR");
+    }
+
+    #[tokio::test]
+    async fn test_problem_error_format() {
+        let response = ProblemDetails::new(&TeiError::InstanceNotFound {
+            name: "missing".into(),
+        })
+        .with_instance("/instances/missing")
+        .into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], PROBLEM_JSON);
+        assert_eq!(
+            body_json(response).await,
+            serde_json::json!({
+                "type": "urn:tei-manager:error:instance-not-found",
+                "title": "Not Found",
+                "status": 404,
+                "detail": "Instance 'missing' not found",
+                "instance": "/instances/missing",
+            })
+        );
+
+        let response = ProblemDetails::new(&TeiError::Internal {
+            message: "boom".into(),
+        })
+        .into_response();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], PROBLEM_JSON);
+        assert_eq!(
+            body_json(response).await,
+            serde_json::json!({
+                "type": "urn:tei-manager:error:internal-error",
+                "title": "Internal Server Error",
+                "status": 500,
+                "detail": "Internal error: boom",
+            })
+        );
+    }
+
     #[test]
     fn test_grpc_status_conversion() {
         let err = TeiError::InstanceNotFound {
@@ -505,4 +731,21 @@ mod tests {
         let status: tonic::Status = err.into();
         assert_eq!(status.code(), tonic::Code::DeadlineExceeded);
     }
+
+    #[test]
+    fn test_grpc_status_has_error_info() {
+        let err = TeiError::InstanceNotFound { name: "bge".into() };
+        let status: tonic::Status = err.into();
+        let info = crate::grpc::status::error_info(&status).unwrap();
+        assert_eq!(info.reason, "INSTANCE_NOT_FOUND");
+        assert_eq!(info.metadata["instance"], "bge");
+
+        let err = TeiError::Timeout {
+            message: "took too long".into(),
+        };
+        let status: tonic::Status = err.into();
+        let info = crate::grpc::status::error_info(&status).unwrap();
+        assert_eq!(info.reason, "TIMEOUT");
+        assert!(info.metadata.is_empty());
+    }
 }
@@ -3,7 +3,12 @@
 //! Detects available GPUs via nvidia-smi and provides virtual-to-physical mapping.
 //! This handles multi-tenant environments (Vast.ai, RunPod) where the container
 //! may see device files for all host GPUs but only has access to a subset.
+//!
+//! Also accounts for the memory instances reserve on each GPU, so placing one
+//! more instance on a full GPU is refused instead of ending in an OOM.
 
+use crate::config::{Dtype, InstanceConfig};
+use std::ffi::OsStr;
 use std::process::Command;
 use std::sync::OnceLock;
 
@@ -18,6 +23,66 @@ pub struct GpuInfo {
     pub indices: Vec<u32>,
     /// Comma-separated string for CUDA_VISIBLE_DEVICES
     pub cuda_visible_devices: String,
+    /// Why detection failed (nvidia-smi missing or erroring), None if it ran
+    pub detection_error: Option<String>,
+    /// Total memory in bytes of each GPU in `indices` order (None if not reported)
+    pub memory_total_bytes: Vec<Option<u64>>,
+}
+
+/// Why a requested gpu_id was not accepted
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum GpuError {
+    /// nvidia-smi could not report GPUs, so the id cannot be checked
+    #[error("GPU detection unavailable: {reason}")]
+    DetectionUnavailable { id: u32, reason: String },
+    /// Detection worked but no such GPU is visible
+    #[error("GPU {id} out of range, available GPUs: {available:?}")]
+    OutOfRange { id: u32, available: Vec<u32> },
+    /// The instance's memory reservation does not fit on the GPU
+    #[error("GPU {id} has {available} bytes unreserved, instance needs {required}")]
+    InsufficientMemory {
+        id: u32,
+        required: u64,
+        available: u64,
+    },
+}
+
+/// Share of an estimated footprint added on top of the weights for activations
+/// and the CUDA context
+const FOOTPRINT_OVERHEAD: f64 = 0.2;
+
+/// GPU memory `config` reserves, if it can be determined
+///
+/// An explicit `gpu_memory_bytes` wins, then `gpu_memory_fraction` of the GPU's
+/// `capacity`, then `estimate` (see [`estimate_footprint`]).
+pub fn instance_footprint(
+    config: &InstanceConfig,
+    capacity: Option<u64>,
+    estimate: Option<u64>,
+) -> Option<u64> {
+    if let Some(bytes) = config.gpu_memory_bytes {
+        return Some(bytes);
+    }
+    if let (Some(fraction), Some(capacity)) = (config.gpu_memory_fraction, capacity) {
+        return Some((capacity as f64 * fraction as f64) as u64);
+    }
+    estimate
+}
+
+/// GPU memory estimated from the cached model's config.json
+///
+/// Reads the model cache; async callers run it on the blocking pool.
+pub fn estimate_footprint(config: &InstanceConfig) -> Option<u64> {
+    let cache_path =
+        crate::models::cache::model_cache_path_in(&config.model_cache_dir(), &config.model_id)?;
+    let metadata = crate::models::parse_model_config(&cache_path)?;
+    let parameters = crate::models::metadata::estimate_parameters(&metadata)?;
+    // TEI loads weights as float16 on GPU unless told otherwise
+    let bytes_per_parameter = match config.dtype {
+        Some(Dtype::Float32) => 4,
+        _ => 2,
+    };
+    Some((parameters as f64 * bytes_per_parameter as f64 * (1.0 + FOOTPRINT_OVERHEAD)) as u64)
 }
 
 impl GpuInfo {
@@ -31,6 +96,81 @@ impl GpuInfo {
         (gpu_id as usize) < self.indices.len()
     }
 
+    /// Check a user-provided gpu_id, telling a bad index apart from missing detection
+    ///
+    /// With `allow_without_detection`, any id is accepted when nvidia-smi failed.
+    pub fn check_gpu_id(&self, gpu_id: u32, allow_without_detection: bool) -> Result<(), GpuError> {
+        if self.is_valid_gpu_id(gpu_id) {
+            return Ok(());
+        }
+        match &self.detection_error {
+            Some(_) if allow_without_detection => {
+                tracing::warn!(
+                    gpu_id,
+                    "GPU detection unavailable, trusting configured gpu_id"
+                );
+                Ok(())
+            }
+            Some(reason) => Err(GpuError::DetectionUnavailable {
+                id: gpu_id,
+                reason: reason.clone(),
+            }),
+            None => Err(GpuError::OutOfRange {
+                id: gpu_id,
+                available: self.indices.clone(),
+            }),
+        }
+    }
+
+    /// Total memory of `gpu_id` in bytes, if nvidia-smi reported it
+    pub fn memory_total(&self, gpu_id: u32) -> Option<u64> {
+        self.memory_total_bytes
+            .get(gpu_id as usize)
+            .copied()
+            .flatten()
+    }
+
+    /// Check that `config` fits on its GPU next to the instances in `placed`
+    ///
+    /// Every instance pinned to the same gpu_id holds its reservation, running or
+    /// not, and `headroom` bytes stay free. Instances without a gpu_id, GPUs of
+    /// unknown size and footprints that can't be determined are not checked.
+    /// `estimate` supplies model-based footprints, e.g. [`estimate_footprint`].
+    pub fn check_placement(
+        &self,
+        config: &InstanceConfig,
+        placed: &[InstanceConfig],
+        headroom: u64,
+        estimate: impl Fn(&InstanceConfig) -> Option<u64>,
+    ) -> Result<(), GpuError> {
+        let Some(gpu_id) = config.gpu_id.filter(|_| !config.cpu_only) else {
+            return Ok(());
+        };
+        let Some(capacity) = self.memory_total(gpu_id) else {
+            return Ok(());
+        };
+        let footprint =
+            |config: &InstanceConfig| instance_footprint(config, Some(capacity), estimate(config));
+        let Some(required) = footprint(config) else {
+            return Ok(());
+        };
+
+        let reserved: u64 = placed
+            .iter()
+            .filter(|other| other.name != config.name && other.gpu_id == Some(gpu_id))
+            .filter_map(footprint)
+            .sum();
+        let available = capacity.saturating_sub(headroom).saturating_sub(reserved);
+        if required > available {
+            return Err(GpuError::InsufficientMemory {
+                id: gpu_id,
+                required,
+                available,
+            });
+        }
+        Ok(())
+    }
+
     /// Get the CUDA_VISIBLE_DEVICES value for a specific gpu_id
     /// User provides virtual index (0, 1, 2...), we return the actual index
     pub fn get_cuda_device(&self, gpu_id: u32) -> Option<String> {
@@ -44,17 +184,34 @@ impl GpuInfo {
 /// this correctly returns only the GPUs allocated to this container, not all
 /// GPUs on the host.
 pub fn detect_gpus() -> GpuInfo {
-    let output = Command::new("nvidia-smi")
-        .args(["--query-gpu=index", "--format=csv,noheader"])
+    detect_gpus_with("nvidia-smi")
+}
+
+/// Detect GPUs by running `program` with nvidia-smi's query arguments
+fn detect_gpus_with(program: impl AsRef<OsStr>) -> GpuInfo {
+    let output = Command::new(program)
+        .args([
+            "--query-gpu=index,memory.total",
+            "--format=csv,noheader,nounits",
+        ])
         .output();
 
     match output {
         Ok(output) if output.status.success() => {
             let stdout = String::from_utf8_lossy(&output.stdout);
-            let indices: Vec<u32> = stdout
+            // "<index>, <MiB>" per GPU; memory is "[N/A]" on some devices
+            let (indices, memory_total_bytes): (Vec<u32>, Vec<Option<u64>>) = stdout
                 .lines()
-                .filter_map(|line| line.trim().parse::<u32>().ok())
-                .collect();
+                .filter_map(|line| {
+                    let mut fields = line.split(',').map(str::trim);
+                    let index = fields.next()?.parse::<u32>().ok()?;
+                    let memory = fields
+                        .next()
+                        .and_then(|mib| mib.parse::<u64>().ok())
+                        .map(|mib| mib * 1024 * 1024);
+                    Some((index, memory))
+                })
+                .unzip();
 
             let cuda_visible_devices = indices
                 .iter()
@@ -72,6 +229,8 @@ pub fn detect_gpus() -> GpuInfo {
             GpuInfo {
                 indices,
                 cuda_visible_devices,
+                detection_error: None,
+                memory_total_bytes,
             }
         }
         Ok(output) => {
@@ -80,14 +239,29 @@ pub fn detect_gpus() -> GpuInfo {
                 stderr = %stderr,
                 "nvidia-smi failed, assuming no GPUs available"
             );
-            GpuInfo::default()
+            GpuInfo {
+                detection_error: Some(format!(
+                    "nvidia-smi failed ({}): {}",
+                    output.status,
+                    stderr.trim()
+                )),
+                ..Default::default()
+            }
         }
         Err(e) => {
             tracing::warn!(
                 error = %e,
                 "Failed to run nvidia-smi, assuming no GPUs available"
             );
-            GpuInfo::default()
+            let reason = if e.kind() == std::io::ErrorKind::NotFound {
+                "nvidia-smi not found".to_string()
+            } else {
+                format!("failed to run nvidia-smi: {}", e)
+            };
+            GpuInfo {
+                detection_error: Some(reason),
+                ..Default::default()
+            }
         }
     }
 }
@@ -118,6 +292,8 @@ mod tests {
         let info = GpuInfo {
             indices: vec![0, 1],
             cuda_visible_devices: "0,1".to_string(),
+            detection_error: None,
+            ..Default::default()
         };
 
         assert_eq!(info.count(), 2);
@@ -132,6 +308,8 @@ mod tests {
         let info = GpuInfo {
             indices: vec![0, 1],
             cuda_visible_devices: "0,1".to_string(),
+            detection_error: None,
+            ..Default::default()
         };
 
         assert_eq!(info.get_cuda_device(0), Some("0".to_string()));
@@ -147,4 +325,155 @@ mod tests {
         assert!(!info.is_valid_gpu_id(0));
         assert_eq!(info.get_cuda_device(0), None);
     }
+
+    /// Write an executable stand-in for nvidia-smi running `script`
+    #[cfg(unix)]
+    fn stub_smi(dir: &tempfile::TempDir, script: &str) -> std::path::PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = dir.path().join("nvidia-smi");
+        std::fs::write(&path, format!("#!/bin/sh\n{}\n", script)).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_missing_smi_is_detection_unavailable() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let info = detect_gpus_with(dir.path().join("nvidia-smi"));
+
+        assert_eq!(info.count(), 0);
+        assert_eq!(
+            info.detection_error.as_deref(),
+            Some("nvidia-smi not found")
+        );
+        assert!(matches!(
+            info.check_gpu_id(0, false),
+            Err(GpuError::DetectionUnavailable { id: 0, .. })
+        ));
+        // The operator can opt in to trusting the id
+        assert_eq!(info.check_gpu_id(3, true), Ok(()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_failing_smi_is_detection_unavailable() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let smi = stub_smi(&dir, "echo 'NVIDIA-SMI has failed' >&2; exit 9");
+        let info = detect_gpus_with(&smi);
+
+        assert!(
+            info.detection_error
+                .unwrap()
+                .contains("NVIDIA-SMI has failed")
+        );
+    }
+
+    const GIB: u64 = 1024 * 1024 * 1024;
+
+    fn pinned(name: &str, gpu_id: u32, gpu_memory_bytes: u64) -> InstanceConfig {
+        InstanceConfig {
+            name: name.to_string(),
+            model_id: "model".to_string(),
+            gpu_id: Some(gpu_id),
+            gpu_memory_bytes: Some(gpu_memory_bytes),
+            ..Default::default()
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_placement_within_gpu_memory() {
+        let dir = tempfile::TempDir::new().unwrap();
+        // Two 24 GiB GPUs
+        let smi = stub_smi(&dir, "echo '0, 24576'; echo '1, 24576'");
+        let info = detect_gpus_with(&smi);
+        assert_eq!(info.memory_total(1), Some(24 * GIB));
+
+        let placed = [pinned("big", 0, 16 * GIB), pinned("other-gpu", 1, 20 * GIB)];
+
+        // 16 + 6 GiB fits 24 GiB with 1 GiB to spare
+        assert_eq!(
+            info.check_placement(
+                &pinned("small", 0, 6 * GIB),
+                &placed,
+                GIB,
+                estimate_footprint
+            ),
+            Ok(())
+        );
+        // A fraction of the GPU counts as its reservation
+        let fraction = InstanceConfig {
+            gpu_memory_bytes: None,
+            gpu_memory_fraction: Some(0.25),
+            ..pinned("fraction", 0, 0)
+        };
+        assert_eq!(
+            info.check_placement(&fraction, &placed, 0, estimate_footprint),
+            Ok(())
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_placement_over_gpu_memory_rejected() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let smi = stub_smi(&dir, "echo '0, 24576'");
+        let info = detect_gpus_with(&smi);
+        let placed = [pinned("big", 0, 16 * GIB)];
+
+        assert_eq!(
+            info.check_placement(
+                &pinned("second-big", 0, 16 * GIB),
+                &placed,
+                0,
+                estimate_footprint
+            ),
+            Err(GpuError::InsufficientMemory {
+                id: 0,
+                required: 16 * GIB,
+                available: 8 * GIB,
+            })
+        );
+        // The headroom is not available either
+        assert!(
+            info.check_placement(&pinned("small", 0, 8 * GIB), &placed, 1, estimate_footprint)
+                .is_err()
+        );
+        // Replacing an instance does not count its own old reservation
+        assert_eq!(
+            info.check_placement(&pinned("big", 0, 20 * GIB), &placed, 0, estimate_footprint),
+            Ok(())
+        );
+        // Unknown GPU sizes are not checked
+        assert_eq!(
+            GpuInfo::default().check_placement(
+                &pinned("any", 0, 100 * GIB),
+                &placed,
+                0,
+                estimate_footprint
+            ),
+            Ok(())
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_out_of_range_gpu_id() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let smi = stub_smi(&dir, "echo 0; echo 1");
+        let info = detect_gpus_with(&smi);
+
+        assert_eq!(info.indices, vec![0, 1]);
+        assert_eq!(info.detection_error, None);
+        assert_eq!(info.check_gpu_id(1, false), Ok(()));
+        // allow_without_detection only applies when detection failed
+        assert_eq!(
+            info.check_gpu_id(2, true),
+            Err(GpuError::OutOfRange {
+                id: 2,
+                available: vec![0, 1]
+            })
+        );
+    }
 }
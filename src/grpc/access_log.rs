@@ -0,0 +1,270 @@
+//! Per-request access log for the multiplexer
+//!
+//! [`AccessLogLayer`] wraps the gRPC server and records one [`AccessLogEntry`]
+//! per multiplexer call: the RPC, how it was routed, the instance it resolved
+//! to, the gRPC status and the caller's mTLS subject. Entries are appended to a
+//! file as JSON lines and broadcast to live subscribers
+//! (`GET /debug/access-log/stream`).
+//!
+//! The route is reported by the multiplexer through a task-local slot scoped to
+//! the call, so streaming RPCs (which resolve each message in a separate task)
+//! are logged without a route or instance.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::future::Future;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context as TaskContext, Poll};
+use std::time::Instant;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::broadcast;
+use tonic::transport::server::{TcpConnectInfo, TlsConnectInfo};
+
+/// Path prefix of multiplexer RPCs (reflection calls are not logged)
+const MULTIPLEXER_PATH: &str = "/tei_multiplexer.v1.TeiMultiplexer/";
+
+/// Entries buffered per live subscriber before it starts missing some
+const SUBSCRIBER_BUFFER: usize = 1024;
+
+/// How a request asked to be routed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessRouting {
+    Instance(String),
+    Model(String),
+    Index(u32),
+}
+
+/// One routed multiplexer call
+#[derive(Debug, Clone, Serialize)]
+pub struct AccessLogEntry {
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// RPC method, e.g. "Embed"
+    pub rpc: String,
+    /// None for streaming RPCs and requests rejected before routing
+    pub routing: Option<AccessRouting>,
+    pub resolved_instance: Option<String>,
+    /// gRPC status code name, e.g. "Ok" or "Unavailable"
+    pub status: String,
+    pub duration_ms: f64,
+    /// Subject of the client certificate, when mTLS is enabled
+    pub principal: Option<String>,
+}
+
+/// Destination of access log entries
+#[derive(Debug)]
+pub struct AccessLog {
+    file: Option<tokio::sync::Mutex<tokio::fs::File>>,
+    tx: broadcast::Sender<AccessLogEntry>,
+}
+
+impl Default for AccessLog {
+    fn default() -> Self {
+        Self {
+            file: None,
+            tx: broadcast::channel(SUBSCRIBER_BUFFER).0,
+        }
+    }
+}
+
+impl AccessLog {
+    /// Log that only feeds live subscribers
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also append entries to `path` as JSON lines
+    pub fn with_file(mut self, path: &Path) -> Result<Self> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open access log {}", path.display()))?;
+        self.file = Some(tokio::sync::Mutex::new(tokio::fs::File::from_std(file)));
+        Ok(self)
+    }
+
+    /// Receive entries recorded from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<AccessLogEntry> {
+        self.tx.subscribe()
+    }
+
+    /// Broadcast `entry` and append it to the file, if any
+    ///
+    /// A failed write is only logged: the call it describes has already been answered.
+    pub async fn record(&self, entry: AccessLogEntry) {
+        if let Some(file) = &self.file {
+            let result = async {
+                let mut line = serde_json::to_vec(&entry)?;
+                line.push(b'\n');
+                file.lock().await.write_all(&line).await?;
+                anyhow::Ok(())
+            }
+            .await;
+            if let Err(e) = result {
+                tracing::warn!(error = %e, "Failed to write access log entry");
+            }
+        }
+        let _ = self.tx.send(entry);
+    }
+}
+
+type RouteSlot = Arc<Mutex<Option<(AccessRouting, String)>>>;
+
+tokio::task_local! {
+    static ROUTE: RouteSlot;
+}
+
+/// Report how the current call was routed (no-op outside a logged call)
+pub fn record_route(routing: AccessRouting, instance: &str) {
+    let _ = ROUTE.try_with(|slot| {
+        *slot.lock().unwrap() = Some((routing, instance.to_string()));
+    });
+}
+
+/// Layer logging every multiplexer call to `log` (a pass-through when None)
+#[derive(Clone, Default)]
+pub struct AccessLogLayer {
+    log: Option<Arc<AccessLog>>,
+}
+
+impl AccessLogLayer {
+    pub fn new(log: Option<Arc<AccessLog>>) -> Self {
+        Self { log }
+    }
+}
+
+impl<S> tower::Layer<S> for AccessLogLayer {
+    type Service = AccessLogService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AccessLogService {
+            inner,
+            log: self.log.clone(),
+        }
+    }
+}
+
+/// Service produced by [`AccessLogLayer`]
+#[derive(Clone)]
+pub struct AccessLogService<S> {
+    inner: S,
+    log: Option<Arc<AccessLog>>,
+}
+
+impl<S, B, ResBody> tower::Service<axum::http::Request<B>> for AccessLogService<S>
+where
+    S: tower::Service<axum::http::Request<B>, Response = axum::http::Response<ResBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: axum::http::Request<B>) -> Self::Future {
+        let rpc = request.uri().path().strip_prefix(MULTIPLEXER_PATH);
+        let (Some(log), Some(rpc)) = (self.log.clone(), rpc) else {
+            return Box::pin(self.inner.call(request));
+        };
+        let rpc = rpc.to_string();
+        let principal = principal(request.extensions());
+        let slot = RouteSlot::default();
+        let started = Instant::now();
+        let response = ROUTE.scope(slot.clone(), self.inner.call(request));
+
+        Box::pin(async move {
+            let result = response.await;
+            let status = match &result {
+                Ok(response) => grpc_code(response.headers()),
+                Err(_) => tonic::Code::Internal,
+            };
+            let route = slot.lock().unwrap().take();
+            let (routing, resolved_instance) = route.unzip();
+            let entry = AccessLogEntry {
+                timestamp: chrono::Utc::now(),
+                rpc,
+                routing,
+                resolved_instance,
+                status: format!("{:?}", status),
+                duration_ms: started.elapsed().as_secs_f64() * 1000.0,
+                principal,
+            };
+            // Written in the background so the response isn't held up by the file
+            tokio::spawn(async move { log.record(entry).await });
+            result
+        })
+    }
+}
+
+/// Status of a response; errors are sent trailers-only, so a missing header means Ok
+fn grpc_code(headers: &axum::http::HeaderMap) -> tonic::Code {
+    headers
+        .get("grpc-status")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<i32>().ok())
+        .map(tonic::Code::from_i32)
+        .unwrap_or(tonic::Code::Ok)
+}
+
+/// Subject of the verified client certificate
+fn principal(extensions: &axum::http::Extensions) -> Option<String> {
+    let certs = extensions
+        .get::<TlsConnectInfo<TcpConnectInfo>>()?
+        .peer_certs()?;
+    let (_, cert) = x509_parser::parse_x509_certificate(certs.first()?).ok()?;
+    Some(cert.subject().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(rpc: &str) -> AccessLogEntry {
+        AccessLogEntry {
+            timestamp: chrono::Utc::now(),
+            rpc: rpc.to_string(),
+            routing: Some(AccessRouting::Model("bge".to_string())),
+            resolved_instance: Some("bge-1".to_string()),
+            status: "Ok".to_string(),
+            duration_ms: 1.5,
+            principal: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_entries_written_and_broadcast() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("access.log");
+        let log = AccessLog::new().with_file(&path).unwrap();
+        let mut rx = log.subscribe();
+
+        log.record(entry("Embed")).await;
+        log.record(entry("Rerank")).await;
+
+        assert_eq!(rx.recv().await.unwrap().rpc, "Embed");
+        assert_eq!(rx.recv().await.unwrap().rpc, "Rerank");
+
+        let lines: Vec<serde_json::Value> = std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["routing"], serde_json::json!({"model": "bge"}));
+        assert_eq!(lines[0]["resolved_instance"], "bge-1");
+    }
+
+    #[test]
+    fn test_grpc_code_from_headers() {
+        let mut headers = axum::http::HeaderMap::new();
+        assert_eq!(grpc_code(&headers), tonic::Code::Ok);
+        headers.insert("grpc-status", "5".parse().unwrap());
+        assert_eq!(grpc_code(&headers), tonic::Code::NotFound);
+    }
+}
@@ -0,0 +1,121 @@
+//! Shared settings for gRPC channels to backend TEI instances
+
+use crate::config::ManagerConfig;
+use std::time::Duration;
+use tonic::transport::Endpoint;
+
+/// TCP keepalive for backend sockets (OS-level, independent of HTTP/2 pings)
+const TCP_KEEPALIVE_SECS: u64 = 60;
+
+/// Connect timeout, HTTP/2 keepalive and compression settings for backend channels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BackendChannelConfig {
+    pub connect_timeout: Duration,
+    /// Interval between HTTP/2 keepalive pings (None = disabled)
+    pub keepalive_interval: Option<Duration>,
+    /// How long to wait for a ping ack before the channel is considered dead
+    pub keepalive_timeout: Duration,
+    /// Send gzip-compressed requests and accept gzip-compressed responses
+    pub compression: bool,
+    /// Max encoded/decoded message size in MB (per-instance overrides take precedence)
+    pub max_message_size_mb: usize,
+}
+
+impl Default for BackendChannelConfig {
+    fn default() -> Self {
+        Self::from_config(&ManagerConfig::default())
+    }
+}
+
+impl BackendChannelConfig {
+    /// Build channel settings from the manager configuration
+    pub fn from_config(config: &ManagerConfig) -> Self {
+        Self {
+            connect_timeout: Duration::from_secs(config.backend_connect_timeout_secs),
+            keepalive_interval: (config.backend_keepalive_secs > 0)
+                .then(|| Duration::from_secs(config.backend_keepalive_secs)),
+            keepalive_timeout: Duration::from_secs(config.backend_keepalive_timeout_secs),
+            compression: config.grpc_compression,
+            max_message_size_mb: config.grpc_max_message_size_mb,
+        }
+    }
+
+    /// Endpoint for a backend listening on localhost at `port`
+    ///
+    /// Keepalive pings are also sent while idle, so pooled connections to dead
+    /// backends are detected before the next request is routed to them.
+    pub fn endpoint(&self, port: u16) -> Result<Endpoint, tonic::transport::Error> {
+        let endpoint = Endpoint::from_shared(format!("http://127.0.0.1:{}", port))?
+            .tcp_keepalive(Some(Duration::from_secs(TCP_KEEPALIVE_SECS)))
+            .connect_timeout(self.connect_timeout);
+
+        Ok(match self.keepalive_interval {
+            Some(interval) => endpoint
+                .http2_keep_alive_interval(interval)
+                .keep_alive_timeout(self.keepalive_timeout)
+                .keep_alive_while_idle(true),
+            None => endpoint,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_match_config_defaults() {
+        let channel = BackendChannelConfig::default();
+        assert_eq!(channel.connect_timeout, Duration::from_secs(5));
+        assert_eq!(channel.keepalive_interval, Some(Duration::from_secs(30)));
+        assert_eq!(channel.keepalive_timeout, Duration::from_secs(10));
+        assert_eq!(channel.max_message_size_mb, 40);
+    }
+
+    #[test]
+    fn test_from_config() {
+        let config = ManagerConfig {
+            backend_connect_timeout_secs: 2,
+            backend_keepalive_secs: 15,
+            backend_keepalive_timeout_secs: 3,
+            grpc_max_message_size_mb: 64,
+            ..Default::default()
+        };
+
+        let channel = BackendChannelConfig::from_config(&config);
+        assert_eq!(channel.connect_timeout, Duration::from_secs(2));
+        assert_eq!(channel.keepalive_interval, Some(Duration::from_secs(15)));
+        assert_eq!(channel.keepalive_timeout, Duration::from_secs(3));
+        assert_eq!(channel.max_message_size_mb, 64);
+    }
+
+    #[test]
+    fn test_zero_keepalive_disables_pings() {
+        let config = ManagerConfig {
+            backend_keepalive_secs: 0,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            BackendChannelConfig::from_config(&config).keepalive_interval,
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_endpoint_builds_channel_without_server() {
+        for keepalive_secs in [0, 15] {
+            let channel = BackendChannelConfig::from_config(&ManagerConfig {
+                backend_connect_timeout_secs: 1,
+                backend_keepalive_secs: keepalive_secs,
+                ..Default::default()
+            });
+
+            let endpoint = channel.endpoint(18080).unwrap();
+            assert_eq!(endpoint.uri().to_string(), "http://127.0.0.1:18080/");
+
+            // Lazy channels don't connect until first use
+            let _channel = endpoint.connect_lazy();
+        }
+    }
+}
@@ -0,0 +1,116 @@
+//! Allowlisted client headers copied onto backend requests
+//!
+//! Lets request ids or tenant headers follow a request from the HTTP data plane
+//! or the multiplexer to the TEI instance. Only names in `forward_headers` are
+//! copied, so credentials are never passed on by accident.
+
+use anyhow::Result;
+use axum::http::{HeaderMap, HeaderName};
+use std::sync::Arc;
+use tonic::Request;
+use tonic::metadata::MetadataMap;
+
+/// Headers that describe the connection or the gRPC call itself, never forwarded
+const RESERVED_HEADERS: &[&str] = &[
+    "connection",
+    "content-length",
+    "content-type",
+    "host",
+    "keep-alive",
+    "te",
+    "trailer",
+    "transfer-encoding",
+    "upgrade",
+    "user-agent",
+];
+
+/// Header names forwarded from client requests to backends
+#[derive(Debug, Clone, Default)]
+pub struct ForwardHeaders {
+    names: Arc<[HeaderName]>,
+}
+
+impl ForwardHeaders {
+    /// Allowlist from `forward_headers`, rejecting invalid or reserved names
+    pub fn new(names: &[String]) -> Result<Self> {
+        let names = names
+            .iter()
+            .map(|name| {
+                let header = HeaderName::from_bytes(name.trim().as_bytes())
+                    .map_err(|_| anyhow::anyhow!("invalid header name '{}'", name))?;
+                if header.as_str().starts_with("grpc-")
+                    || RESERVED_HEADERS.contains(&header.as_str())
+                {
+                    anyhow::bail!("header '{}' cannot be forwarded", header);
+                }
+                Ok(header)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            names: names.into(),
+        })
+    }
+
+    /// Allowlisted entries of an HTTP request's `headers`, as gRPC metadata
+    pub fn from_http(&self, headers: &HeaderMap) -> MetadataMap {
+        let mut selected = HeaderMap::new();
+        for name in self.names.iter() {
+            for value in headers.get_all(name) {
+                selected.append(name.clone(), value.clone());
+            }
+        }
+        MetadataMap::from_headers(selected)
+    }
+
+    /// Allowlisted entries of a gRPC client's `metadata`
+    pub fn from_grpc(&self, metadata: &MetadataMap) -> MetadataMap {
+        if self.names.is_empty() {
+            return MetadataMap::new();
+        }
+        self.from_http(&metadata.clone().into_headers())
+    }
+}
+
+/// `message` as a backend request carrying `metadata`
+pub fn backend_request<T>(metadata: &MetadataMap, message: T) -> Request<T> {
+    let mut request = Request::new(message);
+    *request.metadata_mut() = metadata.clone();
+    request
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_allowlisted_headers_are_selected() {
+        let forward =
+            ForwardHeaders::new(&["X-Tenant-Id".to_string(), "x-request-id".to_string()]).unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-tenant-id", "acme".parse().unwrap());
+        headers.append("x-request-id", "1".parse().unwrap());
+        headers.append("x-request-id", "2".parse().unwrap());
+        headers.insert("authorization", "Bearer secret".parse().unwrap());
+
+        let metadata = forward.from_http(&headers);
+        assert_eq!(metadata.get("x-tenant-id").unwrap(), "acme");
+        assert_eq!(metadata.get_all("x-request-id").iter().count(), 2);
+        assert!(metadata.get("authorization").is_none());
+
+        // Nothing is forwarded by default
+        assert!(ForwardHeaders::default().from_http(&headers).is_empty());
+
+        let metadata = forward.from_grpc(&MetadataMap::from_headers(headers));
+        assert_eq!(metadata.get("x-tenant-id").unwrap(), "acme");
+        assert!(metadata.get("authorization").is_none());
+    }
+
+    #[test]
+    fn test_invalid_and_reserved_names_rejected() {
+        for name in ["not a header", "content-type", "grpc-timeout", "te"] {
+            let err = ForwardHeaders::new(&[name.to_string()]).unwrap_err();
+            assert!(err.to_string().contains(name), "{}", err);
+        }
+    }
+}
@@ -0,0 +1,175 @@
+//! Actionable errors for requests over `grpc_max_message_size_mb`
+//!
+//! tonic rejects an oversized request before the multiplexer sees it, with an
+//! `OUT_OF_RANGE` status about "decoded message length". [`MessageSizeLayer`]
+//! rewrites that into `RESOURCE_EXHAUSTED` naming the configured limit, with
+//! the same `PAYLOAD_TOO_LARGE` reason the HTTP API uses for oversized bodies.
+//!
+//! Client stream messages are decoded inside the handler, so the multiplexer
+//! applies [`translate`] to them itself.
+
+use super::status::error_status;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tonic::{Code, Status};
+
+/// Start of tonic's message for a request over the decoding limit
+const DECODE_LIMIT_PREFIX: &str = "Error, decoded message length too large";
+
+/// Whether `status` is tonic rejecting a request over the decoding limit
+pub fn is_message_too_large(status: &Status) -> bool {
+    status.code() == Code::OutOfRange && status.message().starts_with(DECODE_LIMIT_PREFIX)
+}
+
+/// `RESOURCE_EXHAUSTED` for a request of `size` bytes over a `limit_mb` limit
+pub fn message_too_large(size: Option<usize>, limit_mb: usize) -> Status {
+    let size = size.map_or_else(
+        || "Request".to_string(),
+        |size| format!("Request of {size} bytes"),
+    );
+    let limit_mb_value = limit_mb.to_string();
+    error_status(
+        Code::ResourceExhausted,
+        format!(
+            "{size} exceeds the {limit_mb}MB gRPC message limit; send smaller batches \
+             or raise grpc_max_message_size_mb"
+        ),
+        "PAYLOAD_TOO_LARGE",
+        &[("limit_mb", &limit_mb_value)],
+    )
+}
+
+/// Translate tonic's decoding limit error into [`message_too_large`]
+///
+/// Any other status is returned unchanged.
+pub fn translate(status: Status, limit_mb: usize) -> Status {
+    if !is_message_too_large(&status) {
+        return status;
+    }
+    // "... found {len} bytes, the limit is: {limit} bytes"
+    let size = status
+        .message()
+        .split("found ")
+        .nth(1)
+        .and_then(|rest| rest.split(' ').next())
+        .and_then(|len| len.parse().ok());
+    message_too_large(size, limit_mb)
+}
+
+/// Layer rewriting oversized request rejections (see the module docs)
+#[derive(Clone, Copy)]
+pub struct MessageSizeLayer {
+    limit_mb: usize,
+}
+
+impl MessageSizeLayer {
+    pub fn new(limit_mb: usize) -> Self {
+        Self { limit_mb }
+    }
+}
+
+impl<S> tower::Layer<S> for MessageSizeLayer {
+    type Service = MessageSizeService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        MessageSizeService {
+            inner,
+            limit_mb: self.limit_mb,
+        }
+    }
+}
+
+/// Service produced by [`MessageSizeLayer`]
+#[derive(Clone)]
+pub struct MessageSizeService<S> {
+    inner: S,
+    limit_mb: usize,
+}
+
+impl<S, B, ResBody> tower::Service<axum::http::Request<B>> for MessageSizeService<S>
+where
+    S: tower::Service<axum::http::Request<B>, Response = axum::http::Response<ResBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: axum::http::Request<B>) -> Self::Future {
+        let limit_mb = self.limit_mb;
+        let response = self.inner.call(request);
+        Box::pin(async move {
+            let mut response = response.await?;
+            // Rejected requests are answered trailers-only, so the status is in the headers
+            if let Some(status) = Status::from_header_map(response.headers())
+                && is_message_too_large(&status)
+            {
+                translate(status, limit_mb)
+                    .add_header(response.headers_mut())
+                    .expect("status headers are valid");
+            }
+            Ok(response)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grpc::status::error_info;
+
+    fn tonic_decode_error(len: usize, limit: usize) -> Status {
+        Status::out_of_range(format!(
+            "{DECODE_LIMIT_PREFIX}: found {len} bytes, the limit is: {limit} bytes"
+        ))
+    }
+
+    #[test]
+    fn test_translate_names_limit_and_size() {
+        let status = translate(tonic_decode_error(5_000_000, 4_194_304), 4);
+
+        assert_eq!(status.code(), Code::ResourceExhausted);
+        assert!(status.message().contains("5000000 bytes"));
+        assert!(status.message().contains("4MB"));
+        assert!(status.message().contains("grpc_max_message_size_mb"));
+        let info = error_info(&status).unwrap();
+        assert_eq!(info.reason, "PAYLOAD_TOO_LARGE");
+        assert_eq!(info.metadata["limit_mb"], "4");
+    }
+
+    #[test]
+    fn test_translate_leaves_other_errors() {
+        let status = translate(Status::out_of_range("index 7 out of range"), 4);
+        assert_eq!(status.code(), Code::OutOfRange);
+        assert_eq!(status.message(), "index 7 out of range");
+
+        let status = translate(Status::not_found("gone"), 4);
+        assert_eq!(status.code(), Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_layer_rewrites_trailers_only_response() {
+        use tower::{Layer, ServiceExt};
+
+        let inner = tower::service_fn(|_request: axum::http::Request<()>| async {
+            Ok::<_, std::convert::Infallible>(
+                tonic_decode_error(3_000_000, 1_048_576).into_http::<()>(),
+            )
+        });
+        let response = MessageSizeLayer::new(1)
+            .layer(inner)
+            .oneshot(axum::http::Request::new(()))
+            .await
+            .unwrap();
+
+        let status = Status::from_header_map(response.headers()).unwrap();
+        assert_eq!(status.code(), Code::ResourceExhausted);
+        assert!(status.message().contains("1MB"));
+        assert_eq!(error_info(&status).unwrap().reason, "PAYLOAD_TOO_LARGE");
+    }
+}
@@ -3,9 +3,15 @@
 //! This module provides a high-performance gRPC proxy that routes requests to backend TEI instances
 //! based on instance name, model ID, or index. Designed for zero-copy forwarding and lock-free connection pooling.
 
+pub mod access_log;
+pub mod channel;
+pub mod forward;
+pub mod message_size;
 pub mod multiplexer;
 pub mod pool;
+pub mod routing;
 pub mod server;
+pub mod status;
 
 // Include generated proto code
 pub mod proto {
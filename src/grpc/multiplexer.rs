@@ -6,20 +6,34 @@ use arrow::array::{
 };
 use arrow::buffer::OffsetBuffer;
 use arrow::datatypes::{DataType, Field, Fields, Schema};
+use arrow::ipc::CompressionType;
 use arrow::ipc::reader::StreamReader;
 use arrow::ipc::writer::StreamWriter;
 use arrow::record_batch::RecordBatch;
 use std::io::Cursor;
 use std::sync::Arc;
-use std::time::Duration;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 use tokio::time::timeout;
 use tokio_stream::StreamExt;
+use tonic::metadata::MetadataMap;
 use tonic::{Request, Response, Status, Streaming};
 use tracing::{Span, instrument};
 
-use super::pool::BackendPool;
+use super::access_log::{self, AccessRouting};
+use super::forward::{ForwardHeaders, backend_request};
+use super::message_size::{is_message_too_large, translate};
+use super::pool::{BackendClients, BackendPool};
 use super::proto::multiplexer::v1 as mux;
 use super::proto::tei::v1 as tei;
+use super::status::{invalid_field, missing_field};
+use crate::config::{ArrowEmbedOrdering, StreamBackpressure};
+use crate::instance::{InflightRequest, InstanceStatus};
+use crate::models::{
+    CachedEmbeddingDimensions, CachedPromptNames, EmbeddingDimensionSource, PromptNameSource,
+};
 
 /// Implements a bidirectional streaming RPC method for the multiplexer.
 ///
@@ -28,8 +42,9 @@ use super::proto::tei::v1 as tei;
 ///
 /// 1. **Request extraction**: Reads the first request to get the target instance name
 /// 2. **Instance routing**: Looks up the backend connection from the connection pool
-/// 3. **Stream forwarding**: Spawns a task to forward requests to the backend
-/// 4. **Response streaming**: Returns responses from the backend via a channel
+/// 3. **Inflight limit**: Takes a global inflight slot, held until the stream ends
+/// 4. **Stream forwarding**: Spawns a task to forward requests to the backend
+/// 5. **Response streaming**: Returns responses from the backend via a channel
 ///
 /// # Arguments
 ///
@@ -82,67 +97,112 @@ use super::proto::tei::v1 as tei;
 /// - Returns `InvalidArgument` if the stream is empty
 /// - Returns `NotFound` if the target instance doesn't exist
 /// - Returns `Unavailable` if the backend connection fails
-/// - Stream errors are logged and terminate the forwarding task
+/// - Returns `ResourceExhausted` if `grpc_max_total_inflight` is reached
+/// - Returns `InvalidArgument` if the first message fails the unary RPC's checks
+///   (e.g. an unknown `prompt_name` or out-of-range `dimensions`)
+/// - A client stream error, or a later message failing those checks, ends the
+///   backend stream; once the responses to the earlier messages are forwarded,
+///   the client gets the error (oversized messages as `ResourceExhausted`
+///   naming the limit)
+///
+/// # Cancellation
+///
+/// If the client goes away (response stream dropped), the forwarding task exits
+/// immediately and drops the backend call, which resets the backend HTTP/2 stream.
 macro_rules! impl_stream_rpc {
     ($self:ident, $request:ident, $mux_req:ty, $backend_client:ident, $backend_method:ident) => {{
-        let mut stream: Streaming<$mux_req> = $request.into_inner();
+        let (metadata, _, mut stream): (_, _, Streaming<$mux_req>) = $request.into_parts();
 
         // Read first request to get instance name
         let first_req: $mux_req = stream
             .next()
             .await
             .ok_or_else(|| Status::invalid_argument("Empty stream"))?
-            .map_err(|e| Status::internal(format!("Stream error: {}", e)))?;
+            .map_err(|e| stream_message_error(e, $self.max_message_size_mb))?;
 
-        let instance_name = Self::extract_target(first_req.target)?;
+        let instance_name = $self.resolve_target(first_req.target, &metadata).await?;
+        let metadata = $self.forward_headers.from_grpc(&metadata);
         Span::current().record("instance", instance_name.as_str());
 
+        // Every message gets the unary RPC's checks; the first one fails the call
+        let checks = $self.request_checks(&instance_name).await;
+        if let Some(req) = &first_req.request {
+            checks.check(req)?;
+        }
+
         // Get backend client
         let clients = $self.pool.get_clients(&instance_name).await?;
+        let inflight = $self.acquire_inflight(&clients)?;
+        let mut request_metrics =
+            RequestMetrics::start(&instance_name, &clients, stringify!($backend_method));
         let (tx, rx) = tokio::sync::mpsc::channel($self.max_parallel_stream_requests);
+        let backpressure = $self.stream_backpressure;
+        let max_message_size_mb = $self.max_message_size_mb;
 
         // Spawn task to handle streaming
         tokio::spawn(async move {
-            // Create backend request stream
-            let backend_stream = async_stream::stream! {
-                if let Some(req) = first_req.request {
-                    yield req;
-                }
-                while let Some(result) = stream.next().await {
-                    match result {
-                        Ok(req) => {
-                            if let Some(inner) = req.request {
-                                yield inner;
+            let _inflight = inflight;
+
+            // Create backend request stream, ending it at the first client stream error
+            let stream_error = StreamError::default();
+            let backend_stream = {
+                let stream_error = stream_error.clone();
+                async_stream::stream! {
+                    if let Some(req) = first_req.request {
+                        yield req;
+                    }
+                    while let Some(result) = stream.next().await {
+                        match result {
+                            Ok(req) => {
+                                if let Some(inner) = req.request {
+                                    if let Err(status) = checks.check(&inner) {
+                                        stream_error.set(status);
+                                        break;
+                                    }
+                                    yield inner;
+                                }
+                            }
+                            Err(e) => {
+                                tracing::error!("Stream error: {}", e);
+                                stream_error.set(stream_message_error(e, max_message_size_mb));
+                                break;
                             }
-                        }
-                        Err(e) => {
-                            tracing::error!("Stream error: {}", e);
-                            break;
                         }
                     }
                 }
             };
 
-            // Call backend with stream
-            let response_stream = match clients
-                .$backend_client
-                .clone()
-                .$backend_method(backend_stream)
-                .await
-            {
-                Ok(response) => response.into_inner(),
-                Err(e) => {
-                    let _ = tx.send(Err(e)).await;
+            // Call backend with stream (abandon the call if the client leaves first)
+            let mut backend_client = clients.$backend_client.clone();
+            let response_stream = tokio::select! {
+                _ = tx.closed() => {
+                    request_metrics.cancel();
                     return;
                 }
+                result = backend_client.$backend_method(backend_request(&metadata, backend_stream)) => match result {
+                    Ok(response) => response.into_inner(),
+                    Err(e) => {
+                        let _ = tx.send(Err(e)).await;
+                        return;
+                    }
+                },
             };
 
-            // Forward responses to client
-            tokio::pin!(response_stream);
-            while let Some(result) = response_stream.next().await {
-                if tx.send(result).await.is_err() {
-                    break;
-                }
+            let method = stringify!($backend_method);
+            let error_tx = tx.clone();
+            match forward_responses(response_stream, tx, backpressure, &instance_name, method).await
+            {
+                // Counted as an error: the client is sent the stream error after the
+                // responses to the messages before it
+                ForwardOutcome::Completed => match stream_error.take() {
+                    Some(status) => {
+                        let _ = error_tx.send(Err(status)).await;
+                    }
+                    None => request_metrics.succeed(),
+                },
+                ForwardOutcome::ClientGone => request_metrics.cancel(),
+                // Counted as an error: the client was sent RESOURCE_EXHAUSTED
+                ForwardOutcome::Overflowed => {}
             }
         });
 
@@ -152,12 +212,512 @@ macro_rules! impl_stream_rpc {
     }};
 }
 
+/// Status for a client stream message that failed to decode
+///
+/// Oversized messages name the `limit_mb` limit, as the server's message size
+/// layer does for unary calls; anything else is an internal error. The layer
+/// can't rewrite these itself, since a stream past its first message has
+/// already sent its response headers.
+fn stream_message_error(status: Status, limit_mb: usize) -> Status {
+    if is_message_too_large(&status) {
+        translate(status, limit_mb)
+    } else {
+        Status::internal(format!("Stream error: {}", status))
+    }
+}
+
+/// First error of a client request stream, set once the backend stream has ended at it
+#[derive(Clone, Default)]
+struct StreamError(Arc<std::sync::Mutex<Option<Status>>>);
+
+impl StreamError {
+    fn set(&self, status: Status) {
+        *self.0.lock().unwrap() = Some(status);
+    }
+
+    fn take(&self) -> Option<Status> {
+        self.0.lock().unwrap().take()
+    }
+}
+
+/// Validation a backend request must pass before it is forwarded
+///
+/// Built once per call for the target instance, so every message of a stream
+/// is checked against the same model.
+struct RequestChecks {
+    /// Model served by the instance (None if it is no longer registered)
+    model_id: Option<String>,
+    prompt_names: Arc<dyn PromptNameSource>,
+    dimensions: Arc<dyn EmbeddingDimensionSource>,
+}
+
+impl RequestChecks {
+    fn check(&self, request: &impl CheckedRequest) -> Result<(), Status> {
+        self.check_prompt_name(request.prompt_name())?;
+        self.check_dimensions(request.dimensions())
+    }
+
+    /// Reject `dimensions` of 0 or above the native dimension of the instance's model
+    ///
+    /// Only the zero check applies when the model's dimension is unknown.
+    fn check_dimensions(&self, dimensions: Option<u32>) -> Result<(), Status> {
+        if dimensions.is_none() {
+            return Ok(());
+        }
+        let model_id = self.model_id.as_deref().unwrap_or_default();
+        let native = self.dimensions.embedding_dimension(model_id);
+        crate::models::check_dimensions(model_id, dimensions, native)
+            .map_err(|message| invalid_field("dimensions", message))
+    }
+
+    /// Reject a `prompt_name` the instance's model doesn't define
+    ///
+    /// Passes when no prompt name is given or the model's prompts are unknown,
+    /// leaving the backend to decide as before.
+    fn check_prompt_name(&self, prompt_name: Option<&str>) -> Result<(), Status> {
+        let (Some(prompt_name), Some(model_id)) = (prompt_name, &self.model_id) else {
+            return Ok(());
+        };
+        let Some(known) = self.prompt_names.prompt_names(model_id) else {
+            return Ok(());
+        };
+
+        if known.iter().any(|name| name == prompt_name) {
+            return Ok(());
+        }
+        Err(invalid_field(
+            "request.prompt_name",
+            format!(
+                "Unknown prompt_name '{}' for model '{}' (valid: {})",
+                prompt_name,
+                model_id,
+                known.join(", ")
+            ),
+        ))
+    }
+}
+
+/// Backend request fields covered by [`RequestChecks`]
+trait CheckedRequest {
+    fn prompt_name(&self) -> Option<&str> {
+        None
+    }
+
+    fn dimensions(&self) -> Option<u32> {
+        None
+    }
+}
+
+impl CheckedRequest for tei::EmbedRequest {
+    fn prompt_name(&self) -> Option<&str> {
+        self.prompt_name.as_deref()
+    }
+
+    fn dimensions(&self) -> Option<u32> {
+        self.dimensions
+    }
+}
+
+impl CheckedRequest for tei::EmbedSparseRequest {
+    fn prompt_name(&self) -> Option<&str> {
+        self.prompt_name.as_deref()
+    }
+}
+
+impl CheckedRequest for tei::EmbedAllRequest {
+    fn prompt_name(&self) -> Option<&str> {
+        self.prompt_name.as_deref()
+    }
+}
+
+impl CheckedRequest for tei::PredictRequest {}
+impl CheckedRequest for tei::PredictPairRequest {}
+impl CheckedRequest for tei::EncodeRequest {}
+impl CheckedRequest for tei::DecodeRequest {}
+
+/// How [`forward_responses`] ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ForwardOutcome {
+    /// The backend stream ran to completion
+    Completed,
+    /// The client dropped its receiver
+    ClientGone,
+    /// The client fell behind under [`StreamBackpressure::Error`]
+    Overflowed,
+}
+
+/// Forward backend responses to the client channel until either side finishes
+///
+/// Returns as soon as the client drops its receiver, even while waiting on the
+/// backend, so `response_stream` is dropped and the backend request cancelled.
+///
+/// A full channel means the client is reading slower than the backend answers.
+/// It is counted in `tei_stream_channel_full_total`, then `backpressure` decides
+/// whether to wait for the client or cancel the backend stream and end the call
+/// with RESOURCE_EXHAUSTED.
+async fn forward_responses<T, S>(
+    response_stream: S,
+    tx: tokio::sync::mpsc::Sender<Result<T, Status>>,
+    backpressure: StreamBackpressure,
+    instance: &str,
+    method: &str,
+) -> ForwardOutcome
+where
+    S: tokio_stream::Stream<Item = Result<T, Status>>,
+{
+    let mut response_stream = Box::pin(response_stream);
+    loop {
+        let result = tokio::select! {
+            _ = tx.closed() => {
+                tracing::debug!("Client disconnected, cancelling backend stream");
+                return ForwardOutcome::ClientGone;
+            }
+            next = response_stream.next() => match next {
+                Some(result) => result,
+                None => return ForwardOutcome::Completed,
+            },
+        };
+
+        let permit = match tx.try_reserve() {
+            Ok(permit) => permit,
+            Err(TrySendError::Closed(())) => return ForwardOutcome::ClientGone,
+            Err(TrySendError::Full(())) => {
+                crate::metrics::record_stream_channel_full(instance, method);
+                match backpressure {
+                    StreamBackpressure::Block => match tx.reserve().await {
+                        Ok(permit) => permit,
+                        Err(_) => return ForwardOutcome::ClientGone,
+                    },
+                    StreamBackpressure::Error => {
+                        tracing::warn!(
+                            instance,
+                            method,
+                            "Client is not keeping up with the stream, cancelling backend stream"
+                        );
+                        drop(response_stream);
+                        // Queued after the responses the client has yet to read
+                        let _ = tx
+                            .send(Err(Status::resource_exhausted(
+                                "Client fell too far behind the response stream",
+                            )))
+                            .await;
+                        return ForwardOutcome::Overflowed;
+                    }
+                }
+            }
+        };
+        permit.send(result);
+    }
+}
+
+/// Records request count and latency for one forwarded RPC when dropped
+///
+/// Dropping without [`succeed`](Self::succeed) records the request as an error,
+/// so early returns via `?` are still counted.
+struct RequestMetrics {
+    instance: String,
+    model: Arc<str>,
+    method: &'static str,
+    status: &'static str,
+    started: Instant,
+}
+
+impl RequestMetrics {
+    fn start(instance: &str, clients: &BackendClients, method: &'static str) -> Self {
+        Self {
+            instance: instance.to_string(),
+            model: clients.model_id.clone(),
+            method,
+            status: "error",
+            started: Instant::now(),
+        }
+    }
+
+    fn succeed(&mut self) {
+        self.status = "ok";
+    }
+
+    fn cancel(&mut self) {
+        self.status = "cancelled";
+    }
+}
+
+impl Drop for RequestMetrics {
+    fn drop(&mut self) {
+        crate::metrics::record_grpc_request(
+            &self.instance,
+            &self.model,
+            self.method,
+            self.status,
+            self.started.elapsed().as_secs_f64(),
+        );
+    }
+}
+
+/// Response metadata with the target instance's inflight/capacity ratio
+///
+/// Clients can slow down as it approaches 1. See
+/// [`TeiMultiplexerService::with_instance_load_metadata`].
+pub const INSTANCE_LOAD_METADATA: &str = "x-tei-instance-load";
+
+/// Metadata naming the instance to route to, for requests without a `target`
+///
+/// For clients that can't set the proto target, e.g. grpc-web through a generic
+/// proxy. A `target` in the request always takes precedence.
+pub const INSTANCE_METADATA: &str = "x-tei-instance";
+
+/// Metadata naming the model to route to, used like [`INSTANCE_METADATA`]
+/// (which wins if both are set)
+pub const MODEL_METADATA: &str = "x-tei-model";
+
+/// A slot in the global inflight limit, released when dropped
+///
+/// Keeps the `tei_manager_grpc_inflight_requests` gauge in step with the
+/// number of outstanding backend requests, whether or not a cap is configured.
+/// Also counts the request against the target instance's inflight counter.
+struct InflightPermit {
+    _permit: Option<OwnedSemaphorePermit>,
+    _instance: InflightRequest,
+    inflight: Arc<AtomicUsize>,
+}
+
+impl InflightPermit {
+    fn new(
+        permit: Option<OwnedSemaphorePermit>,
+        instance: InflightRequest,
+        inflight: Arc<AtomicUsize>,
+    ) -> Self {
+        let count = inflight.fetch_add(1, Ordering::Relaxed) + 1;
+        crate::metrics::update_grpc_inflight(count);
+        Self {
+            _permit: permit,
+            _instance: instance,
+            inflight,
+        }
+    }
+}
+
+impl Drop for InflightPermit {
+    fn drop(&mut self) {
+        let count = self.inflight.fetch_sub(1, Ordering::Relaxed) - 1;
+        crate::metrics::update_grpc_inflight(count);
+    }
+}
+
+/// Read every RecordBatch of an Arrow IPC stream as one batch
+///
+/// Clients may split their rows across several batches; they are concatenated in
+/// stream order, so the response still has one row per input row.
+fn read_arrow_batch(arrow_ipc: &[u8]) -> Result<RecordBatch, Status> {
+    let cursor = Cursor::new(arrow_ipc);
+    let reader = StreamReader::try_new(cursor, None)
+        .map_err(|e| invalid_field("arrow_ipc", format!("Invalid Arrow IPC: {}", e)))?;
+    let schema = reader.schema();
+
+    let batches = reader
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| invalid_field("arrow_ipc", format!("Failed to read RecordBatch: {}", e)))?;
+    match batches.len() {
+        0 => Err(invalid_field("arrow_ipc", "No RecordBatch in stream")),
+        1 => Ok(batches.into_iter().next().unwrap()),
+        _ => arrow::compute::concat_batches(&schema, &batches).map_err(|e| {
+            invalid_field(
+                "arrow_ipc",
+                format!("Failed to combine RecordBatches: {}", e),
+            )
+        }),
+    }
+}
+
+/// Deterministic unit vector of `dims` values seeded by `text`
+///
+/// Backs [`mux::NoopMode::Hashed`]: the same text always gives the same vector
+/// (across processes and platforms), different texts almost surely differ.
+fn pseudo_embedding(text: &str, dims: usize) -> Vec<f32> {
+    // FNV-1a seed, expanded with splitmix64
+    let mut state = text.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+    let mut values: Vec<f32> = (0..dims)
+        .map(|_| {
+            state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            z ^= z >> 31;
+            // Top 24 bits as a uniform value in [-1, 1)
+            (z >> 40) as f32 / (1u64 << 23) as f32 - 1.0
+        })
+        .collect();
+
+    let norm = values.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        values.iter_mut().for_each(|v| *v /= norm);
+    }
+    values
+}
+
+/// Validate a raw `TruncationDirection` from a request
+fn truncation_direction(value: i32) -> Result<tei::TruncationDirection, Status> {
+    tei::TruncationDirection::try_from(value).map_err(|_| {
+        invalid_field(
+            "truncation_direction",
+            format!("Unknown truncation_direction {}", value),
+        )
+    })
+}
+
+/// Backend embed requests for the non-null rows of `texts` in `rows`
+fn arrow_embed_requests(
+    texts: &StringArray,
+    rows: std::ops::Range<usize>,
+    truncate: bool,
+    normalize: bool,
+    truncation_direction: tei::TruncationDirection,
+    dimensions: Option<u32>,
+) -> Vec<tei::EmbedRequest> {
+    rows.filter(|&i| !texts.is_null(i))
+        .map(|i| tei::EmbedRequest {
+            inputs: texts.value(i).to_string(),
+            truncate,
+            normalize: Some(normalize),
+            truncation_direction: truncation_direction as i32,
+            prompt_name: None,
+            dimensions,
+        })
+        .collect()
+}
+
+/// Embed `requests` with concurrent unary calls, returning embeddings in request order
+///
+/// Up to `concurrency` calls are in flight at once. Each result is written to
+/// the slot of the request it answers, so completion order does not matter.
+async fn embed_indexed(
+    clients: &BackendClients,
+    metadata: &MetadataMap,
+    requests: Vec<tei::EmbedRequest>,
+    concurrency: usize,
+) -> Result<Vec<Vec<f32>>, Status> {
+    let mut embeddings = vec![Vec::new(); requests.len()];
+    let calls = requests.into_iter().enumerate().map(|(index, request)| {
+        let mut client = clients.embed.clone();
+        async move {
+            let response = client.embed(backend_request(metadata, request)).await;
+            (index, response)
+        }
+    });
+    let mut responses =
+        futures::StreamExt::buffer_unordered(futures::stream::iter(calls), concurrency.max(1));
+
+    while let Some((index, response)) = responses.next().await {
+        let response = response.map_err(|e| Status::internal(format!("embed failed: {}", e)))?;
+        embeddings[index] = response.into_inner().embeddings;
+    }
+    Ok(embeddings)
+}
+
+/// Embed `requests` over one backend stream, trusting it to answer in request order
+async fn embed_streamed(
+    clients: &BackendClients,
+    metadata: &MetadataMap,
+    requests: Vec<tei::EmbedRequest>,
+) -> Result<Vec<Vec<f32>>, Status> {
+    let expected = requests.len();
+    let mut response_stream = clients
+        .embed
+        .clone()
+        .embed_stream(backend_request(metadata, tokio_stream::iter(requests)))
+        .await
+        .map_err(|e| Status::internal(format!("embed_stream failed: {}", e)))?
+        .into_inner();
+
+    let mut embeddings = Vec::with_capacity(expected);
+    while let Some(result) = response_stream.next().await {
+        let response =
+            result.map_err(|e| Status::internal(format!("Stream response error: {}", e)))?;
+        embeddings.push(response.embeddings);
+    }
+    if embeddings.len() != expected {
+        return Err(Status::internal(format!(
+            "Backend returned {} embeddings for {} rows",
+            embeddings.len(),
+            expected
+        )));
+    }
+    Ok(embeddings)
+}
+
+/// Validate a raw `ArrowCompression` from a request, as the IPC codec
+fn arrow_compression(value: i32) -> Result<CompressionType, Status> {
+    match mux::ArrowCompression::try_from(value) {
+        Ok(mux::ArrowCompression::Lz4Frame) => Ok(CompressionType::LZ4_FRAME),
+        Ok(mux::ArrowCompression::Zstd) => Ok(CompressionType::ZSTD),
+        Err(_) => Err(invalid_field(
+            "compression",
+            format!("Unknown compression {}", value),
+        )),
+    }
+}
+
+/// Serialize `batch` to Arrow IPC with `compression`
+fn write_arrow_ipc(batch: &RecordBatch, compression: CompressionType) -> Result<Vec<u8>, Status> {
+    use arrow::ipc::writer::IpcWriteOptions;
+
+    let write_options = IpcWriteOptions::default()
+        .try_with_compression(Some(compression))
+        .map_err(|e| Status::internal(format!("Failed to set compression: {}", e)))?;
+
+    let mut buffer = Vec::new();
+    {
+        let mut writer =
+            StreamWriter::try_new_with_options(&mut buffer, &batch.schema(), write_options)
+                .map_err(|e| Status::internal(format!("Failed to create IPC writer: {}", e)))?;
+
+        writer
+            .write(batch)
+            .map_err(|e| Status::internal(format!("Failed to write RecordBatch: {}", e)))?;
+
+        writer
+            .finish()
+            .map_err(|e| Status::internal(format!("Failed to finish IPC writer: {}", e)))?;
+    }
+
+    Ok(buffer)
+}
+
+/// Routing requested by a `Target`
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Route {
+    Instance(String),
+    Model(String),
+    Index(u32),
+}
+
 /// TeiMultiplexer service implementation
 #[derive(Clone)]
 pub struct TeiMultiplexerService {
     pool: BackendPool,
     max_parallel_stream_requests: usize,
     request_timeout: Option<Duration>,
+    /// Global cap on forwarded backend requests (None = unlimited)
+    inflight_limit: Option<Arc<Semaphore>>,
+    inflight: Arc<AtomicUsize>,
+    /// Rows sent to the backend per embed_arrow or rerank_arrow sub-batch (None = whole batch)
+    arrow_max_rows_per_chunk: Option<usize>,
+    /// How embed_arrow matches backend embeddings to input rows
+    arrow_embed_ordering: ArrowEmbedOrdering,
+    /// What streaming RPCs do when the client's response channel is full
+    stream_backpressure: StreamBackpressure,
+    /// Named prompts per model, for validating `prompt_name` before forwarding
+    prompt_names: Arc<dyn PromptNameSource>,
+    /// Native embedding dimension per model, for validating `dimensions` before forwarding
+    dimensions: Arc<dyn EmbeddingDimensionSource>,
+    /// Client metadata copied onto backend requests
+    forward_headers: ForwardHeaders,
+    /// Add [`INSTANCE_LOAD_METADATA`] to unary responses
+    instance_load_metadata: bool,
+    /// Decoding limit of the server, named in errors for oversized stream messages
+    max_message_size_mb: usize,
 }
 
 impl TeiMultiplexerService {
@@ -169,15 +729,122 @@ impl TeiMultiplexerService {
         Self {
             pool,
             max_parallel_stream_requests,
-            // 0 means no timeout
-            request_timeout: if request_timeout_secs > 0 {
-                Some(Duration::from_secs(request_timeout_secs))
-            } else {
-                None
-            },
+            request_timeout: crate::config::request_timeout(request_timeout_secs),
+            inflight_limit: None,
+            inflight: Arc::new(AtomicUsize::new(0)),
+            arrow_max_rows_per_chunk: None,
+            arrow_embed_ordering: ArrowEmbedOrdering::default(),
+            stream_backpressure: StreamBackpressure::default(),
+            prompt_names: Arc::new(CachedPromptNames::default()),
+            dimensions: Arc::new(CachedEmbeddingDimensions::default()),
+            forward_headers: ForwardHeaders::default(),
+            instance_load_metadata: false,
+            max_message_size_mb: crate::config::default_grpc_max_message_size_mb(),
+        }
+    }
+
+    /// Cap the number of backend requests forwarded at once (0 = unlimited)
+    ///
+    /// Unary calls hold a slot for the duration of the call, streams for as
+    /// long as the backend stream is open.
+    pub fn with_max_total_inflight(mut self, max_total_inflight: usize) -> Self {
+        self.inflight_limit =
+            (max_total_inflight > 0).then(|| Arc::new(Semaphore::new(max_total_inflight)));
+        self
+    }
+
+    /// Split embed_arrow and rerank_arrow batches into sub-batches of at most this many rows (0 = no limit)
+    ///
+    /// Each sub-batch is sent to the backend separately and the results are
+    /// concatenated into a single output batch.
+    pub fn with_arrow_max_rows_per_chunk(mut self, max_rows: usize) -> Self {
+        self.arrow_max_rows_per_chunk = (max_rows > 0).then_some(max_rows);
+        self
+    }
+
+    /// Choose how embed_arrow keeps embeddings aligned with input rows (default: stream)
+    ///
+    /// [`ArrowEmbedOrdering::Stream`] uses one embed stream per sub-batch and
+    /// relies on the backend answering in request order, failing the call when
+    /// the number of responses differs from the number of rows.
+    /// [`ArrowEmbedOrdering::Indexed`] sends one unary call per row, at most
+    /// `max_parallel_stream_requests` (and the instance's `max_concurrent_requests`)
+    /// at a time, and writes each result to its row's slot, so a backend
+    /// answering out of order cannot misalign rows.
+    pub fn with_arrow_embed_ordering(mut self, ordering: ArrowEmbedOrdering) -> Self {
+        self.arrow_embed_ordering = ordering;
+        self
+    }
+
+    /// Choose what streaming RPCs do when a client stops reading (default: block)
+    ///
+    /// Each stream buffers up to `max_parallel_stream_requests` responses.
+    /// [`StreamBackpressure::Block`] then holds back the backend stream until the
+    /// client reads; [`StreamBackpressure::Error`] cancels it and ends the call
+    /// with RESOURCE_EXHAUSTED.
+    pub fn with_stream_backpressure(mut self, backpressure: StreamBackpressure) -> Self {
+        self.stream_backpressure = backpressure;
+        self
+    }
+
+    /// Copy these client metadata entries onto backend requests (default: none)
+    pub fn with_forward_headers(mut self, forward_headers: ForwardHeaders) -> Self {
+        self.forward_headers = forward_headers;
+        self
+    }
+
+    /// Report the target instance's load as [`INSTANCE_LOAD_METADATA`] on unary responses
+    ///
+    /// Sent as response metadata: tonic has no custom trailers on unary calls.
+    pub fn with_instance_load_metadata(mut self, enabled: bool) -> Self {
+        self.instance_load_metadata = enabled;
+        self
+    }
+
+    /// Name this limit (the server's `grpc_max_message_size_mb`) in errors for
+    /// oversized client stream messages (default: 40)
+    pub fn with_max_message_size_mb(mut self, max_message_size_mb: usize) -> Self {
+        self.max_message_size_mb = max_message_size_mb;
+        self
+    }
+
+    /// Look up models' named prompts somewhere other than the HuggingFace cache
+    pub fn with_prompt_name_source(mut self, source: Arc<dyn PromptNameSource>) -> Self {
+        self.prompt_names = source;
+        self
+    }
+
+    /// Look up models' embedding dimensions somewhere other than the HuggingFace cache
+    pub fn with_dimension_source(mut self, source: Arc<dyn EmbeddingDimensionSource>) -> Self {
+        self.dimensions = source;
+        self
+    }
+
+    /// Checks for requests forwarded to `instance_name`, against its model
+    async fn request_checks(&self, instance_name: &str) -> RequestChecks {
+        RequestChecks {
+            model_id: self.pool.instance_model_id(instance_name).await,
+            prompt_names: self.prompt_names.clone(),
+            dimensions: self.dimensions.clone(),
         }
     }
 
+    /// Take a global inflight slot for a forward via `clients`, failing fast when the cap is reached
+    fn acquire_inflight(&self, clients: &BackendClients) -> Result<InflightPermit, Status> {
+        let permit =
+            match &self.inflight_limit {
+                Some(semaphore) => Some(semaphore.clone().try_acquire_owned().map_err(|_| {
+                    Status::resource_exhausted("Too many concurrent backend requests")
+                })?),
+                None => None,
+            };
+        Ok(InflightPermit::new(
+            permit,
+            clients.track_request(),
+            self.inflight.clone(),
+        ))
+    }
+
     /// Wrap a future with an optional timeout
     async fn with_timeout<T, F: std::future::Future<Output = Result<T, Status>>>(
         &self,
@@ -191,32 +858,111 @@ impl TeiMultiplexerService {
         }
     }
 
-    /// Extract target instance from request
-    fn extract_target(target: Option<mux::Target>) -> Result<String, Status> {
-        let target = target.ok_or_else(|| Status::invalid_argument("Missing target"))?;
+    /// Forward a unary request with the configured timeout, recording request metrics
+    ///
+    /// The instance's load is measured before this request's inflight slot is released.
+    async fn forward<T, F: std::future::Future<Output = Result<Response<T>, Status>>>(
+        &self,
+        method: &'static str,
+        instance_name: &str,
+        clients: &BackendClients,
+        fut: F,
+    ) -> Result<Response<T>, Status> {
+        let _inflight = self.acquire_inflight(clients)?;
+        let mut request_metrics = RequestMetrics::start(instance_name, clients, method);
+        let mut result = self.with_timeout(fut).await;
+        if let Ok(response) = &mut result {
+            request_metrics.succeed();
+            if self.instance_load_metadata
+                && let Some(load) = clients.load()
+                && let Ok(value) = format!("{:.3}", load).parse()
+            {
+                response
+                    .metadata_mut()
+                    .insert(INSTANCE_LOAD_METADATA, value);
+            }
+        }
+        result
+    }
+
+    /// Extract the requested route from a request's target
+    fn extract_target(target: Option<mux::Target>) -> Result<Route, Status> {
+        let target = target.ok_or_else(|| missing_field("target", "Missing target"))?;
 
         match target.routing {
             Some(mux::target::Routing::InstanceName(name)) => {
                 if name.is_empty() {
-                    return Err(Status::invalid_argument("Instance name cannot be empty"));
+                    return Err(invalid_field(
+                        "target.instance_name",
+                        "Instance name cannot be empty",
+                    ));
                 }
-                Ok(name)
+                Ok(Route::Instance(name))
             }
-            Some(mux::target::Routing::ModelId(_)) => {
-                // TODO: Auto-select instance by model
-                Err(Status::unimplemented(
-                    "Model-based routing not yet implemented",
-                ))
-            }
-            Some(mux::target::Routing::InstanceIndex(_)) => {
-                // TODO: Index-based routing
-                Err(Status::unimplemented(
-                    "Index-based routing not yet implemented",
-                ))
+            Some(mux::target::Routing::ModelId(model_id)) => {
+                if model_id.is_empty() {
+                    return Err(invalid_field("target.model_id", "Model id cannot be empty"));
+                }
+                Ok(Route::Model(model_id))
             }
-            None => Err(Status::invalid_argument("No routing specified")),
+            Some(mux::target::Routing::InstanceIndex(index)) => Ok(Route::Index(index)),
+            None => Err(missing_field("target.routing", "No routing specified")),
         }
     }
+
+    /// Target from [`INSTANCE_METADATA`] or [`MODEL_METADATA`], instance first
+    fn metadata_target(metadata: &MetadataMap) -> Result<Option<mux::Target>, Status> {
+        let value = |key: &str| {
+            metadata
+                .get(key)
+                .map(|value| {
+                    value
+                        .to_str()
+                        .map(str::to_string)
+                        .map_err(|_| invalid_field(key, format!("Invalid {} metadata value", key)))
+                })
+                .transpose()
+        };
+
+        let routing = if let Some(name) = value(INSTANCE_METADATA)? {
+            mux::target::Routing::InstanceName(name)
+        } else if let Some(model_id) = value(MODEL_METADATA)? {
+            mux::target::Routing::ModelId(model_id)
+        } else {
+            return Ok(None);
+        };
+        Ok(Some(mux::Target {
+            routing: Some(routing),
+        }))
+    }
+
+    /// Resolve a request's target to the instance it is forwarded to
+    ///
+    /// Requests without a routing `target` fall back to the routing metadata.
+    /// Model routing only considers running instances that are not paused.
+    async fn resolve_target(
+        &self,
+        target: Option<mux::Target>,
+        metadata: &MetadataMap,
+    ) -> Result<String, Status> {
+        let target = match target {
+            Some(mux::Target { routing: Some(_) }) => target,
+            _ => Self::metadata_target(metadata)?.or(target),
+        };
+        let (routing, instance) = match Self::extract_target(target)? {
+            Route::Instance(name) => (AccessRouting::Instance(name.clone()), name),
+            Route::Model(model_id) => {
+                let instance = self.pool.resolve_model(&model_id).await?;
+                (AccessRouting::Model(model_id), instance)
+            }
+            Route::Index(index) => (
+                AccessRouting::Index(index),
+                self.pool.resolve_index(index).await?,
+            ),
+        };
+        access_log::record_route(routing, &instance);
+        Ok(instance)
+    }
 }
 
 #[tonic::async_trait]
@@ -230,8 +976,9 @@ impl mux::tei_multiplexer_server::TeiMultiplexer for TeiMultiplexerService {
         &self,
         request: Request<mux::InfoRequest>,
     ) -> Result<Response<tei::InfoResponse>, Status> {
-        let req = request.into_inner();
-        let instance_name = Self::extract_target(req.target)?;
+        let (metadata, _, req) = request.into_parts();
+        let instance_name = self.resolve_target(req.target, &metadata).await?;
+        let metadata = self.forward_headers.from_grpc(&metadata);
 
         // Record instance name in span for tracing
         Span::current().record("instance", instance_name.as_str());
@@ -241,12 +988,39 @@ impl mux::tei_multiplexer_server::TeiMultiplexer for TeiMultiplexerService {
 
         // Forward request to backend with timeout
         let response = self
-            .with_timeout(async { clients.info.clone().info(tei::InfoRequest {}).await })
+            .forward("info", &instance_name, &clients, async {
+                clients
+                    .info
+                    .clone()
+                    .info(backend_request(&metadata, tei::InfoRequest {}))
+                    .await
+            })
             .await?;
 
         Ok(response)
     }
 
+    #[instrument(skip(self, _request))]
+    async fn list_instances(
+        &self,
+        _request: Request<mux::ListInstancesRequest>,
+    ) -> Result<Response<mux::ListInstancesResponse>, Status> {
+        let mut instances = Vec::new();
+        for (index, instance) in self.pool.instances_by_index().await.into_iter().enumerate() {
+            let status = *instance.status.read().await;
+            instances.push(mux::InstanceTarget {
+                name: instance.config.name.clone(),
+                model_id: instance.config.model_id.clone(),
+                status: status.to_string(),
+                index: index as u32,
+                routable: status == InstanceStatus::Running
+                    && !instance.is_paused()
+                    && !instance.is_restarting(),
+            });
+        }
+        Ok(Response::new(mux::ListInstancesResponse { instances }))
+    }
+
     // ========================================================================
     // Embed Service - Unary RPCs
     // ========================================================================
@@ -256,25 +1030,36 @@ impl mux::tei_multiplexer_server::TeiMultiplexer for TeiMultiplexerService {
         &self,
         request: Request<mux::EmbedRequest>,
     ) -> Result<Response<tei::EmbedResponse>, Status> {
-        let req = request.into_inner();
-        let instance_name = Self::extract_target(req.target)?;
+        let (metadata, _, req) = request.into_parts();
+        let instance_name = self.resolve_target(req.target, &metadata).await?;
+        let metadata = self.forward_headers.from_grpc(&metadata);
 
         // Extract inner request
         let embed_req = req
             .request
-            .ok_or_else(|| Status::invalid_argument("Missing embed request"))?;
+            .ok_or_else(|| missing_field("request", "Missing embed request"))?;
 
         // Record metrics
         Span::current()
             .record("instance", instance_name.as_str())
             .record("inputs_len", embed_req.inputs.len());
 
+        self.request_checks(&instance_name)
+            .await
+            .check(&embed_req)?;
+
         // Get backend client
         let clients = self.pool.get_clients(&instance_name).await?;
 
         // Forward to backend with timeout
         let response = self
-            .with_timeout(async { clients.embed.clone().embed(embed_req).await })
+            .forward("embed", &instance_name, &clients, async {
+                clients
+                    .embed
+                    .clone()
+                    .embed(backend_request(&metadata, embed_req))
+                    .await
+            })
             .await?;
 
         Ok(response)
@@ -285,18 +1070,29 @@ impl mux::tei_multiplexer_server::TeiMultiplexer for TeiMultiplexerService {
         &self,
         request: Request<mux::EmbedSparseRequest>,
     ) -> Result<Response<tei::EmbedSparseResponse>, Status> {
-        let req = request.into_inner();
-        let instance_name = Self::extract_target(req.target)?;
+        let (metadata, _, req) = request.into_parts();
+        let instance_name = self.resolve_target(req.target, &metadata).await?;
+        let metadata = self.forward_headers.from_grpc(&metadata);
 
         let inner_req = req
             .request
-            .ok_or_else(|| Status::invalid_argument("Missing embed_sparse request"))?;
+            .ok_or_else(|| missing_field("request", "Missing embed_sparse request"))?;
 
         Span::current().record("instance", instance_name.as_str());
 
+        self.request_checks(&instance_name)
+            .await
+            .check(&inner_req)?;
+
         let clients = self.pool.get_clients(&instance_name).await?;
         let response = self
-            .with_timeout(async { clients.embed.clone().embed_sparse(inner_req).await })
+            .forward("embed_sparse", &instance_name, &clients, async {
+                clients
+                    .embed
+                    .clone()
+                    .embed_sparse(backend_request(&metadata, inner_req))
+                    .await
+            })
             .await?;
 
         Ok(response)
@@ -307,18 +1103,29 @@ impl mux::tei_multiplexer_server::TeiMultiplexer for TeiMultiplexerService {
         &self,
         request: Request<mux::EmbedAllRequest>,
     ) -> Result<Response<tei::EmbedAllResponse>, Status> {
-        let req = request.into_inner();
-        let instance_name = Self::extract_target(req.target)?;
+        let (metadata, _, req) = request.into_parts();
+        let instance_name = self.resolve_target(req.target, &metadata).await?;
+        let metadata = self.forward_headers.from_grpc(&metadata);
 
         let inner_req = req
             .request
-            .ok_or_else(|| Status::invalid_argument("Missing embed_all request"))?;
+            .ok_or_else(|| missing_field("request", "Missing embed_all request"))?;
 
         Span::current().record("instance", instance_name.as_str());
 
+        self.request_checks(&instance_name)
+            .await
+            .check(&inner_req)?;
+
         let clients = self.pool.get_clients(&instance_name).await?;
         let response = self
-            .with_timeout(async { clients.embed.clone().embed_all(inner_req).await })
+            .forward("embed_all", &instance_name, &clients, async {
+                clients
+                    .embed
+                    .clone()
+                    .embed_all(backend_request(&metadata, inner_req))
+                    .await
+            })
             .await?;
 
         Ok(response)
@@ -376,18 +1183,25 @@ impl mux::tei_multiplexer_server::TeiMultiplexer for TeiMultiplexerService {
         &self,
         request: Request<mux::PredictRequest>,
     ) -> Result<Response<tei::PredictResponse>, Status> {
-        let req = request.into_inner();
-        let instance_name = Self::extract_target(req.target)?;
+        let (metadata, _, req) = request.into_parts();
+        let instance_name = self.resolve_target(req.target, &metadata).await?;
+        let metadata = self.forward_headers.from_grpc(&metadata);
 
         let inner_req = req
             .request
-            .ok_or_else(|| Status::invalid_argument("Missing predict request"))?;
+            .ok_or_else(|| missing_field("request", "Missing predict request"))?;
 
         Span::current().record("instance", instance_name.as_str());
 
         let clients = self.pool.get_clients(&instance_name).await?;
         let response = self
-            .with_timeout(async { clients.predict.clone().predict(inner_req).await })
+            .forward("predict", &instance_name, &clients, async {
+                clients
+                    .predict
+                    .clone()
+                    .predict(backend_request(&metadata, inner_req))
+                    .await
+            })
             .await?;
 
         Ok(response)
@@ -398,18 +1212,25 @@ impl mux::tei_multiplexer_server::TeiMultiplexer for TeiMultiplexerService {
         &self,
         request: Request<mux::PredictPairRequest>,
     ) -> Result<Response<tei::PredictResponse>, Status> {
-        let req = request.into_inner();
-        let instance_name = Self::extract_target(req.target)?;
+        let (metadata, _, req) = request.into_parts();
+        let instance_name = self.resolve_target(req.target, &metadata).await?;
+        let metadata = self.forward_headers.from_grpc(&metadata);
 
         let inner_req = req
             .request
-            .ok_or_else(|| Status::invalid_argument("Missing predict_pair request"))?;
+            .ok_or_else(|| missing_field("request", "Missing predict_pair request"))?;
 
         Span::current().record("instance", instance_name.as_str());
 
         let clients = self.pool.get_clients(&instance_name).await?;
         let response = self
-            .with_timeout(async { clients.predict.clone().predict_pair(inner_req).await })
+            .forward("predict_pair", &instance_name, &clients, async {
+                clients
+                    .predict
+                    .clone()
+                    .predict_pair(backend_request(&metadata, inner_req))
+                    .await
+            })
             .await?;
 
         Ok(response)
@@ -452,18 +1273,25 @@ impl mux::tei_multiplexer_server::TeiMultiplexer for TeiMultiplexerService {
         &self,
         request: Request<mux::RerankRequest>,
     ) -> Result<Response<tei::RerankResponse>, Status> {
-        let req = request.into_inner();
-        let instance_name = Self::extract_target(req.target)?;
+        let (metadata, _, req) = request.into_parts();
+        let instance_name = self.resolve_target(req.target, &metadata).await?;
+        let metadata = self.forward_headers.from_grpc(&metadata);
 
         let inner_req = req
             .request
-            .ok_or_else(|| Status::invalid_argument("Missing rerank request"))?;
+            .ok_or_else(|| missing_field("request", "Missing rerank request"))?;
 
         Span::current().record("instance", instance_name.as_str());
 
         let clients = self.pool.get_clients(&instance_name).await?;
         let response = self
-            .with_timeout(async { clients.rerank.clone().rerank(inner_req).await })
+            .forward("rerank", &instance_name, &clients, async {
+                clients
+                    .rerank
+                    .clone()
+                    .rerank(backend_request(&metadata, inner_req))
+                    .await
+            })
             .await?;
 
         Ok(response)
@@ -474,41 +1302,59 @@ impl mux::tei_multiplexer_server::TeiMultiplexer for TeiMultiplexerService {
         &self,
         request: Request<Streaming<mux::RerankStreamRequest>>,
     ) -> Result<Response<tei::RerankResponse>, Status> {
-        let mut stream = request.into_inner();
+        let (metadata, _, mut stream) = request.into_parts();
 
         let first_req = stream
             .next()
             .await
             .ok_or_else(|| Status::invalid_argument("Empty stream"))?
-            .map_err(|e| Status::internal(format!("Stream error: {}", e)))?;
+            .map_err(|e| stream_message_error(e, self.max_message_size_mb))?;
 
-        let instance_name = Self::extract_target(first_req.target)?;
+        let instance_name = self.resolve_target(first_req.target, &metadata).await?;
+        let metadata = self.forward_headers.from_grpc(&metadata);
         Span::current().record("instance", instance_name.as_str());
 
         let clients = self.pool.get_clients(&instance_name).await?;
 
-        // Create backend request stream
-        let backend_stream = async_stream::stream! {
-            if let Some(req) = first_req.request {
-                yield req;
-            }
-            while let Some(result) = stream.next().await {
-                match result {
-                    Ok(req) => {
-                        if let Some(inner) = req.request {
-                            yield inner;
+        // Create backend request stream, ending it at the first client stream error
+        let stream_error = StreamError::default();
+        let backend_stream = {
+            let stream_error = stream_error.clone();
+            let max_message_size_mb = self.max_message_size_mb;
+            async_stream::stream! {
+                if let Some(req) = first_req.request {
+                    yield req;
+                }
+                while let Some(result) = stream.next().await {
+                    match result {
+                        Ok(req) => {
+                            if let Some(inner) = req.request {
+                                yield inner;
+                            }
+                        }
+                        Err(e) => {
+                            tracing::error!("Stream error: {}", e);
+                            stream_error.set(stream_message_error(e, max_message_size_mb));
+                            break;
                         }
-                    }
-                    Err(e) => {
-                        tracing::error!("Stream error: {}", e);
-                        break;
                     }
                 }
             }
         };
 
         // RerankStream returns single response (not streaming)
-        let response = clients.rerank.clone().rerank_stream(backend_stream).await?;
+        let _inflight = self.acquire_inflight(&clients)?;
+        let mut request_metrics = RequestMetrics::start(&instance_name, &clients, "rerank_stream");
+        let response = clients
+            .rerank
+            .clone()
+            .rerank_stream(backend_request(&metadata, backend_stream))
+            .await?;
+        // The backend only ranked the messages before the error
+        if let Some(status) = stream_error.take() {
+            return Err(status);
+        }
+        request_metrics.succeed();
 
         Ok(response)
     }
@@ -522,18 +1368,25 @@ impl mux::tei_multiplexer_server::TeiMultiplexer for TeiMultiplexerService {
         &self,
         request: Request<mux::EncodeRequest>,
     ) -> Result<Response<tei::EncodeResponse>, Status> {
-        let req = request.into_inner();
-        let instance_name = Self::extract_target(req.target)?;
+        let (metadata, _, req) = request.into_parts();
+        let instance_name = self.resolve_target(req.target, &metadata).await?;
+        let metadata = self.forward_headers.from_grpc(&metadata);
 
         let inner_req = req
             .request
-            .ok_or_else(|| Status::invalid_argument("Missing tokenize request"))?;
+            .ok_or_else(|| missing_field("request", "Missing tokenize request"))?;
 
         Span::current().record("instance", instance_name.as_str());
 
         let clients = self.pool.get_clients(&instance_name).await?;
         let response = self
-            .with_timeout(async { clients.tokenize.clone().tokenize(inner_req).await })
+            .forward("tokenize", &instance_name, &clients, async {
+                clients
+                    .tokenize
+                    .clone()
+                    .tokenize(backend_request(&metadata, inner_req))
+                    .await
+            })
             .await?;
 
         Ok(response)
@@ -555,18 +1408,25 @@ impl mux::tei_multiplexer_server::TeiMultiplexer for TeiMultiplexerService {
         &self,
         request: Request<mux::DecodeRequest>,
     ) -> Result<Response<tei::DecodeResponse>, Status> {
-        let req = request.into_inner();
-        let instance_name = Self::extract_target(req.target)?;
+        let (metadata, _, req) = request.into_parts();
+        let instance_name = self.resolve_target(req.target, &metadata).await?;
+        let metadata = self.forward_headers.from_grpc(&metadata);
 
         let inner_req = req
             .request
-            .ok_or_else(|| Status::invalid_argument("Missing decode request"))?;
+            .ok_or_else(|| missing_field("request", "Missing decode request"))?;
 
         Span::current().record("instance", instance_name.as_str());
 
         let clients = self.pool.get_clients(&instance_name).await?;
         let response = self
-            .with_timeout(async { clients.tokenize.clone().decode(inner_req).await })
+            .forward("decode", &instance_name, &clients, async {
+                clients
+                    .tokenize
+                    .clone()
+                    .decode(backend_request(&metadata, inner_req))
+                    .await
+            })
             .await?;
 
         Ok(response)
@@ -592,20 +1452,14 @@ impl mux::tei_multiplexer_server::TeiMultiplexer for TeiMultiplexerService {
         &self,
         request: Request<mux::EmbedArrowRequest>,
     ) -> Result<Response<mux::EmbedArrowResponse>, Status> {
-        let req = request.into_inner();
-        let instance_name = Self::extract_target(req.target)?;
+        let (metadata, _, req) = request.into_parts();
+        let instance_name = self.resolve_target(req.target, &metadata).await?;
+        let metadata = self.forward_headers.from_grpc(&metadata);
 
         Span::current().record("instance", instance_name.as_str());
 
-        // Deserialize Arrow RecordBatch
-        let cursor = Cursor::new(&req.arrow_ipc);
-        let mut reader = StreamReader::try_new(cursor, None)
-            .map_err(|e| Status::invalid_argument(format!("Invalid Arrow IPC: {}", e)))?;
-
-        let batch = reader
-            .next()
-            .ok_or_else(|| Status::invalid_argument("No RecordBatch in stream"))?
-            .map_err(|e| Status::invalid_argument(format!("Failed to read RecordBatch: {}", e)))?;
+        let batch = read_arrow_batch(&req.arrow_ipc)?;
+        let compression = arrow_compression(req.compression)?;
 
         Span::current().record("num_rows", batch.num_rows());
 
@@ -614,65 +1468,103 @@ impl mux::tei_multiplexer_server::TeiMultiplexer for TeiMultiplexerService {
             .column(0)
             .as_any()
             .downcast_ref::<StringArray>()
-            .ok_or_else(|| Status::invalid_argument("First column must be StringArray"))?;
+            .ok_or_else(|| invalid_field("arrow_ipc", "First column must be StringArray"))?;
 
-        // Check if noop mode (for round-trip testing)
+        let truncation_direction = truncation_direction(req.truncation_direction)?;
+        self.request_checks(&instance_name)
+            .await
+            .check_dimensions(req.dimensions)?;
+
+        // Oversized batches are sent as consecutive sub-batches
         let num_rows = text_array.len();
+        let chunk_rows = self.arrow_max_rows_per_chunk.unwrap_or(num_rows).max(1);
+
+        // Check if noop mode (for round-trip testing)
         let (embedding_len, flat_embeddings): (i32, Vec<f32>) = if req.noop {
-            // Noop mode: return dummy embeddings instantly
-            let emb_len = 384i32; // Standard BGE-small embedding size
-            let flat = vec![0.0f32; num_rows * emb_len as usize];
+            // Noop mode: return dummy embeddings instantly, 384 = standard BGE-small size
+            let emb_len = req.dimensions.unwrap_or(384) as i32;
+            let noop_mode = mux::NoopMode::try_from(req.noop_mode).map_err(|_| {
+                invalid_field("noop_mode", format!("Unknown noop_mode {}", req.noop_mode))
+            })?;
+            let mut flat = Vec::with_capacity(num_rows * emb_len as usize);
+            match noop_mode {
+                mux::NoopMode::Zeros => {
+                    for start in (0..num_rows).step_by(chunk_rows) {
+                        let rows = chunk_rows.min(num_rows - start);
+                        flat.extend(std::iter::repeat_n(0.0f32, rows * emb_len as usize));
+                    }
+                }
+                mux::NoopMode::Hashed => {
+                    for row in 0..num_rows {
+                        flat.extend(pseudo_embedding(text_array.value(row), emb_len as usize));
+                    }
+                }
+            }
             (emb_len, flat)
         } else {
-            // Normal mode: use gRPC streaming for efficiency
+            // Normal mode: forward each sub-batch in the configured ordering mode
             let clients = self.pool.get_clients(&instance_name).await?;
+            let _inflight = self.acquire_inflight(&clients)?;
+            let mut request_metrics =
+                RequestMetrics::start(&instance_name, &clients, "embed_arrow");
 
-            // Build requests directly from Arrow array - single allocation per row
-            let truncate = req.truncate;
-            let normalize = Some(req.normalize);
-
-            let requests: Vec<tei::EmbedRequest> = (0..num_rows)
-                .filter(|&i| !text_array.is_null(i))
-                .map(|i| tei::EmbedRequest {
-                    inputs: text_array.value(i).to_string(),
-                    truncate,
-                    normalize,
-                    truncation_direction: 0,
-                    prompt_name: None,
-                    dimensions: None,
-                })
-                .collect();
-
-            let request_stream = tokio_stream::iter(requests);
-
-            // Call TEI's embed_stream (batched streaming)
-            let mut response_stream = clients
-                .embed
-                .clone()
-                .embed_stream(request_stream)
-                .await
-                .map_err(|e| Status::internal(format!("embed_stream failed: {}", e)))?
-                .into_inner();
-
-            // Collect responses directly into flat buffer - avoid intermediate Vec<Vec<f32>>
+            // Collect responses into one flat buffer
             let mut flat_embeddings: Vec<f32> = Vec::new();
             let mut emb_len: Option<i32> = None;
 
-            while let Some(result) = response_stream.next().await {
-                let response = result
-                    .map_err(|e| Status::internal(format!("Stream response error: {}", e)))?;
+            for start in (0..num_rows).step_by(chunk_rows) {
+                let end = (start + chunk_rows).min(num_rows);
+
+                // Build requests directly from Arrow array - single allocation per row
+                let requests = arrow_embed_requests(
+                    text_array,
+                    start..end,
+                    req.truncate,
+                    req.normalize,
+                    truncation_direction,
+                    req.dimensions,
+                );
+
+                let embeddings = match self.arrow_embed_ordering {
+                    ArrowEmbedOrdering::Indexed => {
+                        // More concurrent calls than the backend has permits get "overloaded"
+                        let concurrency = match clients.capacity() {
+                            0 => self.max_parallel_stream_requests,
+                            capacity => self.max_parallel_stream_requests.min(capacity as usize),
+                        };
+                        embed_indexed(&clients, &metadata, requests, concurrency).await?
+                    }
+                    ArrowEmbedOrdering::Stream => {
+                        embed_streamed(&clients, &metadata, requests).await?
+                    }
+                };
+
+                for embedding in embeddings {
+                    // A backend without Matryoshka support would break the fixed row size
+                    if let Some(dimensions) = req.dimensions
+                        && embedding.len() != dimensions as usize
+                    {
+                        return Err(Status::internal(format!(
+                            "Backend returned {} dimensions, {} requested",
+                            embedding.len(),
+                            dimensions
+                        )));
+                    }
 
-                if emb_len.is_none() {
-                    let len = response.embeddings.len() as i32;
-                    emb_len = Some(len);
-                    // Pre-allocate for expected total size
-                    flat_embeddings.reserve(num_rows * len as usize);
-                }
+                    if emb_len.is_none() {
+                        let len = embedding.len() as i32;
+                        emb_len = Some(len);
+                        // Pre-allocate for expected total size
+                        flat_embeddings.reserve(num_rows * len as usize);
+                    }
 
-                flat_embeddings.extend(response.embeddings);
+                    flat_embeddings.extend(embedding);
+                }
             }
 
-            (emb_len.unwrap_or(384), flat_embeddings)
+            request_metrics.succeed();
+            let default_len = req.dimensions.unwrap_or(384) as i32;
+            (emb_len.unwrap_or(default_len), flat_embeddings)
         };
         let values = Arc::new(Float32Array::from(flat_embeddings)) as ArrayRef;
 
@@ -692,31 +1584,7 @@ impl mux::tei_multiplexer_server::TeiMultiplexer for TeiMultiplexerService {
             RecordBatch::try_new(schema, vec![Arc::new(embeddings_array) as ArrayRef])
                 .map_err(|e| Status::internal(format!("Failed to create RecordBatch: {}", e)))?;
 
-        // Serialize to Arrow IPC with LZ4 compression
-        let mut buffer = Vec::new();
-        {
-            use arrow::ipc::CompressionType;
-            use arrow::ipc::writer::IpcWriteOptions;
-
-            let write_options = IpcWriteOptions::default()
-                .try_with_compression(Some(CompressionType::LZ4_FRAME))
-                .map_err(|e| Status::internal(format!("Failed to set compression: {}", e)))?;
-
-            let mut writer = StreamWriter::try_new_with_options(
-                &mut buffer,
-                &result_batch.schema(),
-                write_options,
-            )
-            .map_err(|e| Status::internal(format!("Failed to create IPC writer: {}", e)))?;
-
-            writer
-                .write(&result_batch)
-                .map_err(|e| Status::internal(format!("Failed to write RecordBatch: {}", e)))?;
-
-            writer
-                .finish()
-                .map_err(|e| Status::internal(format!("Failed to finish IPC writer: {}", e)))?;
-        }
+        let buffer = write_arrow_ipc(&result_batch, compression)?;
 
         Ok(Response::new(mux::EmbedArrowResponse { arrow_ipc: buffer }))
     }
@@ -726,20 +1594,14 @@ impl mux::tei_multiplexer_server::TeiMultiplexer for TeiMultiplexerService {
         &self,
         request: Request<mux::EmbedSparseArrowRequest>,
     ) -> Result<Response<mux::EmbedSparseArrowResponse>, Status> {
-        let req = request.into_inner();
-        let instance_name = Self::extract_target(req.target)?;
+        let (metadata, _, req) = request.into_parts();
+        let instance_name = self.resolve_target(req.target, &metadata).await?;
+        let metadata = self.forward_headers.from_grpc(&metadata);
 
         Span::current().record("instance", instance_name.as_str());
 
-        // Deserialize Arrow RecordBatch
-        let cursor = Cursor::new(&req.arrow_ipc);
-        let mut reader = StreamReader::try_new(cursor, None)
-            .map_err(|e| Status::invalid_argument(format!("Invalid Arrow IPC: {}", e)))?;
-
-        let batch = reader
-            .next()
-            .ok_or_else(|| Status::invalid_argument("No RecordBatch in stream"))?
-            .map_err(|e| Status::invalid_argument(format!("Failed to read RecordBatch: {}", e)))?;
+        let batch = read_arrow_batch(&req.arrow_ipc)?;
+        let compression = arrow_compression(req.compression)?;
 
         Span::current().record("num_rows", batch.num_rows());
 
@@ -748,7 +1610,7 @@ impl mux::tei_multiplexer_server::TeiMultiplexer for TeiMultiplexerService {
             .column(0)
             .as_any()
             .downcast_ref::<StringArray>()
-            .ok_or_else(|| Status::invalid_argument("First column must be StringArray"))?;
+            .ok_or_else(|| invalid_field("arrow_ipc", "First column must be StringArray"))?;
 
         let num_rows = text_array.len();
 
@@ -766,6 +1628,9 @@ impl mux::tei_multiplexer_server::TeiMultiplexer for TeiMultiplexerService {
                 .collect()
         } else {
             let clients = self.pool.get_clients(&instance_name).await?;
+            let _inflight = self.acquire_inflight(&clients)?;
+            let mut request_metrics =
+                RequestMetrics::start(&instance_name, &clients, "embed_sparse_arrow");
 
             let truncate = req.truncate;
             let requests: Vec<tei::EmbedSparseRequest> = (0..num_rows)
@@ -783,7 +1648,7 @@ impl mux::tei_multiplexer_server::TeiMultiplexer for TeiMultiplexerService {
             let mut response_stream = clients
                 .embed
                 .clone()
-                .embed_sparse_stream(request_stream)
+                .embed_sparse_stream(backend_request(&metadata, request_stream))
                 .await
                 .map_err(|e| Status::internal(format!("embed_sparse_stream failed: {}", e)))?
                 .into_inner();
@@ -800,6 +1665,7 @@ impl mux::tei_multiplexer_server::TeiMultiplexer for TeiMultiplexerService {
                     .collect();
                 results.push(sparse);
             }
+            request_metrics.succeed();
             results
         };
 
@@ -851,46 +1717,121 @@ impl mux::tei_multiplexer_server::TeiMultiplexer for TeiMultiplexerService {
             None,
         );
 
-        let schema = Arc::new(Schema::new(vec![Field::new(
-            "sparse_embeddings",
-            DataType::List(Arc::new(Field::new(
-                "item",
-                DataType::Struct(struct_fields),
-                false,
-            ))),
-            false,
-        )]));
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "sparse_embeddings",
+            DataType::List(Arc::new(Field::new(
+                "item",
+                DataType::Struct(struct_fields),
+                false,
+            ))),
+            false,
+        )]));
+
+        let result_batch = RecordBatch::try_new(schema, vec![Arc::new(list_array) as ArrayRef])
+            .map_err(|e| Status::internal(format!("Failed to create RecordBatch: {}", e)))?;
+
+        let buffer = write_arrow_ipc(&result_batch, compression)?;
+
+        Ok(Response::new(mux::EmbedSparseArrowResponse {
+            arrow_ipc: buffer,
+        }))
+    }
+    #[instrument(skip(self, request), fields(instance, num_rows))]
+    async fn rerank_arrow(
+        &self,
+        request: Request<mux::RerankArrowRequest>,
+    ) -> Result<Response<mux::RerankArrowResponse>, Status> {
+        let (metadata, _, req) = request.into_parts();
+        let instance_name = self.resolve_target(req.target, &metadata).await?;
+        let metadata = self.forward_headers.from_grpc(&metadata);
+
+        Span::current().record("instance", instance_name.as_str());
+
+        let batch = read_arrow_batch(&req.arrow_ipc)?;
+        let compression = arrow_compression(req.compression)?;
+
+        Span::current().record("num_rows", batch.num_rows());
+
+        // Extract text column
+        let text_array = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .ok_or_else(|| invalid_field("arrow_ipc", "First column must be StringArray"))?;
+
+        // Oversized batches are sent as consecutive sub-batches
+        let num_rows = text_array.len();
+        let chunk_rows = self.arrow_max_rows_per_chunk.unwrap_or(num_rows).max(1);
 
-        let result_batch = RecordBatch::try_new(schema, vec![Arc::new(list_array) as ArrayRef])
-            .map_err(|e| Status::internal(format!("Failed to create RecordBatch: {}", e)))?;
+        // One score per input row; null texts are not sent and keep a null score
+        let scores: Vec<Option<f32>> = if req.noop {
+            // Noop mode: score rows in descending order instantly
+            (0..num_rows)
+                .map(|i| (!text_array.is_null(i)).then_some(1.0 / (i as f32 + 1.0)))
+                .collect()
+        } else {
+            let clients = self.pool.get_clients(&instance_name).await?;
+            let _inflight = self.acquire_inflight(&clients)?;
+            let mut request_metrics =
+                RequestMetrics::start(&instance_name, &clients, "rerank_arrow");
+
+            let mut scores = vec![None; num_rows];
+            for start in (0..num_rows).step_by(chunk_rows) {
+                let end = (start + chunk_rows).min(num_rows);
+
+                // Ranks index into the texts sent, so remember which row each came from
+                let rows: Vec<usize> = (start..end).filter(|&i| !text_array.is_null(i)).collect();
+                if rows.is_empty() {
+                    continue;
+                }
 
-        // Serialize to Arrow IPC with LZ4 compression
-        let mut buffer = Vec::new();
-        {
-            use arrow::ipc::CompressionType;
-            use arrow::ipc::writer::IpcWriteOptions;
+                let rerank_req = tei::RerankRequest {
+                    query: req.query.clone(),
+                    texts: rows
+                        .iter()
+                        .map(|&i| text_array.value(i).to_string())
+                        .collect(),
+                    truncate: req.truncate,
+                    raw_scores: req.raw_scores,
+                    return_text: false,
+                    truncation_direction: 0,
+                };
+
+                let response = clients
+                    .rerank
+                    .clone()
+                    .rerank(backend_request(&metadata, rerank_req))
+                    .await
+                    .map_err(|e| Status::internal(format!("rerank failed: {}", e)))?
+                    .into_inner();
+
+                for rank in response.ranks {
+                    let row = rows.get(rank.index as usize).ok_or_else(|| {
+                        Status::internal(format!("rerank returned unknown index {}", rank.index))
+                    })?;
+                    scores[*row] = Some(rank.score);
+                }
+            }
 
-            let write_options = IpcWriteOptions::default()
-                .try_with_compression(Some(CompressionType::LZ4_FRAME))
-                .map_err(|e| Status::internal(format!("Failed to set compression: {}", e)))?;
+            request_metrics.succeed();
+            scores
+        };
 
-            let mut writer = StreamWriter::try_new_with_options(
-                &mut buffer,
-                &result_batch.schema(),
-                write_options,
-            )
-            .map_err(|e| Status::internal(format!("Failed to create IPC writer: {}", e)))?;
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "scores",
+            DataType::Float32,
+            true,
+        )]));
 
-            writer
-                .write(&result_batch)
-                .map_err(|e| Status::internal(format!("Failed to write RecordBatch: {}", e)))?;
+        let result_batch = RecordBatch::try_new(
+            schema,
+            vec![Arc::new(Float32Array::from(scores)) as ArrayRef],
+        )
+        .map_err(|e| Status::internal(format!("Failed to create RecordBatch: {}", e)))?;
 
-            writer
-                .finish()
-                .map_err(|e| Status::internal(format!("Failed to finish IPC writer: {}", e)))?;
-        }
+        let buffer = write_arrow_ipc(&result_batch, compression)?;
 
-        Ok(Response::new(mux::EmbedSparseArrowResponse {
+        Ok(Response::new(mux::RerankArrowResponse {
             arrow_ipc: buffer,
         }))
     }
@@ -946,7 +1887,10 @@ mod tests {
         });
         let result = TeiMultiplexerService::extract_target(target);
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), "test-instance");
+        assert_eq!(
+            result.unwrap(),
+            Route::Instance("test-instance".to_string())
+        );
     }
 
     #[test]
@@ -981,33 +1925,186 @@ mod tests {
     }
 
     #[test]
-    fn test_extract_target_model_routing_unimplemented() {
+    fn test_extract_target_model_id() {
         let target = Some(mux::Target {
             routing: Some(mux::target::Routing::ModelId("bert-base".to_string())),
         });
         let result = TeiMultiplexerService::extract_target(target);
-        assert!(result.is_err());
-        let err = result.unwrap_err();
-        assert_eq!(err.code(), Code::Unimplemented);
-        assert!(
-            err.message()
-                .contains("Model-based routing not yet implemented")
+        assert_eq!(result.unwrap(), Route::Model("bert-base".to_string()));
+
+        let target = Some(mux::Target {
+            routing: Some(mux::target::Routing::ModelId(String::new())),
+        });
+        let err = TeiMultiplexerService::extract_target(target).unwrap_err();
+        assert_eq!(err.code(), Code::InvalidArgument);
+        assert!(err.message().contains("cannot be empty"));
+    }
+
+    #[tokio::test]
+    async fn test_model_routing_without_instances() {
+        let service = create_test_service();
+        let target = Some(mux::Target {
+            routing: Some(mux::target::Routing::ModelId("bert-base".to_string())),
+        });
+        let err = service
+            .resolve_target(target, &MetadataMap::new())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::NotFound);
+    }
+
+    fn routing_metadata(pairs: &[(&'static str, &'static str)]) -> MetadataMap {
+        let mut metadata = MetadataMap::new();
+        for (key, value) in pairs {
+            metadata.insert(*key, value.parse().unwrap());
+        }
+        metadata
+    }
+
+    #[tokio::test]
+    async fn test_metadata_instance_routing() {
+        let service = create_test_service();
+        let metadata = routing_metadata(&[(INSTANCE_METADATA, "bert")]);
+        assert_eq!(
+            service.resolve_target(None, &metadata).await.unwrap(),
+            "bert"
+        );
+
+        // An empty target is treated as absent
+        let empty = Some(mux::Target { routing: None });
+        assert_eq!(
+            service.resolve_target(empty, &metadata).await.unwrap(),
+            "bert"
+        );
+
+        let err = service
+            .resolve_target(None, &routing_metadata(&[(INSTANCE_METADATA, "")]))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_metadata_model_routing() {
+        let registry = Arc::new(Registry::new(
+            None,
+            "text-embeddings-router".to_string(),
+            8080,
+            8180,
+        ));
+        add_test_instance(&registry, "a", 8081).await;
+        *registry.get("a").await.unwrap().status.write().await =
+            crate::instance::InstanceStatus::Running;
+        let service = TeiMultiplexerService::new(BackendPool::new(registry), 1024, 30);
+
+        let metadata = routing_metadata(&[(MODEL_METADATA, "test-model")]);
+        assert_eq!(service.resolve_target(None, &metadata).await.unwrap(), "a");
+
+        // The instance header wins over the model header
+        let metadata =
+            routing_metadata(&[(MODEL_METADATA, "test-model"), (INSTANCE_METADATA, "b")]);
+        assert_eq!(service.resolve_target(None, &metadata).await.unwrap(), "b");
+
+        let metadata = routing_metadata(&[(MODEL_METADATA, "other-model")]);
+        let err = service.resolve_target(None, &metadata).await.unwrap_err();
+        assert_eq!(err.code(), Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_list_instances_reports_targets_by_index() {
+        let registry = Arc::new(Registry::new(
+            None,
+            "text-embeddings-router".to_string(),
+            8080,
+            8180,
+        ));
+        for (name, port) in [("charlie", 8083), ("alpha", 8081), ("bravo", 8082)] {
+            add_test_instance(&registry, name, port).await;
+        }
+        registry
+            .get("bravo")
+            .await
+            .unwrap()
+            .set_status(InstanceStatus::Running)
+            .await;
+        let service = TeiMultiplexerService::new(BackendPool::new(registry), 1024, 30);
+
+        let instances = service
+            .list_instances(Request::new(mux::ListInstancesRequest {}))
+            .await
+            .unwrap()
+            .into_inner()
+            .instances;
+        let listed: Vec<_> = instances
+            .iter()
+            .map(|i| (i.name.as_str(), i.model_id.as_str(), i.index, i.routable))
+            .collect();
+        assert_eq!(
+            listed,
+            [
+                ("alpha", "test-model", 0, false),
+                ("bravo", "test-model", 1, true),
+                ("charlie", "test-model", 2, false),
+            ]
+        );
+        assert_eq!(instances[0].status, "stopped");
+        assert_eq!(instances[1].status, "running");
+
+        // Indices route to the instance listed at that position
+        for instance in &instances {
+            let target = Some(mux::Target {
+                routing: Some(mux::target::Routing::InstanceIndex(instance.index)),
+            });
+            assert_eq!(
+                service
+                    .resolve_target(target, &MetadataMap::new())
+                    .await
+                    .unwrap(),
+                instance.name
+            );
+        }
+        let target = Some(mux::Target {
+            routing: Some(mux::target::Routing::InstanceIndex(3)),
+        });
+        let err = service
+            .resolve_target(target, &MetadataMap::new())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_target_takes_precedence_over_metadata() {
+        let service = create_test_service();
+        let target = Some(mux::Target {
+            routing: Some(mux::target::Routing::InstanceName(
+                "from-target".to_string(),
+            )),
+        });
+        let metadata = routing_metadata(&[
+            (INSTANCE_METADATA, "from-metadata"),
+            (MODEL_METADATA, "test-model"),
+        ]);
+        assert_eq!(
+            service.resolve_target(target, &metadata).await.unwrap(),
+            "from-target"
         );
+
+        // Without either, the request is still rejected
+        let err = service
+            .resolve_target(None, &MetadataMap::new())
+            .await
+            .unwrap_err();
+        assert!(err.message().contains("Missing target"));
     }
 
     #[test]
-    fn test_extract_target_index_routing_unimplemented() {
+    fn test_extract_target_index_routing() {
         let target = Some(mux::Target {
-            routing: Some(mux::target::Routing::InstanceIndex(0)),
+            routing: Some(mux::target::Routing::InstanceIndex(2)),
         });
         let result = TeiMultiplexerService::extract_target(target);
-        assert!(result.is_err());
-        let err = result.unwrap_err();
-        assert_eq!(err.code(), Code::Unimplemented);
-        assert!(
-            err.message()
-                .contains("Index-based routing not yet implemented")
-        );
+        assert!(matches!(result, Ok(Route::Index(2))));
     }
 
     // ========================================================================
@@ -1459,6 +2556,10 @@ mod tests {
             truncate: true,
             normalize: true,
             noop: false,
+            truncation_direction: 0,
+            compression: 0,
+            dimensions: None,
+            noop_mode: 0,
         });
         let result = service.embed_arrow(request).await;
         assert!(result.is_err());
@@ -1476,6 +2577,10 @@ mod tests {
             truncate: true,
             normalize: true,
             noop: false,
+            truncation_direction: 0,
+            compression: 0,
+            dimensions: None,
+            noop_mode: 0,
         });
         let result = service.embed_arrow(request).await;
         assert!(result.is_err());
@@ -1495,6 +2600,10 @@ mod tests {
             truncate: true,
             normalize: true,
             noop: false,
+            truncation_direction: 0,
+            compression: 0,
+            dimensions: None,
+            noop_mode: 0,
         });
         let result = service.embed_arrow(request).await;
         assert!(result.is_err());
@@ -1532,6 +2641,10 @@ mod tests {
             truncate: true,
             normalize: true,
             noop: true, // Noop mode - returns dummy embeddings
+            truncation_direction: 0,
+            compression: 0,
+            dimensions: None,
+            noop_mode: 0,
         });
 
         let result = service.embed_arrow(request).await;
@@ -1548,6 +2661,173 @@ mod tests {
         assert_eq!(result_batch.num_rows(), 2); // 2 texts -> 2 embeddings
     }
 
+    #[tokio::test]
+    async fn test_embed_arrow_zstd_round_trip() {
+        use arrow::ipc::writer::IpcWriteOptions;
+
+        let service = create_test_service();
+
+        // A zstd-compressed request decodes without being told the codec
+        let schema = Arc::new(Schema::new(vec![Field::new("text", DataType::Utf8, false)]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(StringArray::from(vec!["Hello", "World"])) as ArrayRef],
+        )
+        .unwrap();
+        let write_options = IpcWriteOptions::default()
+            .try_with_compression(Some(CompressionType::ZSTD))
+            .unwrap();
+        let mut arrow_ipc = Vec::new();
+        {
+            let mut writer =
+                StreamWriter::try_new_with_options(&mut arrow_ipc, &schema, write_options).unwrap();
+            writer.write(&batch).unwrap();
+            writer.finish().unwrap();
+        }
+
+        let request = |compression: mux::ArrowCompression| {
+            Request::new(mux::EmbedArrowRequest {
+                target: Some(mux::Target {
+                    routing: Some(mux::target::Routing::InstanceName("test".to_string())),
+                }),
+                arrow_ipc: arrow_ipc.clone(),
+                truncate: true,
+                normalize: true,
+                noop: true,
+                truncation_direction: 0,
+                compression: compression as i32,
+                dimensions: None,
+                noop_mode: 0,
+            })
+        };
+
+        let zstd = service
+            .embed_arrow(request(mux::ArrowCompression::Zstd))
+            .await
+            .unwrap()
+            .into_inner()
+            .arrow_ipc;
+        let lz4 = service
+            .embed_arrow(request(mux::ArrowCompression::Lz4Frame))
+            .await
+            .unwrap()
+            .into_inner()
+            .arrow_ipc;
+        assert_ne!(zstd, lz4);
+
+        // Readers detect the codec from the stream
+        for response in [zstd, lz4] {
+            let mut reader = StreamReader::try_new(Cursor::new(response), None).unwrap();
+            let result_batch = reader.next().unwrap().unwrap();
+            assert_eq!(result_batch.num_rows(), 2);
+        }
+
+        let mut unknown = request(mux::ArrowCompression::Zstd);
+        unknown.get_mut().compression = 7;
+        let err = service.embed_arrow(unknown).await.unwrap_err();
+        assert_eq!(err.code(), Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_embed_arrow_chunked_preserves_rows() {
+        use arrow::array::StringArray;
+        use arrow::datatypes::{DataType, Field, Schema};
+        use arrow::ipc::writer::StreamWriter;
+        use arrow::record_batch::RecordBatch;
+
+        // 10 rows with a chunk size of 3 -> sub-batches of 3, 3, 3, 1
+        let service = create_test_service().with_arrow_max_rows_per_chunk(3);
+
+        let texts: Vec<String> = (0..10).map(|i| format!("text {}", i)).collect();
+        let text_array = StringArray::from(texts);
+        let schema = Arc::new(Schema::new(vec![Field::new("text", DataType::Utf8, false)]));
+        let batch =
+            RecordBatch::try_new(schema.clone(), vec![Arc::new(text_array) as ArrayRef]).unwrap();
+
+        let mut arrow_ipc = Vec::new();
+        {
+            let mut writer = StreamWriter::try_new(&mut arrow_ipc, &schema).unwrap();
+            writer.write(&batch).unwrap();
+            writer.finish().unwrap();
+        }
+
+        let request = Request::new(mux::EmbedArrowRequest {
+            target: Some(mux::Target {
+                routing: Some(mux::target::Routing::InstanceName("test".to_string())),
+            }),
+            arrow_ipc,
+            truncate: true,
+            normalize: true,
+            noop: true,
+            truncation_direction: 0,
+            compression: 0,
+            dimensions: None,
+            noop_mode: 0,
+        });
+
+        let response = service.embed_arrow(request).await.unwrap().into_inner();
+
+        // Sub-batches are concatenated back into one output batch
+        let cursor = std::io::Cursor::new(response.arrow_ipc);
+        let mut reader = StreamReader::try_new(cursor, None).unwrap();
+        let result_batch = reader.next().unwrap().unwrap();
+        assert_eq!(result_batch.num_rows(), 10);
+        assert!(reader.next().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_embed_arrow_multi_batch_stream() {
+        use arrow::array::StringArray;
+        use arrow::datatypes::{DataType, Field, Schema};
+        use arrow::ipc::writer::StreamWriter;
+        use arrow::record_batch::RecordBatch;
+
+        let service = create_test_service();
+
+        let schema = Arc::new(Schema::new(vec![Field::new("text", DataType::Utf8, false)]));
+        let first = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(StringArray::from(vec!["a", "b"])) as ArrayRef],
+        )
+        .unwrap();
+        let second = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(StringArray::from(vec!["c", "d", "e"])) as ArrayRef],
+        )
+        .unwrap();
+
+        let mut arrow_ipc = Vec::new();
+        {
+            let mut writer = StreamWriter::try_new(&mut arrow_ipc, &schema).unwrap();
+            writer.write(&first).unwrap();
+            writer.write(&second).unwrap();
+            writer.finish().unwrap();
+        }
+
+        let request = Request::new(mux::EmbedArrowRequest {
+            target: Some(mux::Target {
+                routing: Some(mux::target::Routing::InstanceName("test".to_string())),
+            }),
+            arrow_ipc,
+            truncate: true,
+            normalize: true,
+            noop: true,
+            truncation_direction: 0,
+            compression: 0,
+            dimensions: None,
+            noop_mode: 0,
+        });
+
+        let response = service.embed_arrow(request).await.unwrap().into_inner();
+
+        // Rows from both input batches come back in one output batch
+        let cursor = std::io::Cursor::new(response.arrow_ipc);
+        let mut reader = StreamReader::try_new(cursor, None).unwrap();
+        let result_batch = reader.next().unwrap().unwrap();
+        assert_eq!(result_batch.num_rows(), 5);
+        assert!(reader.next().is_none());
+    }
+
     #[tokio::test]
     async fn test_embed_arrow_wrong_column_type() {
         use arrow::array::Int32Array;
@@ -1582,6 +2862,10 @@ mod tests {
             truncate: true,
             normalize: true,
             noop: true,
+            truncation_direction: 0,
+            compression: 0,
+            dimensions: None,
+            noop_mode: 0,
         });
 
         let result = service.embed_arrow(request).await;
@@ -1623,11 +2907,111 @@ mod tests {
             truncate: true,
             normalize: true,
             noop: false, // Not noop, so it will try to find instance
+            truncation_direction: 0,
+            compression: 0,
+            dimensions: None,
+            noop_mode: 0,
+        });
+
+        let result = service.embed_arrow(request).await;
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err().code(), Code::NotFound);
+    }
+
+    /// Embeddings returned by a hashed-noop embed_arrow call, one Vec per row
+    async fn hashed_noop_embeddings(texts: Vec<&str>) -> Vec<Vec<f32>> {
+        let service = create_test_service();
+        let schema = Arc::new(Schema::new(vec![Field::new("text", DataType::Utf8, false)]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(StringArray::from(texts)) as ArrayRef],
+        )
+        .unwrap();
+        let mut arrow_ipc = Vec::new();
+        {
+            let mut writer = StreamWriter::try_new(&mut arrow_ipc, &schema).unwrap();
+            writer.write(&batch).unwrap();
+            writer.finish().unwrap();
+        }
+
+        let request = Request::new(mux::EmbedArrowRequest {
+            target: Some(mux::Target {
+                routing: Some(mux::target::Routing::InstanceName("test".to_string())),
+            }),
+            arrow_ipc,
+            noop: true,
+            noop_mode: mux::NoopMode::Hashed as i32,
+            dimensions: Some(16),
+            ..Default::default()
+        });
+        let response = service.embed_arrow(request).await.unwrap().into_inner();
+        let mut reader =
+            StreamReader::try_new(std::io::Cursor::new(response.arrow_ipc), None).unwrap();
+        let result = reader.next().unwrap().unwrap();
+        let embeddings = result
+            .column(0)
+            .as_any()
+            .downcast_ref::<FixedSizeListArray>()
+            .unwrap();
+        (0..embeddings.len())
+            .map(|row| {
+                let values = embeddings.value(row);
+                let values = values.as_any().downcast_ref::<Float32Array>().unwrap();
+                values.values().to_vec()
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_embed_arrow_hashed_noop_is_reproducible() {
+        let first = hashed_noop_embeddings(vec!["alpha", "beta", "alpha"]).await;
+        let second = hashed_noop_embeddings(vec!["alpha"]).await;
+
+        assert_eq!(first[0].len(), 16);
+        assert_eq!(first[0], first[2]);
+        assert_eq!(first[0], second[0]);
+    }
+
+    #[tokio::test]
+    async fn test_embed_arrow_hashed_noop_distinguishes_inputs() {
+        let embeddings = hashed_noop_embeddings(vec!["alpha", "beta", ""]).await;
+
+        assert_ne!(embeddings[0], embeddings[1]);
+        assert_ne!(embeddings[0], embeddings[2]);
+        assert_ne!(embeddings[1], embeddings[2]);
+        for embedding in &embeddings {
+            let norm = embedding.iter().map(|v| v * v).sum::<f32>().sqrt();
+            assert!((norm - 1.0).abs() < 1e-5, "not unit length: {}", norm);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_embed_arrow_rejects_unknown_noop_mode() {
+        let service = create_test_service();
+        let schema = Arc::new(Schema::new(vec![Field::new("text", DataType::Utf8, false)]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(StringArray::from(vec!["a"])) as ArrayRef],
+        )
+        .unwrap();
+        let mut arrow_ipc = Vec::new();
+        {
+            let mut writer = StreamWriter::try_new(&mut arrow_ipc, &schema).unwrap();
+            writer.write(&batch).unwrap();
+            writer.finish().unwrap();
+        }
+
+        let request = Request::new(mux::EmbedArrowRequest {
+            target: Some(mux::Target {
+                routing: Some(mux::target::Routing::InstanceName("test".to_string())),
+            }),
+            arrow_ipc,
+            noop: true,
+            noop_mode: 99,
+            ..Default::default()
         });
-
-        let result = service.embed_arrow(request).await;
-        assert!(result.is_err());
-        assert_eq!(result.unwrap_err().code(), Code::NotFound);
+        let status = service.embed_arrow(request).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
     }
 
     #[tokio::test]
@@ -1660,6 +3044,10 @@ mod tests {
             truncate: true,
             normalize: true,
             noop: true,
+            truncation_direction: 0,
+            compression: 0,
+            dimensions: None,
+            noop_mode: 0,
         });
 
         let result = service.embed_arrow(request).await;
@@ -1704,6 +3092,10 @@ mod tests {
             truncate: true,
             normalize: true,
             noop: true,
+            truncation_direction: 0,
+            compression: 0,
+            dimensions: None,
+            noop_mode: 0,
         });
 
         let result = service.embed_arrow(request).await;
@@ -1747,6 +3139,10 @@ mod tests {
             truncate: true,
             normalize: true,
             noop: true,
+            truncation_direction: 0,
+            compression: 0,
+            dimensions: None,
+            noop_mode: 0,
         });
 
         let result = service.embed_arrow(request).await;
@@ -1779,6 +3175,75 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_arrow_embed_requests_carry_truncation_direction() {
+        use arrow::array::StringArray;
+
+        let texts = StringArray::from(vec![Some("a"), None, Some("c"), Some("d")]);
+        let requests = arrow_embed_requests(
+            &texts,
+            0..3,
+            true,
+            false,
+            tei::TruncationDirection::Left,
+            Some(256),
+        );
+
+        // Null rows are skipped, the range end is exclusive
+        let inputs: Vec<_> = requests.iter().map(|r| r.inputs.as_str()).collect();
+        assert_eq!(inputs, vec!["a", "c"]);
+        for request in &requests {
+            assert_eq!(
+                request.truncation_direction,
+                tei::TruncationDirection::Left as i32
+            );
+            assert!(request.truncate);
+            assert_eq!(request.normalize, Some(false));
+            assert_eq!(request.dimensions, Some(256));
+        }
+
+        // An unset field decodes as 0, which keeps the old Right default
+        assert_eq!(
+            truncation_direction(0).unwrap(),
+            tei::TruncationDirection::Right
+        );
+    }
+
+    #[tokio::test]
+    async fn test_embed_arrow_rejects_unknown_truncation_direction() {
+        let service = create_test_service();
+
+        let text_array = StringArray::from(vec!["Hello"]);
+        let schema = Arc::new(Schema::new(vec![Field::new("text", DataType::Utf8, false)]));
+        let batch =
+            RecordBatch::try_new(schema.clone(), vec![Arc::new(text_array) as ArrayRef]).unwrap();
+        let mut arrow_ipc = Vec::new();
+        {
+            let mut writer =
+                arrow::ipc::writer::StreamWriter::try_new(&mut arrow_ipc, &schema).unwrap();
+            writer.write(&batch).unwrap();
+            writer.finish().unwrap();
+        }
+
+        let request = Request::new(mux::EmbedArrowRequest {
+            target: Some(mux::Target {
+                routing: Some(mux::target::Routing::InstanceName("test".to_string())),
+            }),
+            arrow_ipc,
+            truncate: true,
+            normalize: true,
+            noop: true,
+            truncation_direction: 7,
+            compression: 0,
+            dimensions: None,
+            noop_mode: 0,
+        });
+
+        let status = service.embed_arrow(request).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        assert!(status.message().contains("Unknown truncation_direction 7"));
+    }
+
     // ========================================================================
     // EmbedSparseArrow RPC Tests
     // ========================================================================
@@ -1791,6 +3256,7 @@ mod tests {
             arrow_ipc: vec![],
             truncate: true,
             noop: false,
+            compression: 0,
         });
         let result = service.embed_sparse_arrow(request).await;
         assert!(result.is_err());
@@ -1807,6 +3273,7 @@ mod tests {
             arrow_ipc: vec![1, 2, 3, 4], // Invalid Arrow IPC bytes
             truncate: true,
             noop: false,
+            compression: 0,
         });
         let result = service.embed_sparse_arrow(request).await;
         assert!(result.is_err());
@@ -1825,6 +3292,7 @@ mod tests {
             arrow_ipc: vec![], // Empty Arrow IPC
             truncate: true,
             noop: false,
+            compression: 0,
         });
         let result = service.embed_sparse_arrow(request).await;
         assert!(result.is_err());
@@ -1861,6 +3329,7 @@ mod tests {
             arrow_ipc,
             truncate: true,
             noop: true, // Noop mode - returns dummy sparse embeddings
+            compression: 0,
         });
 
         let result = service.embed_sparse_arrow(request).await;
@@ -1910,6 +3379,7 @@ mod tests {
             arrow_ipc,
             truncate: true,
             noop: true,
+            compression: 0,
         });
 
         let result = service.embed_sparse_arrow(request).await;
@@ -1950,6 +3420,7 @@ mod tests {
             arrow_ipc,
             truncate: true,
             noop: false, // Not noop, so it will try to find instance
+            compression: 0,
         });
 
         let result = service.embed_sparse_arrow(request).await;
@@ -1986,6 +3457,7 @@ mod tests {
             arrow_ipc,
             truncate: true,
             noop: true,
+            compression: 0,
         });
 
         let result = service.embed_sparse_arrow(request).await;
@@ -2028,6 +3500,7 @@ mod tests {
             arrow_ipc,
             truncate: true,
             noop: true,
+            compression: 0,
         });
 
         let result = service.embed_sparse_arrow(request).await;
@@ -2086,6 +3559,149 @@ mod tests {
         assert_eq!(values.value(2), 0.25);
     }
 
+    // ========================================================================
+    // RerankArrow RPC Tests
+    // ========================================================================
+
+    /// Arrow IPC stream with a single "text" column
+    fn text_arrow_ipc(texts: Vec<Option<&str>>) -> Vec<u8> {
+        let schema = Arc::new(Schema::new(vec![Field::new("text", DataType::Utf8, true)]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(StringArray::from(texts)) as ArrayRef],
+        )
+        .unwrap();
+
+        let mut arrow_ipc = Vec::new();
+        {
+            let mut writer = StreamWriter::try_new(&mut arrow_ipc, &schema).unwrap();
+            writer.write(&batch).unwrap();
+            writer.finish().unwrap();
+        }
+        arrow_ipc
+    }
+
+    fn rerank_arrow_request(
+        instance: &str,
+        arrow_ipc: Vec<u8>,
+        noop: bool,
+    ) -> mux::RerankArrowRequest {
+        mux::RerankArrowRequest {
+            target: Some(mux::Target {
+                routing: Some(mux::target::Routing::InstanceName(instance.to_string())),
+            }),
+            query: "What is Deep Learning?".to_string(),
+            arrow_ipc,
+            truncate: true,
+            raw_scores: false,
+            noop,
+            compression: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rerank_arrow_missing_target() {
+        let service = create_test_service();
+        let request = Request::new(mux::RerankArrowRequest {
+            target: None,
+            ..rerank_arrow_request("test", text_arrow_ipc(vec![Some("Hello")]), true)
+        });
+
+        let result = service.rerank_arrow(request).await;
+        assert_eq!(result.unwrap_err().code(), Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_rerank_arrow_invalid_ipc() {
+        let service = create_test_service();
+        let request = Request::new(rerank_arrow_request("test", vec![1, 2, 3, 4], true));
+
+        let err = service.rerank_arrow(request).await.unwrap_err();
+        assert_eq!(err.code(), Code::InvalidArgument);
+        assert!(err.message().contains("Invalid Arrow IPC"));
+    }
+
+    #[tokio::test]
+    async fn test_rerank_arrow_noop_mode() {
+        let service = create_test_service();
+        let arrow_ipc = text_arrow_ipc(vec![Some("Deep Learning is..."), None, Some("Cheese")]);
+        let request = Request::new(rerank_arrow_request("test", arrow_ipc, true));
+
+        let response = service.rerank_arrow(request).await.unwrap().into_inner();
+        assert!(!response.arrow_ipc.is_empty());
+
+        let cursor = std::io::Cursor::new(response.arrow_ipc);
+        let mut reader = StreamReader::try_new(cursor, None).unwrap();
+        let result_batch = reader.next().unwrap().unwrap();
+        assert_eq!(result_batch.num_rows(), 3); // one score per input row
+
+        assert_eq!(result_batch.schema().field(0).name(), "scores");
+        let scores = result_batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<Float32Array>()
+            .expect("Scores should be Float32Array");
+
+        // Aligned to input order, with null texts left unscored
+        assert!(scores.is_valid(0));
+        assert!(scores.is_null(1));
+        assert!(scores.is_valid(2));
+        assert!(scores.value(0) > scores.value(2));
+    }
+
+    #[tokio::test]
+    async fn test_rerank_arrow_noop_empty_batch() {
+        let service = create_test_service();
+        let request = Request::new(rerank_arrow_request("test", text_arrow_ipc(vec![]), true));
+
+        let response = service.rerank_arrow(request).await.unwrap().into_inner();
+        let cursor = std::io::Cursor::new(response.arrow_ipc);
+        let mut reader = StreamReader::try_new(cursor, None).unwrap();
+        let result_batch = reader.next().unwrap().unwrap();
+        assert_eq!(result_batch.num_rows(), 0);
+        assert_eq!(result_batch.schema().field(0).name(), "scores");
+    }
+
+    #[tokio::test]
+    async fn test_rerank_arrow_wrong_column_type() {
+        use arrow::array::Int32Array;
+
+        let service = create_test_service();
+
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "data",
+            DataType::Int32,
+            false,
+        )]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(Int32Array::from(vec![1, 2, 3])) as ArrayRef],
+        )
+        .unwrap();
+
+        let mut arrow_ipc = Vec::new();
+        {
+            let mut writer = StreamWriter::try_new(&mut arrow_ipc, &schema).unwrap();
+            writer.write(&batch).unwrap();
+            writer.finish().unwrap();
+        }
+
+        let request = Request::new(rerank_arrow_request("test", arrow_ipc, true));
+        let err = service.rerank_arrow(request).await.unwrap_err();
+        assert_eq!(err.code(), Code::InvalidArgument);
+        assert!(err.message().contains("StringArray"));
+    }
+
+    #[tokio::test]
+    async fn test_rerank_arrow_instance_not_found() {
+        let service = create_test_service();
+        let arrow_ipc = text_arrow_ipc(vec![Some("Hello")]);
+        let request = Request::new(rerank_arrow_request("nonexistent", arrow_ipc, false));
+
+        let result = service.rerank_arrow(request).await;
+        assert_eq!(result.unwrap_err().code(), Code::NotFound);
+    }
+
     // ========================================================================
     // Request Timeout Tests
     // ========================================================================
@@ -2197,4 +3813,353 @@ mod tests {
         assert_eq!(status.code(), Code::DeadlineExceeded);
         assert!(status.message().contains("timeout"));
     }
+
+    // ========================================================================
+    // Stream cancellation
+    // ========================================================================
+
+    /// Sets a flag when dropped, used to observe backend stream teardown
+    struct DropFlag(Arc<std::sync::atomic::AtomicBool>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.store(true, std::sync::atomic::Ordering::SeqCst);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_forward_responses_cancels_backend_on_client_drop() {
+        let dropped = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let guard = DropFlag(dropped.clone());
+
+        // Backend yields one response and then stalls forever
+        let backend = async_stream::stream! {
+            let _guard = guard;
+            yield Ok::<u32, Status>(1);
+            std::future::pending::<()>().await;
+        };
+
+        let (tx, mut rx) = tokio::sync::mpsc::channel(4);
+        let task = tokio::spawn(async move {
+            forward_responses(
+                backend,
+                tx,
+                StreamBackpressure::Block,
+                "inst",
+                "embed_stream",
+            )
+            .await
+        });
+
+        assert_eq!(rx.recv().await.unwrap().unwrap(), 1);
+
+        // Client disconnects mid-stream
+        drop(rx);
+
+        let outcome = tokio::time::timeout(Duration::from_secs(1), task)
+            .await
+            .expect("forwarding task should exit after client drop")
+            .unwrap();
+        assert_eq!(outcome, ForwardOutcome::ClientGone);
+        assert!(dropped.load(std::sync::atomic::Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_forward_responses_completes_with_backend() {
+        let backend = tokio_stream::iter(vec![Ok::<u32, Status>(1), Ok(2), Ok(3)]);
+
+        let (tx, mut rx) = tokio::sync::mpsc::channel(4);
+        let outcome = forward_responses(
+            backend,
+            tx,
+            StreamBackpressure::Block,
+            "inst",
+            "embed_stream",
+        )
+        .await;
+        assert_eq!(outcome, ForwardOutcome::Completed);
+
+        let mut received = Vec::new();
+        while let Some(item) = rx.recv().await {
+            received.push(item.unwrap());
+        }
+        assert_eq!(received, vec![1, 2, 3]);
+    }
+
+    /// Forward three responses through a one-slot channel to a client that only
+    /// starts reading once the forwarder has stalled on it
+    async fn forward_to_slow_client(
+        backpressure: StreamBackpressure,
+    ) -> (ForwardOutcome, Vec<Result<u32, Status>>) {
+        let backend = tokio_stream::iter(vec![Ok::<u32, Status>(1), Ok(2), Ok(3)]);
+        let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+        let task = tokio::spawn(async move {
+            forward_responses(backend, tx, backpressure, "inst", "embed_stream").await
+        });
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let mut received = Vec::new();
+        while let Some(item) = rx.recv().await {
+            received.push(item);
+        }
+        (task.await.unwrap(), received)
+    }
+
+    #[tokio::test]
+    async fn test_forward_responses_blocks_for_slow_client() {
+        let (outcome, received) = forward_to_slow_client(StreamBackpressure::Block).await;
+
+        assert_eq!(outcome, ForwardOutcome::Completed);
+        let received: Vec<u32> = received.into_iter().map(Result::unwrap).collect();
+        assert_eq!(received, vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_forward_responses_errors_for_slow_client() {
+        let (outcome, received) = forward_to_slow_client(StreamBackpressure::Error).await;
+
+        assert_eq!(outcome, ForwardOutcome::Overflowed);
+        assert_eq!(received.len(), 2);
+        assert_eq!(received[0].as_ref().unwrap(), &1);
+        let status = received[1].as_ref().unwrap_err();
+        assert_eq!(status.code(), tonic::Code::ResourceExhausted);
+    }
+
+    /// Prompt names served from a fixed map instead of the HuggingFace cache
+    struct MockPromptNames(std::collections::HashMap<String, Arc<[String]>>);
+
+    impl PromptNameSource for MockPromptNames {
+        fn prompt_names(&self, model_id: &str) -> Option<Arc<[String]>> {
+            self.0.get(model_id).cloned()
+        }
+    }
+
+    fn embed_request_with_prompt(instance: &str, prompt_name: &str) -> Request<mux::EmbedRequest> {
+        Request::new(mux::EmbedRequest {
+            target: Some(mux::Target {
+                routing: Some(mux::target::Routing::InstanceName(instance.to_string())),
+            }),
+            request: Some(tei::EmbedRequest {
+                inputs: "test".to_string(),
+                prompt_name: Some(prompt_name.to_string()),
+                ..Default::default()
+            }),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_embed_rejects_unknown_prompt_name() {
+        let registry = Arc::new(Registry::new(None, "mock".to_string(), 8080, 8180));
+        // Nothing listens on these ports, so requests that pass validation fail to connect
+        add_test_instance(&registry, "with-prompts", 18091).await;
+        registry
+            .add(InstanceConfig {
+                name: "no-prompts".to_string(),
+                model_id: "other-model".to_string(),
+                port: 18092,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let prompts = MockPromptNames(std::collections::HashMap::from([(
+            "test-model".to_string(),
+            Arc::from(vec!["query".to_string(), "document".to_string()]),
+        )]));
+        let service = TeiMultiplexerService::new(BackendPool::new(registry), 1024, 30)
+            .with_prompt_name_source(Arc::new(prompts));
+
+        let err = service
+            .embed(embed_request_with_prompt("with-prompts", "passage"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::InvalidArgument);
+        assert!(err.message().contains("'passage'"));
+        assert!(err.message().contains("valid: query, document"));
+
+        // A known prompt, or a model without prompt metadata, is forwarded
+        let err = service
+            .embed(embed_request_with_prompt("with-prompts", "query"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::Unavailable);
+
+        let err = service
+            .embed(embed_request_with_prompt("no-prompts", "passage"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::Unavailable);
+    }
+
+    /// Serve `service` on an ephemeral local port, for RPCs taking a client stream
+    async fn serve_multiplexer(
+        service: TeiMultiplexerService,
+    ) -> mux::tei_multiplexer_client::TeiMultiplexerClient<tonic::transport::Channel> {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(mux::tei_multiplexer_server::TeiMultiplexerServer::new(
+                    service,
+                ))
+                .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener)),
+        );
+        mux::tei_multiplexer_client::TeiMultiplexerClient::connect(format!("http://{addr}"))
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_embed_stream_rejects_unknown_prompt_name() {
+        let registry = Arc::new(Registry::new(None, "mock".to_string(), 8080, 8180));
+        add_test_instance(&registry, "with-prompts", 18091).await;
+        let prompts = MockPromptNames(std::collections::HashMap::from([(
+            "test-model".to_string(),
+            Arc::from(vec!["query".to_string(), "document".to_string()]),
+        )]));
+        let service = TeiMultiplexerService::new(BackendPool::new(registry), 1024, 30)
+            .with_prompt_name_source(Arc::new(prompts));
+        let mut client = serve_multiplexer(service).await;
+
+        // Same error as the unary RPC, before any backend is contacted
+        let request = embed_request_with_prompt("with-prompts", "passage").into_inner();
+        let err = client
+            .embed_stream(tokio_stream::iter(vec![request]))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::InvalidArgument);
+        assert!(err.message().contains("'passage'"), "{}", err.message());
+
+        let err = client
+            .embed_all_stream(tokio_stream::iter(vec![mux::EmbedAllRequest {
+                target: Some(mux::Target {
+                    routing: Some(mux::target::Routing::InstanceName(
+                        "with-prompts".to_string(),
+                    )),
+                }),
+                request: Some(tei::EmbedAllRequest {
+                    inputs: "test".to_string(),
+                    prompt_name: Some("passage".to_string()),
+                    ..Default::default()
+                }),
+            }]))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::InvalidArgument);
+
+        // A known prompt is forwarded
+        let request = embed_request_with_prompt("with-prompts", "query").into_inner();
+        let err = client
+            .embed_stream(tokio_stream::iter(vec![request]))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::Unavailable);
+    }
+
+    struct MockDimensions(u32);
+
+    impl EmbeddingDimensionSource for MockDimensions {
+        fn embedding_dimension(&self, model_id: &str) -> Option<u32> {
+            (model_id == "test-model").then_some(self.0)
+        }
+    }
+
+    fn embed_arrow_request(
+        instance: &str,
+        dimensions: Option<u32>,
+    ) -> Request<mux::EmbedArrowRequest> {
+        use arrow::array::StringArray;
+        use arrow::datatypes::{DataType, Field, Schema};
+
+        let schema = Arc::new(Schema::new(vec![Field::new("text", DataType::Utf8, false)]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(StringArray::from(vec!["a", "b"])) as ArrayRef],
+        )
+        .unwrap();
+        let mut arrow_ipc = Vec::new();
+        {
+            let mut writer = StreamWriter::try_new(&mut arrow_ipc, &schema).unwrap();
+            writer.write(&batch).unwrap();
+            writer.finish().unwrap();
+        }
+
+        Request::new(mux::EmbedArrowRequest {
+            target: Some(mux::Target {
+                routing: Some(mux::target::Routing::InstanceName(instance.to_string())),
+            }),
+            arrow_ipc,
+            noop: true,
+            dimensions,
+            noop_mode: 0,
+            ..Default::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_embed_arrow_dimensions_checked_against_model() {
+        use arrow::array::FixedSizeListArray;
+
+        let registry = Arc::new(Registry::new(None, "mock".to_string(), 8080, 8180));
+        add_test_instance(&registry, "bge", 18093).await;
+        let service = TeiMultiplexerService::new(BackendPool::new(registry), 1024, 30)
+            .with_dimension_source(Arc::new(MockDimensions(384)));
+
+        let response = service
+            .embed_arrow(embed_arrow_request("bge", Some(128)))
+            .await
+            .unwrap()
+            .into_inner();
+        let mut reader = StreamReader::try_new(Cursor::new(response.arrow_ipc), None).unwrap();
+        let batch = reader.next().unwrap().unwrap();
+        let embeddings = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<FixedSizeListArray>()
+            .unwrap();
+        assert_eq!(embeddings.value_length(), 128);
+        assert_eq!(embeddings.len(), 2);
+
+        let err = service
+            .embed_arrow(embed_arrow_request("bge", Some(512)))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::InvalidArgument);
+        assert!(err.message().contains("512"));
+    }
+
+    #[tokio::test]
+    async fn test_embed_stream_dimensions_checked_against_model() {
+        let registry = Arc::new(Registry::new(None, "mock".to_string(), 8080, 8180));
+        add_test_instance(&registry, "bge", 18093).await;
+        let service = TeiMultiplexerService::new(BackendPool::new(registry), 1024, 30)
+            .with_dimension_source(Arc::new(MockDimensions(384)));
+        let mut client = serve_multiplexer(service).await;
+        let request = |dimensions| mux::EmbedRequest {
+            target: Some(mux::Target {
+                routing: Some(mux::target::Routing::InstanceName("bge".to_string())),
+            }),
+            request: Some(tei::EmbedRequest {
+                inputs: "test".to_string(),
+                dimensions: Some(dimensions),
+                ..Default::default()
+            }),
+        };
+
+        for dimensions in [0, 512] {
+            let err = client
+                .embed_stream(tokio_stream::iter(vec![request(dimensions)]))
+                .await
+                .unwrap_err();
+            assert_eq!(err.code(), Code::InvalidArgument);
+            assert!(err.message().contains("dimensions"), "{}", err.message());
+        }
+
+        // In range: forwarded to the (unreachable) backend
+        let err = client
+            .embed_stream(tokio_stream::iter(vec![request(128)]))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::Unavailable);
+    }
 }
@@ -4,13 +4,20 @@ use dashmap::DashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::broadcast;
-use tonic::Status;
-use tonic::transport::{Channel, Endpoint};
+use tonic::codec::CompressionEncoding;
+use tonic::transport::Channel;
+use tonic::{Code, Status};
 
+use super::channel::BackendChannelConfig;
 use super::proto::tei::v1::{
     embed_client::EmbedClient, info_client::InfoClient, predict_client::PredictClient,
     rerank_client::RerankClient, tokenize_client::TokenizeClient,
 };
+use super::routing::ModelRouter;
+use super::status::error_status;
+use crate::config::ModelRoutingStrategy;
+use crate::health::{HealthChecker, wait_for_ready_with};
+use crate::instance::{InflightCounter, InflightRequest, InstanceStatus, TeiInstance};
 use crate::registry::Registry;
 
 /// All gRPC clients for a single backend instance
@@ -22,20 +29,115 @@ pub struct BackendClients {
     pub rerank: RerankClient<Channel>,
     pub tokenize: TokenizeClient<Channel>,
     pub info: InfoClient<Channel>,
+    /// Model served by the backend, cached so metrics don't need a registry lookup
+    pub model_id: Arc<str>,
+    /// The instance's inflight counter, see [`BackendClients::track_request`]
+    inflight: InflightCounter,
+    /// The instance's `max_concurrent_requests`
+    capacity: u32,
+    /// Max encoded/decoded message size in bytes applied to every client
+    max_message_size: usize,
+}
+
+impl BackendClients {
+    /// Count a forward to this instance until the returned guard is dropped
+    pub fn track_request(&self) -> InflightRequest {
+        self.inflight.begin()
+    }
+
+    /// Requests in flight to this instance as a share of its `max_concurrent_requests`
+    ///
+    /// None when the instance has no concurrency limit configured.
+    pub fn load(&self) -> Option<f64> {
+        (self.capacity > 0).then(|| self.inflight.get() as f64 / f64::from(self.capacity))
+    }
+
+    /// The instance's `max_concurrent_requests` (0 = unlimited)
+    pub fn capacity(&self) -> u32 {
+        self.capacity
+    }
+
+    /// Max encoded/decoded message size in bytes
+    pub fn max_message_size(&self) -> usize {
+        self.max_message_size
+    }
+
+    /// Limit encoded and decoded messages on every client to `bytes`
+    fn with_max_message_size(self, bytes: usize) -> Self {
+        Self {
+            embed: self
+                .embed
+                .max_decoding_message_size(bytes)
+                .max_encoding_message_size(bytes),
+            predict: self
+                .predict
+                .max_decoding_message_size(bytes)
+                .max_encoding_message_size(bytes),
+            rerank: self
+                .rerank
+                .max_decoding_message_size(bytes)
+                .max_encoding_message_size(bytes),
+            tokenize: self
+                .tokenize
+                .max_decoding_message_size(bytes)
+                .max_encoding_message_size(bytes),
+            info: self
+                .info
+                .max_decoding_message_size(bytes)
+                .max_encoding_message_size(bytes),
+            model_id: self.model_id,
+            inflight: self.inflight,
+            capacity: self.capacity,
+            max_message_size: bytes,
+        }
+    }
+
+    /// Compress requests with `encoding` and accept responses compressed with it
+    fn with_compression(self, encoding: CompressionEncoding) -> Self {
+        Self {
+            embed: self
+                .embed
+                .send_compressed(encoding)
+                .accept_compressed(encoding),
+            predict: self
+                .predict
+                .send_compressed(encoding)
+                .accept_compressed(encoding),
+            rerank: self
+                .rerank
+                .send_compressed(encoding)
+                .accept_compressed(encoding),
+            tokenize: self
+                .tokenize
+                .send_compressed(encoding)
+                .accept_compressed(encoding),
+            info: self
+                .info
+                .send_compressed(encoding)
+                .accept_compressed(encoding),
+            model_id: self.model_id,
+            inflight: self.inflight,
+            capacity: self.capacity,
+            max_message_size: self.max_message_size,
+        }
+    }
 }
 
 /// Connection entry with metadata for pruning
 struct ConnectionEntry {
     clients: BackendClients,
+    /// Backend instance, used to record request activity for idle tracking
+    instance: Arc<TeiInstance>,
     created_at: Instant,
     last_used: Instant,
 }
 
 impl ConnectionEntry {
-    fn new(clients: BackendClients) -> Self {
+    fn new(clients: BackendClients, instance: Arc<TeiInstance>) -> Self {
         let now = Instant::now();
         Self {
             clients,
+            instance,
             created_at: now,
             last_used: now,
         }
@@ -46,6 +148,14 @@ impl ConnectionEntry {
     }
 }
 
+/// Settings for lazily starting stopped instances on request
+#[derive(Clone)]
+struct Autostart {
+    /// Default readiness timeout (per-instance `startup_timeout_secs` takes precedence)
+    startup_timeout: Duration,
+    health_checker: Arc<dyn HealthChecker>,
+}
+
 /// Lock-free connection pool for backend TEI instances
 #[derive(Clone)]
 pub struct BackendPool {
@@ -58,6 +168,18 @@ pub struct BackendPool {
     // Pruning configuration
     prune_interval: Duration,
     max_idle_time: Duration,
+
+    // Auto-start of stopped instances (None = disabled)
+    autostart: Option<Autostart>,
+
+    // Per-instance locks so concurrent requests only start an instance once
+    start_locks: Arc<DashMap<String, Arc<tokio::sync::Mutex<()>>>>,
+
+    // Connect timeout and keepalive settings for backend channels
+    channel_config: BackendChannelConfig,
+
+    // Picks among routable instances for model-based routing
+    model_router: ModelRouter,
 }
 
 /// Default pruning interval (5 minutes)
@@ -66,6 +188,26 @@ const DEFAULT_PRUNE_INTERVAL_SECS: u64 = 300;
 /// Default max idle time before connection is pruned (10 minutes)
 const DEFAULT_MAX_IDLE_SECS: u64 = 600;
 
+/// Poll interval while waiting for an auto-started instance to become ready
+const AUTOSTART_POLL_INTERVAL_MS: u64 = 500;
+
+/// Seconds clients are told to wait before retrying a restarting instance
+const RESTART_RETRY_AFTER_SECS: u64 = 5;
+
+/// `Unavailable` for a restarting instance, with a `retry-after` hint in the metadata
+fn restarting_status(message: String, metadata: &[(&str, &str)]) -> Status {
+    let mut status = error_status(
+        Code::Unavailable,
+        format!("{}, retry in {}s", message, RESTART_RETRY_AFTER_SECS),
+        "BACKEND_UNAVAILABLE",
+        metadata,
+    );
+    status
+        .metadata_mut()
+        .insert("retry-after", RESTART_RETRY_AFTER_SECS.into());
+    status
+}
+
 impl Drop for BackendPool {
     fn drop(&mut self) {
         tracing::debug!("BackendPool dropped, clearing all connections");
@@ -93,6 +235,10 @@ impl BackendPool {
             registry: registry.clone(),
             prune_interval,
             max_idle_time,
+            autostart: None,
+            start_locks: Arc::new(DashMap::new()),
+            channel_config: BackendChannelConfig::default(),
+            model_router: ModelRouter::default(),
         };
 
         // Spawn background task to listen for lifecycle events
@@ -110,6 +256,40 @@ impl BackendPool {
         pool
     }
 
+    /// Start stopped instances when a request is routed to them
+    ///
+    /// The request waits until the instance is ready, up to `startup_timeout`
+    /// (or the instance's own `startup_timeout_secs` when set).
+    pub fn with_autostart(self, startup_timeout: Duration) -> Self {
+        let health_checker = self.registry.health_checker();
+        self.with_autostart_checker(startup_timeout, health_checker)
+    }
+
+    /// Like [`with_autostart`](Self::with_autostart) with a custom readiness checker
+    pub fn with_autostart_checker(
+        mut self,
+        startup_timeout: Duration,
+        health_checker: Arc<dyn HealthChecker>,
+    ) -> Self {
+        self.autostart = Some(Autostart {
+            startup_timeout,
+            health_checker,
+        });
+        self
+    }
+
+    /// Use custom connect timeout and keepalive settings for backend channels
+    pub fn with_channel_config(mut self, channel_config: BackendChannelConfig) -> Self {
+        self.channel_config = channel_config;
+        self
+    }
+
+    /// Pick instances for model-routed requests with `strategy` instead of round-robin
+    pub fn with_model_routing(mut self, strategy: ModelRoutingStrategy) -> Self {
+        self.model_router = ModelRouter::new(strategy);
+        self
+    }
+
     /// Background task that handles instance lifecycle events
     async fn handle_lifecycle_events(&self) {
         let mut event_rx = self.registry.subscribe_events();
@@ -118,9 +298,20 @@ impl BackendPool {
             match event_rx.recv().await {
                 Ok(event) => {
                     use crate::registry::InstanceEvent;
+                    if let InstanceEvent::Removed(name) = &event {
+                        self.start_locks.remove(name);
+                    }
                     match &event {
-                        InstanceEvent::Removed(name) | InstanceEvent::Stopped(name) => {
-                            // Remove connection when instance is removed or stopped
+                        InstanceEvent::Removed(name)
+                        | InstanceEvent::Stopped(name)
+                        | InstanceEvent::Exited { name, .. }
+                        | InstanceEvent::StatusChanged {
+                            name,
+                            to: InstanceStatus::Failed | InstanceStatus::Quarantined,
+                            ..
+                        } => {
+                            // Remove connection when instance is removed, stopped, exited,
+                            // failed or quarantined
                             if self.remove(name) {
                                 tracing::debug!(
                                     instance = %name,
@@ -129,7 +320,9 @@ impl BackendPool {
                                 );
                             }
                         }
-                        InstanceEvent::Added(_) | InstanceEvent::Started(_) => {
+                        InstanceEvent::Added(_)
+                        | InstanceEvent::Started(_)
+                        | InstanceEvent::StatusChanged { .. } => {
                             // No action needed - connections are created on-demand
                         }
                     }
@@ -149,26 +342,190 @@ impl BackendPool {
     }
 
     /// Get or create clients for an instance (lock-free read, minimal locking for write)
+    ///
+    /// Every call counts as a forwarded request and updates the instance's `last_request_at`.
     pub async fn get_clients(&self, instance_name: &str) -> Result<BackendClients, Status> {
+        if let Some(instance) = self.registry.get(instance_name).await {
+            if instance.is_paused() {
+                return Err(error_status(
+                    Code::Unavailable,
+                    format!("Instance '{}' is paused", instance_name),
+                    "BACKEND_UNAVAILABLE",
+                    &[("instance", instance_name), ("state", "paused")],
+                ));
+            }
+            if *instance.status.read().await == InstanceStatus::Quarantined {
+                return Err(error_status(
+                    Code::Unavailable,
+                    format!("Instance '{}' is quarantined", instance_name),
+                    "BACKEND_UNAVAILABLE",
+                    &[("instance", instance_name), ("state", "quarantined")],
+                ));
+            }
+            if instance.restart_in_progress().await {
+                return Err(restarting_status(
+                    format!("Instance '{}' is restarting", instance_name),
+                    &[("instance", instance_name), ("state", "restarting")],
+                ));
+            }
+            if let Some(autostart) = &self.autostart {
+                self.start_if_stopped(&instance, autostart).await?;
+            }
+        }
+
+        let (clients, instance) = self.get_or_create(instance_name).await?;
+
+        // DashMap guard is released by now, safe to await on the instance lock
+        instance.record_request().await;
+
+        Ok(clients)
+    }
+
+    /// Model served by `instance_name`, if it is registered
+    pub async fn instance_model_id(&self, instance_name: &str) -> Option<String> {
+        let instance = self.registry.get(instance_name).await?;
+        Some(instance.config.model_id.clone())
+    }
+
+    /// Registered instances in name order, the order instance indices refer to
+    pub async fn instances_by_index(&self) -> Vec<Arc<TeiInstance>> {
+        self.registry.instances_by_index().await
+    }
+
+    /// Name of the instance at `index` in [`Self::instances_by_index`]
+    pub async fn resolve_index(&self, index: u32) -> Result<String, Status> {
+        let instances = self.instances_by_index().await;
+        instances
+            .get(index as usize)
+            .map(|instance| instance.config.name.clone())
+            .ok_or_else(|| {
+                error_status(
+                    Code::NotFound,
+                    format!(
+                        "No instance at index {} ({} registered)",
+                        index,
+                        instances.len()
+                    ),
+                    "INSTANCE_NOT_FOUND",
+                    &[("index", &index.to_string())],
+                )
+            })
+    }
+
+    /// Pick a running, unpaused instance serving `model_id` (see [`ModelRoutingStrategy`])
+    ///
+    /// Instances being restarted by the health monitor are skipped.
+    pub async fn resolve_model(&self, model_id: &str) -> Result<String, Status> {
+        let mut serving = false;
+        let mut restarting = false;
+        let mut candidates = Vec::new();
+        // Sorted by name, so the cursor cycles through every candidate
+        for instance in self.registry.instances_for_model(model_id, false).await {
+            serving = true;
+            if instance.restart_in_progress().await {
+                restarting = true;
+            } else if instance.model_routable().await {
+                candidates.push(instance);
+            }
+        }
+
+        if candidates.is_empty() {
+            return Err(if !serving {
+                error_status(
+                    Code::NotFound,
+                    format!("No instance serves model '{}'", model_id),
+                    "MODEL_NOT_FOUND",
+                    &[("model", model_id)],
+                )
+            } else if restarting {
+                restarting_status(
+                    format!("Instances for model '{}' are restarting", model_id),
+                    &[("model", model_id), ("state", "restarting")],
+                )
+            } else {
+                error_status(
+                    Code::Unavailable,
+                    format!("No running, unpaused instance for model '{}'", model_id),
+                    "BACKEND_UNAVAILABLE",
+                    &[("model", model_id)],
+                )
+            });
+        }
+
+        let index = self.model_router.pick(&candidates);
+        Ok(candidates.swap_remove(index).config.name.clone())
+    }
+
+    /// Start a stopped instance and wait for it to become ready
+    ///
+    /// Concurrent callers for the same instance share a lock, so only one process is spawned.
+    /// An instance that does not become ready in time is marked `Failed`, so later
+    /// requests and the health monitor don't treat it as still starting.
+    async fn start_if_stopped(
+        &self,
+        instance: &TeiInstance,
+        autostart: &Autostart,
+    ) -> Result<(), Status> {
+        if *instance.status.read().await != InstanceStatus::Stopped {
+            return Ok(());
+        }
+
+        let name = instance.config.name.clone();
+        let lock = self.start_locks.entry(name.clone()).or_default().clone();
+        let _guard = lock.lock().await;
+
+        // Another request may have started it while we waited for the lock
+        if *instance.status.read().await != InstanceStatus::Stopped {
+            return Ok(());
+        }
+
+        tracing::info!(instance = %name, "Auto-starting stopped instance for routed request");
+
+        instance
+            .start(self.registry.tei_binary_path())
+            .await
+            .map_err(|e| Status::unavailable(format!("Failed to start instance: {}", e)))?;
+
+        let timeout = instance.config.startup_timeout(autostart.startup_timeout);
+
+        if let Err(e) = wait_for_ready_with(
+            autostart.health_checker.as_ref(),
+            instance,
+            timeout,
+            Duration::from_millis(AUTOSTART_POLL_INTERVAL_MS),
+        )
+        .await
+        {
+            tracing::warn!(instance = %name, error = %e, "Auto-started instance did not become ready");
+            instance.set_status(InstanceStatus::Failed).await;
+            return Err(Status::deadline_exceeded(e.to_string()));
+        }
+        Ok(())
+    }
+
+    async fn get_or_create(
+        &self,
+        instance_name: &str,
+    ) -> Result<(BackendClients, Arc<TeiInstance>), Status> {
         // Fast path: client already exists (DashMap read is lock-free)
         if let Some(mut entry) = self.connections.get_mut(instance_name) {
             entry.touch(); // Update last_used timestamp
-            return Ok(entry.clients.clone()); // Cheap Arc clone
+            return Ok((entry.clients.clone(), entry.instance.clone())); // Cheap Arc clones
         }
 
-        // Slow path: create new connection
-        // Using entry API prevents race condition where two threads both try to create connection
+        // Slow path: connect without holding the shard lock, since a lifecycle event
+        // removing a connection in the meantime would block on it for the whole connect
+        let (clients, instance) = self.create_connection(instance_name).await?;
+
+        // Entry API keeps the first connection when two requests connected concurrently
         match self.connections.entry(instance_name.to_string()) {
             dashmap::mapref::entry::Entry::Occupied(mut entry) => {
-                // Another thread created it while we were waiting
                 entry.get_mut().touch();
-                Ok(entry.get().clients.clone())
+                Ok((entry.get().clients.clone(), entry.get().instance.clone()))
             }
             dashmap::mapref::entry::Entry::Vacant(entry) => {
-                // We got the lock, create the connection
-                let clients = self.create_connection(instance_name).await?;
-                entry.insert(ConnectionEntry::new(clients.clone()));
-                Ok(clients)
+                entry.insert(ConnectionEntry::new(clients.clone(), instance.clone()));
+                Ok((clients, instance))
             }
         }
     }
@@ -240,6 +597,15 @@ impl BackendPool {
             .flatten()
             .collect();
 
+        // Start locks of removed instances are normally dropped on the Removed event,
+        // which is lost if the event receiver lagged
+        let lock_keys: Vec<String> = self.start_locks.iter().map(|e| e.key().clone()).collect();
+        for key in lock_keys {
+            if self.registry.get(&key).await.is_none() {
+                self.start_locks.remove(&key);
+            }
+        }
+
         // Remove orphaned connections and count successes
         let pruned = orphaned_keys
             .into_iter()
@@ -264,38 +630,40 @@ impl BackendPool {
         pruned
     }
 
-    async fn create_connection(&self, instance_name: &str) -> Result<BackendClients, Status> {
+    async fn create_connection(
+        &self,
+        instance_name: &str,
+    ) -> Result<(BackendClients, Arc<TeiInstance>), Status> {
         // Get instance info from registry
-        let instance =
-            self.registry.get(instance_name).await.ok_or_else(|| {
-                Status::not_found(format!("Instance '{}' not found", instance_name))
-            })?;
+        let instance = self.registry.get(instance_name).await.ok_or_else(|| {
+            error_status(
+                Code::NotFound,
+                format!("Instance '{}' not found", instance_name),
+                "INSTANCE_NOT_FOUND",
+                &[("instance", instance_name)],
+            )
+        })?;
 
         // Note: We don't check instance status here - if the TEI server is ready,
         // we can route to it. The connection attempt below will fail naturally if not ready.
 
-        // Build endpoint with optimized settings from TEI patterns
-        let endpoint = Endpoint::from_shared(format!("http://127.0.0.1:{}", instance.config.port))
-            .map_err(|e| Status::internal(format!("Invalid endpoint: {}", e)))?
-            .tcp_keepalive(Some(Duration::from_secs(60)))
-            .http2_keep_alive_interval(Duration::from_secs(30))
-            .keep_alive_timeout(Duration::from_secs(10))
-            .connect_timeout(Duration::from_secs(5));
+        // Build endpoint with configured connect timeout and keepalive
+        let endpoint = self
+            .channel_config
+            .endpoint(instance.config.port)
+            .map_err(|e| Status::internal(format!("Invalid endpoint: {}", e)))?;
 
         // Establish connection
-        let channel = endpoint
-            .connect()
-            .await
-            .map_err(|e| Status::unavailable(format!("Failed to connect to backend: {}", e)))?;
+        let channel = endpoint.connect().await.map_err(|e| {
+            error_status(
+                Code::Unavailable,
+                format!("Failed to connect to backend: {}", e),
+                "BACKEND_UNAVAILABLE",
+                &[("instance", instance_name)],
+            )
+        })?;
 
-        // Create all clients (they share the channel internally via HTTP/2 multiplexing)
-        let clients = BackendClients {
-            embed: EmbedClient::new(channel.clone()),
-            predict: PredictClient::new(channel.clone()),
-            rerank: RerankClient::new(channel.clone()),
-            tokenize: TokenizeClient::new(channel.clone()),
-            info: InfoClient::new(channel),
-        };
+        let clients = self.build_clients(channel, &instance);
 
         tracing::debug!(
             instance = instance_name,
@@ -303,7 +671,37 @@ impl BackendPool {
             "Created gRPC connection to backend"
         );
 
-        Ok(clients)
+        Ok((clients, instance))
+    }
+
+    /// Create all clients for `instance` on `channel`
+    ///
+    /// Clients share the channel internally via HTTP/2 multiplexing. The instance's
+    /// `grpc_max_message_size_mb` takes precedence over the pool-wide limit.
+    fn build_clients(&self, channel: Channel, instance: &TeiInstance) -> BackendClients {
+        let max_message_size_mb = instance
+            .config
+            .grpc_max_message_size_mb
+            .unwrap_or(self.channel_config.max_message_size_mb);
+
+        let clients = BackendClients {
+            embed: EmbedClient::new(channel.clone()),
+            predict: PredictClient::new(channel.clone()),
+            rerank: RerankClient::new(channel.clone()),
+            tokenize: TokenizeClient::new(channel.clone()),
+            info: InfoClient::new(channel),
+            model_id: Arc::from(instance.config.model_id.as_str()),
+            inflight: instance.inflight().clone(),
+            capacity: instance.config.max_concurrent_requests,
+            max_message_size: 0,
+        }
+        .with_max_message_size(max_message_size_mb.saturating_mul(1024 * 1024));
+
+        if self.channel_config.compression {
+            clients.with_compression(CompressionEncoding::Gzip)
+        } else {
+            clients
+        }
     }
 
     /// Remove a client from the pool (when instance is deleted/stopped)
@@ -455,6 +853,229 @@ mod tests {
         assert_eq!(result.unwrap_err().code(), tonic::Code::Unavailable);
     }
 
+    #[tokio::test]
+    async fn test_paused_instance_rejected_by_name() {
+        let registry = Arc::new(Registry::new(
+            None,
+            "text-embeddings-router".to_string(),
+            8080,
+            8180,
+        ));
+        let pool = BackendPool::new(registry.clone());
+
+        let instance = registry
+            .add(InstanceConfig {
+                name: "paused".to_string(),
+                model_id: "model".to_string(),
+                port: 59997,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        *instance.status.write().await = InstanceStatus::Running;
+        instance.set_paused(true);
+
+        let err = pool.get_clients("paused").await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::Unavailable);
+        assert!(err.message().contains("paused"));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_model_skips_paused_instances() {
+        let registry = Arc::new(Registry::new(
+            None,
+            "text-embeddings-router".to_string(),
+            8080,
+            8180,
+        ));
+        let pool = BackendPool::new(registry.clone());
+
+        for (name, port) in [("a", 59990), ("b", 59991)] {
+            let instance = registry
+                .add(InstanceConfig {
+                    name: name.to_string(),
+                    model_id: "model".to_string(),
+                    port,
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+            *instance.status.write().await = InstanceStatus::Running;
+        }
+
+        // Round-robin across both while neither is paused
+        let mut picked = vec![
+            pool.resolve_model("model").await.unwrap(),
+            pool.resolve_model("model").await.unwrap(),
+        ];
+        picked.sort();
+        assert_eq!(picked, vec!["a", "b"]);
+
+        // A paused instance is skipped but stays registered and running
+        let paused = registry.get("a").await.unwrap();
+        paused.set_paused(true);
+        for _ in 0..4 {
+            assert_eq!(pool.resolve_model("model").await.unwrap(), "b");
+        }
+        assert_eq!(registry.list().await.len(), 2);
+        assert_eq!(*paused.status.read().await, InstanceStatus::Running);
+
+        // Nothing routable while every instance for the model is paused
+        registry.get("b").await.unwrap().set_paused(true);
+        let err = pool.resolve_model("model").await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::Unavailable);
+
+        // Resuming makes the instance routable again
+        paused.set_paused(false);
+        assert_eq!(pool.resolve_model("model").await.unwrap(), "a");
+
+        let err = pool.resolve_model("other-model").await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::NotFound);
+    }
+
+    /// Pool with running instances `(name, max_concurrent_requests)` for "model"
+    async fn model_pool(
+        strategy: ModelRoutingStrategy,
+        instances: &[(&str, u32)],
+    ) -> (BackendPool, Arc<Registry>) {
+        let registry = Arc::new(Registry::new(
+            None,
+            "text-embeddings-router".to_string(),
+            8080,
+            8180,
+        ));
+        let pool = BackendPool::new(registry.clone()).with_model_routing(strategy);
+        for (offset, (name, capacity)) in instances.iter().enumerate() {
+            let instance = registry
+                .add(InstanceConfig {
+                    name: name.to_string(),
+                    model_id: "model".to_string(),
+                    port: 59980 + offset as u16,
+                    max_concurrent_requests: *capacity,
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+            *instance.status.write().await = InstanceStatus::Running;
+        }
+        (pool, registry)
+    }
+
+    #[tokio::test]
+    async fn test_least_loaded_routes_around_busy_instance() {
+        let (pool, registry) =
+            model_pool(ModelRoutingStrategy::LeastLoaded, &[("a", 512), ("b", 512)]).await;
+
+        // While idle, ties alternate between instances
+        let mut picked = vec![
+            pool.resolve_model("model").await.unwrap(),
+            pool.resolve_model("model").await.unwrap(),
+        ];
+        picked.sort();
+        assert_eq!(picked, vec!["a", "b"]);
+
+        // A held-open request on "a" sends every new request to "b"
+        let held = registry.get("a").await.unwrap().inflight().begin();
+        for _ in 0..4 {
+            assert_eq!(pool.resolve_model("model").await.unwrap(), "b");
+        }
+
+        // Once "b" is busier, "a" is preferred again
+        let b = registry.get("b").await.unwrap();
+        let _busy = [b.inflight().begin(), b.inflight().begin()];
+        assert_eq!(pool.resolve_model("model").await.unwrap(), "a");
+        drop(held);
+        assert_eq!(pool.resolve_model("model").await.unwrap(), "a");
+    }
+
+    #[tokio::test]
+    async fn test_round_robin_ignores_inflight() {
+        let (pool, registry) =
+            model_pool(ModelRoutingStrategy::RoundRobin, &[("a", 512), ("b", 512)]).await;
+        let _held = registry.get("a").await.unwrap().inflight().begin();
+
+        let mut picked = Vec::new();
+        for _ in 0..4 {
+            picked.push(pool.resolve_model("model").await.unwrap());
+        }
+        assert_eq!(picked.iter().filter(|name| *name == "a").count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_weighted_follows_max_concurrent_requests() {
+        let (pool, _registry) = model_pool(
+            ModelRoutingStrategy::Weighted,
+            &[("a", 1), ("b", 3), ("c", 0)],
+        )
+        .await;
+
+        // Total weight 5 (unlimited "c" counts as 1): one full cycle matches the weights
+        let mut picked = Vec::new();
+        for _ in 0..5 {
+            picked.push(pool.resolve_model("model").await.unwrap());
+        }
+        let count = |name: &str| picked.iter().filter(|picked| *picked == name).count();
+        assert_eq!((count("a"), count("b"), count("c")), (1, 3, 1));
+    }
+
+    #[tokio::test]
+    async fn test_restarting_instance_routed_away_or_rejected() {
+        let registry = Arc::new(Registry::new(
+            None,
+            "text-embeddings-router".to_string(),
+            8080,
+            8180,
+        ));
+        let pool = BackendPool::new(registry.clone());
+
+        for (name, port) in [("a", 59988), ("b", 59989)] {
+            let instance = registry
+                .add(InstanceConfig {
+                    name: name.to_string(),
+                    model_id: "model".to_string(),
+                    port,
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+            *instance.status.write().await = InstanceStatus::Running;
+        }
+
+        // The health monitor flags "a" before its status changes
+        let a = registry.get("a").await.unwrap();
+        a.set_restarting(true);
+        for _ in 0..4 {
+            assert_eq!(pool.resolve_model("model").await.unwrap(), "b");
+        }
+
+        let err = pool.get_clients("a").await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::Unavailable);
+        assert!(err.message().contains("Instance 'a' is restarting"));
+        assert_eq!(err.metadata().get("retry-after").unwrap(), "5");
+
+        // With every instance restarting, model routing reports it too
+        registry.get("b").await.unwrap().set_restarting(true);
+        let err = pool.resolve_model("model").await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::Unavailable);
+        assert!(err.message().contains("restarting"));
+        assert!(err.metadata().get("retry-after").is_some());
+
+        a.set_restarting(false);
+        assert_eq!(pool.resolve_model("model").await.unwrap(), "a");
+
+        // A flag left on a failed instance is not reported as a restart
+        *a.status.write().await = InstanceStatus::Failed;
+        a.set_restarting(true);
+        *registry.get("b").await.unwrap().status.write().await = InstanceStatus::Failed;
+        let err = pool.resolve_model("model").await.unwrap_err();
+        assert!(err.message().contains("No running"), "{}", err.message());
+        assert!(err.metadata().get("retry-after").is_none());
+
+        // Stopping or starting directly clears it
+        a.stop().await.unwrap();
+        assert!(!a.is_restarting());
+    }
+
     #[tokio::test]
     async fn test_lifecycle_events_subscribed() {
         // Test that pool subscribes to lifecycle events
@@ -592,4 +1213,150 @@ mod tests {
         assert_eq!(stats.prune_interval_secs, DEFAULT_PRUNE_INTERVAL_SECS);
         assert_eq!(stats.max_idle_threshold_secs, DEFAULT_MAX_IDLE_SECS);
     }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_autostart_single_start_under_concurrency() {
+        use crate::health::mocks::MockHealthChecker;
+        use crate::instance::mocks::MockProcessManager;
+
+        let registry = Arc::new(Registry::new(None, "mock".to_string(), 8080, 8180));
+        let pool = BackendPool::new(registry)
+            .with_autostart_checker(Duration::from_secs(5), Arc::new(MockHealthChecker::new()));
+
+        let process_manager = Arc::new(MockProcessManager::new());
+        let instance = Arc::new(TeiInstance::new_with_manager(
+            InstanceConfig {
+                name: "lazy".to_string(),
+                model_id: "model".to_string(),
+                port: 8080,
+                ..Default::default()
+            },
+            process_manager.clone(),
+        ));
+        assert_eq!(*instance.status.read().await, InstanceStatus::Stopped);
+
+        let autostart = pool.autostart.clone().unwrap();
+        let tasks: Vec<_> = (0..16)
+            .map(|_| {
+                let pool = pool.clone();
+                let instance = instance.clone();
+                let autostart = autostart.clone();
+                tokio::spawn(async move { pool.start_if_stopped(&instance, &autostart).await })
+            })
+            .collect();
+
+        for task in tasks {
+            task.await.unwrap().unwrap();
+        }
+
+        assert_eq!(process_manager.process_count().await, 1);
+        assert_eq!(*instance.status.read().await, InstanceStatus::Running);
+    }
+
+    #[tokio::test]
+    async fn test_autostart_deadline_exceeded_when_not_ready() {
+        use crate::health::mocks::MockHealthChecker;
+        use crate::instance::mocks::MockProcessManager;
+
+        let checker = Arc::new(MockHealthChecker::new());
+        checker.set_unhealthy("still loading".to_string());
+
+        let registry = Arc::new(Registry::new(None, "mock".to_string(), 8080, 8180));
+        let pool =
+            BackendPool::new(registry).with_autostart_checker(Duration::from_millis(100), checker);
+
+        let instance = TeiInstance::new_with_manager(
+            InstanceConfig {
+                name: "slow".to_string(),
+                model_id: "model".to_string(),
+                port: 8080,
+                ..Default::default()
+            },
+            Arc::new(MockProcessManager::new()),
+        );
+
+        let autostart = pool.autostart.clone().unwrap();
+        let err = pool
+            .start_if_stopped(&instance, &autostart)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::DeadlineExceeded);
+        assert_eq!(*instance.status.read().await, InstanceStatus::Failed);
+    }
+
+    #[tokio::test]
+    async fn test_start_locks_pruned_with_instances() {
+        let registry = Arc::new(Registry::new(None, "mock".to_string(), 8080, 8180));
+        let pool = BackendPool::new(registry.clone());
+        // Let the lifecycle task subscribe
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        for (name, port) in [("removed", 8081), ("orphaned", 8082)] {
+            registry
+                .add(InstanceConfig {
+                    name: name.to_string(),
+                    model_id: "model".to_string(),
+                    port,
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+            pool.start_locks.entry(name.to_string()).or_default();
+        }
+
+        registry.remove("removed").await.unwrap();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while pool.start_locks.contains_key("removed") && Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(!pool.start_locks.contains_key("removed"));
+        assert!(pool.start_locks.contains_key("orphaned"));
+
+        // A lock left behind by a missed event goes with the orphan prune
+        pool.start_locks.entry("gone".to_string()).or_default();
+        pool.prune_orphaned_connections().await;
+        assert!(!pool.start_locks.contains_key("gone"));
+        assert!(pool.start_locks.contains_key("orphaned"));
+    }
+
+    #[tokio::test]
+    async fn test_autostart_disabled_by_default() {
+        let registry = Arc::new(Registry::new(
+            None,
+            "text-embeddings-router".to_string(),
+            8080,
+            8180,
+        ));
+        let pool = BackendPool::new(registry);
+        assert!(pool.autostart.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_build_clients_applies_message_size_override() {
+        let registry = Arc::new(Registry::new(None, "mock".to_string(), 8080, 8180));
+        let pool = BackendPool::new(registry).with_channel_config(BackendChannelConfig {
+            max_message_size_mb: 16,
+            ..Default::default()
+        });
+        let channel = pool.channel_config.endpoint(18080).unwrap().connect_lazy();
+
+        let instance = TeiInstance::new(InstanceConfig {
+            name: "default".to_string(),
+            model_id: "model".to_string(),
+            port: 18080,
+            ..Default::default()
+        });
+        let clients = pool.build_clients(channel.clone(), &instance);
+        assert_eq!(clients.max_message_size(), 16 * 1024 * 1024);
+
+        let instance = TeiInstance::new(InstanceConfig {
+            name: "reranker".to_string(),
+            model_id: "model".to_string(),
+            port: 18080,
+            grpc_max_message_size_mb: Some(128),
+            ..Default::default()
+        });
+        let clients = pool.build_clients(channel, &instance);
+        assert_eq!(clients.max_message_size(), 128 * 1024 * 1024);
+    }
 }
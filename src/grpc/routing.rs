@@ -0,0 +1,111 @@
+//! Model-based routing: picking an instance for a `model_id` target
+//!
+//! Kept apart from instance pausing, which only decides whether an instance is
+//! routable. [`BackendPool::resolve_model`](super::pool::BackendPool::resolve_model)
+//! collects the routable instances serving a model and [`ModelRouter`] picks one
+//! of them according to the configured [`ModelRoutingStrategy`].
+
+use crate::config::ModelRoutingStrategy;
+use crate::instance::TeiInstance;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Picks among the instances serving a model, shared by every clone of the pool
+#[derive(Clone, Default)]
+pub struct ModelRouter {
+    // Advances on every pick, so round-robin and ties cycle through candidates
+    cursor: Arc<AtomicUsize>,
+    strategy: ModelRoutingStrategy,
+}
+
+impl ModelRouter {
+    pub fn new(strategy: ModelRoutingStrategy) -> Self {
+        Self {
+            cursor: Arc::new(AtomicUsize::new(0)),
+            strategy,
+        }
+    }
+
+    /// Index into `candidates` (non-empty, in a stable order) of the instance to use
+    pub fn pick(&self, candidates: &[Arc<TeiInstance>]) -> usize {
+        let cursor = self.cursor.fetch_add(1, Ordering::Relaxed);
+        match self.strategy {
+            ModelRoutingStrategy::RoundRobin => cursor % candidates.len(),
+            // Start the scan at the cursor so idle instances still take turns
+            ModelRoutingStrategy::LeastLoaded => (0..candidates.len())
+                .map(|offset| (cursor + offset) % candidates.len())
+                .min_by_key(|&i| candidates[i].inflight().get())
+                .unwrap_or(0),
+            ModelRoutingStrategy::Weighted => {
+                let total: u64 = candidates
+                    .iter()
+                    .map(|i| u64::from(i.config.routing_weight()))
+                    .sum();
+                let mut ticket = weighted_ticket(cursor as u64, total);
+                candidates
+                    .iter()
+                    .position(|instance| {
+                        let weight = u64::from(instance.config.routing_weight());
+                        if ticket < weight {
+                            true
+                        } else {
+                            ticket -= weight;
+                            false
+                        }
+                    })
+                    .unwrap_or(0)
+            }
+        }
+    }
+}
+
+/// Slot in `0..total` for the `cursor`th weighted pick
+///
+/// Stepping by a stride coprime to `total` visits every slot once per `total`
+/// picks, so each instance gets exactly its weight per cycle, while consecutive
+/// picks are spread out instead of sending `weight` requests in a row to one
+/// instance. Weights are u32s, so the product is taken in u128 to stay exact
+/// for any number of instances.
+fn weighted_ticket(cursor: u64, total: u64) -> u64 {
+    let mut stride = (total / 8 * 5 + total % 8 * 5 / 8).max(1);
+    while gcd(stride, total) != 1 {
+        stride += 1;
+    }
+    let ticket = u128::from(cursor % total) * u128::from(stride) % u128::from(total);
+    ticket as u64
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weighted_ticket_covers_every_slot_spread_out() {
+        for total in [1, 2, 3, 8, 512, 1000] {
+            let mut tickets: Vec<u64> = (0..total).map(|c| weighted_ticket(c, total)).collect();
+            tickets.sort();
+            assert_eq!(tickets, (0..total).collect::<Vec<_>>(), "total {}", total);
+        }
+
+        // Two instances of weight 512 alternate instead of taking turns in runs of 512
+        let first: Vec<bool> = (0..4).map(|c| weighted_ticket(c, 1024) < 512).collect();
+        assert!(first.contains(&true) && first.contains(&false));
+    }
+
+    #[test]
+    fn test_weighted_ticket_large_totals_do_not_overflow() {
+        // Many instances at the maximum weight
+        let total = u64::from(u32::MAX) * 1000;
+        for cursor in [0, 1, total - 1, u64::MAX] {
+            assert!(weighted_ticket(cursor, total) < total);
+        }
+        assert!(weighted_ticket(u64::MAX, u64::MAX) < u64::MAX);
+    }
+}
@@ -2,72 +2,142 @@
 
 use std::future::Future;
 use std::net::SocketAddr;
+use std::path::Path;
 use std::sync::Arc;
-use tonic::transport::{Certificate, Identity, Server, ServerTlsConfig};
-
+use std::time::Duration;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio_rustls::TlsAcceptor;
+use tokio_rustls::server::TlsStream;
+use tokio_stream::wrappers::{ReceiverStream, UnixListenerStream};
+use tonic::codec::CompressionEncoding;
+use tonic::transport::Server;
+
+use super::access_log::{AccessLog, AccessLogLayer};
+use super::channel::BackendChannelConfig;
+use super::forward::ForwardHeaders;
+use super::message_size::MessageSizeLayer;
 use super::multiplexer::TeiMultiplexerService;
 use super::pool::BackendPool;
 use super::proto::multiplexer::v1::tei_multiplexer_server::TeiMultiplexerServer;
+use crate::config::{ArrowEmbedOrdering, ManagerConfig, ModelRoutingStrategy, StreamBackpressure};
 use crate::registry::Registry;
 
+/// Tuning options for the gRPC multiplexer server
+#[derive(Debug, Clone)]
+pub struct GrpcServerOptions {
+    /// Max request/response message size in MB
+    pub max_message_size_mb: usize,
+    /// Channel buffer size for streaming RPCs
+    pub max_parallel_streams: usize,
+    /// Global cap on concurrently forwarded backend requests (0 = unlimited)
+    pub max_total_inflight: usize,
+    /// Rows per backend sub-batch for embed_arrow (0 = send the whole batch)
+    pub arrow_max_rows_per_chunk: usize,
+    /// How embed_arrow matches backend embeddings to input rows
+    pub arrow_embed_ordering: ArrowEmbedOrdering,
+    /// What streaming RPCs do when a client stops reading responses
+    pub stream_backpressure: StreamBackpressure,
+    /// How model-routed requests pick among the model's instances
+    pub model_routing: ModelRoutingStrategy,
+    /// Timeout for forwarded requests (0 = disabled)
+    pub request_timeout_secs: u64,
+    /// Start stopped instances on the first routed request
+    pub autostart_on_request: bool,
+    /// How long an auto-started instance may take to become ready
+    /// (per-instance `startup_timeout_secs` takes precedence)
+    pub startup_timeout_secs: u64,
+    /// Connect timeout, keepalive and compression for backend channels
+    pub backend_channel: BackendChannelConfig,
+    /// Accept gzip requests and gzip responses for clients that support it
+    pub compression: bool,
+    /// Client metadata copied onto backend requests
+    pub forward_headers: ForwardHeaders,
+    /// Add `x-tei-instance-load` metadata to unary responses
+    pub instance_load_metadata: bool,
+    /// Record every multiplexer call here (None = disabled)
+    pub access_log: Option<Arc<AccessLog>>,
+}
+
+impl Default for GrpcServerOptions {
+    fn default() -> Self {
+        Self::from_config(&ManagerConfig::default())
+    }
+}
+
+impl GrpcServerOptions {
+    /// Build options from the manager configuration
+    pub fn from_config(config: &ManagerConfig) -> Self {
+        Self {
+            max_message_size_mb: config.grpc_max_message_size_mb,
+            max_parallel_streams: config.grpc_max_parallel_streams,
+            max_total_inflight: config.grpc_max_total_inflight,
+            arrow_max_rows_per_chunk: config.arrow_max_rows_per_chunk,
+            arrow_embed_ordering: config.arrow_embed_ordering,
+            stream_backpressure: config.grpc_stream_backpressure,
+            model_routing: config.model_routing_strategy,
+            request_timeout_secs: config.grpc_request_timeout_secs,
+            autostart_on_request: config.autostart_on_request,
+            startup_timeout_secs: config.startup_timeout_secs,
+            backend_channel: BackendChannelConfig::from_config(config),
+            compression: config.grpc_compression,
+            // Names were checked by ManagerConfig::validate
+            forward_headers: ForwardHeaders::new(&config.forward_headers).unwrap_or_default(),
+            instance_load_metadata: config.grpc_instance_load_metadata,
+            access_log: None,
+        }
+    }
+}
+
 /// Start the gRPC multiplexer server with graceful shutdown support
 ///
 /// This runs until the shutdown signal is received or an error occurs.
 /// The server will stop accepting new connections when shutdown is triggered,
 /// but will allow in-flight requests to complete.
+///
+/// With `tls_config`, connections are accepted through [`tls_incoming`], which
+/// asks the config's certificate resolver on every handshake. Sharing the API's
+/// [`ReloadableCertResolver`](crate::tls::ReloadableCertResolver) therefore
+/// rotates the gRPC certificate on reload as well.
 pub async fn start_grpc_server_with_shutdown<F>(
     addr: SocketAddr,
     registry: Arc<Registry>,
-    tls_config: Option<(String, String, String)>, // (cert, key, ca)
-    max_message_size_mb: usize,
-    max_parallel_streams: usize,
-    request_timeout_secs: u64,
+    tls_config: Option<rustls::ServerConfig>,
+    options: GrpcServerOptions,
     shutdown_signal: F,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
 where
     F: Future<Output = ()> + Send,
 {
-    let (service, reflection_service, max_message_size) = build_services(
-        registry,
-        max_parallel_streams,
-        request_timeout_secs,
-        max_message_size_mb,
-    )?;
+    let max_message_size_mb = options.max_message_size_mb;
+    let (server, reflection_service, _) = build_services(registry, &options)?;
 
-    // Build server with optional TLS
-    let mut builder = Server::builder();
+    let router = Server::builder()
+        .layer(AccessLogLayer::new(options.access_log))
+        .layer(MessageSizeLayer::new(options.max_message_size_mb))
+        .add_service(server)
+        .add_service(reflection_service);
 
-    if let Some((cert_pem, key_pem, ca_pem)) = tls_config {
+    if let Some(tls_config) = tls_config {
         tracing::info!(
             "Starting gRPC multiplexer on {} with mTLS (max message: {}MB)",
             addr,
             max_message_size_mb
         );
 
-        let server_identity = Identity::from_pem(cert_pem, key_pem);
-        let client_ca = Certificate::from_pem(ca_pem);
-        let tls = ServerTlsConfig::new()
-            .identity(server_identity)
-            .client_ca_root(client_ca);
-
-        builder = builder.tls_config(tls)?;
+        let listener = TcpListener::bind(addr).await?;
+        router
+            .serve_with_incoming_shutdown(tls_incoming(listener, tls_config), shutdown_signal)
+            .await?;
     } else {
         tracing::info!(
             "Starting gRPC multiplexer on {} (no TLS, max message: {}MB)",
             addr,
             max_message_size_mb
         );
-    }
 
-    builder
-        .add_service(
-            TeiMultiplexerServer::new(service)
-                .max_decoding_message_size(max_message_size)
-                .max_encoding_message_size(max_message_size),
-        )
-        .add_service(reflection_service)
-        .serve_with_shutdown(addr, shutdown_signal)
-        .await?;
+        router.serve_with_shutdown(addr, shutdown_signal).await?;
+    }
 
     tracing::info!("gRPC server shut down gracefully");
     Ok(())
@@ -80,65 +150,110 @@ where
 pub async fn start_grpc_server(
     addr: SocketAddr,
     registry: Arc<Registry>,
-    tls_config: Option<(String, String, String)>, // (cert, key, ca)
-    max_message_size_mb: usize,
-    max_parallel_streams: usize,
-    request_timeout_secs: u64,
+    tls_config: Option<rustls::ServerConfig>,
+    options: GrpcServerOptions,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let (service, reflection_service, max_message_size) = build_services(
-        registry,
-        max_parallel_streams,
-        request_timeout_secs,
-        max_message_size_mb,
-    )?;
+    start_grpc_server_with_shutdown(addr, registry, tls_config, options, std::future::pending())
+        .await
+}
 
-    // Build server with optional TLS
-    let mut builder = Server::builder();
+/// TLS handshakes that may complete before the server picks them up
+const TLS_ACCEPT_BACKLOG: usize = 64;
 
-    if let Some((cert_pem, key_pem, ca_pem)) = tls_config {
-        tracing::info!(
-            "Starting gRPC multiplexer on {} with mTLS (max message: {}MB)",
-            addr,
-            max_message_size_mb
-        );
+/// How long a client may take to finish the TLS handshake
+const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
 
-        let server_identity = Identity::from_pem(cert_pem, key_pem);
-        let client_ca = Certificate::from_pem(ca_pem);
-        let tls = ServerTlsConfig::new()
-            .identity(server_identity)
-            .client_ca_root(client_ca);
+/// Accept connections on `listener` and yield them once their TLS handshake completes
+///
+/// Handshakes run concurrently, so a slow client doesn't hold up the others, and
+/// failed ones are dropped. ALPN is set to `h2` as gRPC requires. Accepting stops
+/// when the returned stream is dropped.
+fn tls_incoming(
+    listener: TcpListener,
+    mut tls_config: rustls::ServerConfig,
+) -> ReceiverStream<std::io::Result<TlsStream<TcpStream>>> {
+    tls_config.alpn_protocols = vec![b"h2".to_vec()];
+    let acceptor = TlsAcceptor::from(Arc::new(tls_config));
+    let (tx, rx) = mpsc::channel(TLS_ACCEPT_BACKLOG);
+
+    tokio::spawn(async move {
+        loop {
+            let accepted = tokio::select! {
+                accepted = listener.accept() => accepted,
+                _ = tx.closed() => break,
+            };
+            let (tcp, peer) = match accepted {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    // e.g. out of file descriptors; back off instead of spinning
+                    tracing::warn!(error = %e, "Failed to accept gRPC connection");
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    continue;
+                }
+            };
+            let _ = tcp.set_nodelay(true);
+
+            let acceptor = acceptor.clone();
+            let tx = tx.clone();
+            tokio::spawn(async move {
+                match tokio::time::timeout(TLS_HANDSHAKE_TIMEOUT, acceptor.accept(tcp)).await {
+                    Ok(Ok(stream)) => {
+                        let _ = tx.send(Ok(stream)).await;
+                    }
+                    Ok(Err(e)) => {
+                        tracing::debug!(peer = %peer, error = %e, "gRPC TLS handshake failed");
+                    }
+                    Err(_) => {
+                        tracing::debug!(peer = %peer, "gRPC TLS handshake timed out");
+                    }
+                }
+            });
+        }
+    });
 
-        builder = builder.tls_config(tls)?;
-    } else {
-        tracing::info!(
-            "Starting gRPC multiplexer on {} (no TLS, max message: {}MB)",
-            addr,
-            max_message_size_mb
-        );
-    }
+    ReceiverStream::new(rx)
+}
 
-    builder
-        .add_service(
-            TeiMultiplexerServer::new(service)
-                .max_decoding_message_size(max_message_size)
-                .max_encoding_message_size(max_message_size),
-        )
+/// Start the gRPC multiplexer on a Unix domain socket with graceful shutdown support
+///
+/// TLS is not available on sockets; the socket file is removed once the server stops.
+pub async fn start_grpc_server_on_unix_socket<F>(
+    path: &Path,
+    registry: Arc<Registry>,
+    options: GrpcServerOptions,
+    shutdown_signal: F,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+where
+    F: Future<Output = ()> + Send,
+{
+    let (server, reflection_service, _) = build_services(registry, &options)?;
+    let (listener, _socket_file) = crate::unix_socket::bind(path)?;
+
+    tracing::info!(
+        "Starting gRPC multiplexer on unix:{} (max message: {}MB)",
+        path.display(),
+        options.max_message_size_mb
+    );
+
+    Server::builder()
+        .layer(AccessLogLayer::new(options.access_log))
+        .layer(MessageSizeLayer::new(options.max_message_size_mb))
+        .add_service(server)
         .add_service(reflection_service)
-        .serve(addr)
+        .serve_with_incoming_shutdown(UnixListenerStream::new(listener), shutdown_signal)
         .await?;
 
+    tracing::info!("gRPC server shut down gracefully");
     Ok(())
 }
 
 /// Build the gRPC services (shared between server variants)
 fn build_services(
     registry: Arc<Registry>,
-    max_parallel_streams: usize,
-    request_timeout_secs: u64,
-    max_message_size_mb: usize,
+    options: &GrpcServerOptions,
 ) -> Result<
     (
-        TeiMultiplexerService,
+        TeiMultiplexerServer<TeiMultiplexerService>,
         tonic_reflection::server::v1::ServerReflectionServer<
             impl tonic_reflection::server::v1::ServerReflection,
         >,
@@ -147,10 +262,26 @@ fn build_services(
     Box<dyn std::error::Error + Send + Sync>,
 > {
     // Create connection pool
-    let pool = BackendPool::new(registry);
+    let mut pool = BackendPool::new(registry)
+        .with_channel_config(options.backend_channel)
+        .with_model_routing(options.model_routing);
+    if options.autostart_on_request {
+        pool = pool.with_autostart(Duration::from_secs(options.startup_timeout_secs));
+    }
 
     // Create multiplexer service with timeout
-    let service = TeiMultiplexerService::new(pool, max_parallel_streams, request_timeout_secs);
+    let service = TeiMultiplexerService::new(
+        pool,
+        options.max_parallel_streams,
+        options.request_timeout_secs,
+    )
+    .with_max_total_inflight(options.max_total_inflight)
+    .with_arrow_max_rows_per_chunk(options.arrow_max_rows_per_chunk)
+    .with_arrow_embed_ordering(options.arrow_embed_ordering)
+    .with_stream_backpressure(options.stream_backpressure)
+    .with_forward_headers(options.forward_headers.clone())
+    .with_instance_load_metadata(options.instance_load_metadata)
+    .with_max_message_size_mb(options.max_message_size_mb);
 
     // Enable gRPC reflection
     let file_descriptor_set: &[u8] = tonic::include_file_descriptor_set!("descriptor");
@@ -159,17 +290,39 @@ fn build_services(
         .build_v1()?;
 
     // Message size limits from config
-    let max_message_size: usize = max_message_size_mb * 1024 * 1024;
+    let max_message_size: usize = options.max_message_size_mb.saturating_mul(1024 * 1024);
+    let mut server = TeiMultiplexerServer::new(service)
+        .max_decoding_message_size(max_message_size)
+        .max_encoding_message_size(max_message_size);
+
+    // Responses are only compressed when the client's grpc-accept-encoding allows it
+    if options.compression {
+        server = server
+            .accept_compressed(CompressionEncoding::Gzip)
+            .send_compressed(CompressionEncoding::Gzip);
+    }
 
-    Ok((service, reflection_service, max_message_size))
+    Ok((server, reflection_service, max_message_size))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::time::Duration;
     use tokio::time::timeout;
 
+    fn options(
+        max_message_size_mb: usize,
+        max_parallel_streams: usize,
+        request_timeout_secs: u64,
+    ) -> GrpcServerOptions {
+        GrpcServerOptions {
+            max_message_size_mb,
+            max_parallel_streams,
+            request_timeout_secs,
+            ..Default::default()
+        }
+    }
+
     fn create_test_registry() -> Arc<Registry> {
         Arc::new(Registry::new(
             None,
@@ -218,10 +371,14 @@ mod tests {
         // Spawn server in background and cancel quickly
         let handle = tokio::spawn(async move {
             start_grpc_server(
-                addr, registry, None, // No TLS
-                16,   // 16 MB max message
-                1024, // max parallel streams
-                30,   // 30s request timeout
+                addr,
+                registry,
+                None, // No TLS
+                options(
+                    16,   // 16 MB max message
+                    1024, // max parallel streams
+                    30,   // 30s request timeout
+                ),
             )
             .await
         });
@@ -242,7 +399,7 @@ mod tests {
             let addr: SocketAddr = "127.0.0.1:0".parse().unwrap();
 
             let handle = tokio::spawn(async move {
-                start_grpc_server(addr, registry, None, size_mb, 1024, 30).await
+                start_grpc_server(addr, registry, None, options(size_mb, 1024, 30)).await
             });
 
             tokio::time::sleep(Duration::from_millis(30)).await;
@@ -257,7 +414,7 @@ mod tests {
             let addr: SocketAddr = "127.0.0.1:0".parse().unwrap();
 
             let handle = tokio::spawn(async move {
-                start_grpc_server(addr, registry, None, 16, streams, 30).await
+                start_grpc_server(addr, registry, None, options(16, streams, 30)).await
             });
 
             tokio::time::sleep(Duration::from_millis(30)).await;
@@ -266,32 +423,47 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_server_with_invalid_tls_config_fails() {
-        // Install rustls crypto provider for TLS tests
-        let _ = rustls::crypto::ring::default_provider().install_default();
-
-        let registry = create_test_registry();
-        let addr: SocketAddr = "127.0.0.1:0".parse().unwrap();
-
-        // Invalid TLS config (garbage data)
-        let invalid_tls = Some((
-            "not a valid cert".to_string(),
-            "not a valid key".to_string(),
-            "not a valid ca".to_string(),
-        ));
-
-        let result = timeout(
-            Duration::from_secs(1),
-            start_grpc_server(addr, registry, invalid_tls, 16, 1024, 30),
-        )
-        .await;
-
-        // Should either timeout or fail due to invalid TLS
-        match result {
-            Ok(Err(_)) => {} // Expected: TLS config error
-            Err(_) => {}     // Timeout is also acceptable
-            Ok(Ok(())) => panic!("Should not succeed with invalid TLS"),
-        }
+    async fn test_tls_listener_serves_reloaded_certificate() {
+        use crate::tls::ReloadableCertResolver;
+        use crate::tls::fixtures::*;
+        use rustls::pki_types::ServerName;
+        use tokio_rustls::TlsConnector;
+        use tokio_stream::StreamExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let (cert_path, key_path) = write_pair(dir.path(), CERT_A, KEY_A);
+        let resolver = Arc::new(ReloadableCertResolver::new(&cert_path, &key_path).unwrap());
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let tls_config = rustls::ServerConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()
+            .unwrap()
+            .with_no_client_auth()
+            .with_cert_resolver(resolver.clone());
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let mut incoming = tls_incoming(listener, tls_config);
+
+        let mut client_config = accept_any_client_config();
+        client_config.alpn_protocols = vec![b"h2".to_vec()];
+        let connector = TlsConnector::from(Arc::new(client_config));
+        let mut served_cert = async || {
+            let tcp = TcpStream::connect(addr).await.unwrap();
+            let name = ServerName::try_from("localhost").unwrap();
+            let client = connector.connect(name, tcp).await.unwrap();
+            let server = incoming.next().await.unwrap().unwrap();
+            assert_eq!(server.get_ref().1.alpn_protocol(), Some(&b"h2"[..]));
+            client.get_ref().1.peer_certificates().unwrap()[0]
+                .clone()
+                .into_owned()
+        };
+
+        assert_eq!(served_cert().await, pem_der(CERT_A));
+
+        // The listener picks up a reload without being rebuilt
+        write_pair(dir.path(), CERT_B, KEY_B);
+        resolver.reload().unwrap();
+        assert_eq!(served_cert().await, pem_der(CERT_B));
     }
 
     #[tokio::test]
@@ -365,9 +537,9 @@ mod tests {
             .map(|_| {
                 let registry = create_test_registry();
                 let addr: SocketAddr = "127.0.0.1:0".parse().unwrap();
-                tokio::spawn(
-                    async move { start_grpc_server(addr, registry, None, 16, 1024, 30).await },
-                )
+                tokio::spawn(async move {
+                    start_grpc_server(addr, registry, None, options(16, 1024, 30)).await
+                })
             })
             .collect();
 
@@ -387,9 +559,15 @@ mod tests {
         let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
 
         let handle = tokio::spawn(async move {
-            start_grpc_server_with_shutdown(addr, registry, None, 16, 1024, 30, async move {
-                let _ = shutdown_rx.await;
-            })
+            start_grpc_server_with_shutdown(
+                addr,
+                registry,
+                None,
+                options(16, 1024, 30),
+                async move {
+                    let _ = shutdown_rx.await;
+                },
+            )
             .await
         });
 
@@ -418,9 +596,15 @@ mod tests {
         let mut shutdown_rx = shutdown_tx.subscribe();
 
         let handle = tokio::spawn(async move {
-            start_grpc_server_with_shutdown(addr, registry, None, 16, 1024, 30, async move {
-                let _ = shutdown_rx.recv().await;
-            })
+            start_grpc_server_with_shutdown(
+                addr,
+                registry,
+                None,
+                options(16, 1024, 30),
+                async move {
+                    let _ = shutdown_rx.recv().await;
+                },
+            )
             .await
         });
 
@@ -438,10 +622,10 @@ mod tests {
     #[tokio::test]
     async fn test_build_services_creates_valid_services() {
         let registry = create_test_registry();
-        let result = build_services(registry, 1024, 30, 16);
+        let result = build_services(registry, &options(16, 1024, 30));
 
         assert!(result.is_ok());
-        let (_service, _reflection, max_size) = result.unwrap();
+        let (_server, _reflection, max_size) = result.unwrap();
         assert_eq!(max_size, 16 * 1024 * 1024);
     }
 }
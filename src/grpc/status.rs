@@ -0,0 +1,198 @@
+//! Machine-readable details on multiplexer errors
+//!
+//! Statuses built here carry a `google.rpc.Status` in `grpc-status-details-bin`
+//! (gRPC's richer error model) holding an `ErrorInfo` whose `reason` is the same
+//! code the HTTP API returns (e.g. `INSTANCE_NOT_FOUND`), plus a `BadRequest`
+//! naming the offending field for invalid requests.
+//!
+//! The messages are declared by hand with the field numbers of the googleapis
+//! definitions, so any richer-error client (e.g. `tonic-types`) can decode them.
+
+use std::collections::HashMap;
+use tonic::{Code, Status};
+
+/// `ErrorInfo.domain` of every error raised by the manager
+pub const ERROR_DOMAIN: &str = "tei-manager";
+
+const ERROR_INFO_TYPE_URL: &str = "type.googleapis.com/google.rpc.ErrorInfo";
+const BAD_REQUEST_TYPE_URL: &str = "type.googleapis.com/google.rpc.BadRequest";
+
+/// `google.rpc.Status`
+#[derive(Clone, PartialEq, prost::Message)]
+struct RpcStatus {
+    #[prost(int32, tag = "1")]
+    code: i32,
+    #[prost(string, tag = "2")]
+    message: String,
+    #[prost(message, repeated, tag = "3")]
+    details: Vec<Any>,
+}
+
+/// `google.protobuf.Any`
+#[derive(Clone, PartialEq, prost::Message)]
+struct Any {
+    #[prost(string, tag = "1")]
+    type_url: String,
+    #[prost(bytes = "vec", tag = "2")]
+    value: Vec<u8>,
+}
+
+/// `google.rpc.ErrorInfo`: why a request failed, as a stable code
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ErrorInfo {
+    /// Error code, e.g. `INSTANCE_NOT_FOUND`
+    #[prost(string, tag = "1")]
+    pub reason: String,
+    #[prost(string, tag = "2")]
+    pub domain: String,
+    /// Context such as the instance or model involved
+    #[prost(map = "string, string", tag = "3")]
+    pub metadata: HashMap<String, String>,
+}
+
+/// `google.rpc.BadRequest`: the request fields that were rejected
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct BadRequest {
+    #[prost(message, repeated, tag = "1")]
+    pub field_violations: Vec<FieldViolation>,
+}
+
+/// `google.rpc.BadRequest.FieldViolation`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct FieldViolation {
+    /// Path to the field, e.g. `request.prompt_name`
+    #[prost(string, tag = "1")]
+    pub field: String,
+    #[prost(string, tag = "2")]
+    pub description: String,
+}
+
+/// Status with an `ErrorInfo` detail
+pub fn error_status(
+    code: Code,
+    message: impl Into<String>,
+    reason: &str,
+    metadata: &[(&str, &str)],
+) -> Status {
+    with_details(code, message.into(), reason, metadata, None)
+}
+
+/// `InvalidArgument` for a bad value in `field` (reason `VALIDATION_ERROR`)
+pub fn invalid_field(field: &str, message: impl Into<String>) -> Status {
+    field_status("VALIDATION_ERROR", field, message.into())
+}
+
+/// `InvalidArgument` for a required `field` that is absent (reason `MISSING_FIELD`)
+pub fn missing_field(field: &str, message: impl Into<String>) -> Status {
+    field_status("MISSING_FIELD", field, message.into())
+}
+
+fn field_status(reason: &str, field: &str, message: String) -> Status {
+    let bad_request = BadRequest {
+        field_violations: vec![FieldViolation {
+            field: field.to_string(),
+            description: message.clone(),
+        }],
+    };
+    with_details(
+        Code::InvalidArgument,
+        message,
+        reason,
+        &[("field", field)],
+        Some(bad_request),
+    )
+}
+
+fn with_details(
+    code: Code,
+    message: String,
+    reason: &str,
+    metadata: &[(&str, &str)],
+    bad_request: Option<BadRequest>,
+) -> Status {
+    let info = ErrorInfo {
+        reason: reason.to_string(),
+        domain: ERROR_DOMAIN.to_string(),
+        metadata: metadata
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect(),
+    };
+    let mut details = vec![Any {
+        type_url: ERROR_INFO_TYPE_URL.to_string(),
+        value: prost::Message::encode_to_vec(&info),
+    }];
+    if let Some(bad_request) = bad_request {
+        details.push(Any {
+            type_url: BAD_REQUEST_TYPE_URL.to_string(),
+            value: prost::Message::encode_to_vec(&bad_request),
+        });
+    }
+    let status = RpcStatus {
+        code: code as i32,
+        message: message.clone(),
+        details,
+    };
+    Status::with_details(code, message, prost::Message::encode_to_vec(&status).into())
+}
+
+/// The `ErrorInfo` attached to `status`, if any
+pub fn error_info(status: &Status) -> Option<ErrorInfo> {
+    decode_detail(status, ERROR_INFO_TYPE_URL)
+}
+
+/// The `BadRequest` attached to `status`, if any
+pub fn bad_request(status: &Status) -> Option<BadRequest> {
+    decode_detail(status, BAD_REQUEST_TYPE_URL)
+}
+
+fn decode_detail<M: prost::Message + Default>(status: &Status, type_url: &str) -> Option<M> {
+    let rpc_status = <RpcStatus as prost::Message>::decode(status.details()).ok()?;
+    let any = rpc_status
+        .details
+        .into_iter()
+        .find(|any| any.type_url == type_url)?;
+    M::decode(any.value.as_slice()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_info_round_trip() {
+        let status = error_status(
+            Code::NotFound,
+            "Instance 'bge' not found",
+            "INSTANCE_NOT_FOUND",
+            &[("instance", "bge")],
+        );
+
+        assert_eq!(status.code(), Code::NotFound);
+        assert_eq!(status.message(), "Instance 'bge' not found");
+        let info = error_info(&status).unwrap();
+        assert_eq!(info.reason, "INSTANCE_NOT_FOUND");
+        assert_eq!(info.domain, ERROR_DOMAIN);
+        assert_eq!(info.metadata["instance"], "bge");
+        assert!(bad_request(&status).is_none());
+    }
+
+    #[test]
+    fn test_invalid_field_has_bad_request() {
+        let status = invalid_field("truncation_direction", "Unknown truncation_direction 7");
+
+        assert_eq!(status.code(), Code::InvalidArgument);
+        assert_eq!(error_info(&status).unwrap().reason, "VALIDATION_ERROR");
+        let violations = bad_request(&status).unwrap().field_violations;
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].field, "truncation_direction");
+        assert_eq!(violations[0].description, "Unknown truncation_direction 7");
+    }
+
+    #[test]
+    fn test_plain_status_has_no_details() {
+        let status = Status::not_found("gone");
+        assert!(error_info(&status).is_none());
+        assert!(bad_request(&status).is_none());
+    }
+}
@@ -1,36 +1,91 @@
 //! Health monitoring for TEI instances with dependency injection and testability
 
-use crate::instance::{InstanceStatus, TeiInstance};
-use crate::registry::Registry;
+use crate::config::{FailurePolicy, HealthCheckMode, ManagerConfig};
+use crate::grpc::channel::BackendChannelConfig;
+use crate::instance::{HealthCheckRecord, InstanceStats, InstanceStatus, ProcessExit, TeiInstance};
+use crate::registry::{InstanceEvent, Registry};
 use async_trait::async_trait;
+use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::time::{Duration, interval, sleep};
+use tokio::sync::Semaphore;
+use tokio::task::JoinHandle;
+use tokio::time::{Duration, Instant, MissedTickBehavior, interval, sleep, sleep_until};
 
 // ============================================================================
 // Trait Definitions
 // ============================================================================
 
+/// How serious a failed health check is
+///
+/// Hard failures mean the process is gone and restart sooner; soft failures mean the
+/// process is alive but not answering, and need more consecutive failures before a restart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureSeverity {
+    /// Process alive but the Info RPC failed (e.g. transient network issue)
+    Soft,
+    /// Process is not running
+    Hard,
+}
+
+impl std::fmt::Display for FailureSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FailureSeverity::Soft => write!(f, "soft"),
+            FailureSeverity::Hard => write!(f, "hard"),
+        }
+    }
+}
+
 /// Result of a health check
 #[derive(Debug, Clone)]
 pub struct HealthCheckResult {
     pub healthy: bool,
     pub reason: Option<String>,
+    /// Severity of the failure (None when healthy)
+    pub severity: Option<FailureSeverity>,
 }
 
 impl HealthCheckResult {
+    /// Outcome label for metrics: "healthy", "soft_failure" or "hard_failure"
+    pub fn outcome(&self) -> &'static str {
+        match (self.healthy, self.severity) {
+            (true, _) => "healthy",
+            (false, Some(FailureSeverity::Soft)) => "soft_failure",
+            (false, _) => "hard_failure",
+        }
+    }
+
     pub fn healthy() -> Self {
         Self {
             healthy: true,
             reason: None,
+            severity: None,
         }
     }
 
-    pub fn unhealthy(reason: String) -> Self {
+    /// Unhealthy result with an explicit severity
+    pub fn unhealthy_with(reason: String, severity: FailureSeverity) -> Self {
         Self {
             healthy: false,
             reason: Some(reason),
+            severity: Some(severity),
         }
     }
+
+    /// Unhealthy result, treated as a hard failure
+    pub fn unhealthy(reason: String) -> Self {
+        Self::unhealthy_with(reason, FailureSeverity::Hard)
+    }
+
+    /// Process alive but not responding
+    pub fn soft_failure(reason: String) -> Self {
+        Self::unhealthy_with(reason, FailureSeverity::Soft)
+    }
+
+    /// Process not running
+    pub fn hard_failure(reason: String) -> Self {
+        Self::unhealthy_with(reason, FailureSeverity::Hard)
+    }
 }
 
 /// Trait for checking instance health
@@ -54,18 +109,43 @@ pub enum HealthEvent {
     CheckSucceeded {
         instance_name: String,
     },
+    /// The health checker returned (outcome as in [`HealthCheckResult::outcome`])
+    CheckCompleted {
+        instance_name: String,
+        outcome: &'static str,
+        duration: Duration,
+    },
     CheckFailed {
         instance_name: String,
         consecutive_failures: u32,
+        severity: FailureSeverity,
         reason: String,
     },
     RestartTriggered {
         instance_name: String,
         failure_count: u32,
     },
+    /// Restart threshold reached but skipped because of maintenance mode
+    RestartSkipped {
+        instance_name: String,
+        failure_count: u32,
+        reason: String,
+    },
     RestartSucceeded {
         instance_name: String,
     },
+    /// Another restart was needed after `restarts` in the last `window`;
+    /// the instance is quarantined instead
+    RestartLimitReached {
+        instance_name: String,
+        restarts: u32,
+        window: Duration,
+    },
+    /// Threshold reached with the quarantine policy; the instance was stopped
+    Quarantined {
+        instance_name: String,
+        failure_count: u32,
+    },
     RestartFailed {
         instance_name: String,
         error: String,
@@ -75,6 +155,19 @@ pub enum HealthEvent {
         from: InstanceStatus,
         to: InstanceStatus,
     },
+    /// Result of the secondary Prometheus endpoint probe (error is None when reachable)
+    MetricsProbed {
+        instance_name: String,
+        port: u16,
+        error: Option<String>,
+    },
+    /// The process exited the way the OOM killer ends it
+    /// (max_batch_tokens is the reduced value for the next start, if backoff is enabled)
+    OomKilled {
+        instance_name: String,
+        exit: ProcessExit,
+        max_batch_tokens: Option<u32>,
+    },
 }
 
 /// Trait for handling health events
@@ -83,14 +176,41 @@ pub trait HealthEventHandler: Send + Sync {
     async fn handle(&self, event: HealthEvent);
 }
 
+/// Trait for probing an instance's Prometheus endpoint
+///
+/// Metrics are non-critical: a failed probe is reported but never fails the instance.
+#[async_trait]
+pub trait MetricsProbe: Send + Sync {
+    /// Ok when `http://localhost:{port}/metrics` answers with 200
+    async fn probe(&self, port: u16) -> Result<(), String>;
+}
+
+/// Trait for measuring the embedding dimension an instance returns
+#[async_trait]
+pub trait DimensionProbe: Send + Sync {
+    /// Length of the embedding returned for a fixed probe text
+    async fn dimension(&self, port: u16) -> Result<usize, String>;
+}
+
 // ============================================================================
 // Production Implementations
 // ============================================================================
 
+/// Timeout for the Info RPC used as a health probe
+const HEALTH_RPC_TIMEOUT_SECS: u64 = 5;
+
 /// gRPC-based health checker that calls TEI's Info service
-pub struct GrpcHealthChecker;
+#[derive(Default)]
+pub struct GrpcHealthChecker {
+    channel_config: BackendChannelConfig,
+}
 
 impl GrpcHealthChecker {
+    /// Create a checker with custom connect timeout and keepalive settings
+    pub fn new(channel_config: BackendChannelConfig) -> Self {
+        Self { channel_config }
+    }
+
     /// Poll for instance readiness with retries after startup
     /// Returns Ok(()) when ready, Err if timeout reached
     pub async fn wait_for_ready(
@@ -98,39 +218,135 @@ impl GrpcHealthChecker {
         timeout: Duration,
         poll_interval: Duration,
     ) -> anyhow::Result<()> {
-        let checker = GrpcHealthChecker;
-        let start = std::time::Instant::now();
+        wait_for_ready_with(
+            &GrpcHealthChecker::default(),
+            instance,
+            timeout,
+            poll_interval,
+        )
+        .await
+    }
+}
 
-        loop {
-            if start.elapsed() > timeout {
-                anyhow::bail!(
-                    "Instance '{}' did not become ready within {:?}",
-                    instance.config.name,
-                    timeout
+/// Poll `checker` until the instance is ready, marking it `Running`
+/// Returns Err if timeout reached
+///
+/// With a startup download grace on the instance, the deadline is extended while
+/// the model's cache directory keeps growing (see [`wait_for_ready_tracking`]).
+pub async fn wait_for_ready_with(
+    checker: &dyn HealthChecker,
+    instance: &TeiInstance,
+    timeout: Duration,
+    poll_interval: Duration,
+) -> anyhow::Result<()> {
+    wait_for_ready_tracking(
+        checker,
+        instance,
+        timeout,
+        poll_interval,
+        &CacheDownloadProgress,
+    )
+    .await
+}
+
+/// [`wait_for_ready_with`], reading download progress from `progress`
+///
+/// Each poll that sees more downloaded bytes than before moves the deadline to
+/// at least [`TeiInstance::startup_download_grace`] from now, so the instance only
+/// times out once `timeout` has passed and the download stalled for the grace.
+pub async fn wait_for_ready_tracking(
+    checker: &dyn HealthChecker,
+    instance: &TeiInstance,
+    timeout: Duration,
+    poll_interval: Duration,
+    progress: &dyn DownloadProgress,
+) -> anyhow::Result<()> {
+    let start = Instant::now();
+    let mut deadline = start + timeout;
+    let grace = instance.startup_download_grace();
+    let mut downloaded = if grace.is_zero() {
+        None
+    } else {
+        progress.downloaded_bytes(instance).await
+    };
+
+    loop {
+        if !grace.is_zero()
+            && let Some(bytes) = progress.downloaded_bytes(instance).await
+        {
+            if downloaded.is_none_or(|before| bytes > before) {
+                deadline = deadline.max(Instant::now() + grace);
+                tracing::debug!(
+                    instance = %instance.config.name,
+                    downloaded_bytes = bytes,
+                    "Model download progressing, extending startup deadline"
                 );
             }
+            downloaded = Some(bytes);
+        }
 
-            let result = checker.check(instance).await;
-            if result.healthy {
-                // Update status to Running
-                *instance.status.write().await = InstanceStatus::Running;
-                tracing::info!(
-                    instance = %instance.config.name,
-                    elapsed_ms = start.elapsed().as_millis(),
-                    "Instance is ready"
+        if Instant::now() > deadline {
+            if deadline > start + timeout {
+                anyhow::bail!(
+                    "Instance '{}' did not become ready within {:?} (model download stalled for {:?})",
+                    instance.config.name,
+                    start.elapsed(),
+                    grace
                 );
-                return Ok(());
             }
+            anyhow::bail!(
+                "Instance '{}' did not become ready within {:?}",
+                instance.config.name,
+                timeout
+            );
+        }
 
-            tracing::debug!(
+        let result = checker.check(instance).await;
+        if result.healthy {
+            instance.set_status(InstanceStatus::Running).await;
+            tracing::info!(
                 instance = %instance.config.name,
-                reason = ?result.reason,
                 elapsed_ms = start.elapsed().as_millis(),
-                "Waiting for instance to be ready"
+                "Instance is ready"
             );
-
-            sleep(poll_interval).await;
+            return Ok(());
         }
+
+        tracing::debug!(
+            instance = %instance.config.name,
+            reason = ?result.reason,
+            elapsed_ms = start.elapsed().as_millis(),
+            "Waiting for instance to be ready"
+        );
+
+        sleep(poll_interval).await;
+    }
+}
+
+/// Source of how much of an instance's model has been downloaded so far
+#[async_trait]
+pub trait DownloadProgress: Send + Sync {
+    /// Bytes downloaded (None while nothing is known about the model)
+    async fn downloaded_bytes(&self, instance: &TeiInstance) -> Option<u64>;
+}
+
+/// Size of the model's directory in the instance's HF cache, which grows while
+/// TEI downloads it
+///
+/// The directory walk runs on the blocking pool, as a large snapshot has many files.
+pub struct CacheDownloadProgress;
+
+#[async_trait]
+impl DownloadProgress for CacheDownloadProgress {
+    async fn downloaded_bytes(&self, instance: &TeiInstance) -> Option<u64> {
+        let cache_dir = instance.config.model_cache_dir();
+        let model_id = instance.config.model_id.clone();
+        tokio::task::spawn_blocking(move || {
+            crate::models::cache::cache_size_in(&cache_dir, &model_id)
+        })
+        .await
+        .ok()
+        .flatten()
     }
 }
 
@@ -139,28 +355,33 @@ impl HealthChecker for GrpcHealthChecker {
     async fn check(&self, instance: &TeiInstance) -> HealthCheckResult {
         // Check if process is running
         if !instance.is_running().await {
-            return HealthCheckResult::unhealthy("Process not running".to_string());
+            return HealthCheckResult::hard_failure("Process not running".to_string());
         }
 
+        // From here on the process is alive, so any failure is soft
+
         // gRPC health check - call Info RPC to verify TEI is ready
-        let addr = format!("http://localhost:{}", instance.config.port);
 
         // Create gRPC channel with timeout
-        let channel = match tonic::transport::Channel::from_shared(addr) {
+        let channel = match self.channel_config.endpoint(instance.config.port) {
             Ok(endpoint) => {
                 match endpoint
-                    .timeout(Duration::from_secs(5))
-                    .connect_timeout(Duration::from_secs(5))
+                    .timeout(Duration::from_secs(HEALTH_RPC_TIMEOUT_SECS))
                     .connect()
                     .await
                 {
                     Ok(ch) => ch,
                     Err(e) => {
-                        return HealthCheckResult::unhealthy(format!("gRPC connect failed: {}", e));
+                        return HealthCheckResult::soft_failure(format!(
+                            "gRPC connect failed: {}",
+                            e
+                        ));
                     }
                 }
             }
-            Err(_) => return HealthCheckResult::unhealthy("Invalid gRPC address".to_string()),
+            Err(_) => {
+                return HealthCheckResult::soft_failure("Invalid gRPC address".to_string());
+            }
         };
 
         // Call Info RPC - this only succeeds if TEI is fully ready
@@ -169,11 +390,133 @@ impl HealthChecker for GrpcHealthChecker {
 
         match client.info(InfoRequest {}).await {
             Ok(_response) => HealthCheckResult::healthy(),
-            Err(e) => HealthCheckResult::unhealthy(format!("Info RPC failed: {}", e)),
+            Err(e) => HealthCheckResult::soft_failure(format!("Info RPC failed: {}", e)),
+        }
+    }
+}
+
+/// Health checker that reports every instance healthy without contacting it
+///
+/// Only for smoke tests without real TEI backends; see [`checker_for_mode`].
+pub struct AlwaysHealthyChecker;
+
+#[async_trait]
+impl HealthChecker for AlwaysHealthyChecker {
+    async fn check(&self, _instance: &TeiInstance) -> HealthCheckResult {
+        HealthCheckResult::healthy()
+    }
+}
+
+/// Environment variable that must be set to `1` to allow `health_check_mode = "always_healthy"`
+pub const ALLOW_FAKE_HEALTH_ENV: &str = "TEI_MANAGER_ALLOW_FAKE_HEALTH";
+
+/// Whether the fake health checker has been explicitly allowed via the environment
+pub fn fake_health_allowed() -> bool {
+    std::env::var(ALLOW_FAKE_HEALTH_ENV).is_ok_and(|v| v == "1")
+}
+
+/// Build the health checker for a configured mode
+///
+/// `AlwaysHealthy` is refused unless `allow_fake` is set, and logs a warning when used.
+pub fn checker_for_mode(
+    mode: HealthCheckMode,
+    channel_config: BackendChannelConfig,
+    allow_fake: bool,
+) -> anyhow::Result<Arc<dyn HealthChecker>> {
+    match mode {
+        HealthCheckMode::Grpc => Ok(Arc::new(GrpcHealthChecker::new(channel_config))),
+        HealthCheckMode::AlwaysHealthy => {
+            if !allow_fake {
+                anyhow::bail!(
+                    "health_check_mode = \"always_healthy\" is test-only; set {}=1 to allow it",
+                    ALLOW_FAKE_HEALTH_ENV
+                );
+            }
+            tracing::warn!(
+                "INSECURE: health_check_mode is always_healthy - instances are reported \
+                 healthy without contacting them. Use for testing only"
+            );
+            Ok(Arc::new(AlwaysHealthyChecker))
+        }
+    }
+}
+
+/// Text embedded by the dimension probe
+const DIMENSION_PROBE_TEXT: &str = "tei-manager dimension probe";
+
+/// Calls TEI's Embed RPC with [`DIMENSION_PROBE_TEXT`]
+#[derive(Default)]
+pub struct GrpcDimensionProbe {
+    channel_config: BackendChannelConfig,
+}
+
+impl GrpcDimensionProbe {
+    /// Create a probe with custom connect timeout and keepalive settings
+    pub fn new(channel_config: BackendChannelConfig) -> Self {
+        Self { channel_config }
+    }
+}
+
+#[async_trait]
+impl DimensionProbe for GrpcDimensionProbe {
+    async fn dimension(&self, port: u16) -> Result<usize, String> {
+        use crate::grpc::proto::tei::v1::{EmbedRequest, embed_client::EmbedClient};
+
+        let channel = self
+            .channel_config
+            .endpoint(port)
+            .map_err(|e| e.to_string())?
+            .timeout(Duration::from_secs(HEALTH_RPC_TIMEOUT_SECS))
+            .connect()
+            .await
+            .map_err(|e| format!("gRPC connect failed: {}", e))?;
+
+        let response = EmbedClient::new(channel)
+            .embed(EmbedRequest {
+                inputs: DIMENSION_PROBE_TEXT.to_string(),
+                truncate: true,
+                normalize: None,
+                truncation_direction: 0,
+                prompt_name: None,
+                dimensions: None,
+            })
+            .await
+            .map_err(|e| format!("Embed RPC failed: {}", e))?;
+        Ok(response.into_inner().embeddings.len())
+    }
+}
+
+/// Timeout for the Prometheus endpoint probe
+const METRICS_PROBE_TIMEOUT_SECS: u64 = 2;
+
+/// Probes `GET /metrics` with a bare HTTP/1.0 request; only the status line is read
+#[derive(Default)]
+pub struct HttpMetricsProbe;
+
+#[async_trait]
+impl MetricsProbe for HttpMetricsProbe {
+    async fn probe(&self, port: u16) -> Result<(), String> {
+        let status_line = tokio::time::timeout(
+            Duration::from_secs(METRICS_PROBE_TIMEOUT_SECS),
+            read_metrics_status_line(port),
+        )
+        .await
+        .map_err(|_| format!("timed out after {}s", METRICS_PROBE_TIMEOUT_SECS))?
+        .map_err(|e| e.to_string())?;
+
+        match status_line.split_whitespace().nth(1) {
+            Some("200") => Ok(()),
+            _ => Err(format!("unexpected response '{}'", status_line)),
         }
     }
 }
 
+async fn read_metrics_status_line(port: u16) -> std::io::Result<String> {
+    let head = crate::metrics::fetch_metrics(port, 1024, true).await?;
+    let line = head.split(|b| *b == b'\n').next().unwrap_or_default();
+    Ok(String::from_utf8_lossy(line).trim_end().to_string())
+}
+
 /// Default restart strategy using instance.restart()
 pub struct DefaultRestartStrategy;
 
@@ -197,14 +540,27 @@ impl HealthEventHandler for MetricsEventHandler {
             HealthEvent::CheckSucceeded { instance_name } => {
                 tracing::debug!(instance = %instance_name, "Health check succeeded");
             }
+            HealthEvent::CheckCompleted {
+                instance_name,
+                outcome,
+                duration,
+            } => {
+                crate::metrics::record_health_check_duration(
+                    &instance_name,
+                    outcome,
+                    duration.as_secs_f64(),
+                );
+            }
             HealthEvent::CheckFailed {
                 instance_name,
                 consecutive_failures,
+                severity,
                 reason,
             } => {
                 tracing::warn!(
                     instance = %instance_name,
                     failures = consecutive_failures,
+                    severity = %severity,
                     reason = %reason,
                     "Health check failed"
                 );
@@ -220,6 +576,40 @@ impl HealthEventHandler for MetricsEventHandler {
                 );
                 crate::metrics::record_instance_restart(&instance_name);
             }
+            HealthEvent::RestartSkipped {
+                instance_name,
+                failure_count,
+                reason,
+            } => {
+                tracing::info!(
+                    instance = %instance_name,
+                    failures = failure_count,
+                    reason = %reason,
+                    "Restart skipped"
+                );
+            }
+            HealthEvent::RestartLimitReached {
+                instance_name,
+                restarts,
+                window,
+            } => {
+                tracing::error!(
+                    instance = %instance_name,
+                    restarts,
+                    window_secs = window.as_secs(),
+                    "Restart limit reached, quarantining instance instead of restarting"
+                );
+            }
+            HealthEvent::Quarantined {
+                instance_name,
+                failure_count,
+            } => {
+                tracing::error!(
+                    instance = %instance_name,
+                    failures = failure_count,
+                    "Instance quarantined after repeated health check failures"
+                );
+            }
             HealthEvent::RestartSucceeded { instance_name } => {
                 tracing::info!(instance = %instance_name, "Instance restarted successfully");
             }
@@ -245,7 +635,154 @@ impl HealthEventHandler for MetricsEventHandler {
                     "Instance status changed"
                 );
             }
+            HealthEvent::MetricsProbed {
+                instance_name,
+                port,
+                error,
+            } => {
+                if let Some(error) = &error {
+                    tracing::warn!(
+                        instance = %instance_name,
+                        prometheus_port = port,
+                        error = %error,
+                        "Prometheus metrics endpoint unreachable"
+                    );
+                }
+                crate::metrics::record_metrics_reachable(&instance_name, error.is_none());
+            }
+            HealthEvent::OomKilled {
+                instance_name,
+                exit,
+                max_batch_tokens,
+            } => {
+                tracing::error!(
+                    instance = %instance_name,
+                    exit = %exit,
+                    max_batch_tokens = ?max_batch_tokens,
+                    "TEI process appears to have been OOM-killed"
+                );
+                crate::metrics::record_instance_oom(&instance_name);
+            }
+        }
+    }
+}
+
+/// Passes every event to each handler in turn
+pub struct CompositeEventHandler {
+    handlers: Vec<Arc<dyn HealthEventHandler>>,
+}
+
+impl CompositeEventHandler {
+    pub fn new(handlers: Vec<Arc<dyn HealthEventHandler>>) -> Self {
+        Self { handlers }
+    }
+}
+
+#[async_trait]
+impl HealthEventHandler for CompositeEventHandler {
+    async fn handle(&self, event: HealthEvent) {
+        for handler in &self.handlers {
+            handler.handle(event.clone()).await;
+        }
+    }
+}
+
+/// Body POSTed to the readiness webhook
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct ReadinessNotification {
+    pub instance: String,
+    pub status: InstanceStatus,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+/// POSTs a [`ReadinessNotification`] when an instance becomes Running or Failed
+///
+/// Delivery runs in the background, so a slow endpoint never delays health checks.
+/// Failed deliveries (errors or non-2xx responses) are retried, then logged and dropped.
+pub struct WebhookEventHandler {
+    client: reqwest::Client,
+    url: String,
+    retries: u32,
+    retry_delay: Duration,
+}
+
+impl WebhookEventHandler {
+    /// Handler for `url`, each attempt bounded by `timeout`
+    pub fn new(url: String, timeout: Duration) -> anyhow::Result<Self> {
+        let client = reqwest::Client::builder().timeout(timeout).build()?;
+        Ok(Self {
+            client,
+            url,
+            retries: 3,
+            retry_delay: Duration::from_secs(1),
+        })
+    }
+
+    /// Retry a failed delivery `retries` times, `delay` apart
+    pub fn with_retry(mut self, retries: u32, delay: Duration) -> Self {
+        self.retries = retries;
+        self.retry_delay = delay;
+        self
+    }
+
+    async fn deliver(
+        client: reqwest::Client,
+        url: String,
+        notification: ReadinessNotification,
+        retries: u32,
+        retry_delay: Duration,
+    ) {
+        for attempt in 0..=retries {
+            if attempt > 0 {
+                sleep(retry_delay).await;
+            }
+            let error = match client.post(&url).json(&notification).send().await {
+                Ok(response) if response.status().is_success() => return,
+                Ok(response) => format!("webhook returned {}", response.status()),
+                Err(e) => e.to_string(),
+            };
+            tracing::debug!(
+                instance = %notification.instance,
+                attempt,
+                error = %error,
+                "Readiness webhook delivery failed"
+            );
+            if attempt == retries {
+                tracing::error!(
+                    instance = %notification.instance,
+                    status = ?notification.status,
+                    error = %error,
+                    "Giving up on readiness webhook delivery"
+                );
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl HealthEventHandler for WebhookEventHandler {
+    async fn handle(&self, event: HealthEvent) {
+        let HealthEvent::StatusTransition {
+            instance_name, to, ..
+        } = event
+        else {
+            return;
+        };
+        if !matches!(to, InstanceStatus::Running | InstanceStatus::Failed) {
+            return;
         }
+        let notification = ReadinessNotification {
+            instance: instance_name,
+            status: to,
+            timestamp: chrono::Utc::now(),
+        };
+        tokio::spawn(Self::deliver(
+            self.client.clone(),
+            self.url.clone(),
+            notification,
+            self.retries,
+            self.retry_delay,
+        ));
     }
 }
 
@@ -258,8 +795,26 @@ impl HealthEventHandler for MetricsEventHandler {
 pub struct HealthMonitorConfig {
     pub check_interval: Duration,
     pub initial_delay: Duration,
-    pub max_failures_before_restart: u32,
+    /// Consecutive failures of any severity before restart
+    pub max_soft_failures: u32,
+    /// Consecutive hard failures (process not running) before restart
+    pub max_hard_failures: u32,
     pub auto_restart: bool,
+    /// Restart or quarantine instances that reach a threshold
+    pub failure_policy: FailurePolicy,
+    /// Factor applied to max_batch_tokens after an OOM kill (1.0 = unchanged)
+    pub oom_backoff_batch_factor: f64,
+    /// Spread each round's checks over this window (zero = check all at once)
+    pub check_jitter: Duration,
+    /// Seed for the per-instance jitter offsets
+    pub jitter_seed: u64,
+    /// Results kept in each instance's health history (0 = none)
+    pub history_size: usize,
+    /// Restarts allowed per instance within `restart_window` (0 = unlimited)
+    pub max_restarts_per_window: u32,
+    pub restart_window: Duration,
+    /// Restarts running at once across all instances (0 = unlimited)
+    pub max_concurrent_restarts: usize,
 }
 
 impl Default for HealthMonitorConfig {
@@ -267,16 +822,49 @@ impl Default for HealthMonitorConfig {
         Self {
             check_interval: Duration::from_secs(30),
             initial_delay: Duration::from_secs(60),
-            max_failures_before_restart: 3,
+            max_soft_failures: 6,
+            max_hard_failures: 3,
             auto_restart: true,
+            failure_policy: FailurePolicy::Restart,
+            oom_backoff_batch_factor: 1.0,
+            check_jitter: Duration::ZERO,
+            jitter_seed: random_seed(),
+            history_size: 10,
+            max_restarts_per_window: 0,
+            restart_window: Duration::from_secs(600),
+            max_concurrent_restarts: 1,
         }
     }
 }
 
+/// Seed that differs between manager processes without pulling in an RNG
+fn random_seed() -> u64 {
+    use std::hash::{BuildHasher, RandomState};
+    RandomState::new().hash_one(std::process::id())
+}
+
 impl HealthMonitorConfig {
     pub fn builder() -> HealthMonitorConfigBuilder {
         HealthMonitorConfigBuilder::default()
     }
+
+    /// Monitor settings taken from the manager config
+    pub fn from_manager_config(config: &ManagerConfig) -> Self {
+        Self::builder()
+            .check_interval(Duration::from_secs(config.health_check_interval_secs))
+            .initial_delay(Duration::from_secs(config.startup_timeout_secs))
+            .max_soft_failures(config.soft_failure_threshold())
+            .max_hard_failures(config.hard_failure_threshold())
+            .oom_backoff_batch_factor(config.oom_backoff_batch_factor)
+            .check_jitter(Duration::from_secs(config.health_check_jitter_secs))
+            .history_size(config.health_check_history_size)
+            .auto_restart(config.auto_restart)
+            .failure_policy(config.failure_policy)
+            .max_restarts_per_window(config.max_restarts_per_window)
+            .restart_window(Duration::from_secs(config.restart_window_secs))
+            .max_concurrent_restarts(config.max_concurrent_restarts)
+            .build()
+    }
 }
 
 /// Builder for HealthMonitorConfig
@@ -284,8 +872,17 @@ impl HealthMonitorConfig {
 pub struct HealthMonitorConfigBuilder {
     check_interval: Option<Duration>,
     initial_delay: Option<Duration>,
-    max_failures_before_restart: Option<u32>,
+    max_soft_failures: Option<u32>,
+    max_hard_failures: Option<u32>,
     auto_restart: Option<bool>,
+    failure_policy: Option<FailurePolicy>,
+    oom_backoff_batch_factor: Option<f64>,
+    check_jitter: Option<Duration>,
+    jitter_seed: Option<u64>,
+    history_size: Option<usize>,
+    max_restarts_per_window: Option<u32>,
+    restart_window: Option<Duration>,
+    max_concurrent_restarts: Option<usize>,
 }
 
 impl HealthMonitorConfigBuilder {
@@ -299,8 +896,20 @@ impl HealthMonitorConfigBuilder {
         self
     }
 
+    /// Use the same threshold for both soft and hard failures
     pub fn max_failures_before_restart(mut self, max: u32) -> Self {
-        self.max_failures_before_restart = Some(max);
+        self.max_soft_failures = Some(max);
+        self.max_hard_failures = Some(max);
+        self
+    }
+
+    pub fn max_soft_failures(mut self, max: u32) -> Self {
+        self.max_soft_failures = Some(max);
+        self
+    }
+
+    pub fn max_hard_failures(mut self, max: u32) -> Self {
+        self.max_hard_failures = Some(max);
         self
     }
 
@@ -309,15 +918,75 @@ impl HealthMonitorConfigBuilder {
         self
     }
 
+    pub fn failure_policy(mut self, policy: FailurePolicy) -> Self {
+        self.failure_policy = Some(policy);
+        self
+    }
+
+    pub fn oom_backoff_batch_factor(mut self, factor: f64) -> Self {
+        self.oom_backoff_batch_factor = Some(factor);
+        self
+    }
+
+    /// Offset each instance's check by up to `jitter` within a round
+    pub fn check_jitter(mut self, jitter: Duration) -> Self {
+        self.check_jitter = Some(jitter);
+        self
+    }
+
+    /// Fix the jitter seed (for reproducible offsets in tests)
+    pub fn jitter_seed(mut self, seed: u64) -> Self {
+        self.jitter_seed = Some(seed);
+        self
+    }
+
+    /// Keep the last `size` check results per instance
+    pub fn history_size(mut self, size: usize) -> Self {
+        self.history_size = Some(size);
+        self
+    }
+
+    /// Quarantine instead of restarting once an instance was restarted `max`
+    /// times within the restart window (0 = unlimited)
+    pub fn max_restarts_per_window(mut self, max: u32) -> Self {
+        self.max_restarts_per_window = Some(max);
+        self
+    }
+
+    /// Sliding window for `max_restarts_per_window`
+    pub fn restart_window(mut self, window: Duration) -> Self {
+        self.restart_window = Some(window);
+        self
+    }
+
+    /// Run at most `max` restarts at once across all instances (0 = unlimited)
+    pub fn max_concurrent_restarts(mut self, max: usize) -> Self {
+        self.max_concurrent_restarts = Some(max);
+        self
+    }
+
     pub fn build(self) -> HealthMonitorConfig {
         let defaults = HealthMonitorConfig::default();
         HealthMonitorConfig {
             check_interval: self.check_interval.unwrap_or(defaults.check_interval),
             initial_delay: self.initial_delay.unwrap_or(defaults.initial_delay),
-            max_failures_before_restart: self
-                .max_failures_before_restart
-                .unwrap_or(defaults.max_failures_before_restart),
+            max_soft_failures: self.max_soft_failures.unwrap_or(defaults.max_soft_failures),
+            max_hard_failures: self.max_hard_failures.unwrap_or(defaults.max_hard_failures),
             auto_restart: self.auto_restart.unwrap_or(defaults.auto_restart),
+            failure_policy: self.failure_policy.unwrap_or(defaults.failure_policy),
+            oom_backoff_batch_factor: self
+                .oom_backoff_batch_factor
+                .unwrap_or(defaults.oom_backoff_batch_factor),
+            check_jitter: self.check_jitter.unwrap_or(defaults.check_jitter),
+            jitter_seed: self.jitter_seed.unwrap_or(defaults.jitter_seed),
+            history_size: self.history_size.unwrap_or(defaults.history_size),
+            max_restarts_per_window: self
+                .max_restarts_per_window
+                .unwrap_or(defaults.max_restarts_per_window),
+            restart_window: self.restart_window.unwrap_or(defaults.restart_window),
+            max_concurrent_restarts: self
+                .max_concurrent_restarts
+                .unwrap_or(defaults.max_concurrent_restarts),
         }
     }
 }
@@ -327,17 +996,40 @@ impl HealthMonitorConfigBuilder {
 // ============================================================================
 
 /// Health monitor with configurable checks and auto-restart
+#[derive(Clone)]
 pub struct HealthMonitor {
     registry: Arc<Registry>,
     config: HealthMonitorConfig,
     health_checker: Arc<dyn HealthChecker>,
     restart_strategy: Arc<dyn RestartStrategy>,
     event_handler: Arc<dyn HealthEventHandler>,
+    metrics_probe: Option<Arc<dyn MetricsProbe>>,
+    dimension_probe: Option<Arc<dyn DimensionProbe>>,
     tei_binary_path: Arc<str>,
+    /// Bounds restarts across all instances (None = unlimited)
+    restart_permits: Option<Arc<Semaphore>>,
+    /// Background restarts, by instance name (see [`HealthMonitor::spawn_restart`])
+    restart_tasks: Arc<std::sync::Mutex<HashMap<String, JoinHandle<()>>>>,
+}
+
+/// Instances checked at once within a round
+///
+/// Checks of unreachable instances last until the checker's timeout, so
+/// checking one instance at a time would stall the others behind them.
+const MAX_CONCURRENT_CHECKS: usize = 32;
+
+/// How often a restarted instance is polled while its restart permit is held
+const RESTART_READY_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Semaphore for `max_concurrent_restarts` (0 = unlimited)
+fn restart_permits(max_concurrent_restarts: usize) -> Option<Arc<Semaphore>> {
+    (max_concurrent_restarts > 0).then(|| Arc::new(Semaphore::new(max_concurrent_restarts)))
 }
 
 impl HealthMonitor {
     /// Create a new health monitor with default implementations (backward compatible)
+    ///
+    /// Uses `max_failures_before_restart` for both soft and hard failures.
     pub fn new(
         registry: Arc<Registry>,
         check_interval_secs: u64,
@@ -349,16 +1041,22 @@ impl HealthMonitor {
         let config = HealthMonitorConfig {
             check_interval: Duration::from_secs(check_interval_secs),
             initial_delay: Duration::from_secs(initial_delay_secs),
-            max_failures_before_restart,
+            max_soft_failures: max_failures_before_restart,
+            max_hard_failures: max_failures_before_restart,
             auto_restart,
+            ..HealthMonitorConfig::default()
         };
 
         Self {
             registry,
+            restart_permits: restart_permits(config.max_concurrent_restarts),
+            restart_tasks: Arc::default(),
             config,
-            health_checker: Arc::new(GrpcHealthChecker),
+            health_checker: Arc::new(GrpcHealthChecker::default()),
             restart_strategy: Arc::new(DefaultRestartStrategy),
             event_handler: Arc::new(MetricsEventHandler),
+            metrics_probe: None,
+            dimension_probe: None,
             tei_binary_path: Arc::from(tei_binary_path),
         }
     }
@@ -375,9 +1073,17 @@ impl HealthMonitor {
             delay_secs = self.config.initial_delay.as_secs(),
             "Waiting before starting health checks"
         );
+        // Status changes are reported right away, even during the initial delay
+        tokio::spawn(
+            self.clone()
+                .forward_status_changes(self.registry.subscribe_events()),
+        );
         sleep(self.config.initial_delay).await;
 
         let mut ticker = interval(self.config.check_interval);
+        // A round outlasting the interval delays the next one instead of bursting
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        let mut events = self.registry.subscribe_events();
 
         tracing::info!(
             interval_secs = self.config.check_interval.as_secs(),
@@ -385,34 +1091,158 @@ impl HealthMonitor {
         );
 
         loop {
-            ticker.tick().await;
-            self.check_all_instances().await;
+            tokio::select! {
+                _ = ticker.tick() => self.check_all_instances().await,
+                // A process that exited is checked at once instead of at the next tick
+                // (the registry holds the sender, so the channel never closes)
+                event = events.recv() => {
+                    if let Ok(InstanceEvent::Exited { name, .. }) = event
+                        && let Some(instance) = self.registry.get(&name).await
+                    {
+                        self.check_single_instance(&instance).await;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Pass every status change in the registry to the event handler
+    ///
+    /// Covers transitions made outside the monitor (readiness waits, process
+    /// exits) as well as the monitor's own, so each is reported exactly once.
+    async fn forward_status_changes(
+        self: Arc<Self>,
+        mut events: tokio::sync::broadcast::Receiver<InstanceEvent>,
+    ) {
+        use tokio::sync::broadcast::error::RecvError;
+
+        loop {
+            match events.recv().await {
+                Ok(InstanceEvent::StatusChanged { name, from, to }) => {
+                    self.event_handler
+                        .handle(HealthEvent::StatusTransition {
+                            instance_name: name,
+                            from,
+                            to,
+                        })
+                        .await;
+                }
+                Ok(_) => {}
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!(skipped, "Health monitor lagged behind status changes");
+                }
+                Err(RecvError::Closed) => return,
+            }
         }
     }
 
     /// Check all instances (now public for testing)
+    ///
+    /// Up to [`MAX_CONCURRENT_CHECKS`] instances are checked at once, so a slow
+    /// check or restart doesn't hold up the others. With jitter configured, each
+    /// instance is instead checked at its own offset into the round.
     pub async fn check_all_instances(&self) {
         let instances = self.registry.list().await;
 
-        for instance in instances {
-            self.check_single_instance(&instance).await;
+        if self.config.check_jitter.is_zero() {
+            futures::StreamExt::for_each_concurrent(
+                futures::stream::iter(&instances),
+                MAX_CONCURRENT_CHECKS,
+                |instance| self.check_single_instance(instance),
+            )
+            .await;
+            return;
+        }
+
+        // Each check waits for its own offset, so a slow check doesn't delay the
+        // ones after it
+        let round_start = Instant::now();
+        let mut scheduled: Vec<_> = instances
+            .iter()
+            .map(|instance| (self.jitter_offset(&instance.config.name), instance))
+            .collect();
+        scheduled.sort_by_key(|(offset, _)| *offset);
+
+        futures::StreamExt::for_each_concurrent(
+            futures::stream::iter(scheduled),
+            MAX_CONCURRENT_CHECKS,
+            |(offset, instance)| async move {
+                sleep_until(round_start + offset).await;
+                self.check_single_instance(instance).await;
+            },
+        )
+        .await;
+    }
+
+    /// Offset of an instance's check within a round, in `[0, check_jitter)`
+    ///
+    /// Stable per instance, so each instance is still checked once per interval.
+    fn jitter_offset(&self, instance_name: &str) -> Duration {
+        use std::hash::{DefaultHasher, Hash, Hasher};
+
+        let jitter_nanos = self.config.check_jitter.as_nanos() as u64;
+        if jitter_nanos == 0 {
+            return Duration::ZERO;
         }
+        let mut hasher = DefaultHasher::new();
+        self.config.jitter_seed.hash(&mut hasher);
+        instance_name.hash(&mut hasher);
+        Duration::from_nanos(hasher.finish() % jitter_nanos)
     }
 
     /// Check a single instance (now public for testing)
-    pub async fn check_single_instance(&self, instance: &TeiInstance) {
+    pub async fn check_single_instance(&self, instance: &Arc<TeiInstance>) {
+        // Instances stopped on purpose (API or idle reaper) are not health checked,
+        // otherwise they would be restarted behind the operator's back. Nor are
+        // instances being restarted, which would only be restarted again.
+        let status = *instance.status.read().await;
+        if instance.is_stopped_on_purpose()
+            || matches!(
+                status,
+                InstanceStatus::Stopping | InstanceStatus::Quarantined
+            )
+            || self.restart_in_progress(&instance.config.name)
+        {
+            return;
+        }
+
         self.event_handler
             .handle(HealthEvent::CheckStarted {
                 instance_name: instance.config.name.clone(),
             })
             .await;
 
-        let result = self.health_checker.check(instance).await;
+        let started = Instant::now();
+        let mut result = self.health_checker.check(instance).await;
+        if result.healthy
+            && let Some(reason) = self.check_dimension(instance).await
+        {
+            result = HealthCheckResult::soft_failure(reason);
+        }
+
+        self.event_handler
+            .handle(HealthEvent::CheckCompleted {
+                instance_name: instance.config.name.clone(),
+                outcome: result.outcome(),
+                duration: started.elapsed(),
+            })
+            .await;
+
+        instance.stats.write().await.record_health_check(
+            HealthCheckRecord {
+                checked_at: chrono::Utc::now(),
+                healthy: result.healthy,
+                reason: result.reason.clone(),
+            },
+            self.config.history_size,
+        );
 
         if result.healthy {
             self.handle_success(instance).await;
+            self.probe_metrics(instance).await;
         } else {
-            self.handle_failure(instance, result.reason.unwrap_or_default())
+            let severity = result.severity.unwrap_or(FailureSeverity::Hard);
+            self.handle_failure(instance, result.reason.unwrap_or_default(), severity)
                 .await;
         }
     }
@@ -421,7 +1251,9 @@ impl HealthMonitor {
         // Reset failure count on success
         let mut stats = instance.stats.write().await;
         stats.health_check_failures = 0;
+        stats.health_check_hard_failures = 0;
         stats.last_health_check = Some(chrono::Utc::now());
+        stats.last_health_error = None;
 
         // Update status to Running if it was Starting
         let mut status = instance.status.write().await;
@@ -429,13 +1261,10 @@ impl HealthMonitor {
 
         if old_status == InstanceStatus::Starting {
             *status = InstanceStatus::Running;
+            drop(status);
+            instance.set_restarting(false);
 
-            self.event_handler
-                .handle(HealthEvent::StatusTransition {
-                    instance_name: instance.config.name.clone(),
-                    from: old_status,
-                    to: InstanceStatus::Running,
-                })
+            self.report_transition(instance, old_status, InstanceStatus::Running)
                 .await;
         }
 
@@ -446,7 +1275,65 @@ impl HealthMonitor {
             .await;
     }
 
-    async fn handle_failure(&self, instance: &TeiInstance, reason: String) {
+    /// Compare the instance's embedding dimension with the first one it reported
+    ///
+    /// Returns the failure reason on a mismatch. Probe errors are only logged:
+    /// reachability is the health checker's job, and models that can't embed
+    /// (rerankers) would otherwise never be healthy.
+    async fn check_dimension(&self, instance: &TeiInstance) -> Option<String> {
+        let probe = self.dimension_probe.as_ref()?;
+        let dimension = match probe.dimension(instance.config.port).await {
+            Ok(dimension) => dimension,
+            Err(e) => {
+                tracing::debug!(
+                    instance = %instance.config.name,
+                    error = %e,
+                    "Dimension probe failed"
+                );
+                return None;
+            }
+        };
+
+        let mut stats = instance.stats.write().await;
+        let expected = *stats.embedding_dimension.get_or_insert(dimension);
+        (dimension != expected).then(|| {
+            format!(
+                "Embedding dimension changed from {} to {}",
+                expected, dimension
+            )
+        })
+    }
+
+    /// Secondary probe of the Prometheus endpoint; the outcome never changes health
+    async fn probe_metrics(&self, instance: &TeiInstance) {
+        let Some(probe) = &self.metrics_probe else {
+            return;
+        };
+        let Some(port) = instance.config.prometheus_port.filter(|p| *p != 0) else {
+            return;
+        };
+
+        let result = probe.probe(port).await;
+        instance.stats.write().await.metrics_reachable = Some(result.is_ok());
+
+        self.event_handler
+            .handle(HealthEvent::MetricsProbed {
+                instance_name: instance.config.name.clone(),
+                port,
+                error: result.err(),
+            })
+            .await;
+    }
+
+    async fn handle_failure(
+        &self,
+        instance: &Arc<TeiInstance>,
+        reason: String,
+        severity: FailureSeverity,
+    ) {
+        instance.stats.write().await.last_health_error = Some(reason.clone());
+        self.record_exit(instance).await;
+
         // Check if instance is still starting - don't count failures or restart during startup
         // This prevents premature failure marking while the instance is loading model weights
         let current_status = *instance.status.read().await;
@@ -463,17 +1350,69 @@ impl HealthMonitor {
 
         let mut stats = instance.stats.write().await;
         stats.health_check_failures += 1;
+        match severity {
+            FailureSeverity::Hard => stats.health_check_hard_failures += 1,
+            // A soft failure means the process is back, so the hard streak is broken
+            FailureSeverity::Soft => stats.health_check_hard_failures = 0,
+        }
         let failures = stats.health_check_failures;
+        let hard_failures = stats.health_check_hard_failures;
 
         self.event_handler
             .handle(HealthEvent::CheckFailed {
                 instance_name: instance.config.name.clone(),
                 consecutive_failures: failures,
+                severity,
                 reason: reason.clone(),
             })
             .await;
 
-        if self.config.auto_restart && failures >= self.config.max_failures_before_restart {
+        let threshold_reached = failures >= self.config.max_soft_failures
+            || hard_failures >= self.config.max_hard_failures;
+
+        let quarantine = self.config.failure_policy == FailurePolicy::Quarantine;
+        if threshold_reached && (quarantine || self.config.auto_restart) {
+            // Operators stopping or fixing instances by hand don't want the monitor
+            // to fight them; failures are still recorded above
+            let maintenance = if self.registry.maintenance_enabled() {
+                Some("global maintenance mode")
+            } else if instance.in_maintenance() {
+                Some("instance maintenance mode")
+            } else {
+                None
+            };
+            if let Some(reason) = maintenance {
+                self.event_handler
+                    .handle(HealthEvent::RestartSkipped {
+                        instance_name: instance.config.name.clone(),
+                        failure_count: failures,
+                        reason: reason.to_string(),
+                    })
+                    .await;
+                return;
+            }
+
+            if quarantine {
+                drop(stats);
+                self.quarantine(instance, failures).await;
+                return;
+            }
+
+            // An absolute bound on top of the thresholds: a crash loop is surfaced
+            // instead of being hidden behind endless restarts
+            if let Some(restarts) = self.take_restart_slot(&mut stats) {
+                drop(stats);
+                self.event_handler
+                    .handle(HealthEvent::RestartLimitReached {
+                        instance_name: instance.config.name.clone(),
+                        restarts,
+                        window: self.config.restart_window,
+                    })
+                    .await;
+                self.quarantine(instance, failures).await;
+                return;
+            }
+
             self.event_handler
                 .handle(HealthEvent::RestartTriggered {
                     instance_name: instance.config.name.clone(),
@@ -483,30 +1422,204 @@ impl HealthMonitor {
 
             drop(stats); // Release lock before restart
 
-            match self
-                .restart_strategy
-                .restart(instance, &self.tei_binary_path)
-                .await
-            {
-                Ok(()) => {
-                    self.event_handler
-                        .handle(HealthEvent::RestartSucceeded {
-                            instance_name: instance.config.name.clone(),
-                        })
-                        .await;
-                }
-                Err(e) => {
-                    self.event_handler
-                        .handle(HealthEvent::RestartFailed {
-                            instance_name: instance.config.name.clone(),
-                            error: e.to_string(),
-                        })
-                        .await;
+            // Routed requests get a clear "restarting" error until it is healthy again
+            instance.set_restarting(true);
+            self.spawn_restart(instance);
+        }
+    }
+
+    /// Restart `instance` in the background
+    ///
+    /// The restart waits for its permit and then for the model to load, which
+    /// can take minutes; checks (of this round, later rounds and exits) go on
+    /// meanwhile. Until the task ends, the instance is not checked.
+    fn spawn_restart(&self, instance: &Arc<TeiInstance>) {
+        let name = instance.config.name.clone();
+        let task = {
+            let monitor = self.clone();
+            let instance = instance.clone();
+            tokio::spawn(async move { monitor.restart(&instance).await })
+        };
+
+        let mut tasks = self.restart_tasks.lock().unwrap();
+        tasks.retain(|_, task| !task.is_finished());
+        tasks.insert(name, task);
+    }
 
-                    *instance.status.write().await = InstanceStatus::Failed;
+    /// Whether a restart of `instance_name` is still running
+    fn restart_in_progress(&self, instance_name: &str) -> bool {
+        self.restart_tasks
+            .lock()
+            .unwrap()
+            .get(instance_name)
+            .is_some_and(|task| !task.is_finished())
+    }
+
+    /// Wait for every background restart started so far
+    #[cfg(test)]
+    pub(crate) async fn wait_for_restarts(&self) {
+        let tasks: Vec<_> = self.restart_tasks.lock().unwrap().drain().collect();
+        for (_, task) in tasks {
+            task.await.unwrap();
+        }
+    }
+
+    /// Restart `instance` under a restart permit, held until it serves again
+    async fn restart(&self, instance: &TeiInstance) {
+        let permit = self.acquire_restart_permit(instance).await;
+        match self
+            .restart_strategy
+            .restart(instance, &self.tei_binary_path)
+            .await
+        {
+            Ok(()) => {
+                self.event_handler
+                    .handle(HealthEvent::RestartSucceeded {
+                        instance_name: instance.config.name.clone(),
+                    })
+                    .await;
+                if permit.is_some() {
+                    self.wait_until_restarted(instance).await;
                 }
             }
+            Err(e) => {
+                self.event_handler
+                    .handle(HealthEvent::RestartFailed {
+                        instance_name: instance.config.name.clone(),
+                        error: e.to_string(),
+                    })
+                    .await;
+
+                instance.set_restarting(false);
+                instance.set_status(InstanceStatus::Failed).await;
+            }
+        }
+    }
+
+    /// Poll a restarted instance until it serves again, for up to its startup timeout
+    ///
+    /// `restart` returns once the process is spawned, before the model has
+    /// loaded. The caller holds its restart permit through this wait, so
+    /// correlated failures don't reload every model at once. An instance still
+    /// starting at the deadline is left to the regular checks.
+    async fn wait_until_restarted(&self, instance: &TeiInstance) {
+        let deadline = Instant::now() + instance.config.startup_timeout(self.config.initial_delay);
+        while *instance.status.read().await == InstanceStatus::Starting {
+            if self.health_checker.check(instance).await.healthy {
+                self.handle_success(instance).await;
+                return;
+            }
+            if Instant::now() >= deadline {
+                return;
+            }
+            sleep(RESTART_READY_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Wait for a slot among `max_concurrent_restarts`, held until dropped
+    async fn acquire_restart_permit(
+        &self,
+        instance: &TeiInstance,
+    ) -> Option<tokio::sync::OwnedSemaphorePermit> {
+        let permits = self.restart_permits.clone()?;
+        if let Ok(permit) = permits.clone().try_acquire_owned() {
+            return Some(permit);
+        }
+        tracing::info!(
+            instance = %instance.config.name,
+            max_concurrent_restarts = self.config.max_concurrent_restarts,
+            "Restart queued until another restart finishes"
+        );
+        // The semaphore is never closed
+        permits.acquire_owned().await.ok()
+    }
+
+    /// Record a restart in the sliding window, unless the window is already full
+    ///
+    /// Returns the number of restarts in the window when it is full.
+    fn take_restart_slot(&self, stats: &mut InstanceStats) -> Option<u32> {
+        let max = self.config.max_restarts_per_window;
+        if max == 0 {
+            return None;
+        }
+        let now = Instant::now();
+        let window = self.config.restart_window;
+        while stats
+            .recent_restarts
+            .front()
+            .is_some_and(|at| now.duration_since(*at) >= window)
+        {
+            stats.recent_restarts.pop_front();
+        }
+        if stats.recent_restarts.len() >= max as usize {
+            return Some(stats.recent_restarts.len() as u32);
+        }
+        stats.recent_restarts.push_back(now);
+        None
+    }
+
+    /// Stop `instance` and keep it quarantined until it is cleared via the API
+    ///
+    /// If it cannot be stopped it is marked Failed instead, so it stays checked
+    /// and the quarantine is tried again on the next failure.
+    async fn quarantine(&self, instance: &TeiInstance, failures: u32) {
+        let name = &instance.config.name;
+        // The instance announces its own status changes, Quarantined included
+        if let Err(e) = instance.quarantine().await {
+            tracing::error!(instance = %name, error = %e, "Failed to stop instance for quarantine");
+            instance.set_status(InstanceStatus::Failed).await;
+            return;
+        }
+        self.event_handler
+            .handle(HealthEvent::Quarantined {
+                instance_name: name.clone(),
+                failure_count: failures,
+            })
+            .await;
+    }
+
+    /// Record how a dead process exited, reporting an OOM kill once per process
+    ///
+    /// Runs before the startup check, since loading weights is when OOM kills
+    /// are most likely.
+    async fn record_exit(&self, instance: &TeiInstance) {
+        let Some(exit) = instance.exit_status().await else {
+            return;
+        };
+        let mut stats = instance.stats.write().await;
+        if stats.last_exit.is_some() {
+            return;
         }
+        stats.last_exit = Some(exit);
+        if !exit.is_oom_kill() {
+            return;
+        }
+        stats.oom_kills += 1;
+        drop(stats);
+
+        let factor = self.config.oom_backoff_batch_factor;
+        let max_batch_tokens = (factor < 1.0).then(|| instance.reduce_max_batch_tokens(factor));
+        self.event_handler
+            .handle(HealthEvent::OomKilled {
+                instance_name: instance.config.name.clone(),
+                exit,
+                max_batch_tokens,
+            })
+            .await;
+    }
+
+    /// Report a status change to registry subscribers
+    ///
+    /// The monitor loop passes it on to the event handler along with status
+    /// changes made elsewhere (readiness waits, process exits).
+    async fn report_transition(
+        &self,
+        instance: &TeiInstance,
+        from: InstanceStatus,
+        to: InstanceStatus,
+    ) {
+        self.registry
+            .notify_status_change(&instance.config.name, from, to);
     }
 }
 
@@ -520,6 +1633,8 @@ pub struct HealthMonitorBuilder {
     health_checker: Option<Arc<dyn HealthChecker>>,
     restart_strategy: Option<Arc<dyn RestartStrategy>>,
     event_handler: Option<Arc<dyn HealthEventHandler>>,
+    metrics_probe: Option<Arc<dyn MetricsProbe>>,
+    dimension_probe: Option<Arc<dyn DimensionProbe>>,
 }
 
 impl HealthMonitorBuilder {
@@ -530,6 +1645,8 @@ impl HealthMonitorBuilder {
             health_checker: None,
             restart_strategy: None,
             event_handler: None,
+            metrics_probe: None,
+            dimension_probe: None,
         }
     }
 
@@ -553,19 +1670,36 @@ impl HealthMonitorBuilder {
         self
     }
 
+    /// Also probe each healthy instance's Prometheus port (off unless set)
+    pub fn metrics_probe(mut self, probe: Arc<dyn MetricsProbe>) -> Self {
+        self.metrics_probe = Some(probe);
+        self
+    }
+
+    /// Also check each healthy instance's embedding dimension (off unless set)
+    pub fn dimension_probe(mut self, probe: Arc<dyn DimensionProbe>) -> Self {
+        self.dimension_probe = Some(probe);
+        self
+    }
+
     pub fn build(self, tei_binary_path: String) -> HealthMonitor {
+        let config = self.config.unwrap_or_default();
         HealthMonitor {
             registry: self.registry,
-            config: self.config.unwrap_or_default(),
+            restart_permits: restart_permits(config.max_concurrent_restarts),
+            restart_tasks: Arc::default(),
+            config,
             health_checker: self
                 .health_checker
-                .unwrap_or_else(|| Arc::new(GrpcHealthChecker)),
+                .unwrap_or_else(|| Arc::new(GrpcHealthChecker::default())),
             restart_strategy: self
                 .restart_strategy
                 .unwrap_or_else(|| Arc::new(DefaultRestartStrategy)),
             event_handler: self
                 .event_handler
                 .unwrap_or_else(|| Arc::new(MetricsEventHandler)),
+            metrics_probe: self.metrics_probe,
+            dimension_probe: self.dimension_probe,
             tei_binary_path: Arc::from(tei_binary_path),
         }
     }
@@ -586,6 +1720,8 @@ pub mod mocks {
         should_fail: AtomicBool,
         check_count: AtomicU32,
         failure_reason: std::sync::RwLock<String>,
+        failure_severity: std::sync::RwLock<FailureSeverity>,
+        delay: std::sync::RwLock<Duration>,
     }
 
     impl Default for MockHealthChecker {
@@ -600,16 +1736,28 @@ pub mod mocks {
                 should_fail: AtomicBool::new(false),
                 check_count: AtomicU32::new(0),
                 failure_reason: std::sync::RwLock::new("Mock failure".to_string()),
+                failure_severity: std::sync::RwLock::new(FailureSeverity::Hard),
+                delay: std::sync::RwLock::new(Duration::ZERO),
             }
         }
 
+        /// Make every check take this long before answering
+        pub fn set_delay(&self, delay: Duration) {
+            *self.delay.write().unwrap() = delay;
+        }
+
         pub fn set_healthy(&self) {
             self.should_fail.store(false, Ordering::SeqCst);
         }
 
         pub fn set_unhealthy(&self, reason: String) {
+            self.set_unhealthy_with(reason, FailureSeverity::Hard);
+        }
+
+        pub fn set_unhealthy_with(&self, reason: String, severity: FailureSeverity) {
             self.should_fail.store(true, Ordering::SeqCst);
             *self.failure_reason.write().unwrap() = reason;
+            *self.failure_severity.write().unwrap() = severity;
         }
 
         pub fn check_count(&self) -> u32 {
@@ -622,20 +1770,60 @@ pub mod mocks {
         async fn check(&self, _instance: &TeiInstance) -> HealthCheckResult {
             self.check_count.fetch_add(1, Ordering::SeqCst);
 
+            let delay = *self.delay.read().unwrap();
+            if !delay.is_zero() {
+                sleep(delay).await;
+            }
+
             if self.should_fail.load(Ordering::SeqCst) {
                 let reason = self.failure_reason.read().unwrap().clone();
-                HealthCheckResult::unhealthy(reason)
+                let severity = *self.failure_severity.read().unwrap();
+                HealthCheckResult::unhealthy_with(reason, severity)
             } else {
                 HealthCheckResult::healthy()
             }
         }
     }
 
+    /// Health checker that fails with severities taken from a repeating sequence
+    pub struct AlternatingHealthChecker {
+        severities: Vec<FailureSeverity>,
+        check_count: AtomicU32,
+    }
+
+    impl AlternatingHealthChecker {
+        pub fn new(severities: Vec<FailureSeverity>) -> Self {
+            assert!(!severities.is_empty(), "severities cannot be empty");
+            Self {
+                severities,
+                check_count: AtomicU32::new(0),
+            }
+        }
+
+        pub fn check_count(&self) -> u32 {
+            self.check_count.load(Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait]
+    impl HealthChecker for AlternatingHealthChecker {
+        async fn check(&self, _instance: &TeiInstance) -> HealthCheckResult {
+            let n = self.check_count.fetch_add(1, Ordering::SeqCst) as usize;
+            let severity = self.severities[n % self.severities.len()];
+            HealthCheckResult::unhealthy_with(format!("Mock {} failure", severity), severity)
+        }
+    }
+
     /// Mock restart strategy for testing
     pub struct MockRestartStrategy {
         should_fail: AtomicBool,
         restart_count: AtomicU32,
         last_restarted_instance: Mutex<Option<String>>,
+        saw_restarting: AtomicBool,
+        delay: std::sync::RwLock<Duration>,
+        running: AtomicU32,
+        max_running: AtomicU32,
+        marks_starting: AtomicBool,
     }
 
     impl Default for MockRestartStrategy {
@@ -650,9 +1838,29 @@ pub mod mocks {
                 should_fail: AtomicBool::new(false),
                 restart_count: AtomicU32::new(0),
                 last_restarted_instance: Mutex::new(None),
+                saw_restarting: AtomicBool::new(false),
+                delay: std::sync::RwLock::new(Duration::ZERO),
+                running: AtomicU32::new(0),
+                max_running: AtomicU32::new(0),
+                marks_starting: AtomicBool::new(false),
             }
         }
 
+        /// Leave restarted instances `Starting`, as a real restart does
+        pub fn set_marks_starting(&self, marks_starting: bool) {
+            self.marks_starting.store(marks_starting, Ordering::SeqCst);
+        }
+
+        /// Make every restart take this long
+        pub fn set_delay(&self, delay: Duration) {
+            *self.delay.write().unwrap() = delay;
+        }
+
+        /// Most restarts that were in progress at the same time
+        pub fn max_concurrent_restarts(&self) -> u32 {
+            self.max_running.load(Ordering::SeqCst)
+        }
+
         pub fn set_should_fail(&self, should_fail: bool) {
             self.should_fail.store(should_fail, Ordering::SeqCst);
         }
@@ -664,6 +1872,11 @@ pub mod mocks {
         pub async fn last_restarted_instance(&self) -> Option<String> {
             self.last_restarted_instance.lock().await.clone()
         }
+
+        /// Whether the last restarted instance was flagged as restarting meanwhile
+        pub fn saw_restarting(&self) -> bool {
+            self.saw_restarting.load(Ordering::SeqCst)
+        }
     }
 
     #[async_trait]
@@ -675,15 +1888,67 @@ pub mod mocks {
         ) -> anyhow::Result<()> {
             self.restart_count.fetch_add(1, Ordering::SeqCst);
             *self.last_restarted_instance.lock().await = Some(instance.config.name.clone());
+            self.saw_restarting
+                .store(instance.is_restarting(), Ordering::SeqCst);
+
+            let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_running.fetch_max(running, Ordering::SeqCst);
+            let delay = *self.delay.read().unwrap();
+            sleep(delay).await;
+            self.running.fetch_sub(1, Ordering::SeqCst);
 
             if self.should_fail.load(Ordering::SeqCst) {
                 anyhow::bail!("Mock restart failed");
             }
 
+            if self.marks_starting.load(Ordering::SeqCst) {
+                *instance.status.write().await = InstanceStatus::Starting;
+            }
             Ok(())
         }
     }
 
+    /// Mock Prometheus endpoint probe for testing
+    pub struct MockMetricsProbe {
+        reachable: AtomicBool,
+        probe_count: AtomicU32,
+    }
+
+    impl Default for MockMetricsProbe {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl MockMetricsProbe {
+        pub fn new() -> Self {
+            Self {
+                reachable: AtomicBool::new(true),
+                probe_count: AtomicU32::new(0),
+            }
+        }
+
+        pub fn set_reachable(&self, reachable: bool) {
+            self.reachable.store(reachable, Ordering::SeqCst);
+        }
+
+        pub fn probe_count(&self) -> u32 {
+            self.probe_count.load(Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait]
+    impl MetricsProbe for MockMetricsProbe {
+        async fn probe(&self, _port: u16) -> Result<(), String> {
+            self.probe_count.fetch_add(1, Ordering::SeqCst);
+            if self.reachable.load(Ordering::SeqCst) {
+                Ok(())
+            } else {
+                Err("Mock metrics failure".to_string())
+            }
+        }
+    }
+
     /// Recording event handler for testing
     pub struct RecordingEventHandler {
         events: Mutex<Vec<HealthEvent>>,
@@ -755,7 +2020,8 @@ mod tests {
 
         assert_eq!(monitor.config.check_interval.as_secs(), 30);
         assert_eq!(monitor.config.initial_delay.as_secs(), 60);
-        assert_eq!(monitor.config.max_failures_before_restart, 3);
+        assert_eq!(monitor.config.max_soft_failures, 3);
+        assert_eq!(monitor.config.max_hard_failures, 3);
         assert!(monitor.config.auto_restart);
     }
 
@@ -780,7 +2046,8 @@ mod tests {
 
         assert_eq!(monitor.config.check_interval.as_secs(), 45);
         assert_eq!(monitor.config.initial_delay.as_secs(), 90);
-        assert_eq!(monitor.config.max_failures_before_restart, 5);
+        assert_eq!(monitor.config.max_soft_failures, 5);
+        assert_eq!(monitor.config.max_hard_failures, 5);
         assert!(!monitor.config.auto_restart);
     }
 
@@ -887,6 +2154,7 @@ mod tests {
             .handle(HealthEvent::CheckFailed {
                 instance_name: "test".to_string(),
                 consecutive_failures: 1,
+                severity: FailureSeverity::Soft,
                 reason: "timeout".to_string(),
             })
             .await;
@@ -925,7 +2193,7 @@ mod tests {
         };
 
         let instance = registry.add(config).await.unwrap();
-
+        *instance.status.write().await = InstanceStatus::Running;
         let checker = Arc::new(MockHealthChecker::new());
         let restart = Arc::new(MockRestartStrategy::new());
         let events = Arc::new(RecordingEventHandler::new());
@@ -958,6 +2226,7 @@ mod tests {
         for _ in 0..3 {
             monitor.check_single_instance(&instance).await;
         }
+        monitor.wait_for_restarts().await;
 
         assert_eq!(checker.check_count(), 4); // 1 success + 3 failures
         assert_eq!(restart.restart_count(), 1);
@@ -973,6 +2242,143 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_health_history_keeps_recent_checks_in_order() {
+        use mocks::MockHealthChecker;
+
+        let registry = Arc::new(Registry::new(
+            None,
+            "text-embeddings-router".to_string(),
+            8080,
+            8180,
+        ));
+        let instance = registry
+            .add(InstanceConfig {
+                name: "flappy".to_string(),
+                model_id: "model".to_string(),
+                port: 8080,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        *instance.status.write().await = InstanceStatus::Running;
+
+        let checker = Arc::new(MockHealthChecker::new());
+        let monitor = HealthMonitor::builder(registry)
+            .config(
+                HealthMonitorConfig::builder()
+                    .auto_restart(false)
+                    .history_size(4)
+                    .build(),
+            )
+            .health_checker(checker.clone())
+            .build("mock".to_string());
+
+        // Times out every 3rd check
+        for round in 1..=6 {
+            if round % 3 == 0 {
+                checker.set_unhealthy(format!("timeout #{}", round));
+            } else {
+                checker.set_healthy();
+            }
+            monitor.check_single_instance(&instance).await;
+        }
+
+        let stats = instance.stats.read().await;
+        let history: Vec<_> = stats
+            .health_history
+            .iter()
+            .map(|r| (r.healthy, r.reason.as_deref()))
+            .collect();
+        // Only the last 4 of 6 checks are kept, oldest first
+        assert_eq!(
+            history,
+            vec![
+                (false, Some("timeout #3")),
+                (true, None),
+                (true, None),
+                (false, Some("timeout #6")),
+            ]
+        );
+        assert!(
+            stats
+                .health_history
+                .iter()
+                .zip(stats.health_history.iter().skip(1))
+                .all(|(a, b)| a.checked_at <= b.checked_at)
+        );
+    }
+
+    #[test]
+    fn test_health_history_disabled_with_zero_size() {
+        let mut stats = crate::instance::InstanceStats::default();
+        stats.record_health_check(
+            HealthCheckRecord {
+                checked_at: chrono::Utc::now(),
+                healthy: true,
+                reason: None,
+            },
+            0,
+        );
+        assert!(stats.health_history.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_restarting_flag_held_until_healthy() {
+        use mocks::{MockHealthChecker, MockRestartStrategy};
+
+        let registry = Arc::new(Registry::new(
+            None,
+            "text-embeddings-router".to_string(),
+            8080,
+            8180,
+        ));
+        let instance = registry
+            .add(InstanceConfig {
+                name: "flagged".to_string(),
+                model_id: "model".to_string(),
+                port: 8080,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        *instance.status.write().await = InstanceStatus::Running;
+
+        let checker = Arc::new(MockHealthChecker::new());
+        let restart = Arc::new(MockRestartStrategy::new());
+        let monitor = HealthMonitor::builder(registry)
+            .config(
+                HealthMonitorConfig::builder()
+                    .max_failures_before_restart(1)
+                    .build(),
+            )
+            .health_checker(checker.clone())
+            .restart_strategy(restart.clone())
+            .build("mock".to_string());
+
+        checker.set_unhealthy("Connection lost".to_string());
+        monitor.check_single_instance(&instance).await;
+        monitor.wait_for_restarts().await;
+        assert!(restart.saw_restarting());
+        assert!(instance.is_restarting());
+
+        // Still flagged while the new process starts, cleared once it is healthy
+        *instance.status.write().await = InstanceStatus::Starting;
+        checker.set_healthy();
+        monitor.check_single_instance(&instance).await;
+        assert!(!instance.is_restarting());
+        assert_eq!(*instance.status.read().await, InstanceStatus::Running);
+
+        // A failed restart clears the flag as well
+        restart.set_should_fail(true);
+        checker.set_unhealthy("Connection lost".to_string());
+        monitor.check_single_instance(&instance).await;
+        monitor.wait_for_restarts().await;
+        assert!(restart.saw_restarting());
+        assert!(!instance.is_restarting());
+        assert_eq!(*instance.status.read().await, InstanceStatus::Failed);
+    }
+
     #[tokio::test]
     async fn test_auto_restart_disabled() {
         use mocks::{MockHealthChecker, MockRestartStrategy, RecordingEventHandler};
@@ -996,7 +2402,7 @@ mod tests {
         };
 
         let instance = registry.add(config).await.unwrap();
-
+        *instance.status.write().await = InstanceStatus::Running;
         let checker = Arc::new(MockHealthChecker::new());
         let restart = Arc::new(MockRestartStrategy::new());
         let events = Arc::new(RecordingEventHandler::new());
@@ -1030,7 +2436,7 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_recovery_after_failure() {
+    async fn test_quarantine_policy_stops_instance_until_cleared() {
         use mocks::{MockHealthChecker, MockRestartStrategy, RecordingEventHandler};
 
         let registry = Arc::new(Registry::new(
@@ -1039,29 +2445,1008 @@ mod tests {
             8080,
             8180,
         ));
-        let config = InstanceConfig {
-            name: "recovery-test".to_string(),
-            model_id: "model".to_string(),
-            port: 8080,
-            max_batch_tokens: 1024,
-            max_concurrent_requests: 10,
-            pooling: None,
-            gpu_id: None,
-            prometheus_port: None,
-            ..Default::default()
-        };
-
-        let instance = registry.add(config).await.unwrap();
+        let instance = registry
+            .add(InstanceConfig {
+                name: "poison".to_string(),
+                model_id: "model".to_string(),
+                port: 8080,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        *instance.status.write().await = InstanceStatus::Running;
 
         let checker = Arc::new(MockHealthChecker::new());
         let restart = Arc::new(MockRestartStrategy::new());
         let events = Arc::new(RecordingEventHandler::new());
+        checker.set_unhealthy("fail".to_string());
+
+        let monitor = HealthMonitor::builder(registry.clone())
+            .config(
+                HealthMonitorConfig::builder()
+                    .max_failures_before_restart(2)
+                    .failure_policy(FailurePolicy::Quarantine)
+                    .build(),
+            )
+            .health_checker(checker.clone())
+            .restart_strategy(restart.clone())
+            .event_handler(events.clone())
+            .build("mock".to_string());
+        let mut registry_events = registry.subscribe_events();
+
+        monitor.check_single_instance(&instance).await;
+        assert_eq!(*instance.status.read().await, InstanceStatus::Running);
+        monitor.check_single_instance(&instance).await;
+        assert_eq!(*instance.status.read().await, InstanceStatus::Quarantined);
+        assert!(
+            events
+                .has_event_type(|e| matches!(
+                    e,
+                    HealthEvent::Quarantined {
+                        failure_count: 2,
+                        ..
+                    }
+                ))
+                .await
+        );
+        // Stopped first, then marked, each step announced once
+        for (expected_from, expected_to) in [
+            (InstanceStatus::Running, InstanceStatus::Stopping),
+            (InstanceStatus::Stopping, InstanceStatus::Stopped),
+            (InstanceStatus::Stopped, InstanceStatus::Quarantined),
+        ] {
+            assert!(matches!(
+                registry_events.try_recv().unwrap(),
+                InstanceEvent::StatusChanged { from, to, .. }
+                    if from == expected_from && to == expected_to
+            ));
+        }
+        assert!(registry_events.try_recv().is_err());
+
+        // Neither checked, restarted nor started while quarantined
+        for _ in 0..3 {
+            monitor.check_single_instance(&instance).await;
+        }
+        assert_eq!(checker.check_count(), 2);
+        assert_eq!(restart.restart_count(), 0);
+        let err = instance.start("mock").await.unwrap_err();
+        assert!(err.to_string().contains("quarantined"), "{}", err);
+        instance.stop().await.unwrap();
+        assert_eq!(*instance.status.read().await, InstanceStatus::Quarantined);
+
+        // Manual recovery leaves it stopped with a clean failure streak
+        instance.unquarantine().await.unwrap();
+        assert_eq!(*instance.status.read().await, InstanceStatus::Stopped);
+        assert_eq!(instance.stats.read().await.health_check_failures, 0);
+        assert!(instance.unquarantine().await.is_err());
+    }
+
+    /// Restarts of `count` instances failing together, with at most `max` at once
+    /// Unhealthy while running; once restarted, ready after a 10s model load
+    struct SlowLoadChecker {
+        load_started: std::sync::Mutex<std::collections::HashMap<String, Instant>>,
+        loading: std::sync::atomic::AtomicUsize,
+        max_loading: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl HealthChecker for SlowLoadChecker {
+        async fn check(&self, instance: &TeiInstance) -> HealthCheckResult {
+            use std::sync::atomic::Ordering;
+
+            if *instance.status.read().await != InstanceStatus::Starting {
+                return HealthCheckResult::unhealthy("dependency down".to_string());
+            }
+            let mut started = self.load_started.lock().unwrap();
+            let since = *started
+                .entry(instance.config.name.clone())
+                .or_insert_with(|| {
+                    let loading = self.loading.fetch_add(1, Ordering::SeqCst) + 1;
+                    self.max_loading.fetch_max(loading, Ordering::SeqCst);
+                    Instant::now()
+                });
+            if since.elapsed() < Duration::from_secs(10) {
+                return HealthCheckResult::unhealthy("loading".to_string());
+            }
+            started.remove(&instance.config.name);
+            self.loading.fetch_sub(1, Ordering::SeqCst);
+            HealthCheckResult::healthy()
+        }
+    }
+
+    /// Fail `count` instances at once and return the most model loads seen together
+    /// (with no cap: the instances left loading when the round ends)
+    async fn correlated_restarts(count: usize, max: usize) -> usize {
+        use mocks::MockRestartStrategy;
+
+        let registry = Arc::new(Registry::new(
+            None,
+            "text-embeddings-router".to_string(),
+            8080,
+            8180,
+        ));
+        for i in 0..count {
+            let instance = registry
+                .add(InstanceConfig {
+                    name: format!("shared-{}", i),
+                    model_id: "model".to_string(),
+                    port: 8080 + i as u16,
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+            *instance.status.write().await = InstanceStatus::Running;
+        }
+
+        let checker = Arc::new(SlowLoadChecker {
+            load_started: Default::default(),
+            loading: Default::default(),
+            max_loading: Default::default(),
+        });
+        // Spawning the process is quick; the model load is what takes time
+        let restart = Arc::new(MockRestartStrategy::new());
+        restart.set_marks_starting(true);
+
+        let monitor = HealthMonitor::builder(registry.clone())
+            .config(
+                HealthMonitorConfig::builder()
+                    .max_failures_before_restart(1)
+                    .max_concurrent_restarts(max)
+                    .build(),
+            )
+            .health_checker(checker.clone())
+            .restart_strategy(restart.clone())
+            .build("mock".to_string());
+
+        let started = Instant::now();
+        monitor.check_all_instances().await;
+        monitor.wait_for_restarts().await;
+        assert_eq!(restart.restart_count(), count as u32);
+        let mut starting = 0;
+        for instance in registry.list().await {
+            if *instance.status.read().await == InstanceStatus::Starting {
+                starting += 1;
+            }
+        }
+
+        if max == 0 {
+            // Without a cap nothing waits for the models: all are loading together
+            assert_eq!(started.elapsed(), Duration::ZERO);
+            return starting;
+        }
+        // Each wave of `max` restarts holds its permits until the models load
+        assert_eq!(
+            started.elapsed(),
+            Duration::from_secs(10 * count.div_ceil(max) as u64)
+        );
+        assert_eq!(starting, 0);
+        for instance in registry.list().await {
+            assert!(!instance.is_restarting());
+        }
+        checker
+            .max_loading
+            .load(std::sync::atomic::Ordering::SeqCst)
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_concurrent_restarts_capped() {
+        assert_eq!(correlated_restarts(5, 2).await, 2);
+        assert_eq!(correlated_restarts(3, 1).await, 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_concurrent_restarts_unlimited() {
+        assert_eq!(correlated_restarts(4, 0).await, 4);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_restart_limit_quarantines_after_cap() {
+        use mocks::{MockHealthChecker, MockRestartStrategy, RecordingEventHandler};
+
+        let registry = Arc::new(Registry::new(
+            None,
+            "text-embeddings-router".to_string(),
+            8080,
+            8180,
+        ));
+        let instance = registry
+            .add(InstanceConfig {
+                name: "crashloop".to_string(),
+                model_id: "model".to_string(),
+                port: 8080,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        *instance.status.write().await = InstanceStatus::Running;
+
+        let checker = Arc::new(MockHealthChecker::new());
+        let restart = Arc::new(MockRestartStrategy::new());
+        let events = Arc::new(RecordingEventHandler::new());
+        checker.set_unhealthy("fail".to_string());
+
+        let monitor = HealthMonitor::builder(registry.clone())
+            .config(
+                HealthMonitorConfig::builder()
+                    .max_failures_before_restart(1)
+                    .max_restarts_per_window(2)
+                    .restart_window(Duration::from_secs(600))
+                    .build(),
+            )
+            .health_checker(checker.clone())
+            .restart_strategy(restart.clone())
+            .event_handler(events.clone())
+            .build("mock".to_string());
+
+        // A restart that has left the window no longer counts
+        monitor.check_single_instance(&instance).await;
+        monitor.wait_for_restarts().await;
+        tokio::time::advance(Duration::from_secs(601)).await;
+        monitor.check_single_instance(&instance).await;
+        monitor.wait_for_restarts().await;
+        monitor.check_single_instance(&instance).await;
+        monitor.wait_for_restarts().await;
+        assert_eq!(restart.restart_count(), 3);
+        assert_eq!(*instance.status.read().await, InstanceStatus::Running);
+
+        // The third failure inside the window is not restarted
+        monitor.check_single_instance(&instance).await;
+        assert_eq!(restart.restart_count(), 3);
+        assert_eq!(*instance.status.read().await, InstanceStatus::Quarantined);
+        assert!(
+            events
+                .has_event_type(|e| matches!(
+                    e,
+                    HealthEvent::RestartLimitReached { restarts: 2, .. }
+                ))
+                .await
+        );
+
+        // Later rounds leave it alone
+        for _ in 0..3 {
+            monitor.check_single_instance(&instance).await;
+        }
+        assert_eq!(restart.restart_count(), 3);
+
+        // Clearing the quarantine starts a fresh window
+        instance.unquarantine().await.unwrap();
+        assert!(instance.stats.read().await.recent_restarts.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_auto_restart_disabled_from_manager_config() {
+        use mocks::{MockHealthChecker, MockRestartStrategy, RecordingEventHandler};
+
+        let registry = Arc::new(Registry::new(
+            None,
+            "text-embeddings-router".to_string(),
+            8080,
+            8180,
+        ));
+        let instance = registry
+            .add(InstanceConfig {
+                name: "monitor-only".to_string(),
+                model_id: "model".to_string(),
+                port: 8080,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        *instance.status.write().await = InstanceStatus::Running;
+
+        let checker = Arc::new(MockHealthChecker::new());
+        let restart = Arc::new(MockRestartStrategy::new());
+        let events = Arc::new(RecordingEventHandler::new());
+        checker.set_unhealthy_with("process gone".to_string(), FailureSeverity::Hard);
+
+        let manager_config = ManagerConfig {
+            max_failures_before_restart: 1,
+            auto_restart: false,
+            ..Default::default()
+        };
+        let monitor = HealthMonitor::builder(registry)
+            .config(HealthMonitorConfig::from_manager_config(&manager_config))
+            .health_checker(checker)
+            .restart_strategy(restart.clone())
+            .event_handler(events.clone())
+            .build("mock".to_string());
+        assert!(!monitor.config.auto_restart);
+
+        // Well past the threshold, through the same per-round path as run()
+        for _ in 0..5 {
+            monitor.check_all_instances().await;
+        }
+
+        assert_eq!(instance.stats.read().await.health_check_failures, 5);
+        assert_eq!(restart.restart_count(), 0);
+        assert!(
+            !events
+                .has_event_type(|e| matches!(e, HealthEvent::RestartTriggered { .. }))
+                .await
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_check_duration_recorded() {
+        use crate::metrics::{MetricsService, mocks::MockMetricsRecorder};
+        use mocks::{MockHealthChecker, RecordingEventHandler};
+
+        let registry = Arc::new(Registry::new(
+            None,
+            "text-embeddings-router".to_string(),
+            8080,
+            8180,
+        ));
+        let instance = registry
+            .add(InstanceConfig {
+                name: "slow".to_string(),
+                model_id: "model".to_string(),
+                port: 8080,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        *instance.status.write().await = InstanceStatus::Running;
+
+        let checker = Arc::new(MockHealthChecker::new());
+        checker.set_delay(Duration::from_millis(250));
+        let events = Arc::new(RecordingEventHandler::new());
+
+        let monitor = HealthMonitor::builder(registry)
+            .health_checker(checker)
+            .event_handler(events.clone())
+            .build("mock".to_string());
+        monitor.check_single_instance(&instance).await;
+
+        let (outcome, duration) = events
+            .events()
+            .await
+            .into_iter()
+            .find_map(|event| match event {
+                HealthEvent::CheckCompleted {
+                    instance_name,
+                    outcome,
+                    duration,
+                } if instance_name == "slow" => Some((outcome, duration)),
+                _ => None,
+            })
+            .expect("CheckCompleted event");
+        assert_eq!(outcome, "healthy");
+        assert!(duration >= Duration::from_millis(250));
+
+        // The metrics handler turns the event into a histogram observation
+        let recorder = Arc::new(MockMetricsRecorder::new());
+        MetricsService::new(recorder.clone()).record_health_check_duration(
+            "slow",
+            outcome,
+            duration.as_secs_f64(),
+        );
+        let histograms = recorder.get_histograms();
+        assert_eq!(histograms[0].0, "tei_health_check_duration_seconds");
+        assert!(histograms[0].1 > 0.0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_check_jitter_spreads_checks() {
+        /// Records when each instance was checked
+        #[derive(Default)]
+        struct TimedChecker {
+            checks: std::sync::Mutex<Vec<(String, Instant)>>,
+        }
+
+        #[async_trait]
+        impl HealthChecker for TimedChecker {
+            async fn check(&self, instance: &TeiInstance) -> HealthCheckResult {
+                self.checks
+                    .lock()
+                    .unwrap()
+                    .push((instance.config.name.clone(), Instant::now()));
+                HealthCheckResult::healthy()
+            }
+        }
+
+        let registry = Arc::new(Registry::new(
+            None,
+            "text-embeddings-router".to_string(),
+            8080,
+            8180,
+        ));
+        for i in 0..8u16 {
+            let instance = registry
+                .add(InstanceConfig {
+                    name: format!("jitter-{}", i),
+                    model_id: "model".to_string(),
+                    port: 8080 + i,
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+            *instance.status.write().await = InstanceStatus::Running;
+        }
+
+        let jitter = Duration::from_secs(10);
+        let build_monitor = |checker: Arc<TimedChecker>| {
+            HealthMonitor::builder(registry.clone())
+                .config(
+                    HealthMonitorConfig::builder()
+                        .check_jitter(jitter)
+                        .jitter_seed(42)
+                        .build(),
+                )
+                .health_checker(checker)
+                .build("text-embeddings-router".to_string())
+        };
+
+        let checker = Arc::new(TimedChecker::default());
+        let round_start = Instant::now();
+        build_monitor(checker.clone()).check_all_instances().await;
+
+        let checks = checker.checks.lock().unwrap().clone();
+        assert_eq!(checks.len(), 8);
+
+        // Every check lands inside the jitter window, at distinct times
+        let mut offsets: Vec<Duration> = checks.iter().map(|(_, at)| *at - round_start).collect();
+        assert!(offsets.iter().all(|offset| *offset < jitter));
+        offsets.sort();
+        offsets.dedup();
+        assert_eq!(
+            offsets.len(),
+            8,
+            "checks fired simultaneously: {:?}",
+            offsets
+        );
+        assert!(offsets[7] - offsets[0] >= jitter / 4);
+
+        // The same seed reproduces the same per-instance schedule
+        let rerun = Arc::new(TimedChecker::default());
+        let rerun_start = Instant::now();
+        build_monitor(rerun.clone()).check_all_instances().await;
+        let schedule = |checks: Vec<(String, Instant)>, start: Instant| {
+            let mut schedule: Vec<(String, Duration)> = checks
+                .into_iter()
+                .map(|(name, at)| (name, at - start))
+                .collect();
+            schedule.sort();
+            schedule
+        };
+        assert_eq!(
+            schedule(checks, round_start),
+            schedule(rerun.checks.lock().unwrap().clone(), rerun_start)
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_check_jitter_not_blocked_by_slow_restart() {
+        use mocks::MockRestartStrategy;
+
+        /// Fails `failing` and records when every other instance was checked
+        struct OneFailingChecker {
+            failing: String,
+            checks: std::sync::Mutex<Vec<Instant>>,
+        }
+
+        #[async_trait]
+        impl HealthChecker for OneFailingChecker {
+            async fn check(&self, instance: &TeiInstance) -> HealthCheckResult {
+                if instance.config.name == self.failing {
+                    return HealthCheckResult::unhealthy("down".to_string());
+                }
+                self.checks.lock().unwrap().push(Instant::now());
+                HealthCheckResult::healthy()
+            }
+        }
+
+        let registry = Arc::new(Registry::new(
+            None,
+            "text-embeddings-router".to_string(),
+            8080,
+            8180,
+        ));
+        for i in 0..4u16 {
+            let instance = registry
+                .add(InstanceConfig {
+                    name: format!("jitter-{}", i),
+                    model_id: "model".to_string(),
+                    port: 8080 + i,
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+            *instance.status.write().await = InstanceStatus::Running;
+        }
+
+        let jitter = Duration::from_secs(10);
+        let checker = Arc::new(OneFailingChecker {
+            failing: "jitter-0".to_string(),
+            checks: Default::default(),
+        });
+        let restart = Arc::new(MockRestartStrategy::new());
+        restart.set_delay(Duration::from_secs(60));
+        let build_monitor = |seed: u64| {
+            HealthMonitor::builder(registry.clone())
+                .config(
+                    HealthMonitorConfig::builder()
+                        .check_jitter(jitter)
+                        .jitter_seed(seed)
+                        .max_failures_before_restart(1)
+                        .max_concurrent_restarts(1)
+                        .build(),
+                )
+                .health_checker(checker.clone())
+                .restart_strategy(restart.clone())
+                .build("text-embeddings-router".to_string())
+        };
+
+        // A schedule where the failing instance is checked first
+        let monitor = (0..)
+            .map(build_monitor)
+            .find(|monitor| {
+                let failing = monitor.jitter_offset("jitter-0");
+                (1..4).all(|i| monitor.jitter_offset(&format!("jitter-{}", i)) > failing)
+            })
+            .unwrap();
+
+        let round_start = Instant::now();
+        monitor.check_all_instances().await;
+        assert_eq!(restart.restart_count(), 1);
+
+        // The restart outlasts the round, but the other checks kept their offsets
+        let checks = checker.checks.lock().unwrap().clone();
+        assert_eq!(checks.len(), 3);
+        assert!(checks.iter().all(|at| *at - round_start < jitter));
+    }
+
+    #[tokio::test]
+    async fn test_checks_continue_while_restart_holds_permit() {
+        use mocks::MockRestartStrategy;
+        use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+        /// `loading` never comes back; `other` is healthy until `other_down`
+        #[derive(Default)]
+        struct LoadingChecker {
+            other_down: AtomicBool,
+            other_checks: AtomicU32,
+        }
+
+        #[async_trait]
+        impl HealthChecker for LoadingChecker {
+            async fn check(&self, instance: &TeiInstance) -> HealthCheckResult {
+                if instance.config.name == "loading" {
+                    return HealthCheckResult::unhealthy("still loading".to_string());
+                }
+                self.other_checks.fetch_add(1, Ordering::SeqCst);
+                if self.other_down.load(Ordering::SeqCst) {
+                    return HealthCheckResult::unhealthy("down".to_string());
+                }
+                HealthCheckResult::healthy()
+            }
+        }
+
+        let registry = Arc::new(Registry::new(
+            None,
+            "text-embeddings-router".to_string(),
+            8080,
+            8180,
+        ));
+        for (name, startup_timeout_secs) in [("loading", Some(3600)), ("other", None)] {
+            let instance = registry
+                .add(InstanceConfig {
+                    name: name.to_string(),
+                    model_id: "model".to_string(),
+                    port: 0,
+                    startup_timeout_secs,
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+            *instance.status.write().await = InstanceStatus::Running;
+        }
+
+        let checker = Arc::new(LoadingChecker::default());
+        let restart = Arc::new(MockRestartStrategy::new());
+        restart.set_marks_starting(true);
+        let monitor = Arc::new(
+            HealthMonitor::builder(registry.clone())
+                .config(
+                    HealthMonitorConfig::builder()
+                        .initial_delay(Duration::ZERO)
+                        .check_interval(Duration::from_secs(3600))
+                        .max_failures_before_restart(1)
+                        .max_concurrent_restarts(1)
+                        .build(),
+                )
+                .health_checker(checker.clone())
+                .restart_strategy(restart.clone())
+                .build("mock".to_string()),
+        );
+        let monitor_task = tokio::spawn(monitor.clone().run());
+
+        async fn wait_for(condition: impl Fn() -> bool) -> bool {
+            let deadline = Instant::now() + Duration::from_secs(5);
+            while !condition() && Instant::now() < deadline {
+                sleep(Duration::from_millis(10)).await;
+            }
+            condition()
+        }
+
+        // The first round restarts `loading`, which holds the only permit for an hour
+        assert!(wait_for(|| restart.restart_count() == 1).await);
+        assert!(wait_for(|| checker.other_checks.load(Ordering::SeqCst) == 1).await);
+
+        // Later rounds still check the other instance, and skip the one restarting
+        tokio::time::timeout(Duration::from_secs(1), monitor.check_all_instances())
+            .await
+            .expect("round blocked by the restart");
+        assert_eq!(checker.other_checks.load(Ordering::SeqCst), 2);
+        assert_eq!(restart.restart_count(), 1);
+
+        // And an exit is still handled right away: its restart queues for the permit
+        checker.other_down.store(true, Ordering::SeqCst);
+        registry.send_event(InstanceEvent::Exited {
+            name: "other".to_string(),
+            exit: ProcessExit::Code(1),
+        });
+        assert!(wait_for(|| checker.other_checks.load(Ordering::SeqCst) == 3).await);
+        let other = registry.get("other").await.unwrap();
+        assert!(wait_for(|| other.is_restarting()).await);
+        assert_eq!(restart.restart_count(), 1);
+
+        monitor_task.abort();
+    }
+
+    #[tokio::test]
+    async fn test_maintenance_mode_skips_restart() {
+        use mocks::{MockHealthChecker, MockRestartStrategy, RecordingEventHandler};
+
+        let registry = Arc::new(Registry::new(
+            None,
+            "text-embeddings-router".to_string(),
+            8080,
+            8180,
+        ));
+        let instance = registry
+            .add(InstanceConfig {
+                name: "maintained".to_string(),
+                model_id: "model".to_string(),
+                port: 8080,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        *instance.status.write().await = InstanceStatus::Running;
+
+        let checker = Arc::new(MockHealthChecker::new());
+        let restart = Arc::new(MockRestartStrategy::new());
+        let events = Arc::new(RecordingEventHandler::new());
+        checker.set_unhealthy("fail".to_string());
+
+        let monitor = HealthMonitor::builder(registry.clone())
+            .config(
+                HealthMonitorConfig::builder()
+                    .max_failures_before_restart(2)
+                    .auto_restart(true)
+                    .build(),
+            )
+            .health_checker(checker.clone())
+            .restart_strategy(restart.clone())
+            .event_handler(events.clone())
+            .build("mock".to_string());
+
+        registry.set_maintenance(true);
+        for _ in 0..4 {
+            monitor.check_single_instance(&instance).await;
+        }
+
+        // Failures are still recorded, but nothing is restarted
+        assert_eq!(restart.restart_count(), 0);
+        assert_eq!(instance.stats.read().await.health_check_failures, 4);
+        assert!(
+            events
+                .has_event_type(|e| matches!(e, HealthEvent::CheckFailed { .. }))
+                .await
+        );
+        assert!(
+            events
+                .has_event_type(|e| matches!(e, HealthEvent::RestartSkipped { .. }))
+                .await
+        );
+
+        // Per-instance maintenance alone also blocks restarts
+        registry.set_maintenance(false);
+        instance.set_maintenance(true);
+        monitor.check_single_instance(&instance).await;
+        assert_eq!(restart.restart_count(), 0);
+
+        // Leaving maintenance restores auto-restart
+        instance.set_maintenance(false);
+        monitor.check_single_instance(&instance).await;
+        monitor.wait_for_restarts().await;
+        assert_eq!(restart.restart_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_recovery_after_failure() {
+        use mocks::{MockHealthChecker, MockRestartStrategy, RecordingEventHandler};
+
+        let registry = Arc::new(Registry::new(
+            None,
+            "text-embeddings-router".to_string(),
+            8080,
+            8180,
+        ));
+        let config = InstanceConfig {
+            name: "recovery-test".to_string(),
+            model_id: "model".to_string(),
+            port: 8080,
+            max_batch_tokens: 1024,
+            max_concurrent_requests: 10,
+            pooling: None,
+            gpu_id: None,
+            prometheus_port: None,
+            ..Default::default()
+        };
+
+        let instance = registry.add(config).await.unwrap();
+        *instance.status.write().await = InstanceStatus::Running;
+        let checker = Arc::new(MockHealthChecker::new());
+        let restart = Arc::new(MockRestartStrategy::new());
+        let events = Arc::new(RecordingEventHandler::new());
+
+        let monitor_config = HealthMonitorConfig::builder()
+            .max_failures_before_restart(5)
+            .auto_restart(true)
+            .build();
+
+        let monitor = HealthMonitor::builder(registry)
+            .config(monitor_config)
+            .health_checker(checker.clone())
+            .restart_strategy(restart.clone())
+            .event_handler(events.clone())
+            .build("mock".to_string());
+
+        // Fail 3 times
+        checker.set_unhealthy("temporary issue".to_string());
+        for _ in 0..3 {
+            monitor.check_single_instance(&instance).await;
+        }
+
+        // Then recover
+        checker.set_healthy();
+        monitor.check_single_instance(&instance).await;
+
+        // Should NOT have triggered restart (recovered before threshold)
+        assert_eq!(restart.restart_count(), 0);
+
+        // Verify failure count was reset
+        let stats = instance.stats.read().await;
+        assert_eq!(stats.health_check_failures, 0);
+    }
+
+    #[tokio::test]
+    async fn test_starting_instance_not_failed_by_health_checks() {
+        use mocks::{MockHealthChecker, MockRestartStrategy, RecordingEventHandler};
+
+        let registry = Arc::new(Registry::new(
+            None,
+            "text-embeddings-router".to_string(),
+            8080,
+            8180,
+        ));
+        let config = InstanceConfig {
+            name: "starting-test".to_string(),
+            model_id: "model".to_string(),
+            port: 8080,
+            max_batch_tokens: 1024,
+            max_concurrent_requests: 10,
+            pooling: None,
+            gpu_id: None,
+            prometheus_port: None,
+            ..Default::default()
+        };
+
+        let instance = registry.add(config).await.unwrap();
+
+        // Set instance status to Starting (simulates a just-started instance)
+        *instance.status.write().await = InstanceStatus::Starting;
+
+        let checker = Arc::new(MockHealthChecker::new());
+        let restart = Arc::new(MockRestartStrategy::new());
+        let events = Arc::new(RecordingEventHandler::new());
+
+        checker.set_unhealthy("connection refused".to_string());
+
+        let monitor_config = HealthMonitorConfig::builder()
+            .max_failures_before_restart(3)
+            .auto_restart(true)
+            .build();
+
+        let monitor = HealthMonitor::builder(registry)
+            .config(monitor_config)
+            .health_checker(checker.clone())
+            .restart_strategy(restart.clone())
+            .event_handler(events.clone())
+            .build("mock".to_string());
+
+        // Fail many times while instance is Starting
+        for _ in 0..10 {
+            monitor.check_single_instance(&instance).await;
+        }
+
+        // Should NOT have triggered restart (instance is still Starting)
+        assert_eq!(restart.restart_count(), 0);
+
+        // Verify failure count was NOT incremented (Starting instances are skipped)
+        let stats = instance.stats.read().await;
+        assert_eq!(stats.health_check_failures, 0);
+
+        // CheckFailed events should NOT have been emitted for Starting instances
+        let has_failed_events = events
+            .has_event_type(|e| matches!(e, HealthEvent::CheckFailed { .. }))
+            .await;
+        assert!(!has_failed_events);
+    }
+
+    #[tokio::test]
+    async fn test_running_instance_fails_after_threshold() {
+        use mocks::{MockHealthChecker, MockRestartStrategy, RecordingEventHandler};
+
+        let registry = Arc::new(Registry::new(
+            None,
+            "text-embeddings-router".to_string(),
+            8080,
+            8180,
+        ));
+        let config = InstanceConfig {
+            name: "running-test".to_string(),
+            model_id: "model".to_string(),
+            port: 8080,
+            max_batch_tokens: 1024,
+            max_concurrent_requests: 10,
+            pooling: None,
+            gpu_id: None,
+            prometheus_port: None,
+            ..Default::default()
+        };
+
+        let instance = registry.add(config).await.unwrap();
+
+        // Set instance status to Running (fully operational)
+        *instance.status.write().await = InstanceStatus::Running;
+
+        let checker = Arc::new(MockHealthChecker::new());
+        let restart = Arc::new(MockRestartStrategy::new());
+        let events = Arc::new(RecordingEventHandler::new());
+
+        checker.set_unhealthy("connection refused".to_string());
+
+        let monitor_config = HealthMonitorConfig::builder()
+            .max_failures_before_restart(3)
+            .auto_restart(true)
+            .build();
+
+        let monitor = HealthMonitor::builder(registry)
+            .config(monitor_config)
+            .health_checker(checker.clone())
+            .restart_strategy(restart.clone())
+            .event_handler(events.clone())
+            .build("mock".to_string());
+
+        // Fail exactly 3 times (threshold)
+        for _ in 0..3 {
+            monitor.check_single_instance(&instance).await;
+        }
+        monitor.wait_for_restarts().await;
+
+        // Should have triggered restart (Running instance exceeded threshold)
+        assert_eq!(restart.restart_count(), 1);
+
+        // CheckFailed events should have been emitted
+        let has_failed_events = events
+            .has_event_type(|e| matches!(e, HealthEvent::CheckFailed { .. }))
+            .await;
+        assert!(has_failed_events);
+
+        // RestartTriggered should have been emitted
+        let has_restart_events = events
+            .has_event_type(|e| matches!(e, HealthEvent::RestartTriggered { .. }))
+            .await;
+        assert!(has_restart_events);
+    }
+
+    async fn running_instance(name: &str) -> (Arc<Registry>, Arc<TeiInstance>) {
+        let registry = Arc::new(Registry::new(
+            None,
+            "text-embeddings-router".to_string(),
+            8080,
+            8180,
+        ));
+        let config = InstanceConfig {
+            name: name.to_string(),
+            model_id: "model".to_string(),
+            port: 8080,
+            ..Default::default()
+        };
+
+        let instance = registry.add(config).await.unwrap();
+        *instance.status.write().await = InstanceStatus::Running;
+        (registry, instance)
+    }
+
+    #[test]
+    fn test_health_check_result_severity() {
+        assert_eq!(HealthCheckResult::healthy().severity, None);
+        assert_eq!(
+            HealthCheckResult::soft_failure("rpc".to_string()).severity,
+            Some(FailureSeverity::Soft)
+        );
+        assert_eq!(
+            HealthCheckResult::hard_failure("gone".to_string()).severity,
+            Some(FailureSeverity::Hard)
+        );
+        // Plain unhealthy() keeps the old behaviour of counting as a hard failure
+        assert_eq!(
+            HealthCheckResult::unhealthy("x".to_string()).severity,
+            Some(FailureSeverity::Hard)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_hard_failures_restart_sooner_than_soft() {
+        use mocks::{MockHealthChecker, MockRestartStrategy, RecordingEventHandler};
+
+        let (registry, instance) = running_instance("hard-test").await;
+
+        let checker = Arc::new(MockHealthChecker::new());
+        let restart = Arc::new(MockRestartStrategy::new());
+        let events = Arc::new(RecordingEventHandler::new());
+
+        let monitor_config = HealthMonitorConfig::builder()
+            .max_soft_failures(5)
+            .max_hard_failures(2)
+            .build();
+
+        let monitor = HealthMonitor::builder(registry)
+            .config(monitor_config)
+            .health_checker(checker.clone())
+            .restart_strategy(restart.clone())
+            .event_handler(events.clone())
+            .build("mock".to_string());
+
+        checker.set_unhealthy_with("Process not running".to_string(), FailureSeverity::Hard);
+        monitor.check_single_instance(&instance).await;
+        assert_eq!(restart.restart_count(), 0);
+        monitor.check_single_instance(&instance).await;
+        monitor.wait_for_restarts().await;
+        assert_eq!(restart.restart_count(), 1);
+
+        assert!(
+            events
+                .has_event_type(|e| matches!(
+                    e,
+                    HealthEvent::CheckFailed {
+                        severity: FailureSeverity::Hard,
+                        ..
+                    }
+                ))
+                .await
+        );
+    }
+
+    #[tokio::test]
+    async fn test_soft_failures_use_soft_threshold() {
+        use mocks::{MockHealthChecker, MockRestartStrategy, RecordingEventHandler};
+
+        let (registry, instance) = running_instance("soft-test").await;
+
+        let checker = Arc::new(MockHealthChecker::new());
+        let restart = Arc::new(MockRestartStrategy::new());
+        let events = Arc::new(RecordingEventHandler::new());
+
+        let monitor_config = HealthMonitorConfig::builder()
+            .max_soft_failures(4)
+            .max_hard_failures(2)
+            .build();
 
-        let monitor_config = HealthMonitorConfig::builder()
-            .max_failures_before_restart(5)
-            .auto_restart(true)
-            .build();
-
         let monitor = HealthMonitor::builder(registry)
             .config(monitor_config)
             .health_checker(checker.clone())
@@ -1069,91 +3454,285 @@ mod tests {
             .event_handler(events.clone())
             .build("mock".to_string());
 
-        // Fail 3 times
-        checker.set_unhealthy("temporary issue".to_string());
+        checker.set_unhealthy_with("Info RPC failed".to_string(), FailureSeverity::Soft);
         for _ in 0..3 {
             monitor.check_single_instance(&instance).await;
         }
+        // Past the hard threshold, but soft failures need more
+        assert_eq!(restart.restart_count(), 0);
 
-        // Then recover
-        checker.set_healthy();
         monitor.check_single_instance(&instance).await;
+        monitor.wait_for_restarts().await;
+        assert_eq!(restart.restart_count(), 1);
 
-        // Should NOT have triggered restart (recovered before threshold)
+        assert!(
+            !events
+                .has_event_type(|e| matches!(
+                    e,
+                    HealthEvent::CheckFailed {
+                        severity: FailureSeverity::Hard,
+                        ..
+                    }
+                ))
+                .await
+        );
+    }
+
+    #[tokio::test]
+    async fn test_alternating_severities() {
+        use mocks::{AlternatingHealthChecker, MockRestartStrategy, RecordingEventHandler};
+
+        let (registry, instance) = running_instance("alternating-test").await;
+
+        let checker = Arc::new(AlternatingHealthChecker::new(vec![
+            FailureSeverity::Hard,
+            FailureSeverity::Soft,
+        ]));
+        let restart = Arc::new(MockRestartStrategy::new());
+        let events = Arc::new(RecordingEventHandler::new());
+
+        let monitor_config = HealthMonitorConfig::builder()
+            .max_soft_failures(4)
+            .max_hard_failures(2)
+            .build();
+
+        let monitor = HealthMonitor::builder(registry)
+            .config(monitor_config)
+            .health_checker(checker.clone())
+            .restart_strategy(restart.clone())
+            .event_handler(events.clone())
+            .build("mock".to_string());
+
+        // hard, soft, hard: soft failures break the hard streak
+        for _ in 0..3 {
+            monitor.check_single_instance(&instance).await;
+        }
         assert_eq!(restart.restart_count(), 0);
+        assert_eq!(instance.stats.read().await.health_check_hard_failures, 1);
+        assert_eq!(instance.stats.read().await.health_check_failures, 3);
 
-        // Verify failure count was reset
-        let stats = instance.stats.read().await;
-        assert_eq!(stats.health_check_failures, 0);
+        // Fourth consecutive failure of any kind hits the soft threshold
+        monitor.check_single_instance(&instance).await;
+        monitor.wait_for_restarts().await;
+        assert_eq!(checker.check_count(), 4);
+        assert_eq!(restart.restart_count(), 1);
+
+        let severities: Vec<FailureSeverity> = events
+            .events()
+            .await
+            .into_iter()
+            .filter_map(|e| match e {
+                HealthEvent::CheckFailed { severity, .. } => Some(severity),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            severities,
+            vec![
+                FailureSeverity::Hard,
+                FailureSeverity::Soft,
+                FailureSeverity::Hard,
+                FailureSeverity::Soft
+            ]
+        );
     }
 
     #[tokio::test]
-    async fn test_starting_instance_not_failed_by_health_checks() {
+    async fn test_stopped_instance_not_health_checked() {
         use mocks::{MockHealthChecker, MockRestartStrategy, RecordingEventHandler};
 
-        let registry = Arc::new(Registry::new(
-            None,
-            "text-embeddings-router".to_string(),
-            8080,
-            8180,
-        ));
-        let config = InstanceConfig {
-            name: "starting-test".to_string(),
-            model_id: "model".to_string(),
-            port: 8080,
-            max_batch_tokens: 1024,
-            max_concurrent_requests: 10,
-            pooling: None,
-            gpu_id: None,
-            prometheus_port: None,
-            ..Default::default()
-        };
-
-        let instance = registry.add(config).await.unwrap();
-
-        // Set instance status to Starting (simulates a just-started instance)
-        *instance.status.write().await = InstanceStatus::Starting;
+        let (registry, instance) = running_instance("stopped-test").await;
+        instance.stop().await.unwrap();
+        assert_eq!(*instance.status.read().await, InstanceStatus::Stopped);
 
         let checker = Arc::new(MockHealthChecker::new());
         let restart = Arc::new(MockRestartStrategy::new());
         let events = Arc::new(RecordingEventHandler::new());
 
-        checker.set_unhealthy("connection refused".to_string());
-
-        let monitor_config = HealthMonitorConfig::builder()
-            .max_failures_before_restart(3)
-            .auto_restart(true)
-            .build();
+        checker.set_unhealthy("Process not running".to_string());
 
         let monitor = HealthMonitor::builder(registry)
-            .config(monitor_config)
+            .config(
+                HealthMonitorConfig::builder()
+                    .max_failures_before_restart(1)
+                    .build(),
+            )
             .health_checker(checker.clone())
             .restart_strategy(restart.clone())
             .event_handler(events.clone())
             .build("mock".to_string());
 
-        // Fail many times while instance is Starting
-        for _ in 0..10 {
+        for _ in 0..3 {
             monitor.check_single_instance(&instance).await;
         }
 
-        // Should NOT have triggered restart (instance is still Starting)
+        // Stopped on purpose - must not be restarted
+        assert_eq!(checker.check_count(), 0);
         assert_eq!(restart.restart_count(), 0);
+        assert_eq!(events.event_count().await, 0);
+    }
 
-        // Verify failure count was NOT incremented (Starting instances are skipped)
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_sigkilled_process_reported_as_oom() {
+        use mocks::{MockHealthChecker, RecordingEventHandler};
+        use nix::sys::signal::{Signal, kill};
+        use nix::unistd::Pid;
+        use std::os::unix::fs::PermissionsExt;
+
+        // Stand-in for the TEI binary that ignores its arguments and stays up
+        let dir = tempfile::tempdir().unwrap();
+        let binary = dir.path().join("fake-tei");
+        std::fs::write(&binary, "#!/bin/sh\nexec sleep 30\n").unwrap();
+        std::fs::set_permissions(&binary, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let binary = binary.to_str().unwrap().to_string();
+
+        let registry = Arc::new(
+            Registry::new(None, binary.clone(), 8080, 8180).with_log_dir(dir.path().join("logs")),
+        );
+        let instance = registry
+            .add(InstanceConfig {
+                name: "oom".to_string(),
+                model_id: "model".to_string(),
+                port: 8080,
+                max_batch_tokens: 8192,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        // Exec can briefly fail with ETXTBSY while another test thread forks
+        let mut started = instance.start(&binary).await;
+        for _ in 0..10 {
+            if started.is_ok() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            started = instance.start(&binary).await;
+        }
+        started.unwrap();
+
+        let pid = instance.pid().await.unwrap();
+        kill(Pid::from_raw(pid as i32), Signal::SIGKILL).unwrap();
+        for _ in 0..100 {
+            if !instance.is_running().await {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(instance.exit_status().await, Some(ProcessExit::Signal(9)));
+
+        let checker = Arc::new(MockHealthChecker::new());
+        checker.set_unhealthy("connection refused".to_string());
+        let events = Arc::new(RecordingEventHandler::new());
+        let config = HealthMonitorConfig::builder()
+            .oom_backoff_batch_factor(0.5)
+            .build();
+        let monitor = HealthMonitor::builder(registry)
+            .config(config)
+            .health_checker(checker)
+            .event_handler(events.clone())
+            .build(binary);
+
+        // Reported even while starting, and only once per process
+        monitor.check_single_instance(&instance).await;
+        monitor.check_single_instance(&instance).await;
+
+        let oom_events: Vec<_> = events
+            .events()
+            .await
+            .into_iter()
+            .filter(|e| matches!(e, HealthEvent::OomKilled { .. }))
+            .collect();
+        assert_eq!(oom_events.len(), 1);
+        assert!(matches!(
+            oom_events[0],
+            HealthEvent::OomKilled {
+                exit: ProcessExit::Signal(9),
+                max_batch_tokens: Some(4096),
+                ..
+            }
+        ));
         let stats = instance.stats.read().await;
-        assert_eq!(stats.health_check_failures, 0);
+        assert_eq!(stats.oom_kills, 1);
+        assert_eq!(stats.last_exit, Some(ProcessExit::Signal(9)));
+        assert_eq!(instance.max_batch_tokens(), 4096);
+    }
 
-        // CheckFailed events should NOT have been emitted for Starting instances
-        let has_failed_events = events
-            .has_event_type(|e| matches!(e, HealthEvent::CheckFailed { .. }))
-            .await;
-        assert!(!has_failed_events);
+    #[test]
+    fn test_checker_for_mode_refuses_fake_without_opt_in() {
+        let err = checker_for_mode(
+            HealthCheckMode::AlwaysHealthy,
+            BackendChannelConfig::default(),
+            false,
+        )
+        .err()
+        .expect("fake checker must be refused");
+        assert!(err.to_string().contains(ALLOW_FAKE_HEALTH_ENV));
+
+        assert!(
+            checker_for_mode(
+                HealthCheckMode::Grpc,
+                BackendChannelConfig::default(),
+                false
+            )
+            .is_ok()
+        );
     }
 
     #[tokio::test]
-    async fn test_running_instance_fails_after_threshold() {
-        use mocks::{MockHealthChecker, MockRestartStrategy, RecordingEventHandler};
+    async fn test_always_healthy_mode_marks_instance_running() {
+        use mocks::{MockRestartStrategy, RecordingEventHandler};
+
+        let checker = checker_for_mode(
+            HealthCheckMode::AlwaysHealthy,
+            BackendChannelConfig::default(),
+            true,
+        )
+        .unwrap();
+        let registry = Arc::new(
+            Registry::new(None, "text-embeddings-router".to_string(), 8080, 8180)
+                .with_health_checker(checker),
+        );
+        let instance = registry
+            .add(InstanceConfig {
+                name: "fake-health".to_string(),
+                model_id: "model".to_string(),
+                port: 8080,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        // No process was spawned, so a real checker would fail
+        *instance.status.write().await = InstanceStatus::Starting;
+
+        let restart = Arc::new(MockRestartStrategy::new());
+        let monitor = HealthMonitor::builder(registry.clone())
+            .health_checker(registry.health_checker())
+            .restart_strategy(restart.clone())
+            .event_handler(Arc::new(RecordingEventHandler::new()))
+            .build("mock".to_string());
+
+        monitor.check_single_instance(&instance).await;
+
+        assert_eq!(*instance.status.read().await, InstanceStatus::Running);
+        assert_eq!(restart.restart_count(), 0);
+
+        // Readiness polling uses the same checker
+        *instance.status.write().await = InstanceStatus::Starting;
+        wait_for_ready_with(
+            registry.health_checker().as_ref(),
+            &instance,
+            Duration::from_secs(1),
+            Duration::from_millis(10),
+        )
+        .await
+        .unwrap();
+        assert_eq!(*instance.status.read().await, InstanceStatus::Running);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_probe_reflects_reachability() {
+        use mocks::{MockHealthChecker, MockMetricsProbe, RecordingEventHandler};
 
         let registry = Arc::new(Registry::new(
             None,
@@ -1161,59 +3740,322 @@ mod tests {
             8080,
             8180,
         ));
-        let config = InstanceConfig {
-            name: "running-test".to_string(),
-            model_id: "model".to_string(),
+        let instance = registry
+            .add(InstanceConfig {
+                name: "metrics".to_string(),
+                model_id: "model".to_string(),
+                port: 8080,
+                prometheus_port: Some(9200),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        *instance.status.write().await = InstanceStatus::Running;
+
+        let probe = Arc::new(MockMetricsProbe::new());
+        let events = Arc::new(RecordingEventHandler::new());
+        let monitor = HealthMonitor::builder(registry)
+            .health_checker(Arc::new(MockHealthChecker::new()))
+            .event_handler(events.clone())
+            .metrics_probe(probe.clone())
+            .build("mock".to_string());
+
+        monitor.check_single_instance(&instance).await;
+        assert_eq!(instance.stats.read().await.metrics_reachable, Some(true));
+
+        // An unreachable endpoint is reported but the instance stays healthy
+        probe.set_reachable(false);
+        monitor.check_single_instance(&instance).await;
+        assert_eq!(instance.stats.read().await.metrics_reachable, Some(false));
+        assert_eq!(*instance.status.read().await, InstanceStatus::Running);
+        assert_eq!(instance.stats.read().await.health_check_failures, 0);
+        assert!(
+            events
+                .has_event_type(|e| matches!(
+                    e,
+                    HealthEvent::MetricsProbed {
+                        port: 9200,
+                        error: Some(_),
+                        ..
+                    }
+                ))
+                .await
+        );
+        assert_eq!(probe.probe_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_http_metrics_probe() {
+        let app = axum::Router::new().route("/metrics", axum::routing::get(|| async { "up 1\n" }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        assert!(HttpMetricsProbe.probe(port).await.is_ok());
+
+        // Nothing listening once the port is released
+        let closed = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let closed_port = closed.local_addr().unwrap().port();
+        drop(closed);
+        assert!(HttpMetricsProbe.probe(closed_port).await.is_err());
+    }
+
+    /// Download that grows by a byte per second for `duration`, then stalls
+    struct GrowingDownload {
+        started: Instant,
+        duration: Duration,
+    }
+
+    #[async_trait]
+    impl DownloadProgress for GrowingDownload {
+        async fn downloaded_bytes(&self, _instance: &TeiInstance) -> Option<u64> {
+            Some(self.started.elapsed().min(self.duration).as_secs())
+        }
+    }
+
+    fn starting_instance(grace: Duration) -> TeiInstance {
+        TeiInstance::new(InstanceConfig {
+            name: "downloading".to_string(),
+            model_id: "org/huge-model".to_string(),
             port: 8080,
-            max_batch_tokens: 1024,
-            max_concurrent_requests: 10,
-            pooling: None,
-            gpu_id: None,
-            prometheus_port: None,
             ..Default::default()
-        };
-
-        let instance = registry.add(config).await.unwrap();
+        })
+        .with_startup_download_grace(grace)
+    }
 
-        // Set instance status to Running (fully operational)
-        *instance.status.write().await = InstanceStatus::Running;
+    #[tokio::test(start_paused = true)]
+    async fn test_download_progress_extends_startup_deadline() {
+        use mocks::MockHealthChecker;
 
         let checker = Arc::new(MockHealthChecker::new());
-        let restart = Arc::new(MockRestartStrategy::new());
-        let events = Arc::new(RecordingEventHandler::new());
+        checker.set_unhealthy("loading".to_string());
+        let progress = GrowingDownload {
+            started: Instant::now(),
+            duration: Duration::from_secs(20),
+        };
+        let instance = starting_instance(Duration::from_secs(5));
+
+        // Ready after 22s: past the 10s timeout, but the download kept going
+        let ready = checker.clone();
+        tokio::spawn(async move {
+            sleep(Duration::from_secs(22)).await;
+            ready.set_healthy();
+        });
+        let started = Instant::now();
+        wait_for_ready_tracking(
+            checker.as_ref(),
+            &instance,
+            Duration::from_secs(10),
+            Duration::from_secs(1),
+            &progress,
+        )
+        .await
+        .unwrap();
+        assert!(started.elapsed() >= Duration::from_secs(22));
+        assert_eq!(*instance.status.read().await, InstanceStatus::Running);
+
+        // Never ready: gives up once the download stalled for the grace period
+        checker.set_unhealthy("loading".to_string());
+        let progress = GrowingDownload {
+            started: Instant::now(),
+            duration: Duration::from_secs(20),
+        };
+        let started = Instant::now();
+        let err = wait_for_ready_tracking(
+            checker.as_ref(),
+            &instance,
+            Duration::from_secs(10),
+            Duration::from_secs(1),
+            &progress,
+        )
+        .await
+        .unwrap_err();
+        let elapsed = started.elapsed();
+        assert!(
+            elapsed >= Duration::from_secs(25) && elapsed <= Duration::from_secs(27),
+            "{:?}",
+            elapsed
+        );
+        assert!(err.to_string().contains("stalled"), "{}", err);
+    }
 
-        checker.set_unhealthy("connection refused".to_string());
+    #[tokio::test(start_paused = true)]
+    async fn test_stalled_download_times_out_startup() {
+        use mocks::MockHealthChecker;
 
-        let monitor_config = HealthMonitorConfig::builder()
-            .max_failures_before_restart(3)
-            .auto_restart(true)
-            .build();
+        let checker = MockHealthChecker::new();
+        checker.set_unhealthy("loading".to_string());
+        let progress = GrowingDownload {
+            started: Instant::now(),
+            duration: Duration::ZERO,
+        };
+        let instance = starting_instance(Duration::from_secs(5));
+
+        let started = Instant::now();
+        let err = wait_for_ready_tracking(
+            &checker,
+            &instance,
+            Duration::from_secs(10),
+            Duration::from_secs(1),
+            &progress,
+        )
+        .await
+        .unwrap_err();
+        let elapsed = started.elapsed();
+        assert!(
+            elapsed >= Duration::from_secs(10) && elapsed <= Duration::from_secs(12),
+            "{:?}",
+            elapsed
+        );
+        assert!(err.to_string().contains("within 10s"), "{}", err);
+    }
 
-        let monitor = HealthMonitor::builder(registry)
-            .config(monitor_config)
+    #[tokio::test]
+    async fn test_status_transitions_broadcast_to_registry_subscribers() {
+        use mocks::{MockHealthChecker, MockRestartStrategy};
+
+        let registry = Arc::new(Registry::new(
+            None,
+            "text-embeddings-router".to_string(),
+            8080,
+            8180,
+        ));
+        let instance = registry
+            .add(InstanceConfig {
+                name: "watched".to_string(),
+                model_id: "model".to_string(),
+                port: 8080,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        *instance.status.write().await = InstanceStatus::Starting;
+
+        let checker = Arc::new(MockHealthChecker::new());
+        let restart = Arc::new(MockRestartStrategy::new());
+        restart.set_should_fail(true);
+        let monitor = HealthMonitor::builder(registry.clone())
+            .config(
+                HealthMonitorConfig::builder()
+                    .max_failures_before_restart(1)
+                    .auto_restart(true)
+                    .build(),
+            )
             .health_checker(checker.clone())
-            .restart_strategy(restart.clone())
-            .event_handler(events.clone())
+            .restart_strategy(restart)
             .build("mock".to_string());
 
-        // Fail exactly 3 times (threshold)
-        for _ in 0..3 {
-            monitor.check_single_instance(&instance).await;
+        let mut events = registry.subscribe_events();
+
+        // Starting -> Running
+        monitor.check_single_instance(&instance).await;
+        // Running -> Failed after the restart fails
+        checker.set_unhealthy("down".to_string());
+        monitor.check_single_instance(&instance).await;
+        monitor.wait_for_restarts().await;
+
+        let mut transitions = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            if let InstanceEvent::StatusChanged { name, from, to } = event {
+                assert_eq!(name, "watched");
+                transitions.push((from, to));
+            }
         }
+        assert_eq!(
+            transitions,
+            vec![
+                (InstanceStatus::Starting, InstanceStatus::Running),
+                (InstanceStatus::Running, InstanceStatus::Failed),
+            ]
+        );
+    }
 
-        // Should have triggered restart (Running instance exceeded threshold)
-        assert_eq!(restart.restart_count(), 1);
+    #[tokio::test]
+    async fn test_readiness_webhook_receives_transitions() {
+        use axum::extract::State;
+        use axum::http::StatusCode;
+        use std::sync::Mutex;
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        // Fails the first delivery so the retry is exercised
+        type Received = Arc<Mutex<Vec<ReadinessNotification>>>;
+        async fn receive(
+            State((attempts, received)): State<(Arc<AtomicU32>, Received)>,
+            axum::Json(notification): axum::Json<ReadinessNotification>,
+        ) -> StatusCode {
+            if attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+                return StatusCode::INTERNAL_SERVER_ERROR;
+            }
+            received.lock().unwrap().push(notification);
+            StatusCode::NO_CONTENT
+        }
 
-        // CheckFailed events should have been emitted
-        let has_failed_events = events
-            .has_event_type(|e| matches!(e, HealthEvent::CheckFailed { .. }))
-            .await;
-        assert!(has_failed_events);
+        let attempts = Arc::new(AtomicU32::new(0));
+        let received: Received = Arc::default();
+        let app = axum::Router::new()
+            .route("/hook", axum::routing::post(receive))
+            .with_state((attempts.clone(), received.clone()));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await });
 
-        // RestartTriggered should have been emitted
-        let has_restart_events = events
-            .has_event_type(|e| matches!(e, HealthEvent::RestartTriggered { .. }))
-            .await;
-        assert!(has_restart_events);
+        let registry = Arc::new(Registry::new(
+            None,
+            "text-embeddings-router".to_string(),
+            8080,
+            8180,
+        ));
+        let instance = registry
+            .add(InstanceConfig {
+                name: "hooked".to_string(),
+                model_id: "model".to_string(),
+                port: 8080,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let webhook = WebhookEventHandler::new(url, Duration::from_secs(5))
+            .unwrap()
+            .with_retry(2, Duration::from_millis(10));
+        let monitor = HealthMonitor::builder(registry.clone())
+            .config(
+                HealthMonitorConfig::builder()
+                    .initial_delay(Duration::from_secs(3600))
+                    .build(),
+            )
+            .event_handler(Arc::new(CompositeEventHandler::new(vec![
+                Arc::new(MetricsEventHandler),
+                Arc::new(webhook),
+            ])))
+            .build("mock".to_string());
+        let monitor_task = tokio::spawn(Arc::new(monitor).run());
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        // As the readiness wait does once the instance answers
+        instance.set_status(InstanceStatus::Running).await;
+        instance.set_status(InstanceStatus::Failed).await;
+        // Not a readiness transition
+        instance.set_status(InstanceStatus::Stopped).await;
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while received.lock().unwrap().len() < 2 && Instant::now() < deadline {
+            sleep(Duration::from_millis(10)).await;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        monitor_task.abort();
+
+        let mut statuses: Vec<_> = received
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|n| {
+                assert_eq!(n.instance, "hooked");
+                n.status
+            })
+            .collect();
+        statuses.sort_by_key(|s| format!("{:?}", s));
+        assert_eq!(statuses, [InstanceStatus::Failed, InstanceStatus::Running]);
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
     }
 }
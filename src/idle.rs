@@ -0,0 +1,300 @@
+//! Idle reaper that stops instances which have not received requests for a while
+//!
+//! Stopped instances stay registered, so they can be started again via the API.
+//! Idle time is measured from `last_request_at`, falling back to `started_at` for
+//! instances that have not served a request since they started. Instances with
+//! requests in flight (e.g. a long-lived stream) are never idle.
+
+use crate::instance::{InstanceStatus, TeiInstance};
+use crate::registry::Registry;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use tokio::time::{Duration, interval};
+
+// ============================================================================
+// Trait Definitions
+// ============================================================================
+
+/// Source of the current time (stubbed in tests)
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// Wall-clock time
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+// ============================================================================
+// Idle Reaper
+// ============================================================================
+
+/// Background task that stops instances idle beyond a threshold
+pub struct IdleReaper {
+    registry: Arc<Registry>,
+    idle_timeout: Duration,
+    check_interval: Duration,
+    clock: Arc<dyn Clock>,
+}
+
+impl IdleReaper {
+    /// Create a reaper using the system clock
+    pub fn new(registry: Arc<Registry>, idle_timeout: Duration, check_interval: Duration) -> Self {
+        Self::with_clock(
+            registry,
+            idle_timeout,
+            check_interval,
+            Arc::new(SystemClock),
+        )
+    }
+
+    /// Create a reaper with a custom clock
+    pub fn with_clock(
+        registry: Arc<Registry>,
+        idle_timeout: Duration,
+        check_interval: Duration,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        Self {
+            registry,
+            idle_timeout,
+            check_interval,
+            clock,
+        }
+    }
+
+    /// Start reaper loop
+    pub async fn run(self: Arc<Self>) {
+        let mut ticker = interval(self.check_interval);
+
+        tracing::info!(
+            idle_timeout_secs = self.idle_timeout.as_secs(),
+            interval_secs = self.check_interval.as_secs(),
+            "Idle reaper started"
+        );
+
+        loop {
+            ticker.tick().await;
+            self.reap_idle_instances().await;
+        }
+    }
+
+    /// Stop all running instances idle beyond the timeout (public for testing)
+    ///
+    /// Returns the names of the instances that were stopped.
+    pub async fn reap_idle_instances(&self) -> Vec<String> {
+        let mut stopped = Vec::new();
+
+        for instance in self.registry.list().await {
+            if !self.is_idle(&instance).await {
+                continue;
+            }
+
+            let name = instance.config.name.clone();
+            match instance.stop().await {
+                Ok(()) => {
+                    tracing::info!(
+                        instance = %name,
+                        idle_timeout_secs = self.idle_timeout.as_secs(),
+                        "Stopped idle instance"
+                    );
+                    crate::metrics::record_instance_idle_stop(&name);
+                    stopped.push(name);
+                }
+                Err(e) => {
+                    tracing::error!(
+                        instance = %name,
+                        error = %e,
+                        "Failed to stop idle instance"
+                    );
+                }
+            }
+        }
+
+        stopped
+    }
+
+    async fn is_idle(&self, instance: &TeiInstance) -> bool {
+        if *instance.status.read().await != InstanceStatus::Running {
+            return false;
+        }
+        if instance.inflight().get() > 0 {
+            return false;
+        }
+
+        let stats = instance.stats.read().await;
+        let Some(last_active) = stats.last_request_at.or(stats.started_at) else {
+            return false;
+        };
+
+        let idle_for = (self.clock.now() - last_active)
+            .to_std()
+            .unwrap_or_default();
+        idle_for > self.idle_timeout
+    }
+}
+
+// ============================================================================
+// Mock Implementations for Testing
+// ============================================================================
+
+#[cfg(test)]
+pub mod mocks {
+    use super::*;
+
+    /// Manually advanced clock
+    pub struct MockClock {
+        now: std::sync::RwLock<DateTime<Utc>>,
+    }
+
+    impl MockClock {
+        pub fn new(now: DateTime<Utc>) -> Self {
+            Self {
+                now: std::sync::RwLock::new(now),
+            }
+        }
+
+        pub fn advance(&self, by: chrono::Duration) {
+            *self.now.write().unwrap() += by;
+        }
+    }
+
+    impl Clock for MockClock {
+        fn now(&self) -> DateTime<Utc> {
+            *self.now.read().unwrap()
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::mocks::MockClock;
+    use super::*;
+    use crate::config::InstanceConfig;
+
+    /// Add an instance that looks like it is running (no real process is spawned)
+    async fn running_instance(registry: &Registry, name: &str, port: u16) -> Arc<TeiInstance> {
+        let instance = registry
+            .add(InstanceConfig {
+                name: name.to_string(),
+                model_id: "model".to_string(),
+                port,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        *instance.status.write().await = InstanceStatus::Running;
+        instance.stats.write().await.started_at = Some(Utc::now());
+        instance
+    }
+
+    fn test_registry() -> Arc<Registry> {
+        Arc::new(Registry::new(
+            None,
+            "text-embeddings-router".to_string(),
+            8080,
+            8180,
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_reaper_stops_idle_instance() {
+        let registry = test_registry();
+        let instance = running_instance(&registry, "idle", 8081).await;
+        instance.record_request().await;
+
+        let clock = Arc::new(MockClock::new(Utc::now()));
+        let reaper = IdleReaper::with_clock(
+            registry,
+            Duration::from_secs(60),
+            Duration::from_secs(1),
+            clock.clone(),
+        );
+
+        // Not idle yet
+        clock.advance(chrono::Duration::seconds(30));
+        assert!(reaper.reap_idle_instances().await.is_empty());
+        assert_eq!(*instance.status.read().await, InstanceStatus::Running);
+
+        // Past the idle timeout
+        clock.advance(chrono::Duration::seconds(31));
+        assert_eq!(reaper.reap_idle_instances().await, vec!["idle".to_string()]);
+        assert_eq!(*instance.status.read().await, InstanceStatus::Stopped);
+    }
+
+    #[tokio::test]
+    async fn test_reaper_respects_recent_requests() {
+        let registry = test_registry();
+        let instance = running_instance(&registry, "busy", 8082).await;
+
+        let clock = Arc::new(MockClock::new(Utc::now()));
+        let reaper = IdleReaper::with_clock(
+            registry,
+            Duration::from_secs(60),
+            Duration::from_secs(1),
+            clock.clone(),
+        );
+
+        // Started long ago, but a fresh request resets the idle timer
+        clock.advance(chrono::Duration::seconds(120));
+        instance.stats.write().await.last_request_at = Some(clock.now());
+        clock.advance(chrono::Duration::seconds(10));
+
+        assert!(reaper.reap_idle_instances().await.is_empty());
+        assert_eq!(*instance.status.read().await, InstanceStatus::Running);
+    }
+
+    #[tokio::test]
+    async fn test_reaper_keeps_instances_with_inflight_requests() {
+        let registry = test_registry();
+        let instance = running_instance(&registry, "streaming", 8084).await;
+        instance.record_request().await;
+
+        let clock = Arc::new(MockClock::new(Utc::now()));
+        let reaper = IdleReaper::with_clock(
+            registry,
+            Duration::from_secs(60),
+            Duration::from_secs(1),
+            clock.clone(),
+        );
+
+        // A stream opened before the timeout is still running after it
+        let stream = instance.inflight().begin();
+        clock.advance(chrono::Duration::seconds(600));
+        assert!(reaper.reap_idle_instances().await.is_empty());
+        assert_eq!(*instance.status.read().await, InstanceStatus::Running);
+
+        drop(stream);
+        assert_eq!(
+            reaper.reap_idle_instances().await,
+            vec!["streaming".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reaper_ignores_non_running_instances() {
+        let registry = test_registry();
+        let instance = running_instance(&registry, "starting", 8083).await;
+        *instance.status.write().await = InstanceStatus::Starting;
+
+        let clock = Arc::new(MockClock::new(Utc::now()));
+        let reaper = IdleReaper::with_clock(
+            registry,
+            Duration::from_secs(60),
+            Duration::from_secs(1),
+            clock.clone(),
+        );
+
+        clock.advance(chrono::Duration::seconds(600));
+        assert!(reaper.reap_idle_instances().await.is_empty());
+        assert_eq!(*instance.status.read().await, InstanceStatus::Starting);
+    }
+}
@@ -1,13 +1,18 @@
 //! TEI instance management and process lifecycle
 
-use crate::config::InstanceConfig;
+use crate::config::{Dtype, InstanceConfig, Pooling, interpolate_env, parse_env_file};
+use crate::error::TeiError;
+use crate::registry::{EventSender, InstanceEvent};
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
 use std::time::Duration;
 use tokio::process::{Child, Command};
-use tokio::sync::RwLock;
+use tokio::sync::{RwLock, oneshot, watch};
 
 // ============================================================================
 // Trait Definitions
@@ -22,10 +27,180 @@ pub struct SpawnConfig {
     pub port: u16,
     pub max_batch_tokens: u32,
     pub max_concurrent_requests: u32,
-    pub pooling: Option<String>,
+    pub pooling: Option<Pooling>,
+    pub dtype: Option<Dtype>,
+    pub revision: Option<String>,
     pub gpu_id: Option<u32>,
+    /// Hide all GPUs from the process
+    pub cpu_only: bool,
     pub prometheus_port: Option<u16>,
     pub extra_args: Vec<String>,
+    /// Extra environment for the process, with `${VAR}` references already resolved
+    pub env: Vec<(String, String)>,
+    /// Directory the process output is captured in, see [`instance_log_path`]
+    pub log_dir: PathBuf,
+}
+
+impl SpawnConfig {
+    /// Spawn settings for `config`, with env and extra args already resolved
+    pub fn new(
+        config: &InstanceConfig,
+        binary_path: &str,
+        env: Vec<(String, String)>,
+        extra_args: Vec<String>,
+    ) -> Self {
+        // Explicit entries in `env` win over the ones derived from hf_home
+        let hf_env = config.hf_home.iter().flat_map(|home| {
+            [
+                ("HF_HOME".to_string(), home.display().to_string()),
+                (
+                    "HUGGINGFACE_HUB_CACHE".to_string(),
+                    home.join("hub").display().to_string(),
+                ),
+            ]
+        });
+        let hf_env: Vec<_> = hf_env
+            .filter(|(key, _)| !env.iter().any(|(set, _)| set == key))
+            .collect();
+        let env = hf_env.into_iter().chain(env).collect();
+        Self {
+            instance_name: config.name.clone(),
+            binary_path: binary_path.to_string(),
+            model_id: config.model_id.clone(),
+            port: config.port,
+            max_batch_tokens: config.max_batch_tokens,
+            max_concurrent_requests: config.max_concurrent_requests,
+            pooling: config.pooling,
+            dtype: config.dtype,
+            revision: config.revision.clone(),
+            gpu_id: config.gpu_id,
+            cpu_only: config.cpu_only,
+            prometheus_port: config.prometheus_port,
+            extra_args,
+            env,
+            log_dir: crate::config::default_log_dir(),
+        }
+    }
+
+    /// CUDA_VISIBLE_DEVICES for the process (None = inherited from the manager)
+    ///
+    /// CPU-only instances get an empty value, which hides every GPU.
+    pub fn cuda_visible_devices(&self) -> Option<String> {
+        if self.cpu_only {
+            return Some(String::new());
+        }
+        self.gpu_id.map(|gpu_id| gpu_id.to_string())
+    }
+
+    /// Arguments passed to the TEI binary
+    pub fn args(&self) -> Vec<String> {
+        let mut args = vec![
+            "--model-id".to_string(),
+            self.model_id.clone(),
+            "--port".to_string(),
+            self.port.to_string(),
+            "--max-batch-tokens".to_string(),
+            self.max_batch_tokens.to_string(),
+            "--max-concurrent-requests".to_string(),
+            self.max_concurrent_requests.to_string(),
+            "--json-output".to_string(),
+        ];
+
+        if let Some(pooling) = self.pooling {
+            args.extend(["--pooling".to_string(), pooling.as_str().to_string()]);
+        }
+
+        if let Some(dtype) = self.dtype {
+            args.extend(["--dtype".to_string(), dtype.as_str().to_string()]);
+        }
+
+        // Like --prometheus-port, an explicit --revision in extra_args wins
+        if self.extra_args_value("--revision").is_none()
+            && let Some(revision) = &self.revision
+        {
+            args.extend(["--revision".to_string(), revision.clone()]);
+        }
+
+        // An explicit --prometheus-port in extra_args wins over the assigned one
+        let has_prometheus_port_in_extra_args =
+            self.extra_args.iter().any(|arg| arg == "--prometheus-port");
+
+        if !has_prometheus_port_in_extra_args && let Some(prom_port) = self.prometheus_port {
+            args.extend(["--prometheus-port".to_string(), prom_port.to_string()]);
+        }
+
+        args.extend(self.extra_args.iter().cloned());
+        args
+    }
+
+    /// Revision TEI will be asked to serve, if one is pinned
+    pub fn requested_revision(&self) -> Option<&str> {
+        self.extra_args_value("--revision")
+            .or(self.revision.as_deref())
+    }
+
+    /// Value of `flag` in extra_args, in either `--flag value` or `--flag=value` form
+    fn extra_args_value(&self, flag: &str) -> Option<&str> {
+        self.extra_args.iter().enumerate().find_map(|(i, arg)| {
+            if arg == flag {
+                self.extra_args.get(i + 1).map(String::as_str)
+            } else {
+                arg.strip_prefix(flag)?.strip_prefix('=')
+            }
+        })
+    }
+
+    /// Full command line: binary followed by its arguments
+    pub fn command_line(&self) -> Vec<String> {
+        std::iter::once(self.binary_path.clone())
+            .chain(self.args())
+            .collect()
+    }
+}
+
+/// How a process exited
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessExit {
+    /// Exited on its own with this status code
+    Code(i32),
+    /// Terminated by this signal
+    Signal(i32),
+}
+
+/// SIGKILL, the signal the kernel OOM killer sends
+const SIGKILL: i32 = 9;
+
+impl ProcessExit {
+    /// Whether the exit looks like an OOM kill
+    ///
+    /// The OOM killer sends SIGKILL, which a wrapper shell reports as exit code
+    /// 128 + 9. A manual `kill -9` looks the same, so this is a best guess.
+    pub fn is_oom_kill(&self) -> bool {
+        matches!(self, Self::Signal(SIGKILL) | Self::Code(137))
+    }
+}
+
+impl From<std::process::ExitStatus> for ProcessExit {
+    fn from(status: std::process::ExitStatus) -> Self {
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::ExitStatusExt;
+            if let Some(signal) = status.signal() {
+                return Self::Signal(signal);
+            }
+        }
+        Self::Code(status.code().unwrap_or(-1))
+    }
+}
+
+impl std::fmt::Display for ProcessExit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Code(code) => write!(f, "exit code {}", code),
+            Self::Signal(signal) => write!(f, "signal {}", signal),
+        }
+    }
 }
 
 /// Opaque handle to a spawned process
@@ -48,6 +223,24 @@ pub trait ProcessManager: Send + Sync {
 
     /// Get process ID
     async fn pid(&self, handle: &ProcessHandle) -> Option<u32>;
+
+    /// How the process exited, or None while it is still running
+    async fn exit_status(&self, handle: &ProcessHandle) -> Option<ProcessExit>;
+
+    /// Wait for the process to exit, None if the handle is unknown (already stopped)
+    ///
+    /// The default polls [`ProcessManager::exit_status`].
+    async fn wait(&self, handle: &ProcessHandle) -> Option<ProcessExit> {
+        loop {
+            if let Some(exit) = self.exit_status(handle).await {
+                return Some(exit);
+            }
+            if !self.is_running(handle).await {
+                return None;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
 }
 
 // ============================================================================
@@ -55,8 +248,49 @@ pub trait ProcessManager: Send + Sync {
 // ============================================================================
 
 /// Production process manager using tokio::process
+///
+/// Each child is owned by a reaper task that waits for it, so an exit is seen as
+/// soon as the kernel reports it (SIGCHLD) rather than on the next poll.
 pub struct SystemProcessManager {
-    processes: Arc<RwLock<std::collections::HashMap<String, Child>>>,
+    processes: Arc<RwLock<std::collections::HashMap<String, SystemProcess>>>,
+}
+
+/// A child process, as seen from outside its reaper task
+struct SystemProcess {
+    pid: u32,
+    /// Set by the reaper once the process exited
+    exit: watch::Receiver<Option<ProcessExit>>,
+    /// Sending or dropping this makes the reaper kill the process
+    kill: oneshot::Sender<()>,
+}
+
+impl SystemProcess {
+    fn exit(&self) -> Option<ProcessExit> {
+        *self.exit.borrow()
+    }
+}
+
+/// Wait for `child` to exit (or kill it once `kill` fires) and publish how it exited
+async fn reap(
+    mut child: Child,
+    kill: oneshot::Receiver<()>,
+    exit_tx: watch::Sender<Option<ProcessExit>>,
+) {
+    let status = tokio::select! {
+        status = child.wait() => status,
+        _ = kill => {
+            let _ = child.kill().await;
+            child.wait().await
+        }
+    };
+    let exit = match status {
+        Ok(status) => ProcessExit::from(status),
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to wait for TEI process");
+            ProcessExit::Code(-1)
+        }
+    };
+    let _ = exit_tx.send(Some(exit));
 }
 
 impl SystemProcessManager {
@@ -77,63 +311,21 @@ impl Default for SystemProcessManager {
 impl ProcessManager for SystemProcessManager {
     async fn spawn(&self, config: SpawnConfig) -> Result<ProcessHandle> {
         let mut cmd = Command::new(&config.binary_path);
+        cmd.envs(config.env.iter().map(|(key, value)| (key, value)));
 
         // Set GPU assignment if specified
-        if let Some(gpu_id) = config.gpu_id {
-            cmd.env("CUDA_VISIBLE_DEVICES", gpu_id.to_string());
-            tracing::debug!(gpu_id = gpu_id, "Setting CUDA_VISIBLE_DEVICES");
-        }
-
-        // Build arguments from config
-        cmd.arg("--model-id").arg(&config.model_id);
-        cmd.arg("--port").arg(config.port.to_string());
-        cmd.arg("--max-batch-tokens")
-            .arg(config.max_batch_tokens.to_string());
-        cmd.arg("--max-concurrent-requests")
-            .arg(config.max_concurrent_requests.to_string());
-        cmd.arg("--json-output");
-
-        if let Some(pooling) = &config.pooling {
-            cmd.arg("--pooling").arg(pooling);
-        }
-
-        // Set Prometheus port if provided
-        let has_prometheus_port_in_extra_args = config
-            .extra_args
-            .iter()
-            .any(|arg| arg == "--prometheus-port");
-
-        if !has_prometheus_port_in_extra_args && let Some(prom_port) = config.prometheus_port {
-            cmd.arg("--prometheus-port").arg(prom_port.to_string());
+        if let Some(devices) = config.cuda_visible_devices() {
+            tracing::debug!(devices = %devices, "Setting CUDA_VISIBLE_DEVICES");
+            cmd.env("CUDA_VISIBLE_DEVICES", devices);
         }
 
-        // Add extra args
-        for arg in &config.extra_args {
-            cmd.arg(arg);
-        }
+        cmd.args(config.args());
 
         // Setup log file redirection
-        // Use env var if set, otherwise try /data/logs, fallback to /tmp/tei-manager/logs
-        let log_dir_path =
-            std::env::var("TEI_MANAGER_LOG_DIR").unwrap_or_else(|_| "/data/logs".to_string());
-
-        let log_dir = std::path::Path::new(&log_dir_path);
-
-        // Try to create the directory, fall back to /tmp if it fails
-        let log_dir = if let Err(e) = std::fs::create_dir_all(log_dir) {
-            tracing::warn!(
-                error = %e,
-                attempted_dir = %log_dir_path,
-                "Failed to create log directory, falling back to /tmp/tei-manager/logs"
-            );
-            let fallback = std::path::Path::new("/tmp/tei-manager/logs");
-            std::fs::create_dir_all(fallback).context("Failed to create fallback log directory")?;
-            fallback
-        } else {
-            log_dir
-        };
+        std::fs::create_dir_all(&config.log_dir)
+            .with_context(|| format!("Failed to create log directory: {:?}", config.log_dir))?;
 
-        let log_path = log_dir.join(format!("{}.log", config.instance_name));
+        let log_path = instance_log_path(&config.log_dir, &config.instance_name);
         let log_file = std::fs::OpenOptions::new()
             .create(true)
             .append(true)
@@ -153,7 +345,14 @@ impl ProcessManager for SystemProcessManager {
             .stderr(stderr_file)
             .kill_on_drop(true)
             .spawn()
-            .context("Failed to spawn TEI process")?;
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::NotFound | std::io::ErrorKind::PermissionDenied => {
+                    anyhow::Error::new(TeiError::TeiBinaryNotFound {
+                        path: config.binary_path.clone(),
+                    })
+                }
+                _ => anyhow::Error::new(e).context("Failed to spawn TEI process"),
+            })?;
 
         let pid = child.id().context("Failed to get PID")?;
         let handle_id = format!("process_{}", pid);
@@ -170,57 +369,82 @@ impl ProcessManager for SystemProcessManager {
             id: handle_id.clone(),
         };
 
-        self.processes.write().await.insert(handle_id, child);
+        let (exit_tx, exit) = watch::channel(None);
+        let (kill, kill_rx) = oneshot::channel();
+        tokio::spawn(reap(child, kill_rx, exit_tx));
+
+        self.processes
+            .write()
+            .await
+            .insert(handle_id, SystemProcess { pid, exit, kill });
 
         Ok(handle)
     }
 
     async fn stop(&self, handle: ProcessHandle, timeout: Duration) -> Result<()> {
-        let mut processes = self.processes.write().await;
-
-        if let Some(mut child) = processes.remove(&handle.id) {
-            // Try graceful shutdown first (SIGTERM)
-            if let Some(pid) = child.id() {
-                #[cfg(unix)]
-                {
-                    use nix::sys::signal::{Signal, kill};
-                    use nix::unistd::Pid;
-
-                    let pid = Pid::from_raw(pid as i32);
-                    let _ = kill(pid, Signal::SIGTERM);
-
-                    // Wait for graceful shutdown with timeout
-                    tokio::select! {
-                        _ = child.wait() => {
-                            tracing::info!("Process stopped gracefully");
-                        }
-                        _ = tokio::time::sleep(timeout) => {
-                            tracing::warn!("Graceful shutdown timeout, sending SIGKILL");
-                            let _ = kill(pid, Signal::SIGKILL);
-                            let _ = child.wait().await;
-                        }
-                    }
+        let Some(process) = self.processes.write().await.remove(&handle.id) else {
+            return Ok(());
+        };
+        if process.exit().is_some() {
+            return Ok(());
+        }
+        let SystemProcess {
+            pid,
+            mut exit,
+            kill,
+        } = process;
+
+        // Try graceful shutdown first (SIGTERM)
+        #[cfg(unix)]
+        {
+            use nix::sys::signal::{Signal, kill as send_signal};
+            use nix::unistd::Pid;
+
+            let _ = send_signal(Pid::from_raw(pid as i32), Signal::SIGTERM);
+
+            // Wait for graceful shutdown with timeout
+            tokio::select! {
+                _ = exit.wait_for(Option::is_some) => {
+                    tracing::info!("Process stopped gracefully");
+                    return Ok(());
                 }
-
-                #[cfg(not(unix))]
-                {
-                    // On non-Unix, just kill
-                    let _ = child.kill().await;
+                _ = tokio::time::sleep(timeout) => {
+                    tracing::warn!("Graceful shutdown timeout, sending SIGKILL");
                 }
             }
         }
+        #[cfg(not(unix))]
+        let _ = pid;
 
+        let _ = kill.send(());
+        let _ = exit.wait_for(Option::is_some).await;
         Ok(())
     }
 
     async fn is_running(&self, handle: &ProcessHandle) -> bool {
         let processes = self.processes.read().await;
-        processes.contains_key(&handle.id)
+        processes
+            .get(&handle.id)
+            .is_some_and(|process| process.exit().is_none())
     }
 
     async fn pid(&self, handle: &ProcessHandle) -> Option<u32> {
         let processes = self.processes.read().await;
-        processes.get(&handle.id).and_then(|p| p.id())
+        processes
+            .get(&handle.id)
+            .filter(|process| process.exit().is_none())
+            .map(|process| process.pid)
+    }
+
+    async fn exit_status(&self, handle: &ProcessHandle) -> Option<ProcessExit> {
+        let processes = self.processes.read().await;
+        processes.get(&handle.id)?.exit()
+    }
+
+    async fn wait(&self, handle: &ProcessHandle) -> Option<ProcessExit> {
+        let mut exit = self.processes.read().await.get(&handle.id)?.exit.clone();
+        let exit = exit.wait_for(Option::is_some).await.ok()?;
+        *exit
     }
 }
 
@@ -228,6 +452,71 @@ impl ProcessManager for SystemProcessManager {
 // TEI Instance with Dependency Injection
 // ============================================================================
 
+/// Command run before an instance's process is stopped, see [`TeiInstance::stop`]
+#[derive(Debug, Clone)]
+pub struct PreStopHook {
+    /// Manager-wide command; an instance's `pre_stop_command` takes precedence
+    pub command: Option<String>,
+    /// How long the command may run before it is killed
+    pub timeout: Duration,
+    /// Abort the stop when the command fails or times out
+    pub required: bool,
+}
+
+impl Default for PreStopHook {
+    fn default() -> Self {
+        Self::from_config(&crate::config::ManagerConfig::default())
+    }
+}
+
+impl PreStopHook {
+    /// Hook settings from the manager configuration
+    pub fn from_config(config: &crate::config::ManagerConfig) -> Self {
+        Self {
+            command: config.pre_stop_command.clone(),
+            timeout: Duration::from_secs(config.pre_stop_timeout_secs),
+            required: config.pre_stop_required,
+        }
+    }
+}
+
+/// Respawning of a process that fails to bind its port, see [`TeiInstance::start`]
+#[derive(Debug, Clone, Copy)]
+pub struct BindRetry {
+    /// Respawns after a bind failure (0 = don't watch the new process at all)
+    pub retries: u32,
+    /// How long a new process is watched for an early bind failure
+    pub window: Duration,
+    /// Pause before respawning, giving the old socket time to be released
+    pub delay: Duration,
+}
+
+/// Output (lowercased) of a process that could not bind its listening port
+const BIND_FAILURE_MARKERS: &[&str] = &["address already in use", "addrinuse"];
+
+/// Most output of an exited process searched for [`BIND_FAILURE_MARKERS`]
+///
+/// A bind failure is reported right at startup, so the start of the new output
+/// is enough; the log itself grows across every run and is never rotated.
+const BIND_FAILURE_LOG_BYTES: u64 = 64 * 1024;
+
+impl Default for BindRetry {
+    fn default() -> Self {
+        Self::from_config(&crate::config::ManagerConfig::default())
+    }
+}
+
+impl BindRetry {
+    /// Retry settings from the manager configuration
+    pub fn from_config(config: &crate::config::ManagerConfig) -> Self {
+        Self {
+            retries: config.start_bind_retries,
+            window: Duration::from_secs(5),
+            delay: Duration::from_secs(1),
+        }
+    }
+}
+
 /// TEI instance with process and status tracking
 pub struct TeiInstance {
     pub config: InstanceConfig,
@@ -235,10 +524,87 @@ pub struct TeiInstance {
     process_handle: Arc<RwLock<Option<ProcessHandle>>>,
     pub status: Arc<RwLock<InstanceStatus>>,
     pub stats: Arc<RwLock<InstanceStats>>,
+    /// Per-instance maintenance mode: the health monitor does not restart this instance
+    maintenance: Arc<AtomicBool>,
+    /// Paused: the multiplexer routes no new requests here, the process keeps running
+    paused: Arc<AtomicBool>,
+    /// Being restarted by the health monitor, until it is healthy again
+    restarting: Arc<AtomicBool>,
+    /// Stopped through [`Self::stop`] and not started since
+    stopped_on_purpose: Arc<AtomicBool>,
+    /// Manager-wide args placed before `config.extra_args` at start (never persisted)
+    default_extra_args: Arc<[String]>,
+    /// Manager-wide RUST_LOG used when `config.log_level` is unset
+    default_log_level: Option<Arc<str>>,
+    /// Directory the process output is captured in
+    log_dir: Arc<Path>,
+    /// Manager-wide pre-stop hook settings
+    pre_stop: Arc<PreStopHook>,
+    /// Respawning of processes that fail to bind their port
+    bind_retry: BindRetry,
+    /// Startup deadline extension while the model download progresses (zero = none)
+    startup_download_grace: Duration,
+    /// `max_batch_tokens` lowered after an OOM kill (0 = use the configured value)
+    oom_max_batch_tokens: Arc<AtomicU32>,
+    /// Requests currently being forwarded to this instance
+    inflight: InflightCounter,
+    /// Where status changes and unexpected process exits are announced
+    events: Option<EventSender>,
+    /// GPU memory estimated from the model when the instance was added
+    footprint_estimate: Option<u64>,
+}
+
+/// Count of requests being forwarded to one instance
+///
+/// Shared by the instance and the backend clients that forward to it, and kept in
+/// step with the `tei_instance_inflight` gauge.
+#[derive(Debug, Clone)]
+pub struct InflightCounter {
+    instance_name: Arc<str>,
+    count: Arc<AtomicUsize>,
+}
+
+impl InflightCounter {
+    fn new(instance_name: &str) -> Self {
+        Self {
+            instance_name: Arc::from(instance_name),
+            count: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Requests currently in flight
+    pub fn get(&self) -> usize {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    /// Count one forwarded request until the returned guard is dropped
+    pub fn begin(&self) -> InflightRequest {
+        let count = self.count.fetch_add(1, Ordering::Relaxed) + 1;
+        crate::metrics::update_instance_inflight(&self.instance_name, count);
+        InflightRequest(self.clone())
+    }
+}
+
+/// A request counted in an instance's [`InflightCounter`] while alive
+#[derive(Debug)]
+pub struct InflightRequest(InflightCounter);
+
+impl Drop for InflightRequest {
+    fn drop(&mut self) {
+        let count = self.0.count.fetch_sub(1, Ordering::Relaxed) - 1;
+        crate::metrics::update_instance_inflight(&self.0.instance_name, count);
+    }
 }
 
+/// Lowest `max_batch_tokens` an OOM backoff reduces an instance to
+pub const MIN_OOM_BATCH_TOKENS: u32 = 512;
+
+/// Pause between stopping and starting the process on a restart, so the old
+/// process releases its port and GPU memory first
+pub const RESTART_DELAY: Duration = Duration::from_secs(2);
+
 /// Instance status
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum InstanceStatus {
     Starting,
@@ -246,26 +612,185 @@ pub enum InstanceStatus {
     Stopping,
     Stopped,
     Failed,
+    /// Stopped by the `quarantine` failure policy; not routed to, health checked
+    /// or restarted until cleared via the unquarantine endpoint
+    Quarantined,
+}
+
+impl std::fmt::Display for InstanceStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InstanceStatus::Starting => write!(f, "starting"),
+            InstanceStatus::Running => write!(f, "running"),
+            InstanceStatus::Stopping => write!(f, "stopping"),
+            InstanceStatus::Stopped => write!(f, "stopped"),
+            InstanceStatus::Failed => write!(f, "failed"),
+            InstanceStatus::Quarantined => write!(f, "quarantined"),
+        }
+    }
+}
+
+/// Allocator settings variable read by TEI's Python (PyTorch) backend
+pub const CUDA_ALLOC_CONF_ENV: &str = "PYTORCH_CUDA_ALLOC_CONF";
+
+/// PYTORCH_CUDA_ALLOC_CONF value setting the allocator's garbage collection threshold
+///
+/// Once usage passes `fraction` of GPU memory, PyTorch's caching allocator starts
+/// reclaiming unused cached blocks. This is not a cap: allocations beyond the
+/// threshold still succeed while memory is free. PyTorch rejects a threshold of
+/// 1, which would never trigger anyway, so nothing is set for it.
+pub fn cuda_gc_threshold_for_fraction(fraction: f32) -> Option<String> {
+    (fraction < 1.0).then(|| format!("garbage_collection_threshold:{}", fraction))
+}
+
+/// Log file for instance `name` in `log_dir`, shared by spawn and the logs endpoints
+pub fn instance_log_path(log_dir: &Path, name: &str) -> PathBuf {
+    log_dir.join(format!("{}.log", name))
+}
+
+/// Up to `limit` bytes of the file at `path`, starting at `offset`
+async fn read_log_from(path: &Path, offset: u64, limit: u64) -> std::io::Result<Vec<u8>> {
+    use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+    let mut file = tokio::fs::File::open(path).await?;
+    file.seek(std::io::SeekFrom::Start(offset)).await?;
+    let mut buf = Vec::new();
+    file.take(limit).read_to_end(&mut buf).await?;
+    Ok(buf)
+}
+
+/// TEI short flags and the long flags they stand for
+const SHORT_FLAGS: &[(&str, &str)] = &[("-p", "--port")];
+
+/// Canonical (long) name of a flag argument, or None for a value
+///
+/// Handles `--flag`, `--flag=value`, `-f`, `-f=value` and `-fvalue`; short
+/// flags TEI knows are mapped to their long form.
+fn flag_name(arg: &str) -> Option<String> {
+    if arg.starts_with("--") {
+        return Some(
+            arg.split_once('=')
+                .map_or(arg, |(flag, _)| flag)
+                .to_string(),
+        );
+    }
+    let short = arg.get(..2).filter(|short| {
+        short.starts_with('-') && short[1..].starts_with(|c: char| c.is_ascii_alphabetic())
+    })?;
+    let long = SHORT_FLAGS
+        .iter()
+        .find_map(|(s, long)| (*s == short).then_some(*long));
+    Some(long.unwrap_or(short).to_string())
+}
+
+/// Prepend `defaults` to `own`, skipping default flags (and their values) that `own`
+/// or one of the `typed` flags (set from instance fields) overrides
+fn merge_extra_args(defaults: &[String], own: &[String], typed: &[&str]) -> Vec<String> {
+    let own_flags: std::collections::HashSet<String> = own
+        .iter()
+        .filter_map(|arg| flag_name(arg))
+        .chain(typed.iter().map(|flag| flag.to_string()))
+        .collect();
+
+    let mut merged = Vec::with_capacity(defaults.len() + own.len());
+    let mut args = defaults.iter().peekable();
+    while let Some(arg) = args.next() {
+        let flag = flag_name(arg);
+        // `--flag value` / `-f value` form: the value is the next token unless that
+        // is a flag too
+        let takes_next =
+            flag.is_some() && !arg.contains('=') && (arg.starts_with("--") || arg.len() == 2);
+        let value = if takes_next {
+            args.next_if(|next| flag_name(next).is_none())
+        } else {
+            None
+        };
+        if flag.is_some_and(|flag| own_flags.contains(&flag)) {
+            continue;
+        }
+        merged.push(arg.clone());
+        merged.extend(value.cloned());
+    }
+    merged.extend(own.iter().cloned());
+    merged
 }
 
 /// Instance statistics
 #[derive(Debug, Clone, Default, Serialize)]
 pub struct InstanceStats {
     pub started_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Restarts triggered by health check failures
     pub restarts: u32,
+    /// Graceful restarts requested via the API
+    pub manual_restart_count: u32,
     pub last_health_check: Option<chrono::DateTime<chrono::Utc>>,
     pub health_check_failures: u32,
+    /// Consecutive hard failures (process not running)
+    pub health_check_hard_failures: u32,
+    /// Last time a request was forwarded to this instance
+    pub last_request_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Outcome of the last Prometheus endpoint probe (None until probed)
+    pub metrics_reachable: Option<bool>,
+    /// Reason the most recent health check failed (cleared on success)
+    pub last_health_error: Option<String>,
+    /// Process exits that looked like OOM kills
+    pub oom_kills: u32,
+    /// How the last process exited, once the health monitor noticed (cleared on start)
+    pub last_exit: Option<ProcessExit>,
+    /// Most recent health check results, oldest first
+    pub health_history: VecDeque<HealthCheckRecord>,
+    /// Embedding dimension from the first dimension probe, which later probes must match
+    pub embedding_dimension: Option<usize>,
+    /// When the health monitor restarted the instance, for `max_restarts_per_window`
+    #[serde(skip)]
+    pub recent_restarts: VecDeque<tokio::time::Instant>,
+}
+
+impl InstanceStats {
+    /// Append a health check result, keeping at most `capacity` of them
+    pub fn record_health_check(&mut self, record: HealthCheckRecord, capacity: usize) {
+        if capacity == 0 {
+            return;
+        }
+        while self.health_history.len() >= capacity {
+            self.health_history.pop_front();
+        }
+        self.health_history.push_back(record);
+    }
+}
+
+/// One entry of [`InstanceStats::health_history`]
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthCheckRecord {
+    pub checked_at: chrono::DateTime<chrono::Utc>,
+    pub healthy: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
 }
 
 impl TeiInstance {
     /// Create a new TEI instance with custom process manager
     pub fn new_with_manager(config: InstanceConfig, manager: Arc<dyn ProcessManager>) -> Self {
         Self {
-            config,
             process_manager: manager,
             process_handle: Arc::new(RwLock::new(None)),
             status: Arc::new(RwLock::new(InstanceStatus::Stopped)),
             stats: Arc::new(RwLock::new(InstanceStats::default())),
+            maintenance: Arc::new(AtomicBool::new(false)),
+            paused: Arc::new(AtomicBool::new(false)),
+            restarting: Arc::new(AtomicBool::new(false)),
+            stopped_on_purpose: Arc::new(AtomicBool::new(false)),
+            default_extra_args: Arc::from([]),
+            default_log_level: None,
+            log_dir: Arc::from(crate::config::default_log_dir()),
+            pre_stop: Arc::new(PreStopHook::default()),
+            bind_retry: BindRetry::default(),
+            startup_download_grace: Duration::ZERO,
+            oom_max_batch_tokens: Arc::new(AtomicU32::new(0)),
+            inflight: InflightCounter::new(&config.name),
+            events: None,
+            footprint_estimate: None,
+            config,
         }
     }
 
@@ -274,133 +799,723 @@ impl TeiInstance {
         Self::new_with_manager(config, Arc::new(SystemProcessManager::new()))
     }
 
-    /// Start the TEI process
-    pub async fn start(&self, tei_binary_path: &str) -> Result<()> {
-        let spawn_config = SpawnConfig {
-            instance_name: self.config.name.clone(),
-            binary_path: tei_binary_path.to_string(),
-            model_id: self.config.model_id.clone(),
-            port: self.config.port,
-            max_batch_tokens: self.config.max_batch_tokens,
-            max_concurrent_requests: self.config.max_concurrent_requests,
-            pooling: self.config.pooling.clone(),
-            gpu_id: self.config.gpu_id,
-            prometheus_port: self.config.prometheus_port,
-            extra_args: self.config.extra_args.clone(),
-        };
-
-        let handle = self.process_manager.spawn(spawn_config).await?;
-        let pid = self.process_manager.pid(&handle).await;
-
-        *self.process_handle.write().await = Some(handle);
-        *self.status.write().await = InstanceStatus::Starting;
+    /// Args applied before this instance's own `extra_args` at start
+    pub fn with_default_extra_args(mut self, args: Arc<[String]>) -> Self {
+        self.default_extra_args = args;
+        self
+    }
 
-        // Update stats
-        let mut stats = self.stats.write().await;
-        stats.started_at = Some(chrono::Utc::now());
+    /// RUST_LOG used when this instance doesn't set its own `log_level`
+    pub fn with_default_log_level(mut self, level: Option<Arc<str>>) -> Self {
+        self.default_log_level = level;
+        self
+    }
 
-        tracing::info!(
-            instance = %self.config.name,
-            model = %self.config.model_id,
-            port = self.config.port,
-            pid = ?pid,
-            gpu_id = ?self.config.gpu_id,
-            "TEI instance started"
-        );
+    /// Capture process output in `log_dir` instead of the default /data/logs
+    pub fn with_log_dir(mut self, log_dir: Arc<Path>) -> Self {
+        self.log_dir = log_dir;
+        self
+    }
 
-        Ok(())
+    /// Pre-stop hook settings (the command may be overridden by `config.pre_stop_command`)
+    pub fn with_pre_stop_hook(mut self, hook: Arc<PreStopHook>) -> Self {
+        self.pre_stop = hook;
+        self
     }
 
-    /// Stop the TEI process gracefully
-    pub async fn stop(&self) -> Result<()> {
-        *self.status.write().await = InstanceStatus::Stopping;
+    /// Respawn the process when it exits early because its port is still in use
+    pub fn with_bind_retry(mut self, bind_retry: BindRetry) -> Self {
+        self.bind_retry = bind_retry;
+        self
+    }
 
-        let mut handle_guard = self.process_handle.write().await;
+    /// Keep waiting for readiness at least `grace` after the model download last progressed
+    pub fn with_startup_download_grace(mut self, grace: Duration) -> Self {
+        self.startup_download_grace = grace;
+        self
+    }
 
-        if let Some(handle) = handle_guard.take() {
-            self.process_manager
-                .stop(handle, Duration::from_secs(30))
-                .await?;
+    /// How far download progress pushes the startup deadline (zero = not at all)
+    pub fn startup_download_grace(&self) -> Duration {
+        self.startup_download_grace
+    }
 
-            tracing::info!(instance = %self.config.name, "Instance stopped");
-        }
+    /// Announce status changes and unexpected process exits on `events`
+    pub fn with_events(mut self, events: EventSender) -> Self {
+        self.events = Some(events);
+        self
+    }
 
-        *self.status.write().await = InstanceStatus::Stopped;
-        Ok(())
+    /// GPU memory estimated from the model (see [`crate::gpu::estimate_footprint`])
+    pub fn with_footprint_estimate(mut self, estimate: Option<u64>) -> Self {
+        self.footprint_estimate = estimate;
+        self
     }
 
-    /// Restart the instance
-    pub async fn restart(&self, tei_binary_path: &str) -> Result<()> {
-        tracing::info!(instance = %self.config.name, "Restarting instance");
+    /// GPU memory estimated from the model when the instance was added
+    pub fn footprint_estimate(&self) -> Option<u64> {
+        self.footprint_estimate
+    }
 
-        self.stop().await?;
-        tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
-        self.start(tei_binary_path).await?;
+    /// File the process output is captured in
+    pub fn log_path(&self) -> PathBuf {
+        instance_log_path(&self.log_dir, &self.config.name)
+    }
 
-        let mut stats = self.stats.write().await;
-        stats.restarts += 1;
+    /// Log level the process is launched with (None = TEI's default)
+    pub fn log_level(&self) -> Option<&str> {
+        self.config
+            .log_level
+            .as_deref()
+            .or(self.default_log_level.as_deref())
+    }
 
-        Ok(())
+    /// Launch environment before `${VAR}` resolution
+    ///
+    /// Variables derived from instance settings come first, then `file_vars`
+    /// (from `env_file`), then `env`; later layers win, so an explicit RUST_LOG or
+    /// PYTORCH_CUDA_ALLOC_CONF overrides the derived one.
+    fn launch_env(
+        &self,
+        env: &std::collections::BTreeMap<String, String>,
+        file_vars: Vec<(String, String)>,
+    ) -> std::collections::BTreeMap<String, String> {
+        let mut vars = self.derived_env();
+        vars.extend(file_vars);
+        vars.extend(env.iter().map(|(key, value)| (key.clone(), value.clone())));
+        vars
     }
 
-    /// Check if process is still running
-    pub async fn is_running(&self) -> bool {
-        let handle_guard = self.process_handle.read().await;
-        if let Some(handle) = handle_guard.as_ref() {
-            self.process_manager.is_running(handle).await
-        } else {
-            false
+    /// Environment derived from instance settings, which `env_file` and `env` override
+    fn derived_env(&self) -> std::collections::BTreeMap<String, String> {
+        let mut env = std::collections::BTreeMap::new();
+        if let Some(level) = self.log_level() {
+            env.insert("RUST_LOG".to_string(), level.to_string());
+        }
+        if let Some(conf) = self
+            .config
+            .gpu_memory_fraction
+            .and_then(cuda_gc_threshold_for_fraction)
+        {
+            env.insert(CUDA_ALLOC_CONF_ENV.to_string(), conf);
         }
+        env
     }
 
-    /// Get current PID
-    pub async fn pid(&self) -> Option<u32> {
-        let handle_guard = self.process_handle.read().await;
-        if let Some(handle) = handle_guard.as_ref() {
-            self.process_manager.pid(handle).await
-        } else {
-            None
+    /// Extra args the process is launched with, before `${VAR}` resolution
+    ///
+    /// Defaults come first; a default flag the instance sets itself, in extra_args
+    /// or through `pooling`/`dtype`, is dropped so TEI never sees it twice.
+    pub fn launch_extra_args(&self) -> Vec<String> {
+        let typed: Vec<&str> = [
+            ("--pooling", self.config.pooling.is_some()),
+            ("--dtype", self.config.dtype.is_some()),
+        ]
+        .into_iter()
+        .filter_map(|(flag, is_set)| is_set.then_some(flag))
+        .collect();
+        merge_extra_args(&self.default_extra_args, &self.config.extra_args, &typed)
+    }
+
+    /// Spawn settings the next start would use, for display
+    ///
+    /// Built exactly like [`TeiInstance::start`] does, except that secrets are
+    /// redacted and `${VAR}` references are left unresolved. `env_file` is not read.
+    pub fn launch_preview(&self, tei_binary_path: &str) -> SpawnConfig {
+        let config = InstanceConfig {
+            extra_args: self.launch_extra_args(),
+            ..self.config.clone()
         }
+        .redacted();
+
+        let env = self.launch_env(&config.env, Vec::new());
+        self.spawn_config(
+            &config,
+            tei_binary_path,
+            env.into_iter().collect(),
+            config.extra_args.clone(),
+        )
     }
-}
 
-// ============================================================================
-// Mock Implementation for Testing
-// ============================================================================
+    /// Spawn settings for `config` with the given launch `env` and `extra_args`
+    ///
+    /// Shared by [`Self::start`] and [`Self::launch_preview`], so a preview always
+    /// matches what a start spawns.
+    fn spawn_config(
+        &self,
+        config: &InstanceConfig,
+        tei_binary_path: &str,
+        env: Vec<(String, String)>,
+        extra_args: Vec<String>,
+    ) -> SpawnConfig {
+        let mut spawn_config = SpawnConfig::new(config, tei_binary_path, env, extra_args);
+        spawn_config.max_batch_tokens = self.max_batch_tokens();
+        spawn_config.log_dir = self.log_dir.to_path_buf();
+        spawn_config
+    }
 
-#[cfg(test)]
-pub mod mocks {
-    use super::*;
-    use std::collections::HashMap;
+    /// Enable or disable maintenance mode for this instance
+    pub fn set_maintenance(&self, enabled: bool) {
+        self.maintenance.store(enabled, Ordering::SeqCst);
+    }
 
-    /// Mock process manager for testing
-    pub struct MockProcessManager {
-        processes: Arc<RwLock<HashMap<String, ProcessState>>>,
-        next_id: Arc<RwLock<u32>>,
+    /// Whether this instance is in maintenance mode
+    pub fn in_maintenance(&self) -> bool {
+        self.maintenance.load(Ordering::SeqCst)
     }
 
-    #[derive(Debug, Clone)]
-    struct ProcessState {
-        pid: u32,
-        running: bool,
-        config: SpawnConfig,
+    /// Pause or resume routing to this instance
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::SeqCst);
     }
 
-    impl Default for MockProcessManager {
-        fn default() -> Self {
-            Self::new()
+    /// Whether new requests are kept away from this instance
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    /// Mark the instance as being restarted (or back up) for request routing
+    pub fn set_restarting(&self, restarting: bool) {
+        self.restarting.store(restarting, Ordering::SeqCst);
+    }
+
+    /// Whether the instance is mid-restart and should not be routed to
+    pub fn is_restarting(&self) -> bool {
+        self.restarting.load(Ordering::SeqCst)
+    }
+
+    /// Whether the instance was stopped via [`Self::stop`] (API, idle reaper,
+    /// quarantine) and not started since, so the health monitor leaves it alone
+    pub fn is_stopped_on_purpose(&self) -> bool {
+        self.stopped_on_purpose.load(Ordering::SeqCst)
+    }
+
+    /// Whether a restart is actually in progress
+    ///
+    /// The status is read first: a failed or quarantined instance is not coming
+    /// back on its own, whatever the restarting flag says.
+    pub async fn restart_in_progress(&self) -> bool {
+        let status = *self.status.read().await;
+        !matches!(status, InstanceStatus::Failed | InstanceStatus::Quarantined)
+            && self.is_restarting()
+    }
+
+    /// Whether routing by model id may pick this instance: running, not paused
+    /// and not mid-restart
+    pub async fn model_routable(&self) -> bool {
+        *self.status.read().await == InstanceStatus::Running
+            && !self.is_paused()
+            && !self.is_restarting()
+    }
+
+    /// Set the status, announcing a change as [`InstanceEvent::StatusChanged`]
+    ///
+    /// Returns the previous status.
+    pub async fn set_status(&self, to: InstanceStatus) -> InstanceStatus {
+        let from = std::mem::replace(&mut *self.status.write().await, to);
+        if from != to
+            && let Some(events) = &self.events
+        {
+            events.send(InstanceEvent::StatusChanged {
+                name: self.config.name.clone(),
+                from,
+                to,
+            });
         }
+        from
     }
 
-    impl MockProcessManager {
-        pub fn new() -> Self {
-            Self {
-                processes: Arc::new(RwLock::new(HashMap::new())),
-                next_id: Arc::new(RwLock::new(1000)),
-            }
+    /// `max_batch_tokens` the next start uses, after any OOM backoff
+    pub fn max_batch_tokens(&self) -> u32 {
+        match self.oom_max_batch_tokens.load(Ordering::SeqCst) {
+            0 => self.config.max_batch_tokens,
+            reduced => reduced,
         }
+    }
 
-        /// Get the number of active processes
+    /// Scale `max_batch_tokens` for the next start by `factor` (never below
+    /// [`MIN_OOM_BATCH_TOKENS`]), returning the new value
+    ///
+    /// The reduction compounds across OOM kills and is not persisted.
+    pub fn reduce_max_batch_tokens(&self, factor: f64) -> u32 {
+        let current = self.max_batch_tokens();
+        let reduced = ((current as f64 * factor) as u32)
+            .max(MIN_OOM_BATCH_TOKENS)
+            .min(current);
+        self.oom_max_batch_tokens.store(reduced, Ordering::SeqCst);
+        reduced
+    }
+
+    /// Start the TEI process
+    ///
+    /// Secrets referenced via `env_file` or `${VAR}` are resolved here, so the
+    /// stored config (and persisted state) only ever holds the references.
+    ///
+    /// With [`BindRetry::retries`] set, the new process is watched for a short
+    /// window and respawned if it exits because its port is still in use; startup
+    /// fails once the retries are used up.
+    ///
+    /// A direct start supersedes any restart in progress, so the restarting
+    /// flag is cleared.
+    pub async fn start(&self, tei_binary_path: &str) -> Result<()> {
+        self.set_restarting(false);
+        self.stopped_on_purpose.store(false, Ordering::SeqCst);
+        self.start_process(tei_binary_path).await
+    }
+
+    /// Spawn the TEI process, leaving the restarting flag alone (see [`Self::start`])
+    async fn start_process(&self, tei_binary_path: &str) -> Result<()> {
+        self.ensure_not_quarantined().await?;
+        let (env, extra_args) = self.resolve_env_and_args().await?;
+
+        let spawn_config = self.spawn_config(&self.config, tei_binary_path, env, extra_args);
+
+        // TEI fetches a revision it doesn't have, so a mismatch is only worth a warning
+        let revision_check = match spawn_config.requested_revision() {
+            Some(revision) => {
+                let cache_dir = self.config.model_cache_dir();
+                let model_id = self.config.model_id.clone();
+                let revision = revision.to_string();
+                tokio::task::spawn_blocking(move || {
+                    crate::models::cache::check_revision_in(&cache_dir, &model_id, &revision)
+                })
+                .await
+                .ok()
+            }
+            None => None,
+        };
+        if let Some(revision) = spawn_config.requested_revision()
+            && let Some(crate::models::RevisionCheck::Mismatch { cached }) = revision_check
+        {
+            tracing::warn!(
+                instance = %self.config.name,
+                model = %self.config.model_id,
+                requested = %revision,
+                cached = %cached,
+                "Requested model revision differs from the cached snapshot"
+            );
+        }
+
+        let mut attempt = 0;
+        let handle = loop {
+            let log_offset = tokio::fs::metadata(self.log_path())
+                .await
+                .map_or(0, |m| m.len());
+            let handle = self.process_manager.spawn(spawn_config.clone()).await?;
+            if self.bind_retry.retries == 0
+                || !self.exited_on_bind_failure(&handle, log_offset).await
+            {
+                break handle;
+            }
+
+            // The process already exited, this only drops its bookkeeping
+            self.process_manager.stop(handle, Duration::ZERO).await?;
+            if attempt == self.bind_retry.retries {
+                anyhow::bail!(
+                    "Instance '{}' could not bind port {} after {} retries",
+                    self.config.name,
+                    self.config.port,
+                    attempt
+                );
+            }
+            attempt += 1;
+            tracing::warn!(
+                instance = %self.config.name,
+                port = self.config.port,
+                attempt,
+                max_retries = self.bind_retry.retries,
+                "Port in use at startup, respawning"
+            );
+            tokio::time::sleep(self.bind_retry.delay).await;
+        };
+        let pid = self.process_manager.pid(&handle).await;
+
+        *self.process_handle.write().await = Some(handle.clone());
+        self.set_status(InstanceStatus::Starting).await;
+        self.watch_exit(handle);
+
+        // Update stats
+        let mut stats = self.stats.write().await;
+        stats.started_at = Some(chrono::Utc::now());
+        stats.last_exit = None;
+
+        tracing::info!(
+            instance = %self.config.name,
+            model = %self.config.model_id,
+            port = self.config.port,
+            pid = ?pid,
+            gpu_id = ?self.config.gpu_id,
+            "TEI instance started"
+        );
+
+        Ok(())
+    }
+
+    /// Mark the instance failed as soon as the process behind `handle` exits
+    ///
+    /// Exits caused by [`TeiInstance::stop`] or a later start are ignored.
+    fn watch_exit(&self, handle: ProcessHandle) {
+        let process_manager = self.process_manager.clone();
+        let process_handle = self.process_handle.clone();
+        let status = self.status.clone();
+        let events = self.events.clone();
+        let name = self.config.name.clone();
+
+        tokio::spawn(async move {
+            let Some(exit) = process_manager.wait(&handle).await else {
+                return;
+            };
+            let current = process_handle.read().await.as_ref().map(|h| h.id.clone());
+            if current.as_ref() != Some(&handle.id) {
+                return;
+            }
+            let mut status = status.write().await;
+            if matches!(*status, InstanceStatus::Stopping | InstanceStatus::Stopped) {
+                return;
+            }
+            let from = std::mem::replace(&mut *status, InstanceStatus::Failed);
+            drop(status);
+
+            tracing::warn!(
+                instance = %name,
+                exit = %exit,
+                from = ?from,
+                "Instance process exited unexpectedly"
+            );
+            if let Some(events) = events {
+                events.send(InstanceEvent::StatusChanged {
+                    name: name.clone(),
+                    from,
+                    to: InstanceStatus::Failed,
+                });
+                events.send(InstanceEvent::Exited { name, exit });
+            }
+        });
+    }
+
+    /// Whether the process exited within the bind-check window after logging a
+    /// port-in-use error (only output past `log_offset` is considered)
+    async fn exited_on_bind_failure(&self, handle: &ProcessHandle, log_offset: u64) -> bool {
+        let deadline = tokio::time::Instant::now() + self.bind_retry.window;
+        while self.process_manager.exit_status(handle).await.is_none() {
+            if tokio::time::Instant::now() >= deadline {
+                return false;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+
+        let Ok(log) = read_log_from(&self.log_path(), log_offset, BIND_FAILURE_LOG_BYTES).await
+        else {
+            return false;
+        };
+        let output = String::from_utf8_lossy(&log).to_lowercase();
+        BIND_FAILURE_MARKERS
+            .iter()
+            .any(|marker| output.contains(marker))
+    }
+
+    /// Resolve the process environment and arguments from the manager's environment
+    async fn resolve_env_and_args(&self) -> Result<(Vec<(String, String)>, Vec<String>)> {
+        let lookup = |name: &str| std::env::var(name).ok();
+
+        let file_vars = match &self.config.env_file {
+            Some(path) => {
+                let content = tokio::fs::read_to_string(path)
+                    .await
+                    .with_context(|| format!("Failed to read env_file {}", path.display()))?;
+                parse_env_file(&content)?
+            }
+            None => Vec::new(),
+        };
+
+        let env = self
+            .launch_env(&self.config.env, file_vars)
+            .into_iter()
+            .map(|(key, value)| {
+                let value = interpolate_env(&value, lookup)
+                    .with_context(|| format!("Failed to resolve env var '{}'", key))?;
+                Ok((key, value))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let extra_args = self
+            .launch_extra_args()
+            .iter()
+            .map(|arg| interpolate_env(arg, lookup))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok((env, extra_args))
+    }
+
+    /// Stop the TEI process gracefully
+    ///
+    /// If a process is running and a pre-stop command is configured, it runs first.
+    /// A failing command only aborts the stop when the hook is `required`.
+    /// A stopped instance is not restarting, so the restarting flag is cleared.
+    /// Until the next [`Self::start`] the instance counts as stopped on purpose;
+    /// a stop that fails leaves the process running and is not counted.
+    pub async fn stop(&self) -> Result<()> {
+        self.set_restarting(false);
+        // Set before the status turns Stopped, so no health check sees a gap
+        self.stopped_on_purpose.store(true, Ordering::SeqCst);
+        let result = self.stop_process().await;
+        if result.is_err() {
+            self.stopped_on_purpose.store(false, Ordering::SeqCst);
+        }
+        result
+    }
+
+    /// Stop the TEI process, leaving the restarting flag alone (see [`Self::stop`])
+    async fn stop_process(&self) -> Result<()> {
+        // Stopping must not clear a quarantine, and without a process there is
+        // nothing left to stop (nor a transition to announce when already stopped)
+        let status = *self.status.read().await;
+        let quarantined = status == InstanceStatus::Quarantined;
+        if matches!(
+            status,
+            InstanceStatus::Quarantined | InstanceStatus::Stopped
+        ) && self.process_handle.read().await.is_none()
+        {
+            return Ok(());
+        }
+        if let Some(pid) = self.pid().await {
+            self.run_pre_stop_hook(pid).await?;
+        }
+
+        if !quarantined {
+            self.set_status(InstanceStatus::Stopping).await;
+        }
+
+        let mut handle_guard = self.process_handle.write().await;
+
+        // Cleared only after the stop returns, so a cancelled stop leaves it for force_kill
+        if let Some(handle) = handle_guard.clone() {
+            let result = self
+                .process_manager
+                .stop(handle, Duration::from_secs(30))
+                .await;
+            *handle_guard = None;
+            result?;
+
+            tracing::info!(instance = %self.config.name, "Instance stopped");
+        }
+
+        if !quarantined {
+            self.set_status(InstanceStatus::Stopped).await;
+        }
+        Ok(())
+    }
+
+    /// Stop the instance and mark it [`InstanceStatus::Quarantined`]
+    ///
+    /// If the stop fails the process may still be serving, so the instance is
+    /// not marked and the error is returned.
+    pub async fn quarantine(&self) -> Result<()> {
+        self.stop().await?;
+        self.set_status(InstanceStatus::Quarantined).await;
+        tracing::warn!(instance = %self.config.name, "Instance quarantined");
+        Ok(())
+    }
+
+    /// Clear a quarantine, leaving the instance stopped with its failure counts reset
+    pub async fn unquarantine(&self) -> Result<()> {
+        let mut status = self.status.write().await;
+        if *status != InstanceStatus::Quarantined {
+            return Err(TeiError::InvalidInstanceState {
+                name: self.config.name.clone(),
+                current_state: status.to_string(),
+                expected_state: InstanceStatus::Quarantined.to_string(),
+            }
+            .into());
+        }
+        *status = InstanceStatus::Stopped;
+        drop(status);
+
+        let mut stats = self.stats.write().await;
+        stats.health_check_failures = 0;
+        stats.health_check_hard_failures = 0;
+        stats.recent_restarts.clear();
+        drop(stats);
+
+        if let Some(events) = &self.events {
+            events.send(InstanceEvent::StatusChanged {
+                name: self.config.name.clone(),
+                from: InstanceStatus::Quarantined,
+                to: InstanceStatus::Stopped,
+            });
+        }
+        tracing::info!(instance = %self.config.name, "Instance quarantine cleared");
+        Ok(())
+    }
+
+    async fn ensure_not_quarantined(&self) -> Result<()> {
+        if *self.status.read().await == InstanceStatus::Quarantined {
+            return Err(TeiError::InvalidInstanceState {
+                name: self.config.name.clone(),
+                current_state: InstanceStatus::Quarantined.to_string(),
+                expected_state: "not quarantined (clear it via /unquarantine)".to_string(),
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Kill the process at once, skipping the pre-stop hook and the grace period
+    ///
+    /// For shutdowns that ran out of time while [`TeiInstance::stop`] was pending.
+    pub async fn force_kill(&self) -> Result<()> {
+        if let Some(handle) = self.process_handle.write().await.take() {
+            self.process_manager.stop(handle, Duration::ZERO).await?;
+            tracing::warn!(instance = %self.config.name, "Instance force-killed");
+        }
+
+        self.set_status(InstanceStatus::Stopped).await;
+        Ok(())
+    }
+
+    /// Run the pre-stop command for the process `pid`, if one is configured
+    async fn run_pre_stop_hook(&self, pid: u32) -> Result<()> {
+        let Some(command) = self
+            .config
+            .pre_stop_command
+            .as_deref()
+            .or(self.pre_stop.command.as_deref())
+        else {
+            return Ok(());
+        };
+
+        let output = Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .env("TEI_INSTANCE_NAME", &self.config.name)
+            .env("TEI_INSTANCE_PORT", self.config.port.to_string())
+            .env("TEI_INSTANCE_PID", pid.to_string())
+            .env("TEI_MODEL_ID", &self.config.model_id)
+            .stdin(std::process::Stdio::null())
+            .kill_on_drop(true)
+            .output();
+
+        let failure = match tokio::time::timeout(self.pre_stop.timeout, output).await {
+            Ok(Ok(output)) if output.status.success() => {
+                tracing::info!(instance = %self.config.name, "Pre-stop command succeeded");
+                return Ok(());
+            }
+            Ok(Ok(output)) => format!(
+                "exited with {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+            Ok(Err(e)) => format!("failed to run: {}", e),
+            Err(_) => format!("timed out after {:?}", self.pre_stop.timeout),
+        };
+
+        if self.pre_stop.required {
+            anyhow::bail!(
+                "Pre-stop command for instance '{}' {}; not stopping (pre_stop_required)",
+                self.config.name,
+                failure
+            );
+        }
+        tracing::warn!(
+            instance = %self.config.name,
+            failure = %failure,
+            "Pre-stop command failed, stopping anyway"
+        );
+        Ok(())
+    }
+
+    /// Restart the instance
+    pub async fn restart(&self, tei_binary_path: &str) -> Result<()> {
+        tracing::info!(instance = %self.config.name, "Restarting instance");
+
+        self.ensure_not_quarantined().await?;
+        // The health monitor flags the instance as restarting for the whole cycle
+        self.stop_process().await?;
+        tokio::time::sleep(RESTART_DELAY).await;
+        self.start_process(tei_binary_path).await?;
+
+        let mut stats = self.stats.write().await;
+        stats.restarts += 1;
+
+        Ok(())
+    }
+
+    /// Record that a request was forwarded to this instance
+    pub async fn record_request(&self) {
+        self.stats.write().await.last_request_at = Some(chrono::Utc::now());
+    }
+
+    /// Counter of requests currently forwarded to this instance
+    pub fn inflight(&self) -> &InflightCounter {
+        &self.inflight
+    }
+
+    /// Check if process is still running
+    pub async fn is_running(&self) -> bool {
+        let handle_guard = self.process_handle.read().await;
+        if let Some(handle) = handle_guard.as_ref() {
+            self.process_manager.is_running(handle).await
+        } else {
+            false
+        }
+    }
+
+    /// How the current process exited, or None if it is running or was never started
+    pub async fn exit_status(&self) -> Option<ProcessExit> {
+        let handle_guard = self.process_handle.read().await;
+        let handle = handle_guard.as_ref()?;
+        self.process_manager.exit_status(handle).await
+    }
+
+    /// Get current PID
+    pub async fn pid(&self) -> Option<u32> {
+        let handle_guard = self.process_handle.read().await;
+        if let Some(handle) = handle_guard.as_ref() {
+            self.process_manager.pid(handle).await
+        } else {
+            None
+        }
+    }
+}
+
+// ============================================================================
+// Mock Implementation for Testing
+// ============================================================================
+
+#[cfg(test)]
+pub mod mocks {
+    use super::*;
+    use std::collections::HashMap;
+
+    /// Mock process manager for testing
+    pub struct MockProcessManager {
+        processes: Arc<RwLock<HashMap<String, ProcessState>>>,
+        next_id: Arc<RwLock<u32>>,
+    }
+
+    #[derive(Debug, Clone)]
+    struct ProcessState {
+        pid: u32,
+        running: bool,
+        exit: Option<ProcessExit>,
+        config: SpawnConfig,
+    }
+
+    impl Default for MockProcessManager {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl MockProcessManager {
+        pub fn new() -> Self {
+            Self {
+                processes: Arc::new(RwLock::new(HashMap::new())),
+                next_id: Arc::new(RwLock::new(1000)),
+            }
+        }
+
+        /// Get the number of active processes
         pub async fn process_count(&self) -> usize {
             self.processes.read().await.len()
         }
@@ -413,6 +1528,15 @@ pub mod mocks {
                 .any(|p| p.config.model_id == model_id && p.config.port == port)
         }
 
+        /// Make the process with `pid` exit as if it died with `exit`
+        pub async fn simulate_exit(&self, pid: u32, exit: ProcessExit) {
+            let mut processes = self.processes.write().await;
+            if let Some(process) = processes.values_mut().find(|p| p.pid == pid) {
+                process.running = false;
+                process.exit = Some(exit);
+            }
+        }
+
         /// Get spawn config for a handle
         pub async fn get_config(&self, handle: &ProcessHandle) -> Option<SpawnConfig> {
             let processes = self.processes.read().await;
@@ -435,6 +1559,7 @@ pub mod mocks {
             let state = ProcessState {
                 pid,
                 running: true,
+                exit: None,
                 config,
             };
 
@@ -461,13 +1586,20 @@ pub mod mocks {
             let processes = self.processes.read().await;
             processes.get(&handle.id).map(|p| p.pid)
         }
+
+        async fn exit_status(&self, handle: &ProcessHandle) -> Option<ProcessExit> {
+            let processes = self.processes.read().await;
+            processes.get(&handle.id).and_then(|p| p.exit)
+        }
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::disallowed_methods)] // Tests intentionally use env::set_var for interpolation
 mod tests {
     use super::*;
     use mocks::MockProcessManager;
+    use serial_test::serial;
 
     #[tokio::test]
     async fn test_instance_creation() {
@@ -497,7 +1629,7 @@ mod tests {
             port: 8080,
             max_batch_tokens: 2048,
             max_concurrent_requests: 20,
-            pooling: Some("mean".to_string()),
+            pooling: Some(Pooling::Mean),
             gpu_id: Some(0),
             prometheus_port: Some(9090),
             extra_args: vec!["--trust-remote-code".to_string()],
@@ -661,7 +1793,7 @@ mod tests {
             port: 7777,
             max_batch_tokens: 4096,
             max_concurrent_requests: 50,
-            pooling: Some("cls".to_string()),
+            pooling: Some(Pooling::Cls),
             gpu_id: Some(2),
             prometheus_port: Some(9999),
             extra_args: vec!["--arg1".to_string(), "--arg2".to_string()],
@@ -682,12 +1814,498 @@ mod tests {
         assert_eq!(spawn_config.port, 7777);
         assert_eq!(spawn_config.max_batch_tokens, 4096);
         assert_eq!(spawn_config.max_concurrent_requests, 50);
-        assert_eq!(spawn_config.pooling, Some("cls".to_string()));
+        assert_eq!(spawn_config.pooling, Some(Pooling::Cls));
         assert_eq!(spawn_config.gpu_id, Some(2));
         assert_eq!(spawn_config.prometheus_port, Some(9999));
         assert_eq!(spawn_config.extra_args.len(), 2);
     }
 
+    #[tokio::test]
+    async fn test_default_extra_args_prepended_at_start() {
+        let config = InstanceConfig {
+            name: "defaults".to_string(),
+            model_id: "model".to_string(),
+            port: 8080,
+            extra_args: vec![
+                "--dtype".to_string(),
+                "float32".to_string(),
+                "--payload-limit".to_string(),
+                "1000".to_string(),
+            ],
+            ..Default::default()
+        };
+        let defaults: Arc<[String]> = Arc::from(
+            [
+                "--dtype",
+                "float16",
+                "--auto-truncate",
+                "--max-client-batch-size=64",
+            ]
+            .map(String::from),
+        );
+
+        let manager = Arc::new(MockProcessManager::new());
+        let instance = TeiInstance::new_with_manager(config.clone(), manager.clone())
+            .with_default_extra_args(defaults);
+        instance.start("tei").await.unwrap();
+
+        let handle = instance.process_handle.read().await;
+        let spawn_config = manager.get_config(handle.as_ref().unwrap()).await.unwrap();
+
+        // Defaults first; the instance's own --dtype replaces the default one
+        assert_eq!(
+            spawn_config.extra_args,
+            [
+                "--auto-truncate",
+                "--max-client-batch-size=64",
+                "--dtype",
+                "float32",
+                "--payload-limit",
+                "1000",
+            ]
+        );
+        let args = spawn_config.args();
+        let position = |arg: &str| args.iter().position(|a| a == arg).unwrap();
+        assert!(position("--auto-truncate") < position("--payload-limit"));
+
+        // Persisted config keeps only the per-instance args
+        assert_eq!(instance.config.extra_args, config.extra_args);
+    }
+
+    #[test]
+    fn test_merge_extra_args_short_flags() {
+        let args = |a: &[&str]| a.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+        // A short flag in the instance args overrides the long default, and vice versa
+        assert_eq!(
+            merge_extra_args(
+                &args(&["--port", "9000", "--auto-truncate"]),
+                &args(&["-p", "9001"]),
+                &[]
+            ),
+            ["--auto-truncate", "-p", "9001"]
+        );
+        assert_eq!(
+            merge_extra_args(
+                &args(&["-p", "9000", "--auto-truncate"]),
+                &args(&["--port=9001"]),
+                &[]
+            ),
+            ["--auto-truncate", "--port=9001"]
+        );
+        assert_eq!(
+            merge_extra_args(&args(&["-p9000", "-x", "-1"]), &args(&["-p=9001"]), &[]),
+            ["-x", "-1", "-p=9001"]
+        );
+    }
+
+    #[test]
+    fn test_pooling_and_dtype_flags() {
+        let config = InstanceConfig {
+            name: "typed".to_string(),
+            model_id: "model".to_string(),
+            port: 8080,
+            pooling: Some(Pooling::LastToken),
+            dtype: Some(Dtype::Bfloat16),
+            ..Default::default()
+        };
+        let defaults: Arc<[String]> =
+            Arc::from(["--dtype", "float16", "--auto-truncate"].map(String::from));
+        let instance = TeiInstance::new(config).with_default_extra_args(defaults);
+
+        // The typed dtype replaces the default --dtype
+        assert_eq!(instance.launch_extra_args(), ["--auto-truncate"]);
+
+        let args = instance.launch_preview("tei").args();
+        assert!(args.windows(2).any(|w| w == ["--pooling", "last-token"]));
+        assert!(args.windows(2).any(|w| w == ["--dtype", "bfloat16"]));
+        assert_eq!(args.iter().filter(|a| *a == "--dtype").count(), 1);
+    }
+
+    #[test]
+    fn test_spawn_config_command_line() {
+        let config = InstanceConfig {
+            name: "cmd".to_string(),
+            model_id: "model".to_string(),
+            port: 8080,
+            max_batch_tokens: 1024,
+            max_concurrent_requests: 8,
+            gpu_id: Some(1),
+            prometheus_port: Some(9100),
+            ..Default::default()
+        };
+
+        let spawn_config = SpawnConfig::new(&config, "tei", Vec::new(), Vec::new());
+        assert_eq!(spawn_config.cuda_visible_devices(), Some("1".to_string()));
+        assert_eq!(
+            spawn_config.command_line(),
+            [
+                "tei",
+                "--model-id",
+                "model",
+                "--port",
+                "8080",
+                "--max-batch-tokens",
+                "1024",
+                "--max-concurrent-requests",
+                "8",
+                "--json-output",
+                "--prometheus-port",
+                "9100",
+            ]
+        );
+
+        // An explicit --prometheus-port in extra_args replaces the assigned one
+        let extra_args = vec!["--prometheus-port".to_string(), "9300".to_string()];
+        let args = SpawnConfig::new(&config, "tei", Vec::new(), extra_args).args();
+        assert_eq!(args.iter().filter(|a| *a == "--prometheus-port").count(), 1);
+        assert_eq!(args.last().unwrap(), "9300");
+    }
+
+    #[test]
+    fn test_launch_preview_flags() {
+        let config = InstanceConfig {
+            name: "preview".to_string(),
+            model_id: "BAAI/bge-small-en-v1.5".to_string(),
+            port: 8090,
+            max_batch_tokens: 4096,
+            pooling: Some(Pooling::Cls),
+            gpu_id: Some(2),
+            log_level: Some("debug".to_string()),
+            extra_args: vec![
+                "--hf-api-token".to_string(),
+                "${HF_TOKEN}".to_string(),
+                "--auto-truncate".to_string(),
+            ],
+            env: [("API_KEY".to_string(), "plaintext".to_string())].into(),
+            ..Default::default()
+        };
+        let instance = TeiInstance::new(config).with_default_extra_args(Arc::from(vec![
+            "--dtype".to_string(),
+            "float16".to_string(),
+        ]));
+
+        let spawn_config = instance.launch_preview("/usr/bin/tei");
+        let command = spawn_config.command_line();
+        assert_eq!(command[0], "/usr/bin/tei");
+        for flag in [
+            ["--model-id", "BAAI/bge-small-en-v1.5"],
+            ["--port", "8090"],
+            ["--max-batch-tokens", "4096"],
+            ["--pooling", "cls"],
+            ["--dtype", "float16"],
+            // References are shown unresolved, never as the secret
+            ["--hf-api-token", "${HF_TOKEN}"],
+        ] {
+            assert!(
+                command.windows(2).any(|w| w == flag),
+                "missing {:?} in {:?}",
+                flag,
+                command
+            );
+        }
+        assert!(command.ends_with(&["--auto-truncate".to_string()]));
+        assert_eq!(spawn_config.cuda_visible_devices(), Some("2".to_string()));
+        assert_eq!(
+            spawn_config.env,
+            vec![
+                ("API_KEY".to_string(), crate::config::REDACTED.to_string()),
+                ("RUST_LOG".to_string(), "debug".to_string()),
+            ]
+        );
+
+        // Preview matches what start actually passes, bar redaction
+        instance.reduce_max_batch_tokens(0.5);
+        let command = instance.launch_preview("/usr/bin/tei").command_line();
+        assert!(
+            command
+                .windows(2)
+                .any(|w| w == ["--max-batch-tokens", "2048"])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_launch_preview_matches_start() {
+        // Nothing to redact or resolve, so the preview is exactly what start spawns
+        let instance = TeiInstance::new(InstanceConfig {
+            name: "same".to_string(),
+            model_id: "BAAI/bge-small-en-v1.5".to_string(),
+            port: 8091,
+            dtype: Some(Dtype::Float16),
+            gpu_memory_fraction: Some(0.5),
+            log_level: Some("debug".to_string()),
+            extra_args: vec!["--max-client-batch-size".to_string(), "8".to_string()],
+            env: [("RUST_LOG".to_string(), "info".to_string())].into(),
+            ..Default::default()
+        })
+        .with_default_extra_args(Arc::from(vec![
+            "--dtype".to_string(),
+            "float32".to_string(),
+            "--auto-truncate".to_string(),
+        ]));
+        instance.reduce_max_batch_tokens(0.5);
+
+        let (env, extra_args) = instance.resolve_env_and_args().await.unwrap();
+        let started = instance.spawn_config(&instance.config, "/usr/bin/tei", env, extra_args);
+        let preview = instance.launch_preview("/usr/bin/tei");
+        assert_eq!(preview.command_line(), started.command_line());
+        assert_eq!(preview.env, started.env);
+        assert_eq!(preview.log_dir, started.log_dir);
+    }
+
+    #[test]
+    fn test_process_exit_oom_detection() {
+        assert!(ProcessExit::Signal(9).is_oom_kill());
+        assert!(ProcessExit::Code(137).is_oom_kill());
+        assert!(!ProcessExit::Signal(15).is_oom_kill());
+        assert!(!ProcessExit::Code(1).is_oom_kill());
+        assert_eq!(ProcessExit::Signal(9).to_string(), "signal 9");
+    }
+
+    #[tokio::test]
+    async fn test_oom_backoff_applies_to_next_start() {
+        let config = InstanceConfig {
+            name: "backoff".to_string(),
+            model_id: "model".to_string(),
+            port: 8080,
+            max_batch_tokens: 2048,
+            ..Default::default()
+        };
+        let manager = Arc::new(MockProcessManager::new());
+        let instance = TeiInstance::new_with_manager(config, manager.clone());
+
+        assert_eq!(instance.reduce_max_batch_tokens(0.5), 1024);
+        // Compounds, but never drops below the floor
+        assert_eq!(instance.reduce_max_batch_tokens(0.25), MIN_OOM_BATCH_TOKENS);
+
+        instance.start("tei").await.unwrap();
+        let pid = instance.pid().await.unwrap();
+        let handle = instance.process_handle.read().await.clone().unwrap();
+        let spawn_config = manager.get_config(&handle).await.unwrap();
+        assert_eq!(spawn_config.max_batch_tokens, MIN_OOM_BATCH_TOKENS);
+        assert_eq!(instance.config.max_batch_tokens, 2048);
+
+        assert_eq!(instance.exit_status().await, None);
+        manager.simulate_exit(pid, ProcessExit::Signal(9)).await;
+        assert!(!instance.is_running().await);
+        assert_eq!(instance.exit_status().await, Some(ProcessExit::Signal(9)));
+    }
+
+    #[tokio::test]
+    async fn test_start_with_revision() {
+        let config = InstanceConfig {
+            name: "pinned".to_string(),
+            model_id: "model".to_string(),
+            port: 8080,
+            revision: Some("abc123".to_string()),
+            ..Default::default()
+        };
+
+        let manager = Arc::new(MockProcessManager::new());
+        let instance = TeiInstance::new_with_manager(config.clone(), manager.clone());
+        instance.start("tei").await.unwrap();
+
+        let handle = instance.process_handle.read().await;
+        let spawn_config = manager.get_config(handle.as_ref().unwrap()).await.unwrap();
+        let args = spawn_config.args();
+        let pos = args.iter().position(|a| a == "--revision").unwrap();
+        assert_eq!(args[pos + 1], "abc123");
+        assert_eq!(spawn_config.requested_revision(), Some("abc123"));
+
+        // An explicit --revision in extra_args wins over the field
+        let extra_args = vec!["--revision=def456".to_string()];
+        let spawn_config = SpawnConfig::new(&config, "tei", Vec::new(), extra_args);
+        assert!(!spawn_config.args().contains(&"--revision".to_string()));
+        assert_eq!(spawn_config.requested_revision(), Some("def456"));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_start_resolves_env_references() {
+        unsafe {
+            std::env::set_var("TEI_MANAGER_TEST_HF_TOKEN", "hf_from_env");
+        }
+
+        let mut env_file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(
+            &mut env_file,
+            b"FROM_FILE=file_value\nHF_TOKEN=overridden\n",
+        )
+        .unwrap();
+
+        let config = InstanceConfig {
+            name: "secrets".to_string(),
+            model_id: "model".to_string(),
+            port: 7778,
+            env: [(
+                "HF_TOKEN".to_string(),
+                "${TEI_MANAGER_TEST_HF_TOKEN}".to_string(),
+            )]
+            .into(),
+            env_file: Some(env_file.path().to_path_buf()),
+            extra_args: vec!["--api-key=${TEI_MANAGER_TEST_HF_TOKEN}".to_string()],
+            ..Default::default()
+        };
+
+        let manager = Arc::new(MockProcessManager::new());
+        let instance = TeiInstance::new_with_manager(config, manager.clone());
+        instance.start("/usr/bin/tei").await.unwrap();
+
+        let handle = instance.process_handle.read().await;
+        let spawn_config = manager.get_config(handle.as_ref().unwrap()).await.unwrap();
+
+        // `env` wins over env_file, references are resolved for the process
+        assert_eq!(
+            spawn_config.env,
+            vec![
+                ("FROM_FILE".to_string(), "file_value".to_string()),
+                ("HF_TOKEN".to_string(), "hf_from_env".to_string()),
+            ]
+        );
+        assert_eq!(spawn_config.extra_args, vec!["--api-key=hf_from_env"]);
+
+        // The stored config keeps the unresolved reference
+        assert_eq!(
+            instance.config.env["HF_TOKEN"],
+            "${TEI_MANAGER_TEST_HF_TOKEN}"
+        );
+
+        unsafe {
+            std::env::remove_var("TEI_MANAGER_TEST_HF_TOKEN");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_start_sets_hf_home_env() {
+        let manager = Arc::new(MockProcessManager::new());
+        let config = InstanceConfig {
+            name: "isolated".to_string(),
+            model_id: "model".to_string(),
+            port: 7785,
+            hf_home: Some(PathBuf::from("/models/team-a")),
+            env: [(
+                "HUGGINGFACE_HUB_CACHE".to_string(),
+                "/elsewhere".to_string(),
+            )]
+            .into(),
+            ..Default::default()
+        };
+        let instance = TeiInstance::new_with_manager(config, manager.clone());
+        instance.start("/usr/bin/tei").await.unwrap();
+
+        let handle = instance.process_handle.read().await;
+        let env = manager
+            .get_config(handle.as_ref().unwrap())
+            .await
+            .unwrap()
+            .env;
+        // An explicit entry in env is kept over the derived one
+        assert_eq!(
+            env,
+            vec![
+                ("HF_HOME".to_string(), "/models/team-a".to_string()),
+                (
+                    "HUGGINGFACE_HUB_CACHE".to_string(),
+                    "/elsewhere".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_start_sets_log_level_env() {
+        let manager = Arc::new(MockProcessManager::new());
+        let spawned_env = |instance: TeiInstance| {
+            let manager = manager.clone();
+            async move {
+                instance.start("/usr/bin/tei").await.unwrap();
+                let handle = instance.process_handle.read().await;
+                manager
+                    .get_config(handle.as_ref().unwrap())
+                    .await
+                    .unwrap()
+                    .env
+            }
+        };
+        let default_level = Some(Arc::from("warn"));
+
+        // The instance's own level wins over the manager default
+        let config = InstanceConfig {
+            name: "noisy".to_string(),
+            model_id: "model".to_string(),
+            port: 7780,
+            log_level: Some("debug".to_string()),
+            ..Default::default()
+        };
+        let instance = TeiInstance::new_with_manager(config, manager.clone())
+            .with_default_log_level(default_level.clone());
+        assert_eq!(
+            spawned_env(instance).await,
+            vec![("RUST_LOG".to_string(), "debug".to_string())]
+        );
+
+        // Without one the manager default applies
+        let config = InstanceConfig {
+            name: "quiet".to_string(),
+            model_id: "model".to_string(),
+            port: 7781,
+            ..Default::default()
+        };
+        let instance = TeiInstance::new_with_manager(config, manager.clone())
+            .with_default_log_level(default_level.clone());
+        assert_eq!(
+            spawned_env(instance).await,
+            vec![("RUST_LOG".to_string(), "warn".to_string())]
+        );
+
+        // An explicit RUST_LOG in env is left alone
+        let config = InstanceConfig {
+            name: "explicit".to_string(),
+            model_id: "model".to_string(),
+            port: 7782,
+            log_level: Some("debug".to_string()),
+            env: [("RUST_LOG".to_string(), "trace".to_string())].into(),
+            ..Default::default()
+        };
+        let instance = TeiInstance::new_with_manager(config, manager.clone());
+        assert_eq!(
+            spawned_env(instance).await,
+            vec![("RUST_LOG".to_string(), "trace".to_string())]
+        );
+
+        // No level anywhere leaves TEI's own default
+        let config = InstanceConfig {
+            name: "default".to_string(),
+            model_id: "model".to_string(),
+            port: 7783,
+            ..Default::default()
+        };
+        let instance = TeiInstance::new_with_manager(config, manager.clone());
+        assert!(spawned_env(instance).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_start_fails_on_unset_env_reference() {
+        let config = InstanceConfig {
+            name: "missing-secret".to_string(),
+            model_id: "model".to_string(),
+            port: 7779,
+            env: [(
+                "HF_TOKEN".to_string(),
+                "${TEI_MANAGER_TEST_UNSET_VAR}".to_string(),
+            )]
+            .into(),
+            ..Default::default()
+        };
+
+        let manager = Arc::new(MockProcessManager::new());
+        let instance = TeiInstance::new_with_manager(config, manager.clone());
+
+        let err = instance.start("/usr/bin/tei").await.unwrap_err();
+        assert!(format!("{:#}", err).contains("TEI_MANAGER_TEST_UNSET_VAR"));
+        assert_eq!(manager.process_count().await, 0);
+    }
+
     #[tokio::test]
     async fn test_multiple_instances() {
         let manager = Arc::new(MockProcessManager::new());
@@ -730,4 +2348,465 @@ mod tests {
         inst2.stop().await.unwrap();
         assert_eq!(manager.process_count().await, 0);
     }
+
+    /// Start a real process that stays up, with output captured under `dir`
+    #[cfg(unix)]
+    async fn start_sleeping_instance(dir: &std::path::Path, hook: PreStopHook) -> TeiInstance {
+        let binary = write_fake_tei(dir, "exec sleep 30");
+
+        let instance = TeiInstance::new(InstanceConfig {
+            name: "pre-stop".to_string(),
+            model_id: "model".to_string(),
+            port: 18199,
+            ..Default::default()
+        })
+        .with_log_dir(Arc::from(dir.join("logs")))
+        .with_pre_stop_hook(Arc::new(hook));
+
+        start_real_process(&instance, &binary).await.unwrap();
+        instance
+    }
+
+    /// Write an executable shell script standing in for the TEI binary
+    #[cfg(unix)]
+    fn write_fake_tei(dir: &std::path::Path, script: &str) -> String {
+        use std::os::unix::fs::PermissionsExt;
+
+        let binary = dir.join("fake-tei");
+        std::fs::write(&binary, format!("#!/bin/sh\n{}\n", script)).unwrap();
+        std::fs::set_permissions(&binary, std::fs::Permissions::from_mode(0o755)).unwrap();
+        binary.to_str().unwrap().to_string()
+    }
+
+    /// Start `instance` from a freshly written binary
+    #[cfg(unix)]
+    async fn start_real_process(instance: &TeiInstance, binary: &str) -> Result<()> {
+        // Exec can briefly fail with ETXTBSY while another test thread forks
+        let mut started = instance.start(binary).await;
+        for _ in 0..10 {
+            match &started {
+                Err(e) if format!("{:#}", e).contains("Text file busy") => {}
+                _ => break,
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            started = instance.start(binary).await;
+        }
+        started
+    }
+
+    /// Instance whose process fails to bind its port on the first `failures` starts
+    #[cfg(unix)]
+    fn flaky_bind_instance(
+        dir: &std::path::Path,
+        failures: u32,
+        retries: u32,
+    ) -> (TeiInstance, String) {
+        let attempts = dir.join("attempts");
+        let binary = write_fake_tei(
+            dir,
+            &format!(
+                "echo x >> {attempts}\n\
+                 if [ $(wc -l < {attempts}) -le {failures} ]; then\n\
+                 echo 'Error: Address already in use (os error 98)' >&2\n\
+                 exit 1\n\
+                 fi\n\
+                 exec sleep 30",
+                attempts = attempts.display(),
+            ),
+        );
+
+        let instance = TeiInstance::new(InstanceConfig {
+            name: "bind-retry".to_string(),
+            model_id: "model".to_string(),
+            port: 18877,
+            ..Default::default()
+        })
+        .with_log_dir(Arc::from(dir.join("logs")))
+        .with_bind_retry(BindRetry {
+            retries,
+            window: Duration::from_millis(500),
+            delay: Duration::from_millis(10),
+        });
+        (instance, binary)
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_unexpected_exit_detected_without_polling() {
+        let dir = tempfile::tempdir().unwrap();
+        let events = EventSender::new(8, 0);
+        let mut rx = events.subscribe();
+        let instance = TeiInstance::new(InstanceConfig {
+            name: "short-lived".to_string(),
+            model_id: "model".to_string(),
+            port: 18198,
+            ..Default::default()
+        })
+        .with_log_dir(Arc::from(dir.path().join("logs")))
+        .with_bind_retry(BindRetry {
+            retries: 0,
+            ..Default::default()
+        })
+        .with_events(events);
+
+        start_real_process(&instance, "/bin/true").await.unwrap();
+
+        assert!(matches!(
+            rx.recv().await.unwrap(),
+            InstanceEvent::StatusChanged {
+                to: InstanceStatus::Starting,
+                ..
+            }
+        ));
+        // Far sooner than any health check interval
+        let event = tokio::time::timeout(Duration::from_secs(2), rx.recv())
+            .await
+            .expect("exit not reported")
+            .unwrap();
+        assert!(matches!(
+            event,
+            InstanceEvent::StatusChanged { ref name, to: InstanceStatus::Failed, .. }
+                if name == "short-lived"
+        ));
+        assert!(matches!(
+            rx.recv().await.unwrap(),
+            InstanceEvent::Exited { ref name, exit: ProcessExit::Code(0) } if name == "short-lived"
+        ));
+        assert_eq!(*instance.status.read().await, InstanceStatus::Failed);
+        assert_eq!(instance.exit_status().await, Some(ProcessExit::Code(0)));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_stopped_process_is_not_reported_as_exited() {
+        let dir = tempfile::tempdir().unwrap();
+        let events = EventSender::new(8, 0);
+        let mut rx = events.subscribe();
+        let binary = write_fake_tei(dir.path(), "exec sleep 30");
+        let instance = TeiInstance::new(InstanceConfig {
+            name: "stopped".to_string(),
+            model_id: "model".to_string(),
+            port: 18197,
+            ..Default::default()
+        })
+        .with_log_dir(Arc::from(dir.path().join("logs")))
+        .with_events(events);
+
+        start_real_process(&instance, &binary).await.unwrap();
+        instance.stop().await.unwrap();
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        assert_eq!(*instance.status.read().await, InstanceStatus::Stopped);
+        // The stop's own transitions, but no Failed or Exited
+        for expected in [
+            InstanceStatus::Starting,
+            InstanceStatus::Stopping,
+            InstanceStatus::Stopped,
+        ] {
+            assert!(matches!(
+                rx.try_recv().unwrap(),
+                InstanceEvent::StatusChanged { to, .. } if to == expected
+            ));
+        }
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_start_retries_transient_bind_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        let (instance, binary) = flaky_bind_instance(dir.path(), 1, 2);
+
+        start_real_process(&instance, &binary).await.unwrap();
+
+        assert!(instance.is_running().await);
+        assert_eq!(*instance.status.read().await, InstanceStatus::Starting);
+        let attempts = std::fs::read_to_string(dir.path().join("attempts")).unwrap();
+        assert_eq!(attempts.lines().count(), 2);
+        let log = std::fs::read_to_string(instance.log_path()).unwrap();
+        assert_eq!(log.matches("Address already in use").count(), 1);
+
+        instance.stop().await.unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_cpu_only_instance_launches_with_empty_cuda_visible_devices() {
+        let dir = tempfile::tempdir().unwrap();
+        let seen = dir.path().join("cuda-visible-devices");
+        let binary = write_fake_tei(
+            dir.path(),
+            &format!(
+                "printf '%s' \"${{CUDA_VISIBLE_DEVICES-unset}}\" > {}\nexec sleep 30",
+                seen.display()
+            ),
+        );
+
+        let instance = TeiInstance::new(InstanceConfig {
+            name: "cpu".to_string(),
+            model_id: "model".to_string(),
+            port: 18882,
+            cpu_only: true,
+            ..Default::default()
+        })
+        .with_log_dir(Arc::from(dir.path().join("logs")));
+        assert_eq!(
+            instance.launch_preview("tei").cuda_visible_devices(),
+            Some(String::new())
+        );
+
+        start_real_process(&instance, &binary).await.unwrap();
+        let mut value = None;
+        for _ in 0..50 {
+            value = std::fs::read_to_string(&seen).ok();
+            if value.is_some() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        instance.stop().await.unwrap();
+
+        // Set but empty, so CUDA sees no devices
+        assert_eq!(value.as_deref(), Some(""));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_gpu_memory_fraction_sets_cuda_alloc_conf() {
+        let dir = tempfile::tempdir().unwrap();
+        let seen = dir.path().join("alloc-conf");
+        let binary = write_fake_tei(
+            dir.path(),
+            &format!(
+                "printf '%s' \"${{PYTORCH_CUDA_ALLOC_CONF-unset}}\" > {}\nexec sleep 30",
+                seen.display()
+            ),
+        );
+
+        let instance = TeiInstance::new(InstanceConfig {
+            name: "packed".to_string(),
+            model_id: "model".to_string(),
+            port: 18884,
+            gpu_id: Some(0),
+            gpu_memory_fraction: Some(0.4),
+            ..Default::default()
+        })
+        .with_log_dir(Arc::from(dir.path().join("logs")));
+        assert!(instance.launch_preview("tei").env.contains(&(
+            CUDA_ALLOC_CONF_ENV.to_string(),
+            "garbage_collection_threshold:0.4".to_string()
+        )));
+
+        start_real_process(&instance, &binary).await.unwrap();
+        let mut value = None;
+        for _ in 0..50 {
+            value = std::fs::read_to_string(&seen).ok();
+            if value.is_some() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        instance.stop().await.unwrap();
+        assert_eq!(value.as_deref(), Some("garbage_collection_threshold:0.4"));
+
+        // A threshold of the whole GPU would never trigger
+        assert_eq!(cuda_gc_threshold_for_fraction(1.0), None);
+    }
+
+    #[test]
+    fn test_explicit_cuda_alloc_conf_overrides_fraction() {
+        let instance = TeiInstance::new(InstanceConfig {
+            name: "packed".to_string(),
+            gpu_memory_fraction: Some(0.5),
+            env: [(
+                CUDA_ALLOC_CONF_ENV.to_string(),
+                "expandable_segments:True".to_string(),
+            )]
+            .into(),
+            ..Default::default()
+        });
+        assert_eq!(
+            instance.launch_preview("tei").env,
+            vec![(
+                CUDA_ALLOC_CONF_ENV.to_string(),
+                "expandable_segments:True".to_string()
+            )]
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_start_fails_when_bind_retries_exhausted() {
+        let dir = tempfile::tempdir().unwrap();
+        let (instance, binary) = flaky_bind_instance(dir.path(), 10, 1);
+
+        let err = start_real_process(&instance, &binary).await.unwrap_err();
+
+        assert!(
+            err.to_string()
+                .contains("could not bind port 18877 after 1 retries")
+        );
+        assert_eq!(*instance.status.read().await, InstanceStatus::Stopped);
+        assert!(instance.pid().await.is_none());
+        let attempts = std::fs::read_to_string(dir.path().join("attempts")).unwrap();
+        assert_eq!(attempts.lines().count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_read_log_from_reads_only_new_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("instance.log");
+        std::fs::write(&path, "earlier run\nAddress already in use\nbind failed\n").unwrap();
+
+        let offset = "earlier run\n".len() as u64;
+        let output = read_log_from(&path, offset, 7).await.unwrap();
+        assert_eq!(output, b"Address");
+        // Past the end (e.g. a truncated log) there is nothing new
+        assert!(read_log_from(&path, 1 << 20, 64).await.unwrap().is_empty());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_pre_stop_hook_runs_before_process_is_stopped() {
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("marker");
+        let hook = PreStopHook {
+            // Only writes the marker if the process is still alive
+            command: Some(format!(
+                "kill -0 \"$TEI_INSTANCE_PID\" && echo \"$TEI_INSTANCE_NAME $TEI_INSTANCE_PORT\" > {}",
+                marker.display()
+            )),
+            ..Default::default()
+        };
+        let instance = start_sleeping_instance(dir.path(), hook).await;
+
+        instance.stop().await.unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&marker).unwrap(),
+            "pre-stop 18199\n"
+        );
+        assert_eq!(*instance.status.read().await, InstanceStatus::Stopped);
+        assert!(!instance.is_running().await);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_pre_stop_hook_failure_only_blocks_when_required() {
+        let dir = tempfile::tempdir().unwrap();
+        let failing = PreStopHook {
+            command: Some("echo deregister failed >&2; exit 3".to_string()),
+            timeout: Duration::from_secs(5),
+            required: true,
+        };
+
+        let instance = start_sleeping_instance(dir.path(), failing.clone()).await;
+        let err = instance.stop().await.unwrap_err();
+        assert!(err.to_string().contains("deregister failed"));
+        assert!(instance.is_running().await);
+        assert_eq!(*instance.status.read().await, InstanceStatus::Starting);
+        drop(instance);
+
+        let optional = PreStopHook {
+            required: false,
+            ..failing
+        };
+        let instance = start_sleeping_instance(dir.path(), optional).await;
+        instance.stop().await.unwrap();
+        assert!(!instance.is_running().await);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_stopped_on_purpose_until_started() {
+        let dir = tempfile::tempdir().unwrap();
+        let failing = PreStopHook {
+            command: Some("exit 3".to_string()),
+            timeout: Duration::from_secs(5),
+            required: true,
+        };
+        let instance = start_sleeping_instance(dir.path(), failing).await;
+        assert!(!instance.is_stopped_on_purpose());
+        // A blocked stop leaves the process serving, so it stays health checked
+        assert!(instance.stop().await.is_err());
+        assert!(!instance.is_stopped_on_purpose());
+        drop(instance);
+
+        let instance = start_sleeping_instance(dir.path(), PreStopHook::default()).await;
+        instance.stop().await.unwrap();
+        assert!(instance.is_stopped_on_purpose());
+
+        let binary = write_fake_tei(dir.path(), "exec sleep 30");
+        start_real_process(&instance, &binary).await.unwrap();
+        assert!(!instance.is_stopped_on_purpose());
+        instance.stop().await.unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_quarantine_only_marked_once_stopped() {
+        let dir = tempfile::tempdir().unwrap();
+        let failing = PreStopHook {
+            command: Some("exit 3".to_string()),
+            timeout: Duration::from_secs(5),
+            required: true,
+        };
+        let instance = start_sleeping_instance(dir.path(), failing).await;
+        assert!(instance.quarantine().await.is_err());
+        assert!(instance.is_running().await);
+        assert_ne!(*instance.status.read().await, InstanceStatus::Quarantined);
+        drop(instance);
+
+        // A process left behind by a quarantined instance is still stopped
+        let instance = start_sleeping_instance(dir.path(), PreStopHook::default()).await;
+        *instance.status.write().await = InstanceStatus::Quarantined;
+        instance.stop().await.unwrap();
+        assert!(!instance.is_running().await);
+        assert_eq!(*instance.status.read().await, InstanceStatus::Quarantined);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_pre_stop_hook_timeout() {
+        let dir = tempfile::tempdir().unwrap();
+        let hook = PreStopHook {
+            command: Some("sleep 5".to_string()),
+            timeout: Duration::from_millis(100),
+            required: true,
+        };
+        let instance = start_sleeping_instance(dir.path(), hook).await;
+
+        let err = instance.stop().await.unwrap_err();
+        assert!(err.to_string().contains("timed out"));
+        assert!(instance.is_running().await);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_instance_pre_stop_command_overrides_manager() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager_marker = dir.path().join("manager");
+        let instance_marker = dir.path().join("instance");
+
+        let instance = TeiInstance::new_with_manager(
+            InstanceConfig {
+                name: "override".to_string(),
+                model_id: "model".to_string(),
+                port: 8080,
+                pre_stop_command: Some(format!("touch {}", instance_marker.display())),
+                ..Default::default()
+            },
+            Arc::new(MockProcessManager::new()),
+        )
+        .with_pre_stop_hook(Arc::new(PreStopHook {
+            command: Some(format!("touch {}", manager_marker.display())),
+            ..Default::default()
+        }));
+
+        instance.start("/usr/bin/tei").await.unwrap();
+        instance.stop().await.unwrap();
+
+        assert!(instance_marker.exists());
+        assert!(!manager_marker.exists());
+    }
 }
@@ -4,22 +4,27 @@
 //! instances on a single GPU host.
 
 pub mod api;
+pub mod audit;
 pub mod auth;
 pub mod config;
 pub mod error;
 pub mod gpu;
 pub mod grpc;
 pub mod health;
+pub mod idle;
 pub mod instance;
 pub mod metrics;
 pub mod models;
 pub mod registry;
+pub mod shutdown;
 pub mod state;
+pub mod tls;
+pub mod unix_socket;
 
 pub use config::{InstanceConfig, ManagerConfig};
 pub use error::{TeiError, TeiResult};
 pub use health::HealthMonitor;
 pub use instance::{InstanceStats, InstanceStatus, TeiInstance};
-pub use models::{ModelEntry, ModelLoader, ModelRegistry, ModelStatus};
+pub use models::{ModelEntry, ModelLoader, ModelRegistry, ModelStatus, PreloadTracker};
 pub use registry::{InstanceEvent, Registry};
 pub use state::StateManager;
@@ -7,10 +7,20 @@ use std::io::BufReader;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tei_manager::{
-    HealthMonitor, ModelLoader, ModelRegistry, Registry, StateManager, api,
+    HealthMonitor, ModelLoader, ModelRegistry, PreloadTracker, Registry, StateManager, api,
+    audit::{AuditSink, FileAuditSink},
     auth::{AuthManager, MtlsProvider},
     config::ManagerConfig,
+    grpc::{
+        access_log::AccessLog, channel::BackendChannelConfig, pool::BackendPool,
+        server::GrpcServerOptions,
+    },
+    health::{self, HealthMonitorConfig},
+    idle::IdleReaper,
+    instance::{BindRetry, PreStopHook},
     metrics,
+    models::{DownloadRetryConfig, preload::HfModelDownloader},
+    tls::ReloadableCertResolver,
 };
 use tokio::signal;
 
@@ -34,6 +44,10 @@ struct Cli {
     /// Log format (json or pretty)
     #[arg(long, default_value = "json")]
     log_format: String,
+
+    /// Print the JSON Schema of the config file and exit
+    #[arg(long)]
+    print_config_schema: bool,
 }
 
 #[tokio::main]
@@ -43,6 +57,14 @@ async fn main() -> Result<()> {
 
     let cli = Cli::parse();
 
+    if cli.print_config_schema {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&ManagerConfig::json_schema())?
+        );
+        return Ok(());
+    }
+
     // Setup logging
     match cli.log_format.as_str() {
         "pretty" => {
@@ -95,20 +117,50 @@ async fn main() -> Result<()> {
     // Build auth manager if enabled
     let auth_manager = build_auth_manager(&config)?;
 
+    // Select health checker (the fake one is refused unless explicitly allowed)
+    let health_checker = health::checker_for_mode(
+        config.health_check_mode,
+        BackendChannelConfig::from_config(&config),
+        health::fake_health_allowed(),
+    )?;
+
     // Initialize registry
-    let registry = Arc::new(Registry::new(
-        config.max_instances,
-        config.tei_binary_path.clone(),
-        config.instance_port_start,
-        config.instance_port_end,
-    ));
+    let registry = Arc::new(
+        Registry::new(
+            config.max_instances,
+            config.tei_binary_path.clone(),
+            config.instance_port_start,
+            config.instance_port_end,
+        )
+        .with_health_checker(health_checker.clone())
+        .with_default_extra_args(config.default_extra_args.clone())
+        .with_instance_log_level(config.instance_log_level.clone())
+        .with_log_dir(config.log_dir.clone())
+        .with_pre_stop_hook(PreStopHook::from_config(&config))
+        .with_bind_retry(BindRetry::from_config(&config))
+        .with_startup_download_grace(std::time::Duration::from_secs(
+            config.startup_download_grace_secs,
+        ))
+        .with_metric_labels(config.metric_labels.clone())
+        .with_default_pooling(config.default_pooling.clone())
+        .with_allowed_models(config.allowed_models.clone())
+        .with_gpu_memory_headroom(config.gpu_memory_headroom_mb.saturating_mul(1024 * 1024))
+        .with_event_history(config.event_history_size),
+    );
 
     // Initialize state manager
-    let state_manager = Arc::new(StateManager::new(
-        config.state_file.clone(),
-        registry.clone(),
-        config.tei_binary_path.clone(),
-    ));
+    let state_manager = Arc::new(
+        StateManager::new(
+            config.state_file.clone(),
+            registry.clone(),
+            config.tei_binary_path.clone(),
+        )
+        .with_start_retry(
+            config.restore_start_retries,
+            std::time::Duration::from_secs(config.restore_start_retry_delay_secs),
+        )
+        .with_startup_timeout(std::time::Duration::from_secs(config.startup_timeout_secs)),
+    );
 
     // Initialize model registry and discover cached models
     let configured_models = config.models.clone().unwrap_or_default();
@@ -131,10 +183,21 @@ async fn main() -> Result<()> {
             count = config.instances.len(),
             "Seeding instances from config"
         );
-        for instance_config in &config.instances {
+        // Validated acyclic; dependencies start (and become ready) before their dependents
+        let startup_timeout = std::time::Duration::from_secs(config.startup_timeout_secs);
+        for instance_config in &tei_manager::config::dependency_order(&config.instances)? {
             match registry.add(instance_config.clone()).await {
                 Ok(instance) => {
-                    if let Err(e) = instance.start(&config.tei_binary_path).await {
+                    if let Err(e) = registry
+                        .wait_for_dependencies(instance_config, startup_timeout)
+                        .await
+                    {
+                        tracing::error!(
+                            error = %e,
+                            instance = %instance_config.name,
+                            "Not starting seeded instance"
+                        );
+                    } else if let Err(e) = instance.start(&config.tei_binary_path).await {
                         tracing::error!(
                             error = %e,
                             instance = %instance_config.name,
@@ -154,14 +217,34 @@ async fn main() -> Result<()> {
     }
 
     // Start health monitor
-    let health_monitor = Arc::new(HealthMonitor::new(
-        registry.clone(),
-        config.health_check_interval_secs,
-        config.startup_timeout_secs,
-        config.max_failures_before_restart,
-        true, // auto_restart
-        config.tei_binary_path.clone(),
-    ));
+    let health_config = HealthMonitorConfig::from_manager_config(&config);
+    let mut health_monitor = HealthMonitor::builder(registry.clone())
+        .config(health_config)
+        .health_checker(health_checker);
+    if config.health_check_metrics_port {
+        health_monitor = health_monitor.metrics_probe(Arc::new(health::HttpMetricsProbe));
+    }
+    if config.health_check_embedding_dimension {
+        health_monitor = health_monitor.dimension_probe(Arc::new(health::GrpcDimensionProbe::new(
+            BackendChannelConfig::from_config(&config),
+        )));
+    }
+    if let Some(url) = &config.readiness_webhook_url {
+        let webhook = health::WebhookEventHandler::new(
+            url.clone(),
+            std::time::Duration::from_secs(config.readiness_webhook_timeout_secs),
+        )?
+        .with_retry(
+            config.readiness_webhook_retries,
+            std::time::Duration::from_secs(1),
+        );
+        health_monitor =
+            health_monitor.event_handler(Arc::new(health::CompositeEventHandler::new(vec![
+                Arc::new(health::MetricsEventHandler),
+                Arc::new(webhook),
+            ])));
+    }
+    let health_monitor = Arc::new(health_monitor.build(config.tei_binary_path.clone()));
 
     let monitor_handle = tokio::spawn({
         let monitor = health_monitor.clone();
@@ -170,6 +253,28 @@ async fn main() -> Result<()> {
         }
     });
 
+    // Start idle reaper if enabled
+    if config.idle_timeout_secs > 0 {
+        let reaper = Arc::new(IdleReaper::new(
+            registry.clone(),
+            std::time::Duration::from_secs(config.idle_timeout_secs),
+            std::time::Duration::from_secs(config.health_check_interval_secs),
+        ));
+        tokio::spawn(reaper.run());
+    }
+
+    // One log feeds both the file and the debug stream
+    let access_log = if config.grpc_access_log_file.is_some() || config.debug_endpoints {
+        let mut access_log = AccessLog::new();
+        if let Some(path) = &config.grpc_access_log_file {
+            tracing::info!(path = ?path, "gRPC access log enabled");
+            access_log = access_log.with_file(path)?;
+        }
+        Some(Arc::new(access_log))
+    } else {
+        None
+    };
+
     // Setup API
     let app_state = api::AppState {
         registry: registry.clone(),
@@ -179,11 +284,24 @@ async fn main() -> Result<()> {
         require_cert_headers: config.auth.require_cert_headers,
         model_registry,
         model_loader,
+        preload_tracker: Arc::new(PreloadTracker::new_with_downloader(Arc::new(
+            HfModelDownloader::new(DownloadRetryConfig::from_config(&config)),
+        ))),
+        config: Arc::new(config.clone()),
+        backend_pool: BackendPool::new(registry.clone())
+            .with_channel_config(BackendChannelConfig::from_config(&config))
+            .with_model_routing(config.model_routing_strategy),
+        audit_sink: config.audit_log_file.clone().map(|path| {
+            tracing::info!(path = ?path, "Audit log enabled");
+            Arc::new(FileAuditSink::new(path)) as Arc<dyn AuditSink>
+        }),
+        access_log: access_log.clone(),
+        embedding_dimensions: Arc::new(tei_manager::models::CachedEmbeddingDimensions::default()),
     };
 
     let app = api::create_router(app_state);
 
-    let addr = std::net::SocketAddr::from(([0, 0, 0, 0], config.api_port));
+    let addr = std::net::SocketAddr::new(config.api_bind_ip()?, config.api_port);
 
     // Build TLS configuration if mTLS is enabled
     let tls_config = build_tls_config(&config)?;
@@ -193,47 +311,49 @@ async fn main() -> Result<()> {
 
     // Start gRPC server in background if enabled
     let grpc_handle = if config.grpc_enabled {
-        let grpc_addr = std::net::SocketAddr::from(([0, 0, 0, 0], config.grpc_port));
+        let grpc_addr = std::net::SocketAddr::new(config.grpc_bind_ip()?, config.grpc_port);
         let grpc_registry = registry.clone();
-        let grpc_max_message_size_mb = config.grpc_max_message_size_mb;
-        let grpc_max_parallel_streams = config.grpc_max_parallel_streams;
-        let grpc_request_timeout_secs = config.grpc_request_timeout_secs;
+        let grpc_options = GrpcServerOptions {
+            access_log,
+            ..GrpcServerOptions::from_config(&config)
+        };
         let mut grpc_shutdown_rx = shutdown_tx.subscribe();
 
-        // Build gRPC TLS config if mTLS is enabled
-        let grpc_tls_config =
-            if config.auth.enabled && config.auth.providers.contains(&"mtls".to_string()) {
-                let mtls_config = config.auth.mtls.as_ref().expect("mTLS config should exist");
-
-                // Load certificate files as strings for tonic
-                let cert_pem = std::fs::read_to_string(&mtls_config.server_cert)
-                    .context("Failed to read server certificate for gRPC")?;
-                let key_pem = std::fs::read_to_string(&mtls_config.server_key)
-                    .context("Failed to read server key for gRPC")?;
-                let ca_pem = std::fs::read_to_string(&mtls_config.ca_cert)
-                    .context("Failed to read CA certificate for gRPC")?;
-
-                Some((cert_pem, key_pem, ca_pem))
-            } else {
-                None
-            };
+        // Same server config as the API, so both listeners share the cert resolver
+        let grpc_tls_config = tls_config
+            .as_ref()
+            .map(|(tls_config, _)| tls_config.clone());
+
+        let grpc_unix_socket = config.grpc_unix_socket.clone();
 
         Some(tokio::spawn(async move {
-            tracing::info!(addr = %grpc_addr, "Starting gRPC multiplexer server");
-            if let Err(e) = tei_manager::grpc::server::start_grpc_server_with_shutdown(
-                grpc_addr,
-                grpc_registry,
-                grpc_tls_config,
-                grpc_max_message_size_mb,
-                grpc_max_parallel_streams,
-                grpc_request_timeout_secs,
-                async move {
-                    let _ = grpc_shutdown_rx.recv().await;
-                    tracing::info!("gRPC server received shutdown signal");
-                },
-            )
-            .await
-            {
+            let shutdown = async move {
+                let _ = grpc_shutdown_rx.recv().await;
+                tracing::info!("gRPC server received shutdown signal");
+            };
+            let result = match grpc_unix_socket {
+                Some(path) => {
+                    tei_manager::grpc::server::start_grpc_server_on_unix_socket(
+                        &path,
+                        grpc_registry,
+                        grpc_options,
+                        shutdown,
+                    )
+                    .await
+                }
+                None => {
+                    tracing::info!(addr = %grpc_addr, "Starting gRPC multiplexer server");
+                    tei_manager::grpc::server::start_grpc_server_with_shutdown(
+                        grpc_addr,
+                        grpc_registry,
+                        grpc_tls_config,
+                        grpc_options,
+                        shutdown,
+                    )
+                    .await
+                }
+            };
+            if let Err(e) = result {
                 tracing::error!(error = %e, "gRPC server error");
             }
         }))
@@ -244,8 +364,9 @@ async fn main() -> Result<()> {
 
     // Run HTTP server with graceful shutdown
     // If gRPC is enabled, both servers run concurrently
-    if let Some(tls_config) = tls_config {
+    if let Some((tls_config, cert_resolver)) = tls_config {
         tracing::info!(addr = %addr, "Starting HTTPS API server with mTLS");
+        reload_tls_on_sighup(cert_resolver);
         let rustls_config =
             axum_server::tls_rustls::RustlsConfig::from_config(Arc::new(tls_config));
         tokio::select! {
@@ -272,14 +393,28 @@ async fn main() -> Result<()> {
             }
         }
     } else {
-        tracing::info!(addr = %addr, "Starting HTTP API server (no TLS)");
-        let listener = tokio::net::TcpListener::bind(addr)
-            .await
-            .context("Failed to bind API server")?;
+        let server = async {
+            match &config.api_unix_socket {
+                Some(path) => {
+                    tracing::info!(path = %path.display(), "Starting HTTP API server on Unix socket");
+                    tei_manager::unix_socket::serve_api(path, app, shutdown_signal()).await
+                }
+                None => {
+                    tracing::info!(addr = %addr, "Starting HTTP API server (no TLS)");
+                    let listener = tokio::net::TcpListener::bind(addr)
+                        .await
+                        .context("Failed to bind API server")?;
+                    axum::serve(listener, app)
+                        .with_graceful_shutdown(shutdown_signal())
+                        .await
+                        .context("HTTP API server error")
+                }
+            }
+        };
 
         tokio::select! {
-            result = axum::serve(listener, app).with_graceful_shutdown(shutdown_signal()) => {
-                result.context("HTTP API server error")?;
+            result = server => {
+                result?;
             }
             _ = async {
                 match &grpc_handle {
@@ -305,32 +440,32 @@ async fn main() -> Result<()> {
         let _ = shutdown_tx.send(());
     }
 
-    // Wait for gRPC server to complete graceful shutdown
-    if let Some(handle) = grpc_handle {
-        tracing::info!("Waiting for gRPC server to complete shutdown");
-        match tokio::time::timeout(std::time::Duration::from_secs(30), handle).await {
-            Ok(Ok(())) => tracing::info!("gRPC server shut down successfully"),
-            Ok(Err(e)) => tracing::error!(error = %e, "gRPC server task error"),
-            Err(_) => tracing::warn!("gRPC server shutdown timed out after 30s"),
-        }
-    }
-
-    // Stop all instances
-    tracing::info!("Stopping all instances");
-    for instance in registry.list().await {
-        if let Err(e) = instance.stop().await {
-            tracing::error!(
-                instance = %instance.config.name,
-                error = %e,
-                "Failed to stop instance during shutdown"
-            );
+    // Draining gRPC, stopping instances and saving state share one budget
+    let drain = async {
+        if let Some(handle) = grpc_handle {
+            tracing::info!("Waiting for gRPC server to complete shutdown");
+            match tokio::time::timeout(std::time::Duration::from_secs(30), handle).await {
+                Ok(Ok(())) => tracing::info!("gRPC server shut down successfully"),
+                Ok(Err(e)) => tracing::error!(error = %e, "gRPC server task error"),
+                Err(_) => tracing::warn!("gRPC server shutdown timed out after 30s"),
+            }
         }
+    };
+    let report = tei_manager::shutdown::shutdown(
+        drain,
+        registry.list().await,
+        &state_manager,
+        std::time::Duration::from_secs(config.shutdown_total_timeout_secs),
+    )
+    .await?;
+    if report.drain_timed_out || !report.force_killed.is_empty() {
+        tracing::warn!(
+            drain_timed_out = report.drain_timed_out,
+            force_killed = ?report.force_killed,
+            "Shutdown budget exceeded"
+        );
     }
 
-    // Save final state
-    tracing::info!("Saving final state");
-    state_manager.save().await?;
-
     // Cancel health monitor
     monitor_handle.abort();
 
@@ -410,8 +545,41 @@ fn build_auth_manager(config: &ManagerConfig) -> Result<Option<Arc<AuthManager>>
     Ok(Some(Arc::new(AuthManager::new(providers))))
 }
 
+/// Re-read the server certificate whenever SIGHUP arrives
+///
+/// The HTTPS API and gRPC listeners share the resolver, so both pick it up.
+#[cfg(unix)]
+fn reload_tls_on_sighup(cert_resolver: Arc<ReloadableCertResolver>) {
+    let mut hangup = match signal::unix::signal(signal::unix::SignalKind::hangup()) {
+        Ok(hangup) => hangup,
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to install SIGHUP handler, TLS reload disabled");
+            return;
+        }
+    };
+    tokio::spawn(async move {
+        while hangup.recv().await.is_some() {
+            match cert_resolver.reload() {
+                Ok(()) => tracing::info!("Received SIGHUP, reloaded TLS certificate"),
+                Err(e) => tracing::error!(
+                    error = %format!("{:#}", e),
+                    "Received SIGHUP, TLS reload failed; keeping previous certificate"
+                ),
+            }
+        }
+    });
+}
+
+#[cfg(not(unix))]
+fn reload_tls_on_sighup(_cert_resolver: Arc<ReloadableCertResolver>) {}
+
 /// Build TLS configuration for native mTLS
-fn build_tls_config(config: &ManagerConfig) -> Result<Option<rustls::ServerConfig>> {
+///
+/// The server certificate comes from a [`ReloadableCertResolver`], returned
+/// alongside the config so rotated cert/key files can be picked up on SIGHUP.
+fn build_tls_config(
+    config: &ManagerConfig,
+) -> Result<Option<(rustls::ServerConfig, Arc<ReloadableCertResolver>)>> {
     // Only build TLS config if auth is enabled with mTLS
     if !config.auth.enabled || !config.auth.providers.contains(&"mtls".to_string()) {
         return Ok(None);
@@ -426,22 +594,13 @@ fn build_tls_config(config: &ManagerConfig) -> Result<Option<rustls::ServerConfi
     tracing::info!("Building native TLS configuration for mTLS");
 
     // Load server certificate and key
-    let cert_file =
-        File::open(&mtls_config.server_cert).context("Failed to open server certificate")?;
-    let key_file = File::open(&mtls_config.server_key).context("Failed to open server key")?;
-
-    let mut cert_reader = BufReader::new(cert_file);
-    let mut key_reader = BufReader::new(key_file);
+    let cert_resolver = Arc::new(ReloadableCertResolver::new(
+        &mtls_config.server_cert,
+        &mtls_config.server_key,
+    )?);
 
+    use rustls_pki_types::CertificateDer;
     use rustls_pki_types::pem::PemObject;
-    use rustls_pki_types::{CertificateDer, PrivateKeyDer};
-
-    let certs = CertificateDer::pem_reader_iter(&mut cert_reader)
-        .collect::<Result<Vec<_>, _>>()
-        .context("Failed to parse server certificate")?;
-
-    let key =
-        PrivateKeyDer::from_pem_reader(&mut key_reader).context("Failed to read private key")?;
 
     // Load CA certificate for client verification
     let ca_file = File::open(&mtls_config.ca_cert).context("Failed to open CA certificate")?;
@@ -466,10 +625,9 @@ fn build_tls_config(config: &ManagerConfig) -> Result<Option<rustls::ServerConfi
     // Build TLS config with client authentication
     let tls_config = rustls::ServerConfig::builder()
         .with_client_cert_verifier(client_verifier)
-        .with_single_cert(certs, key)
-        .context("Failed to build TLS config")?;
+        .with_cert_resolver(cert_resolver.clone());
 
     tracing::info!("Native TLS configuration built successfully");
 
-    Ok(Some(tls_config))
+    Ok(Some((tls_config, cert_resolver)))
 }
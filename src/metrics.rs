@@ -2,7 +2,9 @@
 
 use anyhow::Result;
 use metrics_exporter_prometheus::PrometheusBuilder;
-use std::sync::{Arc, OnceLock};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::Duration;
 
 // ============================================================================
 // Trait Definitions
@@ -11,13 +13,13 @@ use std::sync::{Arc, OnceLock};
 /// Trait for recording metrics
 pub trait MetricsRecorder: Send + Sync {
     /// Record a counter increment
-    fn record_counter(&self, name: &'static str, labels: &[(&'static str, &str)], value: u64);
+    fn record_counter(&self, name: &'static str, labels: &[(&str, &str)], value: u64);
 
     /// Record a gauge value
-    fn record_gauge(&self, name: &'static str, value: f64);
+    fn record_gauge(&self, name: &'static str, labels: &[(&str, &str)], value: f64);
 
     /// Record a histogram value
-    fn record_histogram(&self, name: &'static str, labels: &[(&'static str, &str)], value: f64);
+    fn record_histogram(&self, name: &'static str, labels: &[(&str, &str)], value: f64);
 }
 
 // ============================================================================
@@ -28,88 +30,245 @@ pub trait MetricsRecorder: Send + Sync {
 pub struct PrometheusRecorder;
 
 impl MetricsRecorder for PrometheusRecorder {
-    fn record_counter(&self, name: &'static str, labels: &[(&'static str, &str)], value: u64) {
-        match labels.len() {
-            0 => metrics::counter!(name).increment(value),
-            1 => metrics::counter!(name, labels[0].0 => labels[0].1.to_string()).increment(value),
-            _ => {
-                // For 2+ labels, use first 2
-                metrics::counter!(name, labels[0].0 => labels[0].1.to_string(), labels[1].0 => labels[1].1.to_string()).increment(value)
-            }
-        }
+    fn record_counter(&self, name: &'static str, labels: &[(&str, &str)], value: u64) {
+        metrics::counter!(name, to_labels(labels)).increment(value);
     }
 
-    fn record_gauge(&self, name: &'static str, value: f64) {
-        metrics::gauge!(name).set(value);
+    fn record_gauge(&self, name: &'static str, labels: &[(&str, &str)], value: f64) {
+        metrics::gauge!(name, to_labels(labels)).set(value);
     }
 
-    fn record_histogram(&self, name: &'static str, labels: &[(&'static str, &str)], value: f64) {
-        match labels.len() {
-            0 => metrics::histogram!(name).record(value),
-            1 => metrics::histogram!(name, labels[0].0 => labels[0].1.to_string()).record(value),
-            _ => {
-                // For 2+ labels, use first 2
-                metrics::histogram!(name, labels[0].0 => labels[0].1.to_string(), labels[1].0 => labels[1].1.to_string()).record(value)
-            }
-        }
+    fn record_histogram(&self, name: &'static str, labels: &[(&str, &str)], value: f64) {
+        metrics::histogram!(name, to_labels(labels)).record(value);
     }
 }
 
+/// Convert borrowed label pairs into owned `metrics` labels
+fn to_labels(labels: &[(&str, &str)]) -> Vec<metrics::Label> {
+    labels
+        .iter()
+        .map(|(key, value)| metrics::Label::new(key.to_string(), value.to_string()))
+        .collect()
+}
+
 // ============================================================================
 // Metrics Service
 // ============================================================================
 
+/// Operator labels (`metric_labels`) of one instance, as owned pairs
+type InstanceLabels = Arc<[(String, String)]>;
+
 /// Metrics service with dependency injection
 pub struct MetricsService {
     recorder: Arc<dyn MetricsRecorder>,
+    /// Constant labels added to each instance's metrics, by instance name
+    instance_labels: RwLock<HashMap<String, InstanceLabels>>,
 }
 
 impl MetricsService {
     /// Create a new metrics service with the given recorder
     pub fn new(recorder: Arc<dyn MetricsRecorder>) -> Self {
-        Self { recorder }
+        Self {
+            recorder,
+            instance_labels: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Set the constant labels added to metrics of instance `name`
+    ///
+    /// Meant for a handful of low-cardinality operator labels (team, tier, ...);
+    /// every distinct value is a new time series per instance metric.
+    pub fn set_instance_labels(&self, name: &str, labels: &BTreeMap<String, String>) {
+        let mut instance_labels = self.instance_labels.write().unwrap();
+        if labels.is_empty() {
+            instance_labels.remove(name);
+        } else {
+            let labels = labels.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+            instance_labels.insert(name.to_string(), labels);
+        }
+    }
+
+    /// Forget the constant labels of a removed instance
+    pub fn remove_instance_labels(&self, name: &str) {
+        self.instance_labels.write().unwrap().remove(name);
+    }
+
+    /// Call `record` with `labels` plus the constant labels of instance `name`
+    fn with_instance_labels(
+        &self,
+        name: &str,
+        labels: &[(&str, &str)],
+        record: impl FnOnce(&[(&str, &str)]),
+    ) {
+        let extra = self.instance_labels.read().unwrap().get(name).cloned();
+        match extra {
+            None => record(labels),
+            Some(extra) => {
+                let mut all = labels.to_vec();
+                all.extend(extra.iter().map(|(k, v)| (k.as_str(), v.as_str())));
+                record(&all);
+            }
+        }
+    }
+
+    fn instance_counter(&self, metric: &'static str, name: &str, labels: &[(&str, &str)]) {
+        self.with_instance_labels(name, labels, |labels| {
+            self.recorder.record_counter(metric, labels, 1)
+        });
+    }
+
+    fn instance_gauge(
+        &self,
+        metric: &'static str,
+        name: &str,
+        labels: &[(&str, &str)],
+        value: f64,
+    ) {
+        self.with_instance_labels(name, labels, |labels| {
+            self.recorder.record_gauge(metric, labels, value)
+        });
+    }
+
+    fn instance_histogram(
+        &self,
+        metric: &'static str,
+        name: &str,
+        labels: &[(&str, &str)],
+        value: f64,
+    ) {
+        self.with_instance_labels(name, labels, |labels| {
+            self.recorder.record_histogram(metric, labels, value)
+        });
     }
 
     /// Record instance creation
     pub fn record_instance_created(&self, name: &str, model_id: &str) {
-        self.recorder.record_counter(
+        self.instance_counter(
             "tei_manager_instances_created_total",
+            name,
             &[("instance", name), ("model", model_id)],
-            1,
         );
     }
 
     /// Record instance deletion
     pub fn record_instance_deleted(&self, name: &str) {
-        self.recorder.record_counter(
+        self.instance_counter(
             "tei_manager_instances_deleted_total",
+            name,
             &[("instance", name)],
-            1,
         );
     }
 
     /// Record health check failure
     pub fn record_health_check_failure(&self, name: &str) {
-        self.recorder.record_counter(
+        self.instance_counter(
             "tei_manager_health_check_failures_total",
+            name,
             &[("instance", name)],
-            1,
         );
     }
 
+    /// Record how long a health check took
+    ///
+    /// `result` is one of a few fixed outcomes, keeping cardinality per instance bounded.
+    pub fn record_health_check_duration(&self, name: &str, result: &str, duration_secs: f64) {
+        self.instance_histogram(
+            "tei_health_check_duration_seconds",
+            name,
+            &[("instance", name), ("result", result)],
+            duration_secs,
+        );
+    }
+
+    /// Record a process exit that looked like an OOM kill
+    pub fn record_instance_oom(&self, name: &str) {
+        self.instance_counter("tei_instance_oom_total", name, &[("instance", name)]);
+    }
+
     /// Record instance restart
     pub fn record_instance_restart(&self, name: &str) {
-        self.recorder.record_counter(
+        self.instance_counter(
             "tei_manager_instance_restarts_total",
+            name,
             &[("instance", name)],
-            1,
+        );
+    }
+
+    /// Record instance stopped by the idle reaper
+    pub fn record_instance_idle_stop(&self, name: &str) {
+        self.instance_counter(
+            "tei_manager_instance_idle_stops_total",
+            name,
+            &[("instance", name)],
+        );
+    }
+
+    /// Record a request forwarded by the gRPC multiplexer
+    pub fn record_grpc_request(
+        &self,
+        instance: &str,
+        model: &str,
+        method: &str,
+        status: &str,
+        duration_secs: f64,
+    ) {
+        self.instance_counter(
+            "tei_manager_grpc_requests_total",
+            instance,
+            &[
+                ("instance", instance),
+                ("model", model),
+                ("method", method),
+                ("status", status),
+            ],
+        );
+        self.instance_histogram(
+            "tei_manager_grpc_request_duration_seconds",
+            instance,
+            &[("instance", instance), ("model", model), ("method", method)],
+            duration_secs,
+        );
+    }
+
+    /// Record a streaming RPC whose response channel to the client was full
+    pub fn record_stream_channel_full(&self, instance: &str, method: &str) {
+        self.instance_counter(
+            "tei_stream_channel_full_total",
+            instance,
+            &[("instance", instance), ("method", method)],
         );
     }
 
     /// Update total instance count gauge
     pub fn update_instance_count(&self, count: usize) {
         self.recorder
-            .record_gauge("tei_manager_instances_count", count as f64);
+            .record_gauge("tei_manager_instances_count", &[], count as f64);
+    }
+
+    /// Update the gauge of backend requests currently forwarded by the multiplexer
+    pub fn update_grpc_inflight(&self, count: usize) {
+        self.recorder
+            .record_gauge("tei_manager_grpc_inflight_requests", &[], count as f64);
+    }
+
+    /// Update the gauge of requests currently forwarded to one instance
+    pub fn update_instance_inflight(&self, name: &str, count: usize) {
+        self.instance_gauge(
+            "tei_instance_inflight",
+            name,
+            &[("instance", name)],
+            count as f64,
+        );
+    }
+
+    /// Record whether an instance's Prometheus endpoint answered its last probe
+    pub fn record_metrics_reachable(&self, name: &str, reachable: bool) {
+        self.instance_gauge(
+            "tei_instance_metrics_reachable",
+            name,
+            &[("instance", name)],
+            if reachable { 1.0 } else { 0.0 },
+        );
     }
 }
 
@@ -139,6 +298,20 @@ pub fn setup_metrics() -> Result<metrics_exporter_prometheus::PrometheusHandle>
     Ok(handle)
 }
 
+/// Set an instance's constant metric labels (global function for backward compatibility)
+pub fn set_instance_labels(name: &str, labels: &BTreeMap<String, String>) {
+    if let Some(service) = METRICS_SERVICE.get() {
+        service.set_instance_labels(name, labels);
+    }
+}
+
+/// Forget a removed instance's metric labels (global function for backward compatibility)
+pub fn remove_instance_labels(name: &str) {
+    if let Some(service) = METRICS_SERVICE.get() {
+        service.remove_instance_labels(name);
+    }
+}
+
 /// Record instance creation (global function for backward compatibility)
 pub fn record_instance_created(name: &str, model_id: &str) {
     if let Some(service) = METRICS_SERVICE.get() {
@@ -160,6 +333,20 @@ pub fn record_health_check_failure(name: &str) {
     }
 }
 
+/// Record a health check duration (global function for backward compatibility)
+pub fn record_health_check_duration(name: &str, result: &str, duration_secs: f64) {
+    if let Some(service) = METRICS_SERVICE.get() {
+        service.record_health_check_duration(name, result, duration_secs);
+    }
+}
+
+/// Record a suspected OOM kill (global function for backward compatibility)
+pub fn record_instance_oom(name: &str) {
+    if let Some(service) = METRICS_SERVICE.get() {
+        service.record_instance_oom(name);
+    }
+}
+
 /// Record instance restart (global function for backward compatibility)
 pub fn record_instance_restart(name: &str) {
     if let Some(service) = METRICS_SERVICE.get() {
@@ -167,6 +354,33 @@ pub fn record_instance_restart(name: &str) {
     }
 }
 
+/// Record idle auto-stop (global function for backward compatibility)
+pub fn record_instance_idle_stop(name: &str) {
+    if let Some(service) = METRICS_SERVICE.get() {
+        service.record_instance_idle_stop(name);
+    }
+}
+
+/// Record a forwarded gRPC request (global function for backward compatibility)
+pub fn record_grpc_request(
+    instance: &str,
+    model: &str,
+    method: &str,
+    status: &str,
+    duration_secs: f64,
+) {
+    if let Some(service) = METRICS_SERVICE.get() {
+        service.record_grpc_request(instance, model, method, status, duration_secs);
+    }
+}
+
+/// Record a full stream response channel (global function for backward compatibility)
+pub fn record_stream_channel_full(instance: &str, method: &str) {
+    if let Some(service) = METRICS_SERVICE.get() {
+        service.record_stream_channel_full(instance, method);
+    }
+}
+
 /// Update total instance count gauge (global function for backward compatibility)
 pub fn update_instance_count(count: usize) {
     if let Some(service) = METRICS_SERVICE.get() {
@@ -174,6 +388,188 @@ pub fn update_instance_count(count: usize) {
     }
 }
 
+/// Update the multiplexer inflight gauge (global function for backward compatibility)
+pub fn update_grpc_inflight(count: usize) {
+    if let Some(service) = METRICS_SERVICE.get() {
+        service.update_grpc_inflight(count);
+    }
+}
+
+/// Update an instance's inflight gauge (global function for backward compatibility)
+pub fn update_instance_inflight(name: &str, count: usize) {
+    if let Some(service) = METRICS_SERVICE.get() {
+        service.update_instance_inflight(name, count);
+    }
+}
+
+/// Record Prometheus endpoint reachability (global function for backward compatibility)
+pub fn record_metrics_reachable(name: &str, reachable: bool) {
+    if let Some(service) = METRICS_SERVICE.get() {
+        service.record_metrics_reachable(name, reachable);
+    }
+}
+
+// ============================================================================
+// Instance Metrics Scraping
+// ============================================================================
+
+/// Largest `/metrics` response read from an instance when scraping it
+pub const MAX_METRICS_RESPONSE_BYTES: usize = 16 * 1024 * 1024;
+
+/// Fetch `http://localhost:{port}/metrics` with a bare HTTP/1.0 request
+///
+/// Returns the body of a 200 response, or a description of what went wrong.
+/// Responses over [`MAX_METRICS_RESPONSE_BYTES`] are rejected.
+pub async fn scrape_instance_metrics(port: u16, timeout: Duration) -> Result<String, String> {
+    let response = tokio::time::timeout(
+        timeout,
+        fetch_metrics(port, MAX_METRICS_RESPONSE_BYTES, false),
+    )
+    .await
+    .map_err(|_| format!("timed out after {}s", timeout.as_secs()))?
+    .map_err(|e| e.to_string())?;
+    let response = String::from_utf8_lossy(&response);
+
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .ok_or_else(|| "malformed HTTP response".to_string())?;
+    let status_line = head.lines().next().unwrap_or_default();
+    match status_line.split_whitespace().nth(1) {
+        Some("200") => Ok(body.to_string()),
+        _ => Err(format!("unexpected response '{}'", status_line)),
+    }
+}
+
+/// Send `GET /metrics` over HTTP/1.0 to `localhost:{port}` and read the raw response
+///
+/// With HTTP/1.0 the server closes the connection after the body, so the
+/// response ends at EOF, or at the end of the status line with
+/// `status_line_only`. A response growing past `limit` bytes is an error rather
+/// than read into memory. Shared by scraping and the health monitor's probe.
+pub(crate) async fn fetch_metrics(
+    port: u16,
+    limit: usize,
+    status_line_only: bool,
+) -> std::io::Result<Vec<u8>> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let mut stream = tokio::net::TcpStream::connect(("localhost", port)).await?;
+    stream
+        .write_all(b"GET /metrics HTTP/1.0\r\nHost: localhost\r\n\r\n")
+        .await?;
+
+    let mut response = Vec::new();
+    let mut chunk = [0u8; 8192];
+    loop {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Ok(response);
+        }
+        response.extend_from_slice(&chunk[..n]);
+        if status_line_only && response.contains(&b'\n') {
+            return Ok(response);
+        }
+        if response.len() > limit {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("response exceeds {} bytes", limit),
+            ));
+        }
+    }
+}
+
+/// Add an `instance="name"` label to every sample in Prometheus text output
+///
+/// Comments pass through unchanged. A sample's own `instance` label is kept as
+/// `exported_instance`, like Prometheus does for conflicting target labels.
+pub fn relabel_instance_metrics(text: &str, instance_name: &str) -> String {
+    let value = instance_name
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    let label = format!("instance=\"{}\"", value);
+
+    let mut out = String::with_capacity(text.len() + text.lines().count() * label.len());
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            out.push_str(line);
+        } else if let Some(brace) = line.find('{').filter(|&i| !line[..i].contains(' ')) {
+            let (name, labels) = line.split_at(brace + 1);
+            let labels = relabel_existing_instance(labels);
+            out.push_str(name);
+            out.push_str(&label);
+            if !labels.starts_with('}') {
+                out.push(',');
+            }
+            out.push_str(&labels);
+        } else if let Some(space) = line.find(' ') {
+            let (name, rest) = line.split_at(space);
+            out.push_str(name);
+            out.push('{');
+            out.push_str(&label);
+            out.push('}');
+            out.push_str(rest);
+        } else {
+            out.push_str(line);
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Rename the `instance` label in `labels` (the text after `{`) to `exported_instance`
+///
+/// Walks the label set pair by pair, so `instance=` inside a quoted value is left
+/// alone. Anything after the closing `}` is kept as is, as is a malformed set.
+fn relabel_existing_instance(labels: &str) -> String {
+    let mut out = String::with_capacity(labels.len() + "exported_".len());
+    let mut rest = labels;
+    loop {
+        let trimmed = rest.trim_start_matches([' ', ',']);
+        out.push_str(&rest[..rest.len() - trimmed.len()]);
+        rest = trimmed;
+        let Some(eq) = rest.find('=').filter(|_| !rest.starts_with('}')) else {
+            out.push_str(rest);
+            return out;
+        };
+
+        let name = &rest[..eq];
+        if name.trim_end() == "instance" {
+            out.push_str("exported_");
+        }
+        out.push_str(name);
+        out.push('=');
+        rest = &rest[eq + 1..];
+
+        // The quoted value, up to its first unescaped closing quote
+        let Some(value_len) = quoted_len(rest) else {
+            out.push_str(rest);
+            return out;
+        };
+        out.push_str(&rest[..value_len]);
+        rest = &rest[value_len..];
+    }
+}
+
+/// Length of the `"..."` string (with its quotes) that `text` starts with, if any
+fn quoted_len(text: &str) -> Option<usize> {
+    let mut chars = text.char_indices();
+    if chars.next()?.1 != '"' {
+        return None;
+    }
+    let mut escaped = false;
+    for (i, c) in chars {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => return Some(i + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
 // ============================================================================
 // Mock Implementation for Testing
 // ============================================================================
@@ -194,6 +590,7 @@ pub mod mocks {
         counters: Arc<RwLock<HashMap<String, u64>>>,
         counter_labels: Arc<RwLock<CounterLabels>>,
         gauges: Arc<RwLock<HashMap<String, f64>>>,
+        gauge_labels: Arc<RwLock<HashMap<String, LabelVec>>>,
         histograms: Arc<RwLock<Vec<HistogramEntry>>>,
     }
 
@@ -209,6 +606,7 @@ pub mod mocks {
                 counters: Arc::new(RwLock::new(HashMap::new())),
                 counter_labels: Arc::new(RwLock::new(HashMap::new())),
                 gauges: Arc::new(RwLock::new(HashMap::new())),
+                gauge_labels: Arc::new(RwLock::new(HashMap::new())),
                 histograms: Arc::new(RwLock::new(Vec::new())),
             }
         }
@@ -242,6 +640,15 @@ pub mod mocks {
             }
         }
 
+        /// Check if a gauge was last set with a specific label
+        pub fn gauge_has_label(&self, name: &str, key: &str, value: &str) -> bool {
+            if let Some(labels) = self.gauge_labels.read().unwrap().get(name) {
+                labels.iter().any(|(k, v)| k == key && v == value)
+            } else {
+                false
+            }
+        }
+
         /// Get all histogram recordings
         pub fn get_histograms(&self) -> Vec<HistogramEntry> {
             self.histograms.read().unwrap().clone()
@@ -252,12 +659,13 @@ pub mod mocks {
             self.counters.write().unwrap().clear();
             self.counter_labels.write().unwrap().clear();
             self.gauges.write().unwrap().clear();
+            self.gauge_labels.write().unwrap().clear();
             self.histograms.write().unwrap().clear();
         }
     }
 
     impl MetricsRecorder for MockMetricsRecorder {
-        fn record_counter(&self, name: &'static str, labels: &[(&'static str, &str)], value: u64) {
+        fn record_counter(&self, name: &'static str, labels: &[(&str, &str)], value: u64) {
             let mut counters = self.counters.write().unwrap();
             *counters.entry(name.to_string()).or_insert(0) += value;
 
@@ -269,17 +677,21 @@ pub mod mocks {
             }
         }
 
-        fn record_gauge(&self, name: &'static str, value: f64) {
+        fn record_gauge(&self, name: &'static str, labels: &[(&str, &str)], value: f64) {
             let mut gauges = self.gauges.write().unwrap();
             gauges.insert(name.to_string(), value);
+
+            let owned_labels = labels
+                .iter()
+                .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                .collect();
+            self.gauge_labels
+                .write()
+                .unwrap()
+                .insert(name.to_string(), owned_labels);
         }
 
-        fn record_histogram(
-            &self,
-            name: &'static str,
-            labels: &[(&'static str, &str)],
-            value: f64,
-        ) {
+        fn record_histogram(&self, name: &'static str, labels: &[(&str, &str)], value: f64) {
             let mut histograms = self.histograms.write().unwrap();
             let owned_labels: Vec<(String, String)> = labels
                 .iter()
@@ -299,6 +711,29 @@ mod tests {
     use super::*;
     use mocks::MockMetricsRecorder;
 
+    #[test]
+    fn test_instance_labels_added_to_instance_metrics() {
+        let mock = Arc::new(MockMetricsRecorder::new());
+        let service = MetricsService::new(mock.clone());
+        let labels = BTreeMap::from([("team".to_string(), "search".to_string())]);
+        service.set_instance_labels("labelled", &labels);
+
+        service.record_instance_restart("labelled");
+        service.update_instance_inflight("labelled", 2);
+        assert!(mock.counter_has_label("tei_manager_instance_restarts_total", "team", "search"));
+        assert!(mock.gauge_has_label("tei_instance_inflight", "team", "search"));
+
+        // Other instances and manager-wide metrics are unaffected
+        service.update_instance_inflight("other", 1);
+        service.update_instance_count(1);
+        assert!(!mock.gauge_has_label("tei_instance_inflight", "team", "search"));
+        assert!(!mock.gauge_has_label("tei_manager_instances_count", "team", "search"));
+
+        service.remove_instance_labels("labelled");
+        service.update_instance_inflight("labelled", 0);
+        assert!(!mock.gauge_has_label("tei_instance_inflight", "team", "search"));
+    }
+
     #[test]
     fn test_record_instance_created() {
         let mock = Arc::new(MockMetricsRecorder::new());
@@ -359,6 +794,51 @@ mod tests {
         assert_eq!(mock.get_gauge("tei_manager_instances_count"), 10.0);
     }
 
+    #[test]
+    fn test_metrics_reachable_gauge() {
+        let mock = Arc::new(MockMetricsRecorder::new());
+        let service = MetricsService::new(mock.clone());
+
+        service.record_metrics_reachable("inst", true);
+        assert_eq!(mock.get_gauge("tei_instance_metrics_reachable"), 1.0);
+        assert!(mock.gauge_has_label("tei_instance_metrics_reachable", "instance", "inst"));
+
+        service.record_metrics_reachable("inst", false);
+        assert_eq!(mock.get_gauge("tei_instance_metrics_reachable"), 0.0);
+    }
+
+    #[test]
+    fn test_instance_oom_counter() {
+        let mock = Arc::new(MockMetricsRecorder::new());
+        let service = MetricsService::new(mock.clone());
+
+        service.record_instance_oom("inst");
+        service.record_instance_oom("inst");
+        assert_eq!(mock.get_counter("tei_instance_oom_total"), 2);
+    }
+
+    #[test]
+    fn test_grpc_inflight_gauge() {
+        let mock = Arc::new(MockMetricsRecorder::new());
+        let service = MetricsService::new(mock.clone());
+
+        service.update_grpc_inflight(3);
+        assert_eq!(mock.get_gauge("tei_manager_grpc_inflight_requests"), 3.0);
+
+        service.update_grpc_inflight(0);
+        assert_eq!(mock.get_gauge("tei_manager_grpc_inflight_requests"), 0.0);
+    }
+
+    #[test]
+    fn test_instance_inflight_gauge() {
+        let mock = Arc::new(MockMetricsRecorder::new());
+        let service = MetricsService::new(mock.clone());
+
+        service.update_instance_inflight("busy-inst", 2);
+        assert_eq!(mock.get_gauge("tei_instance_inflight"), 2.0);
+        assert!(mock.gauge_has_label("tei_instance_inflight", "instance", "busy-inst"));
+    }
+
     #[test]
     fn test_health_check_failure() {
         let mock = Arc::new(MockMetricsRecorder::new());
@@ -392,6 +872,80 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_instance_idle_stop() {
+        let mock = Arc::new(MockMetricsRecorder::new());
+        let service = MetricsService::new(mock.clone());
+
+        service.record_instance_idle_stop("idle-inst");
+
+        assert_eq!(mock.get_counter("tei_manager_instance_idle_stops_total"), 1);
+        assert!(mock.counter_has_label(
+            "tei_manager_instance_idle_stops_total",
+            "instance",
+            "idle-inst"
+        ));
+    }
+
+    #[test]
+    fn test_grpc_request_has_model_label() {
+        let mock = Arc::new(MockMetricsRecorder::new());
+        let service = MetricsService::new(mock.clone());
+
+        service.record_grpc_request("bge", "BAAI/bge-small-en-v1.5", "embed", "ok", 0.25);
+
+        let name = "tei_manager_grpc_requests_total";
+        assert_eq!(mock.get_counter(name), 1);
+        assert!(mock.counter_has_label(name, "instance", "bge"));
+        assert!(mock.counter_has_label(name, "model", "BAAI/bge-small-en-v1.5"));
+        assert!(mock.counter_has_label(name, "method", "embed"));
+        assert!(mock.counter_has_label(name, "status", "ok"));
+
+        let histograms = mock.get_histograms();
+        assert_eq!(histograms.len(), 1);
+        assert_eq!(histograms[0].0, "tei_manager_grpc_request_duration_seconds");
+        assert_eq!(histograms[0].1, 0.25);
+        assert!(
+            histograms[0]
+                .2
+                .contains(&("model".to_string(), "BAAI/bge-small-en-v1.5".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_stream_channel_full_counter() {
+        let mock = Arc::new(MockMetricsRecorder::new());
+        let service = MetricsService::new(mock.clone());
+
+        service.record_stream_channel_full("bge", "embed_stream");
+        service.record_stream_channel_full("bge", "embed_stream");
+
+        let name = "tei_stream_channel_full_total";
+        assert_eq!(mock.get_counter(name), 2);
+        assert!(mock.counter_has_label(name, "instance", "bge"));
+        assert!(mock.counter_has_label(name, "method", "embed_stream"));
+    }
+
+    #[test]
+    fn test_health_check_duration_histogram() {
+        let mock = Arc::new(MockMetricsRecorder::new());
+        let service = MetricsService::new(mock.clone());
+
+        service.record_health_check_duration("slow-inst", "soft_failure", 1.5);
+
+        let histograms = mock.get_histograms();
+        assert_eq!(histograms.len(), 1);
+        assert_eq!(histograms[0].0, "tei_health_check_duration_seconds");
+        assert_eq!(histograms[0].1, 1.5);
+        assert_eq!(
+            histograms[0].2,
+            vec![
+                ("instance".to_string(), "slow-inst".to_string()),
+                ("result".to_string(), "soft_failure".to_string()),
+            ]
+        );
+    }
+
     #[test]
     fn test_metric_names_consistent() {
         let mock = Arc::new(MockMetricsRecorder::new());
@@ -475,4 +1029,57 @@ mod tests {
         assert!(mock.counter_has_label("tei_manager_instances_created_total", "instance", "inst2"));
         assert!(mock.counter_has_label("tei_manager_instances_created_total", "instance", "inst3"));
     }
+
+    #[test]
+    fn test_relabel_instance_metrics() {
+        let text = "\
+# HELP te_request_count Requests
+# TYPE te_request_count counter
+te_request_count{method=\"embed\"} 3
+te_queue_size 0
+te_build_info{instance=\"pod-1\",version=\"1.8\"} 1
+te_empty{} 2 1700000000
+te_path{path=\"/a,instance=b\",instance=\"p\\\"1\"} 4
+";
+        let relabeled = relabel_instance_metrics(text, "bge");
+        let lines: Vec<_> = relabeled.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "# HELP te_request_count Requests",
+                "# TYPE te_request_count counter",
+                "te_request_count{instance=\"bge\",method=\"embed\"} 3",
+                "te_queue_size{instance=\"bge\"} 0",
+                "te_build_info{instance=\"bge\",exported_instance=\"pod-1\",version=\"1.8\"} 1",
+                "te_empty{instance=\"bge\"} 2 1700000000",
+                "te_path{instance=\"bge\",path=\"/a,instance=b\",exported_instance=\"p\\\"1\"} 4",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_metrics_bounded() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut request = [0u8; 256];
+                let _ = socket.read(&mut request).await;
+                let _ = socket.write_all(b"HTTP/1.0 200 OK\r\n\r\n").await;
+                let _ = socket.write_all(&[b'#'; 64 * 1024]).await;
+            }
+        });
+
+        let response = fetch_metrics(port, 128 * 1024, false).await.unwrap();
+        assert_eq!(response.len(), 19 + 64 * 1024);
+
+        let err = fetch_metrics(port, 1024, false).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+        // The status line alone fits in any sensible limit
+        let head = fetch_metrics(port, 1024, true).await.unwrap();
+        assert!(head.starts_with(b"HTTP/1.0 200 OK\r\n"));
+    }
 }
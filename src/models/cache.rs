@@ -16,7 +16,7 @@
 //!     └── ...
 //! ```
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Get the HuggingFace cache directory
 ///
@@ -41,6 +41,11 @@ pub fn get_cache_dir() -> PathBuf {
         .unwrap_or_else(|| PathBuf::from("/tmp/huggingface/hub"))
 }
 
+/// Hub cache under `hf_home` (as TEI resolves `HF_HOME`), or [`get_cache_dir`] when None
+pub fn cache_dir_for(hf_home: Option<&Path>) -> PathBuf {
+    hf_home.map_or_else(get_cache_dir, |home| home.join("hub"))
+}
+
 /// Convert model ID to cache directory name
 ///
 /// HuggingFace uses `models--{org}--{name}` format
@@ -60,7 +65,10 @@ fn cache_name_to_model_id(cache_name: &str) -> Option<String> {
 
 /// Check if a model is cached (downloaded)
 pub fn is_model_cached(model_id: &str) -> bool {
-    let cache_dir = get_cache_dir();
+    is_model_cached_in(&get_cache_dir(), model_id)
+}
+
+pub(crate) fn is_model_cached_in(cache_dir: &Path, model_id: &str) -> bool {
     let model_dir = cache_dir.join(model_id_to_cache_name(model_id));
 
     // Check if snapshots directory exists with at least one revision
@@ -85,7 +93,10 @@ pub fn is_model_cached(model_id: &str) -> bool {
 ///
 /// Returns the path to the snapshot directory containing model files
 pub fn get_model_cache_path(model_id: &str) -> Option<PathBuf> {
-    let cache_dir = get_cache_dir();
+    model_cache_path_in(&get_cache_dir(), model_id)
+}
+
+pub(crate) fn model_cache_path_in(cache_dir: &Path, model_id: &str) -> Option<PathBuf> {
     let model_dir = cache_dir.join(model_id_to_cache_name(model_id));
 
     // First try to resolve via refs/main
@@ -114,9 +125,93 @@ pub fn get_model_cache_path(model_id: &str) -> Option<PathBuf> {
     None
 }
 
+/// Get the revision (commit hash) of a model's cached snapshot
+///
+/// This is the snapshot [`get_model_cache_path`] resolves to.
+pub fn get_cached_revision(model_id: &str) -> Option<String> {
+    cached_revision_in(&get_cache_dir(), model_id)
+}
+
+fn cached_revision_in(cache_dir: &Path, model_id: &str) -> Option<String> {
+    model_cache_path_in(cache_dir, model_id).and_then(|path| {
+        path.file_name()
+            .map(|name| name.to_string_lossy().to_string())
+    })
+}
+
+/// Result of comparing a requested revision against the cache
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RevisionCheck {
+    /// Nothing is cached for the model; TEI will download the requested revision
+    NotCached,
+    /// The requested revision is in the cache
+    Cached,
+    /// The cache holds a different revision than the one requested
+    Mismatch { cached: String },
+    /// The revision could escape the model's cache directory (see [`is_valid_revision`])
+    Invalid,
+}
+
+/// Whether `revision` can only name a ref or snapshot, never a path
+pub fn is_valid_revision(revision: &str) -> bool {
+    !revision.is_empty() && !revision.contains(['/', '\\']) && !revision.contains("..")
+}
+
+/// Whether `revision` looks like an abbreviated commit hash
+fn is_short_hash(revision: &str) -> bool {
+    (7..40).contains(&revision.len()) && revision.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Check whether `revision` (commit hash, branch or tag) of a model is cached
+///
+/// An abbreviated commit hash matches the one snapshot it is a prefix of. Reads
+/// the cache directory, so call it off the async runtime.
+pub fn check_revision(model_id: &str, revision: &str) -> RevisionCheck {
+    check_revision_in(&get_cache_dir(), model_id, revision)
+}
+
+pub(crate) fn check_revision_in(cache_dir: &Path, model_id: &str, revision: &str) -> RevisionCheck {
+    if !is_valid_revision(revision) {
+        return RevisionCheck::Invalid;
+    }
+    let model_dir = cache_dir.join(model_id_to_cache_name(model_id));
+    let snapshots_dir = model_dir.join("snapshots");
+
+    // A commit hash names its snapshot directly; branches and tags resolve via refs/
+    let commit = std::fs::read_to_string(model_dir.join("refs").join(revision))
+        .map(|commit| commit.trim().to_string())
+        .unwrap_or_else(|_| revision.to_string());
+    if is_valid_revision(&commit) && snapshots_dir.join(&commit).join("config.json").exists() {
+        return RevisionCheck::Cached;
+    }
+
+    // An abbreviated hash, if exactly one snapshot starts with it
+    if is_short_hash(revision) {
+        let mut matches = std::fs::read_dir(&snapshots_dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter(|entry| {
+                entry.file_name().to_string_lossy().starts_with(revision)
+                    && entry.path().join("config.json").exists()
+            });
+        if matches.next().is_some() && matches.next().is_none() {
+            return RevisionCheck::Cached;
+        }
+    }
+
+    match cached_revision_in(cache_dir, model_id) {
+        Some(cached) => RevisionCheck::Mismatch { cached },
+        None => RevisionCheck::NotCached,
+    }
+}
+
 /// Get the total size of a cached model in bytes
 pub fn get_cache_size(model_id: &str) -> Option<u64> {
-    let cache_dir = get_cache_dir();
+    cache_size_in(&get_cache_dir(), model_id)
+}
+
+pub(crate) fn cache_size_in(cache_dir: &Path, model_id: &str) -> Option<u64> {
     let model_dir = cache_dir.join(model_id_to_cache_name(model_id));
 
     if !model_dir.exists() {
@@ -148,15 +243,17 @@ fn dir_size(path: &PathBuf) -> u64 {
 ///
 /// Returns model IDs for all models found in the cache
 pub fn list_cached_models() -> Vec<String> {
-    let cache_dir = get_cache_dir();
+    list_cached_models_in(&get_cache_dir())
+}
 
+pub(crate) fn list_cached_models_in(cache_dir: &Path) -> Vec<String> {
     if !cache_dir.exists() {
         return Vec::new();
     }
 
     let mut models = Vec::new();
 
-    if let Ok(entries) = std::fs::read_dir(&cache_dir) {
+    if let Ok(entries) = std::fs::read_dir(cache_dir) {
         for entry in entries.flatten() {
             let name = entry.file_name().to_string_lossy().to_string();
 
@@ -168,7 +265,7 @@ pub fn list_cached_models() -> Vec<String> {
             // Convert back to model ID
             if let Some(model_id) = cache_name_to_model_id(&name) {
                 // Verify it's actually a valid cached model
-                if is_model_cached(&model_id) {
+                if is_model_cached_in(cache_dir, &model_id) {
                     models.push(model_id);
                 }
             }
@@ -228,6 +325,20 @@ mod tests {
         assert!(cache_dir.to_string_lossy().contains("huggingface/hub"));
     }
 
+    #[test]
+    fn test_cache_dir_for_instance_hf_home() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let hf_home = temp_dir.path();
+        let cache_dir = cache_dir_for(Some(hf_home));
+        assert_eq!(cache_dir, hf_home.join("hub"));
+
+        let snapshot = cache_dir.join("models--org--isolated/snapshots/abc123");
+        std::fs::create_dir_all(&snapshot).unwrap();
+        std::fs::write(snapshot.join("config.json"), "{}").unwrap();
+        assert!(is_model_cached_in(&cache_dir, "org/isolated"));
+        assert!(!is_model_cached("org/isolated"));
+    }
+
     #[test]
     fn test_is_model_cached_not_cached() {
         // A random model ID that won't exist
@@ -287,6 +398,109 @@ mod tests {
         assert_eq!(size, 8); // 3 + 5 bytes
     }
 
+    /// Lay out a fake HF cache entry with one snapshot and `refs/main` pointing at it
+    fn fake_cached_model(cache_dir: &Path, model_id: &str, commit: &str) {
+        let model_dir = cache_dir.join(model_id_to_cache_name(model_id));
+        let snapshot = model_dir.join("snapshots").join(commit);
+        std::fs::create_dir_all(&snapshot).unwrap();
+        std::fs::write(snapshot.join("config.json"), "{}").unwrap();
+        std::fs::create_dir_all(model_dir.join("refs")).unwrap();
+        std::fs::write(model_dir.join("refs/main"), commit).unwrap();
+    }
+
+    #[test]
+    fn test_cached_revision() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fake_cached_model(temp_dir.path(), "org/model", "abc123");
+
+        assert_eq!(
+            cached_revision_in(temp_dir.path(), "org/model"),
+            Some("abc123".to_string())
+        );
+        assert_eq!(cached_revision_in(temp_dir.path(), "org/other"), None);
+    }
+
+    #[test]
+    fn test_check_revision_against_fake_cache() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache_dir = temp_dir.path();
+        fake_cached_model(cache_dir, "org/model", "abc123");
+
+        // Commit hash and branch name both resolve to the cached snapshot
+        assert_eq!(
+            check_revision_in(cache_dir, "org/model", "abc123"),
+            RevisionCheck::Cached
+        );
+        assert_eq!(
+            check_revision_in(cache_dir, "org/model", "main"),
+            RevisionCheck::Cached
+        );
+
+        assert_eq!(
+            check_revision_in(cache_dir, "org/model", "def456"),
+            RevisionCheck::Mismatch {
+                cached: "abc123".to_string()
+            }
+        );
+        assert_eq!(
+            check_revision_in(cache_dir, "org/other", "def456"),
+            RevisionCheck::NotCached
+        );
+
+        // Revisions that could leave the cache directory are never looked up
+        for revision in ["../../etc", "refs/main", "a\\b", ""] {
+            assert_eq!(
+                check_revision_in(cache_dir, "org/model", revision),
+                RevisionCheck::Invalid
+            );
+        }
+    }
+
+    #[test]
+    fn test_check_revision_short_hash() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache_dir = temp_dir.path();
+        let commit = "0123456789abcdef0123456789abcdef01234567";
+        fake_cached_model(cache_dir, "org/model", commit);
+
+        assert_eq!(
+            check_revision_in(cache_dir, "org/model", "0123456"),
+            RevisionCheck::Cached
+        );
+        // Too short to be taken as a hash
+        assert!(matches!(
+            check_revision_in(cache_dir, "org/model", "012345"),
+            RevisionCheck::Mismatch { .. }
+        ));
+
+        // Ambiguous once another snapshot shares the prefix
+        let other = cache_dir.join("models--org--model/snapshots/0123456fff");
+        std::fs::create_dir_all(&other).unwrap();
+        std::fs::write(other.join("config.json"), "{}").unwrap();
+        assert!(matches!(
+            check_revision_in(cache_dir, "org/model", "0123456"),
+            RevisionCheck::Mismatch { .. }
+        ));
+    }
+
+    #[test]
+    fn test_list_cached_models_in_fake_cache() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache_dir = temp_dir.path();
+        fake_cached_model(cache_dir, "org/model-b", "abc123");
+        fake_cached_model(cache_dir, "org/model-a", "def456");
+        // Entries without a snapshot config.json are not complete downloads
+        std::fs::create_dir_all(cache_dir.join("models--org--partial/snapshots/x")).unwrap();
+        std::fs::create_dir_all(cache_dir.join("datasets--org--data")).unwrap();
+
+        assert_eq!(
+            list_cached_models_in(cache_dir),
+            vec!["org/model-a".to_string(), "org/model-b".to_string()]
+        );
+        assert!(cache_size_in(cache_dir, "org/model-a").unwrap() > 0);
+        assert!(list_cached_models_in(&cache_dir.join("missing")).is_empty());
+    }
+
     // Note: Tests that modify HF_HOME env var are removed because they race with
     // parallel tests. The cache functions are tested via integration tests in
     // tests/model_registry.rs which use real cached models.
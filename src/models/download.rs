@@ -2,9 +2,144 @@
 //!
 //! Provides async model downloading from HuggingFace Hub using the native
 //! Rust hf-hub crate instead of shelling out to huggingface-cli.
+//!
+//! Each file is retried with exponential backoff on transient failures. hf-hub
+//! downloads in chunks and keeps committed progress in a `.sync.part` file, so
+//! a retry resumes a partial download instead of starting over.
+//!
+//! Concurrent downloads of the same model into the same cache are coalesced:
+//! later callers await the download already in flight instead of starting another.
+
+use crate::config::ManagerConfig;
+use futures::FutureExt;
+use futures::future::{BoxFuture, Shared};
+use hf_hub::api::tokio::{Api, ApiBuilder, ApiError, ApiRepo};
+use hf_hub::{Repo, RepoType};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
+/// Hub asked by [`check_model_availability`] when no endpoint is given
+const DEFAULT_HUB_ENDPOINT: &str = "https://huggingface.co";
+
+/// A download that any number of callers can await
+type SharedDownload = Shared<BoxFuture<'static, Result<PathBuf, String>>>;
+
+/// Key of a download in flight: model id, revision and cache directory
+type DownloadKey = (String, Option<String>, Option<PathBuf>);
+
+/// Downloads in flight, by model id, revision and cache directory
+static IN_FLIGHT: LazyLock<Mutex<HashMap<DownloadKey, SharedDownload>>> =
+    LazyLock::new(Default::default);
+
+/// Retry policy for individual model files
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DownloadRetryConfig {
+    /// Attempts per file including the first (values below 1 are treated as 1)
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled for each further retry
+    pub base_delay: Duration,
+    /// Upper bound for the delay between attempts
+    pub max_delay: Duration,
+}
+
+impl Default for DownloadRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl DownloadRetryConfig {
+    /// Retry policy from the manager configuration
+    pub fn from_config(config: &ManagerConfig) -> Self {
+        Self {
+            max_attempts: config.model_download_max_attempts,
+            base_delay: Duration::from_millis(config.model_download_retry_base_delay_ms),
+            ..Self::default()
+        }
+    }
+
+    /// Delay before retry number `retry` (1-based)
+    pub fn delay_for(&self, retry: u32) -> Duration {
+        let factor = 1u32 << retry.saturating_sub(1).min(16);
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+}
 
-use hf_hub::api::tokio::{Api, ApiBuilder};
-use std::path::PathBuf;
+/// Options for [`download_model_with_options`]
+#[derive(Debug, Clone, Default)]
+pub struct DownloadOptions {
+    /// Custom cache directory. If None, uses default HF cache.
+    pub cache_dir: Option<PathBuf>,
+    /// Custom Hub endpoint (e.g. a mirror). If None, uses huggingface.co.
+    pub endpoint: Option<String>,
+    /// Revision to download (commit hash, branch or tag). If None, uses main.
+    pub revision: Option<String>,
+    pub retry: DownloadRetryConfig,
+}
+
+/// Whether a failed file download is worth retrying
+///
+/// Timeouts, connection errors, 5xx and 429 are transient. Client errors such as
+/// 404 (missing model or file) and 401/403 (gated or private repo) are not.
+fn is_retryable(error: &ApiError) -> bool {
+    match error {
+        ApiError::RequestError(e) => match e.status() {
+            Some(status) => {
+                status.is_server_error()
+                    || status.as_u16() == 408 // Request Timeout
+                    || status.as_u16() == 429 // Too Many Requests
+            }
+            // No status: timeout, refused or reset connection, truncated body
+            None => true,
+        },
+        ApiError::IoError(e) => matches!(
+            e.kind(),
+            std::io::ErrorKind::ConnectionReset
+                | std::io::ErrorKind::ConnectionAborted
+                | std::io::ErrorKind::TimedOut
+                | std::io::ErrorKind::Interrupted
+                | std::io::ErrorKind::UnexpectedEof
+        ),
+        // hf-hub gave up on a chunk, or another process holds the file lock
+        ApiError::TooManyRetries(_) | ApiError::LockAcquisition(_) => true,
+        _ => false,
+    }
+}
+
+/// Fetch one file, retrying transient failures with exponential backoff
+async fn get_with_retry(
+    repo: &ApiRepo,
+    file: &str,
+    retry: &DownloadRetryConfig,
+) -> Result<PathBuf, ApiError> {
+    let max_attempts = retry.max_attempts.max(1);
+    let mut attempt = 1;
+    loop {
+        match repo.get(file).await {
+            Ok(path) => return Ok(path),
+            Err(e) if attempt < max_attempts && is_retryable(&e) => {
+                let delay = retry.delay_for(attempt);
+                tracing::warn!(
+                    file = %file,
+                    attempt = attempt,
+                    max_attempts = max_attempts,
+                    delay_ms = delay.as_millis() as u64,
+                    error = %e,
+                    "Transient download error, retrying"
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
 
 /// Download a model from HuggingFace Hub
 ///
@@ -34,17 +169,73 @@ pub async fn download_model_to_cache(
     model_id: &str,
     cache_dir: Option<PathBuf>,
 ) -> Result<PathBuf, String> {
-    tracing::info!(model_id = %model_id, cache_dir = ?cache_dir, "Starting model download via hf-hub");
-
-    let api = match cache_dir {
-        Some(dir) => ApiBuilder::new()
-            .with_cache_dir(dir)
-            .build()
-            .map_err(|e| format!("Failed to create HF API client: {}", e))?,
-        None => Api::new().map_err(|e| format!("Failed to create HF API client: {}", e))?,
+    download_model_with_options(
+        model_id,
+        &DownloadOptions {
+            cache_dir,
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+/// Download a model with a custom cache directory, endpoint and retry policy
+///
+/// If the same model and revision is already being downloaded into the same cache, waits for
+/// that download and returns its result instead of starting a second one.
+///
+/// # Returns
+/// * `Ok(PathBuf)` - Path to the downloaded model's snapshot directory
+/// * `Err(String)` - Error message if download failed
+pub async fn download_model_with_options(
+    model_id: &str,
+    options: &DownloadOptions,
+) -> Result<PathBuf, String> {
+    use std::collections::hash_map::Entry;
+
+    let key = (
+        model_id.to_string(),
+        options.revision.clone(),
+        options.cache_dir.clone(),
+    );
+    let download = match IN_FLIGHT.lock().unwrap().entry(key.clone()) {
+        Entry::Occupied(entry) => {
+            tracing::info!(model_id = %model_id, "Model download already in progress, waiting for it");
+            entry.get().clone()
+        }
+        Entry::Vacant(entry) => {
+            let model_id = model_id.to_string();
+            let options = options.clone();
+            let download = async move {
+                let result = fetch_model(&model_id, &options).await;
+                IN_FLIGHT.lock().unwrap().remove(&key);
+                result
+            }
+            .boxed()
+            .shared();
+            entry.insert(download).clone()
+        }
     };
+    download.await
+}
+
+/// Download the files of `model_id`, without coalescing
+async fn fetch_model(model_id: &str, options: &DownloadOptions) -> Result<PathBuf, String> {
+    let cache_dir = options.cache_dir.as_deref();
+    tracing::info!(model_id = %model_id, cache_dir = ?cache_dir, revision = ?options.revision, "Starting model download via hf-hub");
+
+    let api = build_api(cache_dir, options.endpoint.as_deref())
+        .map_err(|e| format!("Failed to create HF API client: {}", e))?;
+    let retry = &options.retry;
 
-    let repo = api.model(model_id.to_string());
+    let repo = match &options.revision {
+        Some(revision) => api.repo(Repo::with_revision(
+            model_id.to_string(),
+            RepoType::Model,
+            revision.clone(),
+        )),
+        None => api.model(model_id.to_string()),
+    };
 
     // Download essential embedding model files
     // These are the minimum files needed for TEI to load a model
@@ -53,8 +244,7 @@ pub async fn download_model_to_cache(
     let mut config_path: Option<PathBuf> = None;
     for file in &essential_files {
         tracing::debug!(model_id = %model_id, file = %file, "Downloading file");
-        let path = repo
-            .get(file)
+        let path = get_with_retry(&repo, file, retry)
             .await
             .map_err(|e| format!("Failed to download {}: {}", file, e))?;
 
@@ -75,17 +265,21 @@ pub async fn download_model_to_cache(
 
     let mut downloaded_weights = false;
     for file in &weight_files {
-        match repo.get(file).await {
+        match get_with_retry(&repo, file, retry).await {
             Ok(_) => {
                 tracing::debug!(model_id = %model_id, file = %file, "Downloaded weight file");
                 downloaded_weights = true;
 
                 // If we got an index file, download all shards
                 if file.ends_with(".index.json") {
-                    download_sharded_weights(&repo, model_id).await?;
+                    download_sharded_weights(&repo, model_id, retry).await?;
                 }
                 break;
             }
+            // Retries exhausted on a transient error: don't fall back to another format
+            Err(e) if is_retryable(&e) => {
+                return Err(format!("Failed to download {}: {}", file, e));
+            }
             Err(_) => continue,
         }
     }
@@ -104,7 +298,7 @@ pub async fn download_model_to_cache(
     ];
 
     for file in &optional_files {
-        if repo.get(file).await.is_ok() {
+        if get_with_retry(&repo, file, retry).await.is_ok() {
             tracing::debug!(model_id = %model_id, file = %file, "Downloaded optional file");
         }
     }
@@ -120,14 +314,83 @@ pub async fn download_model_to_cache(
         })
 }
 
+/// Build an hf-hub client for the given cache directory and endpoint
+fn build_api(cache_dir: Option<&Path>, endpoint: Option<&str>) -> Result<Api, ApiError> {
+    if cache_dir.is_none() && endpoint.is_none() {
+        return Api::new();
+    }
+    let mut builder = ApiBuilder::new();
+    if let Some(dir) = cache_dir {
+        builder = builder.with_cache_dir(dir.to_path_buf());
+    }
+    if let Some(endpoint) = endpoint {
+        builder = builder.with_endpoint(endpoint.to_string());
+    }
+    builder.build()
+}
+
+/// Where the model an instance is about to serve can be found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModelAvailability {
+    /// Already in the local HF cache
+    Cached,
+    /// Not cached, but on the Hub: TEI downloads it on start
+    OnHub,
+    /// Neither cached nor on the Hub
+    NotFound,
+    /// Not cached, and the Hub could not be asked (network error, 5xx, ...)
+    Unknown(String),
+}
+
+/// Check whether `model_id` is in `cache_dir` or, failing that, on the Hub
+///
+/// The Hub is asked with a HEAD on `/api/models/{model_id}`, sending `HF_TOKEN`
+/// when set. Without a token the Hub answers 401 for private and missing repos
+/// alike, so both count as not found.
+pub async fn check_model_availability(
+    cache_dir: &Path,
+    model_id: &str,
+    endpoint: Option<&str>,
+    timeout: Duration,
+) -> ModelAvailability {
+    if crate::models::cache::is_model_cached_in(cache_dir, model_id) {
+        return ModelAvailability::Cached;
+    }
+
+    let endpoint = endpoint
+        .unwrap_or(DEFAULT_HUB_ENDPOINT)
+        .trim_end_matches('/');
+    let token = std::env::var("HF_TOKEN").ok().filter(|t| !t.is_empty());
+    let client = match reqwest::Client::builder().timeout(timeout).build() {
+        Ok(client) => client,
+        Err(e) => return ModelAvailability::Unknown(e.to_string()),
+    };
+    let mut request = client.head(format!("{}/api/models/{}", endpoint, model_id));
+    if let Some(token) = &token {
+        request = request.bearer_auth(token);
+    }
+
+    match request.send().await {
+        Ok(response) if response.status().is_success() => ModelAvailability::OnHub,
+        Ok(response)
+            if response.status() == reqwest::StatusCode::NOT_FOUND
+                || (response.status() == reqwest::StatusCode::UNAUTHORIZED && token.is_none()) =>
+        {
+            ModelAvailability::NotFound
+        }
+        Ok(response) => ModelAvailability::Unknown(format!("Hub returned {}", response.status())),
+        Err(e) => ModelAvailability::Unknown(e.to_string()),
+    }
+}
+
 /// Download sharded weight files referenced in an index file
 async fn download_sharded_weights(
-    repo: &hf_hub::api::tokio::ApiRepo,
+    repo: &ApiRepo,
     model_id: &str,
+    retry: &DownloadRetryConfig,
 ) -> Result<(), String> {
     // Get the index file content
-    let index_path = repo
-        .get("model.safetensors.index.json")
+    let index_path = get_with_retry(repo, "model.safetensors.index.json", retry)
         .await
         .map_err(|e| format!("Failed to get index file: {}", e))?;
 
@@ -152,7 +415,7 @@ async fn download_sharded_weights(
 
         for shard in shards {
             tracing::debug!(model_id = %model_id, shard = %shard, "Downloading shard");
-            repo.get(shard)
+            get_with_retry(repo, shard, retry)
                 .await
                 .map_err(|e| format!("Failed to download shard {}: {}", shard, e))?;
         }
@@ -164,6 +427,283 @@ async fn download_sharded_weights(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use axum::{
+        extract::{Path as UrlPath, State},
+        http::{HeaderMap, StatusCode, header},
+        response::{IntoResponse, Response},
+    };
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    /// Minimal HF Hub that serves files and replays scripted failures per file
+    #[derive(Clone, Default)]
+    struct MockHub {
+        files: Arc<HashMap<String, Vec<u8>>>,
+        /// Statuses returned (in order) before a file is served normally
+        failures: Arc<Mutex<HashMap<String, Vec<StatusCode>>>>,
+        requests: Arc<Mutex<HashMap<String, usize>>>,
+    }
+
+    impl MockHub {
+        fn new(files: &[(&str, &str)]) -> Self {
+            Self {
+                files: Arc::new(
+                    files
+                        .iter()
+                        .map(|(name, body)| (name.to_string(), body.as_bytes().to_vec()))
+                        .collect(),
+                ),
+                ..Default::default()
+            }
+        }
+
+        fn fail(self, file: &str, statuses: &[StatusCode]) -> Self {
+            self.failures
+                .lock()
+                .unwrap()
+                .insert(file.to_string(), statuses.to_vec());
+            self
+        }
+
+        fn requests(&self, file: &str) -> usize {
+            self.requests
+                .lock()
+                .unwrap()
+                .get(file)
+                .copied()
+                .unwrap_or(0)
+        }
+
+        /// Serve on an ephemeral port and return the endpoint URL
+        async fn serve(&self) -> String {
+            let app = axum::Router::new()
+                .route("/{*path}", axum::routing::get(serve_file))
+                .with_state(self.clone());
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            tokio::spawn(async move { axum::serve(listener, app).await });
+            format!("http://{}", addr)
+        }
+    }
+
+    async fn serve_file(
+        State(hub): State<MockHub>,
+        UrlPath(path): UrlPath<String>,
+        headers: HeaderMap,
+    ) -> Response {
+        // Paths look like {org}/{model}/resolve/{revision}/{file}
+        let file = path.rsplit('/').next().unwrap_or_default().to_string();
+        *hub.requests
+            .lock()
+            .unwrap()
+            .entry(file.clone())
+            .or_default() += 1;
+
+        if let Some(statuses) = hub.failures.lock().unwrap().get_mut(&file)
+            && !statuses.is_empty()
+        {
+            return statuses.remove(0).into_response();
+        }
+        let Some(body) = hub.files.get(&file) else {
+            return StatusCode::NOT_FOUND.into_response();
+        };
+
+        // hf-hub always sends an inclusive byte range
+        let range = headers
+            .get(header::RANGE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("bytes="))
+            .and_then(|v| v.split_once('-'))
+            .and_then(|(start, stop)| Some((start.parse().ok()?, stop.parse().ok()?)));
+        let (start, stop): (usize, usize) = range.unwrap_or((0, body.len() - 1));
+        let stop = stop.min(body.len() - 1);
+
+        (
+            StatusCode::PARTIAL_CONTENT,
+            [
+                ("x-repo-commit", "0123456789abcdef".to_string()),
+                ("etag", format!("\"etag-{}\"", file)),
+                (
+                    header::CONTENT_RANGE.as_str(),
+                    format!("bytes {}-{}/{}", start, stop, body.len()),
+                ),
+            ],
+            body[start..=stop].to_vec(),
+        )
+            .into_response()
+    }
+
+    fn fast_retry(max_attempts: u32) -> DownloadRetryConfig {
+        DownloadRetryConfig {
+            max_attempts,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(5),
+        }
+    }
+
+    async fn download_from(
+        hub: &MockHub,
+        retry: DownloadRetryConfig,
+    ) -> (Result<PathBuf, String>, tempfile::TempDir) {
+        let endpoint = hub.serve().await;
+        let temp_dir = tempfile::tempdir().unwrap();
+        let options = DownloadOptions {
+            cache_dir: Some(temp_dir.path().to_path_buf()),
+            endpoint: Some(endpoint),
+            retry,
+            ..Default::default()
+        };
+        let result = download_model_with_options("org/model", &options).await;
+        (result, temp_dir)
+    }
+
+    #[test]
+    fn test_retry_delay_backs_off_exponentially() {
+        let retry = DownloadRetryConfig {
+            max_attempts: 10,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(1000),
+        };
+        assert_eq!(retry.delay_for(1), Duration::from_millis(100));
+        assert_eq!(retry.delay_for(2), Duration::from_millis(200));
+        assert_eq!(retry.delay_for(3), Duration::from_millis(400));
+        assert_eq!(retry.delay_for(5), Duration::from_millis(1000));
+        assert_eq!(retry.delay_for(40), Duration::from_millis(1000));
+    }
+
+    #[tokio::test]
+    async fn test_download_retries_transient_errors() {
+        let hub = MockHub::new(&[
+            ("config.json", r#"{"model_type": "bert"}"#),
+            ("tokenizer.json", "{}"),
+            ("model.safetensors", "weights"),
+        ])
+        .fail(
+            "config.json",
+            &[StatusCode::SERVICE_UNAVAILABLE, StatusCode::BAD_GATEWAY],
+        );
+
+        let (result, _temp_dir) = download_from(&hub, fast_retry(3)).await;
+
+        let snapshot = result.expect("download should succeed after retries");
+        assert_eq!(
+            std::fs::read_to_string(snapshot.join("config.json")).unwrap(),
+            r#"{"model_type": "bert"}"#
+        );
+        assert!(snapshot.join("model.safetensors").exists());
+        // Two failures, then metadata + content requests
+        assert_eq!(hub.requests("config.json"), 4);
+    }
+
+    #[tokio::test]
+    async fn test_download_fails_after_attempts_exhausted() {
+        let hub = MockHub::new(&[("config.json", "{}"), ("tokenizer.json", "{}")])
+            .fail("config.json", &[StatusCode::SERVICE_UNAVAILABLE; 5]);
+
+        let (result, _temp_dir) = download_from(&hub, fast_retry(3)).await;
+
+        let err = result.unwrap_err();
+        assert!(err.contains("config.json"), "{}", err);
+        assert_eq!(hub.requests("config.json"), 3);
+    }
+
+    #[tokio::test]
+    async fn test_download_does_not_retry_client_errors() {
+        let hub = MockHub::new(&[("config.json", "{}"), ("tokenizer.json", "{}")])
+            .fail("config.json", &[StatusCode::UNAUTHORIZED]);
+
+        let (result, _temp_dir) = download_from(&hub, fast_retry(5)).await;
+        assert!(result.is_err());
+        assert_eq!(hub.requests("config.json"), 1);
+
+        // Missing model: 404 on every file, no retries
+        let hub = MockHub::new(&[]);
+        let (result, _temp_dir) = download_from(&hub, fast_retry(5)).await;
+        assert!(result.is_err());
+        assert_eq!(hub.requests("config.json"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_downloads_of_same_model_coalesce() {
+        let hub = MockHub::new(&[
+            ("config.json", "{}"),
+            ("tokenizer.json", "{}"),
+            ("model.safetensors", "weights"),
+        ]);
+        let endpoint = hub.serve().await;
+        let temp_dir = tempfile::tempdir().unwrap();
+        let options = DownloadOptions {
+            cache_dir: Some(temp_dir.path().to_path_buf()),
+            endpoint: Some(endpoint),
+            retry: fast_retry(1),
+            ..Default::default()
+        };
+
+        let (first, second) = tokio::join!(
+            download_model_with_options("org/model", &options),
+            download_model_with_options("org/model", &options),
+        );
+        assert_eq!(first.unwrap(), second.unwrap());
+        // Metadata + content requests of a single download
+        assert_eq!(hub.requests("config.json"), 2);
+        assert_eq!(hub.requests("model.safetensors"), 2);
+
+        // Finished downloads are not kept around
+        let key = ("org/model".to_string(), None, options.cache_dir.clone());
+        assert!(!IN_FLIGHT.lock().unwrap().contains_key(&key));
+    }
+
+    #[tokio::test]
+    async fn test_uncached_model_missing_from_hub_is_not_found() {
+        // Model pages look like /api/models/{org}/{model}
+        let hub = MockHub::new(&[("bge-small", "{}")]);
+        let endpoint = hub.serve().await;
+        let temp_dir = tempfile::tempdir().unwrap();
+        let timeout = Duration::from_secs(5);
+
+        let missing =
+            check_model_availability(temp_dir.path(), "org/missing", Some(&endpoint), timeout)
+                .await;
+        assert_eq!(missing, ModelAvailability::NotFound);
+
+        let on_hub =
+            check_model_availability(temp_dir.path(), "org/bge-small", Some(&endpoint), timeout)
+                .await;
+        assert_eq!(on_hub, ModelAvailability::OnHub);
+    }
+
+    #[tokio::test]
+    async fn test_cached_model_skips_hub_check() {
+        let hub = MockHub::new(&[]).fail("bge-small", &[StatusCode::INTERNAL_SERVER_ERROR]);
+        let endpoint = hub.serve().await;
+        let temp_dir = tempfile::tempdir().unwrap();
+        let snapshot = temp_dir
+            .path()
+            .join("models--org--bge-small/snapshots/0123456789abcdef");
+        std::fs::create_dir_all(&snapshot).unwrap();
+        std::fs::write(snapshot.join("config.json"), "{}").unwrap();
+
+        let availability = check_model_availability(
+            temp_dir.path(),
+            "org/bge-small",
+            Some(&endpoint),
+            Duration::from_secs(5),
+        )
+        .await;
+        assert_eq!(availability, ModelAvailability::Cached);
+        assert_eq!(hub.requests("bge-small"), 0);
+
+        // Uncached, and the Hub errors: unknown rather than missing
+        let empty_cache = tempfile::tempdir().unwrap();
+        let availability = check_model_availability(
+            empty_cache.path(),
+            "org/bge-small",
+            Some(&endpoint),
+            Duration::from_secs(5),
+        )
+        .await;
+        assert!(matches!(availability, ModelAvailability::Unknown(_)));
+    }
 
     #[tokio::test]
     async fn test_api_creation() {
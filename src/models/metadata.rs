@@ -3,8 +3,12 @@
 //! Parses model configuration from HuggingFace's config.json files
 //! to extract embedding dimension, model type, etc.
 
+use crate::config::Pooling;
+use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::Path;
+use std::sync::Arc;
 
 /// Model metadata extracted from HuggingFace config.json
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -32,6 +36,10 @@ pub struct HfModelMetadata {
     /// Number of attention heads
     #[serde(skip_serializing_if = "Option::is_none")]
     pub num_attention_heads: Option<u32>,
+
+    /// Model classes from config.json (e.g., "BertForMaskedLM")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub architectures: Vec<String>,
 }
 
 /// Raw config.json structure (partial)
@@ -43,6 +51,8 @@ struct RawConfig {
     vocab_size: Option<u32>,
     num_hidden_layers: Option<u32>,
     num_attention_heads: Option<u32>,
+    #[serde(default)]
+    architectures: Vec<String>,
     // Some models use different names
     d_model: Option<u32>,
     n_positions: Option<u32>,
@@ -73,9 +83,218 @@ pub fn parse_model_config(cache_path: &Path) -> Option<HfModelMetadata> {
         vocab_size: raw.vocab_size,
         num_hidden_layers: raw.num_hidden_layers,
         num_attention_heads: raw.num_attention_heads,
+        architectures: raw.architectures,
     })
 }
 
+/// Prompt section of config_sentence_transformers.json (partial)
+#[derive(Debug, Deserialize)]
+struct RawSentenceTransformersConfig {
+    #[serde(default)]
+    prompts: BTreeMap<String, String>,
+}
+
+/// Parse the named prompts from a cached model's config_sentence_transformers.json
+///
+/// # Returns
+/// * `Some(names)` sorted, if the file defines at least one prompt
+/// * `None` if the file doesn't exist, can't be parsed or defines no prompts
+pub fn parse_prompt_names(cache_path: &Path) -> Option<Vec<String>> {
+    let content =
+        std::fs::read_to_string(cache_path.join("config_sentence_transformers.json")).ok()?;
+    let raw: RawSentenceTransformersConfig = serde_json::from_str(&content).ok()?;
+
+    (!raw.prompts.is_empty()).then(|| raw.prompts.into_keys().collect())
+}
+
+/// Source of the prompt names a model defines, used to validate `prompt_name`
+pub trait PromptNameSource: Send + Sync {
+    /// Known prompt names for `model_id`, or None when they can't be determined
+    fn prompt_names(&self, model_id: &str) -> Option<Arc<[String]>>;
+}
+
+/// Reads prompt names from the HuggingFace cache, once per model
+///
+/// Results are only memoized once the model is in the cache, so a model
+/// downloaded later is still picked up.
+#[derive(Default)]
+pub struct CachedPromptNames {
+    by_model: DashMap<String, Option<Arc<[String]>>>,
+}
+
+impl PromptNameSource for CachedPromptNames {
+    fn prompt_names(&self, model_id: &str) -> Option<Arc<[String]>> {
+        if let Some(names) = self.by_model.get(model_id) {
+            return names.clone();
+        }
+
+        let cache_path = super::get_model_cache_path(model_id)?;
+        let names: Option<Arc<[String]>> = parse_prompt_names(&cache_path).map(Arc::from);
+        self.by_model.insert(model_id.to_string(), names.clone());
+        names
+    }
+}
+
+/// Pooling section of a sentence-transformers model (1_Pooling/config.json, partial)
+#[derive(Debug, Default, Deserialize)]
+struct RawPoolingConfig {
+    #[serde(default)]
+    pooling_mode_cls_token: bool,
+    #[serde(default)]
+    pooling_mode_mean_tokens: bool,
+    #[serde(default)]
+    pooling_mode_lasttoken: bool,
+}
+
+/// Parse the pooling a cached sentence-transformers model was trained with
+///
+/// # Returns
+/// * `Some(Pooling::Splade)` if modules.json has a SpladePooling module
+/// * `Some(pooling)` if 1_Pooling/config.json enables exactly one mode TEI supports
+/// * `None` if neither file names a mode TEI supports
+pub fn parse_pooling_config(cache_path: &Path) -> Option<Pooling> {
+    let splade = std::fs::read_to_string(cache_path.join("modules.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<Vec<RawModule>>(&content).ok())
+        .is_some_and(|modules| {
+            modules
+                .iter()
+                .any(|module| module.kind.ends_with(".SpladePooling"))
+        });
+    if splade {
+        return Some(Pooling::Splade);
+    }
+
+    let content = std::fs::read_to_string(cache_path.join("1_Pooling/config.json")).ok()?;
+    let raw: RawPoolingConfig = serde_json::from_str(&content).ok()?;
+
+    match (
+        raw.pooling_mode_cls_token,
+        raw.pooling_mode_mean_tokens,
+        raw.pooling_mode_lasttoken,
+    ) {
+        (true, false, false) => Some(Pooling::Cls),
+        (false, true, false) => Some(Pooling::Mean),
+        (false, false, true) => Some(Pooling::LastToken),
+        _ => None,
+    }
+}
+
+/// Pooling to default an instance of a model to, if any
+///
+/// `families` (keyed by architecture or `model_type`) is consulted first, then the
+/// model's own sentence-transformers pooling (including SpladePooling). Without
+/// either, causal LMs get last-token; anything else is left to TEI, since a
+/// masked-LM head alone doesn't make a model SPLADE.
+pub fn infer_pooling(
+    metadata: &HfModelMetadata,
+    sentence_transformers_pooling: Option<Pooling>,
+    families: &BTreeMap<String, Pooling>,
+) -> Option<Pooling> {
+    let configured = metadata
+        .architectures
+        .iter()
+        .chain(metadata.model_type.as_ref())
+        .find_map(|family| families.get(family));
+    if let Some(pooling) = configured {
+        return Some(*pooling);
+    }
+    if sentence_transformers_pooling.is_some() {
+        return sentence_transformers_pooling;
+    }
+
+    metadata
+        .architectures
+        .iter()
+        .any(|a| a.ends_with("ForCausalLM"))
+        .then_some(Pooling::LastToken)
+}
+
+/// Module of a sentence-transformers pipeline (modules.json entry, partial)
+#[derive(Debug, Deserialize)]
+struct RawModule {
+    path: String,
+    #[serde(rename = "type")]
+    kind: String,
+}
+
+/// Dense projection of a sentence-transformers model (e.g. 2_Dense/config.json, partial)
+#[derive(Debug, Deserialize)]
+struct RawDenseConfig {
+    out_features: u32,
+}
+
+/// Native embedding dimension of a cached model (see [`parse_embedding_dimension`])
+pub fn embedding_dimension(model_id: &str) -> Option<u32> {
+    let cache_path = super::get_model_cache_path(model_id)?;
+    parse_embedding_dimension(&cache_path)
+}
+
+/// Size of the embeddings a cached model produces
+///
+/// A sentence-transformers Dense module projects the pooled output, so the last
+/// one in modules.json sets the size; otherwise it is the model's `hidden_size`.
+pub fn parse_embedding_dimension(cache_path: &Path) -> Option<u32> {
+    let dense = std::fs::read_to_string(cache_path.join("modules.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<Vec<RawModule>>(&content).ok())
+        .and_then(|modules| {
+            modules
+                .into_iter()
+                .rev()
+                .find(|module| module.kind.ends_with(".Dense"))
+        });
+    if let Some(dense) = dense {
+        let content = std::fs::read_to_string(cache_path.join(dense.path).join("config.json")).ok();
+        return content
+            .and_then(|content| serde_json::from_str::<RawDenseConfig>(&content).ok())
+            .map(|config| config.out_features);
+    }
+    parse_model_config(cache_path)?.hidden_size
+}
+
+/// Source of models' native embedding dimensions, for validating `dimensions`
+pub trait EmbeddingDimensionSource: Send + Sync {
+    /// Embedding dimension of `model_id`, or None when it can't be determined
+    fn embedding_dimension(&self, model_id: &str) -> Option<u32>;
+}
+
+/// Reads embedding dimensions from the HuggingFace cache, once per cached model
+#[derive(Default)]
+pub struct CachedEmbeddingDimensions {
+    by_model: DashMap<String, u32>,
+}
+
+impl EmbeddingDimensionSource for CachedEmbeddingDimensions {
+    fn embedding_dimension(&self, model_id: &str) -> Option<u32> {
+        if let Some(dimension) = self.by_model.get(model_id) {
+            return Some(*dimension);
+        }
+
+        let dimension = embedding_dimension(model_id)?;
+        self.by_model.insert(model_id.to_string(), dimension);
+        Some(dimension)
+    }
+}
+
+/// Reject requested `dimensions` of 0 or above the model's native `dimension`
+///
+/// Only the zero check applies when the native dimension is unknown.
+pub fn check_dimensions(
+    model_id: &str,
+    dimensions: Option<u32>,
+    dimension: Option<u32>,
+) -> Result<(), String> {
+    match (dimensions, dimension) {
+        (Some(0), _) => Err("dimensions must be > 0".to_string()),
+        (Some(requested), Some(native)) if requested > native => Err(format!(
+            "dimensions {} exceeds the {} dimensions of model '{}'",
+            requested, native, model_id
+        )),
+        _ => Ok(()),
+    }
+}
+
 /// Estimate number of parameters from model metadata
 ///
 /// This is a rough estimate based on transformer architecture
@@ -118,7 +337,8 @@ mod tests {
             "max_position_embeddings": 512,
             "vocab_size": 30522,
             "num_hidden_layers": 6,
-            "num_attention_heads": 12
+            "num_attention_heads": 12,
+            "architectures": ["BertModel"]
         }"#;
 
         let path = create_test_config(&dir, content);
@@ -130,6 +350,7 @@ mod tests {
         assert_eq!(metadata.vocab_size, Some(30522));
         assert_eq!(metadata.num_hidden_layers, Some(6));
         assert_eq!(metadata.num_attention_heads, Some(12));
+        assert_eq!(metadata.architectures, vec!["BertModel".to_string()]);
     }
 
     #[test]
@@ -165,6 +386,32 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    fn test_parse_prompt_names() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("config_sentence_transformers.json"),
+            r#"{"prompts": {"query": "query: ", "document": "passage: "}, "default_prompt_name": null}"#,
+        )
+        .unwrap();
+
+        let names = parse_prompt_names(dir.path()).unwrap();
+        assert_eq!(names, vec!["document".to_string(), "query".to_string()]);
+    }
+
+    #[test]
+    fn test_parse_prompt_names_missing_or_empty() {
+        let dir = TempDir::new().unwrap();
+        assert!(parse_prompt_names(dir.path()).is_none());
+
+        std::fs::write(
+            dir.path().join("config_sentence_transformers.json"),
+            r#"{"prompts": {}}"#,
+        )
+        .unwrap();
+        assert!(parse_prompt_names(dir.path()).is_none());
+    }
+
     #[test]
     fn test_estimate_parameters() {
         let metadata = HfModelMetadata {
@@ -174,6 +421,7 @@ mod tests {
             vocab_size: Some(30522),
             num_hidden_layers: Some(6),
             num_attention_heads: Some(12),
+            ..Default::default()
         };
 
         let params = estimate_parameters(&metadata).unwrap();
@@ -181,4 +429,132 @@ mod tests {
         assert!(params > 10_000_000);
         assert!(params < 50_000_000);
     }
+
+    #[test]
+    fn test_check_dimensions() {
+        assert!(check_dimensions("bge", None, Some(384)).is_ok());
+        assert!(check_dimensions("bge", Some(256), Some(384)).is_ok());
+        assert!(check_dimensions("bge", Some(384), Some(384)).is_ok());
+        assert!(check_dimensions("bge", Some(4096), None).is_ok());
+
+        let err = check_dimensions("bge", Some(512), Some(384)).unwrap_err();
+        assert!(err.contains("512") && err.contains("384"));
+        assert!(check_dimensions("bge", Some(0), None).is_err());
+    }
+
+    #[test]
+    fn test_infer_pooling_splade_model() {
+        // naver/splade-cocondenser-ensembledistil
+        let metadata = HfModelMetadata {
+            model_type: Some("bert".to_string()),
+            architectures: vec!["BertForMaskedLM".to_string()],
+            ..Default::default()
+        };
+        let families = BTreeMap::new();
+
+        // A masked-LM head alone isn't a SPLADE signal (e.g. plain bert-base-uncased)
+        assert_eq!(infer_pooling(&metadata, None, &families), None);
+
+        // sentence-transformers SparseEncoder layout: MLMTransformer -> SpladePooling
+        let dir = TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("modules.json"),
+            r#"[
+                {"idx": 0, "name": "0", "path": "", "type": "sentence_transformers.sparse_encoder.models.MLMTransformer"},
+                {"idx": 1, "name": "1", "path": "1_SpladePooling", "type": "sentence_transformers.sparse_encoder.models.SpladePooling"}
+            ]"#,
+        )
+        .unwrap();
+        let st_pooling = parse_pooling_config(dir.path());
+        assert_eq!(st_pooling, Some(Pooling::Splade));
+        assert_eq!(
+            infer_pooling(&metadata, st_pooling, &families),
+            Some(Pooling::Splade)
+        );
+
+        // So is a configured family
+        let families = BTreeMap::from([("BertForMaskedLM".to_string(), Pooling::Splade)]);
+        assert_eq!(
+            infer_pooling(&metadata, None, &families),
+            Some(Pooling::Splade)
+        );
+    }
+
+    #[test]
+    fn test_infer_pooling_sentence_transformer() {
+        // BAAI/bge-small-en-v1.5: BertModel with CLS pooling in 1_Pooling
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("1_Pooling")).unwrap();
+        std::fs::write(
+            dir.path().join("1_Pooling/config.json"),
+            r#"{"word_embedding_dimension": 384, "pooling_mode_cls_token": true,
+                "pooling_mode_mean_tokens": false, "pooling_mode_max_tokens": false}"#,
+        )
+        .unwrap();
+        let st_pooling = parse_pooling_config(dir.path());
+        assert_eq!(st_pooling, Some(Pooling::Cls));
+
+        let metadata = HfModelMetadata {
+            model_type: Some("bert".to_string()),
+            architectures: vec!["BertModel".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            infer_pooling(&metadata, st_pooling, &BTreeMap::new()),
+            Some(Pooling::Cls)
+        );
+
+        // A model_type entry applies to every architecture of the family
+        let families = BTreeMap::from([("bert".to_string(), Pooling::Mean)]);
+        assert_eq!(
+            infer_pooling(&metadata, st_pooling, &families),
+            Some(Pooling::Mean)
+        );
+
+        // Nothing to go on: left to TEI
+        assert_eq!(infer_pooling(&metadata, None, &BTreeMap::new()), None);
+    }
+
+    #[test]
+    fn test_embedding_dimension_follows_dense_projection() {
+        let dir = TempDir::new().unwrap();
+        let path = create_test_config(&dir, r#"{"model_type": "bert", "hidden_size": 1024}"#);
+        assert_eq!(parse_embedding_dimension(&path), Some(1024));
+
+        // Transformer -> Pooling -> Dense(1024 -> 256), as sentence-transformers lays it out
+        std::fs::write(
+            path.join("modules.json"),
+            r#"[
+                {"idx": 0, "name": "0", "path": "", "type": "sentence_transformers.models.Transformer"},
+                {"idx": 1, "name": "1", "path": "1_Pooling", "type": "sentence_transformers.models.Pooling"},
+                {"idx": 2, "name": "2", "path": "2_Dense", "type": "sentence_transformers.models.Dense"}
+            ]"#,
+        )
+        .unwrap();
+        std::fs::create_dir(path.join("2_Dense")).unwrap();
+        std::fs::write(
+            path.join("2_Dense/config.json"),
+            r#"{"in_features": 1024, "out_features": 256, "bias": true}"#,
+        )
+        .unwrap();
+        assert_eq!(parse_embedding_dimension(&path), Some(256));
+
+        // A Dense module whose config can't be read leaves the size unknown
+        std::fs::remove_file(path.join("2_Dense/config.json")).unwrap();
+        assert_eq!(parse_embedding_dimension(&path), None);
+    }
+
+    #[test]
+    fn test_parse_pooling_config_unsupported_mode() {
+        let dir = TempDir::new().unwrap();
+        assert_eq!(parse_pooling_config(dir.path()), None);
+
+        std::fs::create_dir(dir.path().join("1_Pooling")).unwrap();
+        std::fs::write(
+            dir.path().join("1_Pooling/config.json"),
+            r#"{"pooling_mode_max_tokens": true}"#,
+        )
+        .unwrap();
+        assert_eq!(parse_pooling_config(dir.path()), None);
+    }
 }
@@ -6,15 +6,28 @@
 //! - Parsing model metadata from config.json
 //! - Tracking model status (available, downloaded, verified)
 //! - Smoke testing model loading
+//! - Background preloading of models into the cache
 
 pub mod cache;
 pub mod download;
 pub mod loader;
 pub mod metadata;
+pub mod preload;
 pub mod registry;
 
-pub use cache::{get_cache_dir, get_model_cache_path, is_model_cached, list_cached_models};
-pub use download::{download_model, download_model_to_cache};
+pub use cache::{
+    RevisionCheck, cache_dir_for, check_revision, get_cache_dir, get_cached_revision,
+    get_model_cache_path, is_model_cached, list_cached_models,
+};
+pub use download::{
+    DownloadOptions, DownloadRetryConfig, ModelAvailability, check_model_availability,
+    download_model, download_model_to_cache, download_model_with_options,
+};
 pub use loader::{LoaderConfig, ModelLoader};
-pub use metadata::{HfModelMetadata, parse_model_config};
+pub use metadata::{
+    CachedEmbeddingDimensions, CachedPromptNames, EmbeddingDimensionSource, HfModelMetadata,
+    PromptNameSource, check_dimensions, embedding_dimension, infer_pooling,
+    parse_embedding_dimension, parse_model_config, parse_pooling_config, parse_prompt_names,
+};
+pub use preload::{PreloadJob, PreloadTracker};
 pub use registry::{ModelEntry, ModelRegistry, ModelStatus};
@@ -0,0 +1,570 @@
+//! Background model preloading (HF cache warm-up)
+//!
+//! Lets operators download a batch of models ahead of instance creation so
+//! that instance startup doesn't block on model downloads. Jobs run in the
+//! background and are tracked in memory; clients poll a job by its ID.
+//! Only the most recent [`MAX_FINISHED_JOBS`] finished jobs are kept.
+
+use super::cache::{RevisionCheck, check_revision, is_model_cached};
+use super::download::{DownloadOptions, DownloadRetryConfig, download_model_with_options};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::sync::RwLock;
+
+// ============================================================================
+// Trait Definitions
+// ============================================================================
+
+/// Trait for checking and populating the model cache
+#[async_trait]
+pub trait ModelDownloader: Send + Sync {
+    /// Check if a model (at `revision`, if given) is already present in the cache
+    fn is_cached(&self, model_id: &str, revision: Option<&str>) -> bool;
+
+    /// Download a model (at `revision`, if given) into the cache
+    async fn download(&self, model_id: &str, revision: Option<&str>) -> Result<PathBuf, String>;
+}
+
+// ============================================================================
+// Production Implementation
+// ============================================================================
+
+/// Production downloader backed by the HuggingFace cache and hf-hub
+#[derive(Default)]
+pub struct HfModelDownloader {
+    retry: DownloadRetryConfig,
+}
+
+impl HfModelDownloader {
+    /// Downloader with a custom retry policy
+    pub fn new(retry: DownloadRetryConfig) -> Self {
+        Self { retry }
+    }
+}
+
+#[async_trait]
+impl ModelDownloader for HfModelDownloader {
+    fn is_cached(&self, model_id: &str, revision: Option<&str>) -> bool {
+        match revision {
+            Some(revision) => check_revision(model_id, revision) == RevisionCheck::Cached,
+            None => is_model_cached(model_id),
+        }
+    }
+
+    async fn download(&self, model_id: &str, revision: Option<&str>) -> Result<PathBuf, String> {
+        download_model_with_options(
+            model_id,
+            &DownloadOptions {
+                revision: revision.map(str::to_string),
+                retry: self.retry,
+                ..Default::default()
+            },
+        )
+        .await
+    }
+}
+
+// ============================================================================
+// Job Types
+// ============================================================================
+
+/// Overall status of a preload job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PreloadJobStatus {
+    /// Downloads are still in progress
+    Running,
+    /// All models are cached
+    Completed,
+    /// At least one model failed to download
+    Failed,
+}
+
+/// Status of a single model within a preload job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PreloadModelStatus {
+    /// Waiting for its turn to download
+    Pending,
+    /// Download in progress
+    Downloading,
+    /// Downloaded by this job
+    Downloaded,
+    /// Already present in cache, nothing to do
+    Skipped,
+    /// Download failed (see `error`)
+    Failed,
+}
+
+/// Progress of a single model within a preload job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreloadModelProgress {
+    /// HuggingFace model ID
+    pub model_id: String,
+    /// Current status
+    pub status: PreloadModelStatus,
+    /// Error message if download failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// A background preload job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreloadJob {
+    /// Unique job identifier
+    pub job_id: String,
+    /// Overall job status
+    pub status: PreloadJobStatus,
+    /// Per-model progress, in request order
+    pub models: Vec<PreloadModelProgress>,
+    /// Revision downloaded for every model, if not main
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revision: Option<String>,
+    /// When the job was created
+    pub created_at: DateTime<Utc>,
+    /// When the job finished (all models processed)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+// ============================================================================
+// Preload Tracker
+// ============================================================================
+
+/// Finished jobs kept for polling; older ones are evicted as new jobs start
+pub const MAX_FINISHED_JOBS: usize = 100;
+
+/// In-memory tracker for background preload jobs
+pub struct PreloadTracker {
+    jobs: Arc<RwLock<HashMap<String, PreloadJob>>>,
+    downloader: Arc<dyn ModelDownloader>,
+    next_id: AtomicU64,
+}
+
+impl PreloadTracker {
+    /// Create a new tracker with a custom downloader
+    pub fn new_with_downloader(downloader: Arc<dyn ModelDownloader>) -> Self {
+        Self {
+            jobs: Arc::new(RwLock::new(HashMap::new())),
+            downloader,
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    /// Create a new tracker using the HuggingFace downloader
+    pub fn new() -> Self {
+        Self::new_with_downloader(Arc::new(HfModelDownloader::default()))
+    }
+
+    /// Start a preload job and return immediately
+    ///
+    /// Models already in the cache are marked `Skipped` up front. The rest are
+    /// downloaded sequentially in a background task.
+    pub async fn start(&self, model_ids: Vec<String>) -> PreloadJob {
+        let (job, pending) = self.create_job(model_ids, None).await;
+
+        if !pending.is_empty() {
+            let jobs = self.jobs.clone();
+            let downloader = self.downloader.clone();
+            let job_id = job.job_id.clone();
+            tokio::spawn(async move {
+                Self::run_job(jobs, downloader, job_id, None, pending).await;
+            });
+        }
+
+        job
+    }
+
+    /// Run a preload job to completion and return the finished job
+    ///
+    /// Like [`start`](Self::start), but downloads in the caller's task and takes
+    /// the revision to fetch (main if None). The job is tracked as usual, so its
+    /// progress can be polled while the caller waits.
+    pub async fn run(&self, model_ids: Vec<String>, revision: Option<String>) -> PreloadJob {
+        let (job, pending) = self.create_job(model_ids, revision.clone()).await;
+        if pending.is_empty() {
+            return job;
+        }
+
+        Self::run_job(
+            self.jobs.clone(),
+            self.downloader.clone(),
+            job.job_id.clone(),
+            revision,
+            pending,
+        )
+        .await;
+
+        self.get(&job.job_id).await.unwrap_or(job)
+    }
+
+    /// Register a new job, returning it with the models that still need downloading
+    async fn create_job(
+        &self,
+        model_ids: Vec<String>,
+        revision: Option<String>,
+    ) -> (PreloadJob, Vec<String>) {
+        let job_id = format!("preload-{}", self.next_id.fetch_add(1, Ordering::SeqCst));
+
+        let models = model_ids
+            .into_iter()
+            .map(|model_id| {
+                let status = if self.downloader.is_cached(&model_id, revision.as_deref()) {
+                    PreloadModelStatus::Skipped
+                } else {
+                    PreloadModelStatus::Pending
+                };
+                PreloadModelProgress {
+                    model_id,
+                    status,
+                    error: None,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let pending: Vec<String> = models
+            .iter()
+            .filter(|m| m.status == PreloadModelStatus::Pending)
+            .map(|m| m.model_id.clone())
+            .collect();
+
+        let mut job = PreloadJob {
+            job_id: job_id.clone(),
+            status: PreloadJobStatus::Running,
+            models,
+            revision,
+            created_at: Utc::now(),
+            finished_at: None,
+        };
+
+        if pending.is_empty() {
+            job.status = PreloadJobStatus::Completed;
+            job.finished_at = Some(Utc::now());
+        }
+
+        let mut jobs = self.jobs.write().await;
+        jobs.insert(job_id.clone(), job.clone());
+        evict_finished_jobs(&mut jobs, MAX_FINISHED_JOBS);
+        drop(jobs);
+
+        tracing::info!(
+            job_id = %job_id,
+            total = job.models.len(),
+            pending = pending.len(),
+            "Preload job started"
+        );
+
+        (job, pending)
+    }
+
+    /// Get a job by ID
+    pub async fn get(&self, job_id: &str) -> Option<PreloadJob> {
+        self.jobs.read().await.get(job_id).cloned()
+    }
+
+    /// Download each pending model and record progress on the job
+    async fn run_job(
+        jobs: Arc<RwLock<HashMap<String, PreloadJob>>>,
+        downloader: Arc<dyn ModelDownloader>,
+        job_id: String,
+        revision: Option<String>,
+        pending: Vec<String>,
+    ) {
+        for model_id in pending {
+            Self::update_model(
+                &jobs,
+                &job_id,
+                &model_id,
+                PreloadModelStatus::Downloading,
+                None,
+            )
+            .await;
+
+            match downloader.download(&model_id, revision.as_deref()).await {
+                Ok(path) => {
+                    tracing::info!(job_id = %job_id, model_id = %model_id, path = ?path, "Preloaded model");
+                    Self::update_model(
+                        &jobs,
+                        &job_id,
+                        &model_id,
+                        PreloadModelStatus::Downloaded,
+                        None,
+                    )
+                    .await;
+                }
+                Err(e) => {
+                    tracing::error!(job_id = %job_id, model_id = %model_id, error = %e, "Failed to preload model");
+                    Self::update_model(
+                        &jobs,
+                        &job_id,
+                        &model_id,
+                        PreloadModelStatus::Failed,
+                        Some(e),
+                    )
+                    .await;
+                }
+            }
+        }
+
+        let mut jobs = jobs.write().await;
+        if let Some(job) = jobs.get_mut(&job_id) {
+            let any_failed = job
+                .models
+                .iter()
+                .any(|m| m.status == PreloadModelStatus::Failed);
+            job.status = if any_failed {
+                PreloadJobStatus::Failed
+            } else {
+                PreloadJobStatus::Completed
+            };
+            job.finished_at = Some(Utc::now());
+
+            tracing::info!(job_id = %job_id, status = ?job.status, "Preload job finished");
+        }
+    }
+
+    async fn update_model(
+        jobs: &RwLock<HashMap<String, PreloadJob>>,
+        job_id: &str,
+        model_id: &str,
+        status: PreloadModelStatus,
+        error: Option<String>,
+    ) {
+        let mut jobs = jobs.write().await;
+        if let Some(progress) = jobs
+            .get_mut(job_id)
+            .and_then(|job| job.models.iter_mut().find(|m| m.model_id == model_id))
+        {
+            progress.status = status;
+            progress.error = error;
+        }
+    }
+}
+
+/// Drop the oldest finished jobs beyond `keep`; running jobs are never evicted
+fn evict_finished_jobs(jobs: &mut HashMap<String, PreloadJob>, keep: usize) {
+    // Ids are numbered in creation order, so a shorter id is older on a tie
+    let mut finished: Vec<(DateTime<Utc>, usize, String)> = jobs
+        .values()
+        .filter_map(|job| {
+            job.finished_at
+                .map(|at| (at, job.job_id.len(), job.job_id.clone()))
+        })
+        .collect();
+    if finished.len() <= keep {
+        return;
+    }
+    finished.sort();
+    for (_, _, job_id) in &finished[..finished.len() - keep] {
+        jobs.remove(job_id);
+    }
+}
+
+impl Default for PreloadTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ============================================================================
+// Mock Implementation for Testing
+// ============================================================================
+
+#[cfg(test)]
+pub mod mocks {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::Mutex;
+
+    /// Stub downloader with a configurable cache and failure set
+    pub struct StubDownloader {
+        cached: Mutex<HashSet<String>>,
+        failing: HashSet<String>,
+        downloads: Mutex<Vec<String>>,
+    }
+
+    impl StubDownloader {
+        pub fn new(cached: &[&str], failing: &[&str]) -> Self {
+            Self {
+                cached: Mutex::new(cached.iter().map(|s| s.to_string()).collect()),
+                failing: failing.iter().map(|s| s.to_string()).collect(),
+                downloads: Mutex::new(Vec::new()),
+            }
+        }
+
+        /// Model IDs that were passed to `download`, in call order
+        pub fn downloads(&self) -> Vec<String> {
+            self.downloads.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl ModelDownloader for StubDownloader {
+        fn is_cached(&self, model_id: &str, revision: Option<&str>) -> bool {
+            self.cached
+                .lock()
+                .unwrap()
+                .contains(&stub_key(model_id, revision))
+        }
+
+        async fn download(
+            &self,
+            model_id: &str,
+            revision: Option<&str>,
+        ) -> Result<PathBuf, String> {
+            let key = stub_key(model_id, revision);
+            self.downloads.lock().unwrap().push(key.clone());
+
+            if self.failing.contains(model_id) {
+                return Err(format!("Stub download failed for {}", model_id));
+            }
+
+            self.cached.lock().unwrap().insert(key);
+            Ok(PathBuf::from("/tmp/stub-cache").join(model_id))
+        }
+    }
+
+    /// `model_id`, or `model_id@revision` when a revision is given
+    fn stub_key(model_id: &str, revision: Option<&str>) -> String {
+        match revision {
+            Some(revision) => format!("{}@{}", model_id, revision),
+            None => model_id.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mocks::StubDownloader;
+    use super::*;
+    use std::time::Duration;
+
+    async fn wait_for_finish(tracker: &PreloadTracker, job_id: &str) -> PreloadJob {
+        for _ in 0..100 {
+            let job = tracker.get(job_id).await.unwrap();
+            if job.status != PreloadJobStatus::Running {
+                return job;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("Preload job {} did not finish", job_id);
+    }
+
+    #[tokio::test]
+    async fn test_preload_skips_cached_models() {
+        let downloader = Arc::new(StubDownloader::new(&["org/cached"], &[]));
+        let tracker = PreloadTracker::new_with_downloader(downloader.clone());
+
+        let job = tracker.start(vec!["org/cached".to_string()]).await;
+
+        assert_eq!(job.status, PreloadJobStatus::Completed);
+        assert!(job.finished_at.is_some());
+        assert_eq!(job.models[0].status, PreloadModelStatus::Skipped);
+        assert!(downloader.downloads().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_preload_job_lifecycle() {
+        let downloader = Arc::new(StubDownloader::new(&["org/cached"], &[]));
+        let tracker = PreloadTracker::new_with_downloader(downloader.clone());
+
+        let job = tracker
+            .start(vec!["org/cached".to_string(), "org/fresh".to_string()])
+            .await;
+        assert_eq!(job.status, PreloadJobStatus::Running);
+        assert_eq!(job.models[1].status, PreloadModelStatus::Pending);
+
+        let job = wait_for_finish(&tracker, &job.job_id).await;
+        assert_eq!(job.status, PreloadJobStatus::Completed);
+        assert_eq!(job.models[0].status, PreloadModelStatus::Skipped);
+        assert_eq!(job.models[1].status, PreloadModelStatus::Downloaded);
+        assert_eq!(downloader.downloads(), vec!["org/fresh".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_preload_job_records_failures() {
+        let downloader = Arc::new(StubDownloader::new(&[], &["org/broken"]));
+        let tracker = PreloadTracker::new_with_downloader(downloader);
+
+        let job = tracker
+            .start(vec!["org/broken".to_string(), "org/ok".to_string()])
+            .await;
+
+        let job = wait_for_finish(&tracker, &job.job_id).await;
+        assert_eq!(job.status, PreloadJobStatus::Failed);
+        assert_eq!(job.models[0].status, PreloadModelStatus::Failed);
+        assert!(job.models[0].error.is_some());
+        assert_eq!(job.models[1].status, PreloadModelStatus::Downloaded);
+    }
+
+    #[tokio::test]
+    async fn test_run_waits_for_downloads() {
+        let downloader = Arc::new(StubDownloader::new(&["org/cached"], &["org/broken"]));
+        let tracker = PreloadTracker::new_with_downloader(downloader.clone());
+
+        // Cached models are skipped without touching the downloader
+        let job = tracker.run(vec!["org/cached".to_string()], None).await;
+        assert_eq!(job.status, PreloadJobStatus::Completed);
+        assert_eq!(job.models[0].status, PreloadModelStatus::Skipped);
+        assert!(downloader.downloads().is_empty());
+
+        // Missing models are downloaded before run returns
+        let job = tracker.run(vec!["org/fresh".to_string()], None).await;
+        assert_eq!(job.status, PreloadJobStatus::Completed);
+        assert_eq!(job.models[0].status, PreloadModelStatus::Downloaded);
+        assert_eq!(downloader.downloads(), vec!["org/fresh".to_string()]);
+        assert!(downloader.is_cached("org/fresh", None));
+
+        // A pinned revision is checked and fetched on its own
+        let job = tracker
+            .run(vec!["org/fresh".to_string()], Some("v2".to_string()))
+            .await;
+        assert_eq!(job.revision.as_deref(), Some("v2"));
+        assert_eq!(job.models[0].status, PreloadModelStatus::Downloaded);
+        assert_eq!(downloader.downloads().last().unwrap(), "org/fresh@v2");
+
+        // Failures are reported on the returned job and kept for polling
+        let job = tracker.run(vec!["org/broken".to_string()], None).await;
+        assert_eq!(job.status, PreloadJobStatus::Failed);
+        assert!(job.models[0].error.is_some());
+        assert_eq!(
+            tracker.get(&job.job_id).await.unwrap().status,
+            PreloadJobStatus::Failed
+        );
+    }
+
+    #[tokio::test]
+    async fn test_preload_job_ids_are_unique() {
+        let tracker =
+            PreloadTracker::new_with_downloader(Arc::new(StubDownloader::new(&["a/b"], &[])));
+
+        let first = tracker.start(vec!["a/b".to_string()]).await;
+        let second = tracker.start(vec!["a/b".to_string()]).await;
+
+        assert_ne!(first.job_id, second.job_id);
+        assert!(tracker.get("preload-unknown").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_finished_jobs_evicted_oldest_first() {
+        let downloader = Arc::new(StubDownloader::new(&["a/b"], &[]));
+        let tracker = PreloadTracker::new_with_downloader(downloader);
+
+        // Left running, so it is never evicted
+        let running = tracker.create_job(vec!["c/d".to_string()], None).await.0;
+        let mut finished = Vec::new();
+        for _ in 0..MAX_FINISHED_JOBS + 5 {
+            finished.push(tracker.start(vec!["a/b".to_string()]).await.job_id);
+        }
+
+        assert_eq!(tracker.jobs.read().await.len(), MAX_FINISHED_JOBS + 1);
+        assert!(tracker.get(&running.job_id).await.is_some());
+        assert!(tracker.get(&finished[0]).await.is_none());
+        assert!(tracker.get(finished.last().unwrap()).await.is_some());
+    }
+}
@@ -1,11 +1,13 @@
 //! Model registry for tracking known models and their status
 
-use super::cache::{get_cache_size, get_model_cache_path, is_model_cached, list_cached_models};
+use super::cache::{
+    cache_size_in, get_cache_dir, is_model_cached_in, list_cached_models_in, model_cache_path_in,
+};
 use super::metadata::{HfModelMetadata, parse_model_config};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
@@ -47,6 +49,23 @@ pub struct CacheInfo {
     pub path: PathBuf,
     /// Total size of cached files in bytes
     pub size_bytes: u64,
+    /// Revision (commit hash) of the cached snapshot
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revision: Option<String>,
+}
+
+impl CacheInfo {
+    /// Cache info for a snapshot directory, which is named after its commit hash
+    fn new(path: PathBuf, size_bytes: u64) -> Self {
+        let revision = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string());
+        Self {
+            path,
+            size_bytes,
+            revision,
+        }
+    }
 }
 
 /// Entry for a model in the registry
@@ -87,12 +106,17 @@ impl ModelEntry {
     }
 
     /// Update entry with cache information
-    pub fn with_cache_info(mut self) -> Self {
-        if is_model_cached(&self.model_id)
-            && let Some(path) = get_model_cache_path(&self.model_id)
+    pub fn with_cache_info(self) -> Self {
+        self.with_cache_info_in(&get_cache_dir())
+    }
+
+    /// Update entry with cache information from a specific HF cache directory
+    pub fn with_cache_info_in(mut self, cache_dir: &Path) -> Self {
+        if is_model_cached_in(cache_dir, &self.model_id)
+            && let Some(path) = model_cache_path_in(cache_dir, &self.model_id)
         {
-            let size_bytes = get_cache_size(&self.model_id).unwrap_or(0);
-            self.cache_info = Some(CacheInfo { path, size_bytes });
+            let size_bytes = cache_size_in(cache_dir, &self.model_id).unwrap_or(0);
+            self.cache_info = Some(CacheInfo::new(path, size_bytes));
             self.status = ModelStatus::Downloaded;
         }
         self
@@ -108,62 +132,113 @@ impl ModelEntry {
 
     /// Refresh cache and metadata information
     pub fn refresh(&mut self) {
-        if is_model_cached(&self.model_id) {
-            if let Some(path) = get_model_cache_path(&self.model_id) {
-                let size_bytes = get_cache_size(&self.model_id).unwrap_or(0);
-                self.cache_info = Some(CacheInfo {
-                    path: path.clone(),
-                    size_bytes,
-                });
-                self.metadata = parse_model_config(&path);
-
-                // Update status to Downloaded if not already verified/failed
-                // This handles both Available -> Downloaded and Downloading -> Downloaded
-                if self.status == ModelStatus::Available || self.status == ModelStatus::Downloading
-                {
-                    self.status = ModelStatus::Downloaded;
-                }
+        self.refresh_in(&get_cache_dir());
+    }
+
+    /// Refresh cache and metadata information from a specific HF cache directory
+    pub fn refresh_in(&mut self, cache_dir: &Path) {
+        self.apply_scan(scan_cache_in(cache_dir, &self.model_id));
+    }
+
+    /// Apply the result of [`scan_cache_in`] to this entry
+    fn apply_scan(&mut self, scan: Option<(CacheInfo, Option<HfModelMetadata>)>) {
+        if let Some((cache_info, metadata)) = scan {
+            self.cache_info = Some(cache_info);
+            self.metadata = metadata;
+
+            // Update status to Downloaded if not already verified/failed
+            // This handles both Available -> Downloaded and Downloading -> Downloaded
+            if self.status == ModelStatus::Available || self.status == ModelStatus::Downloading {
+                self.status = ModelStatus::Downloaded;
             }
         } else {
             self.cache_info = None;
             self.metadata = None;
-            self.status = ModelStatus::Available;
+            // A download in progress hasn't produced a snapshot yet
+            if self.status != ModelStatus::Downloading {
+                self.status = ModelStatus::Available;
+            }
         }
     }
 }
 
+/// Cache info and metadata of a model in a specific HF cache directory, if cached
+///
+/// Walks the model's cache directory, so call it off the async runtime.
+fn scan_cache_in(cache_dir: &Path, model_id: &str) -> Option<(CacheInfo, Option<HfModelMetadata>)> {
+    if !is_model_cached_in(cache_dir, model_id) {
+        return None;
+    }
+    let path = model_cache_path_in(cache_dir, model_id)?;
+    let size_bytes = cache_size_in(cache_dir, model_id).unwrap_or(0);
+    let metadata = parse_model_config(&path);
+    Some((CacheInfo::new(path, size_bytes), metadata))
+}
+
 /// Registry for tracking models
 pub struct ModelRegistry {
     models: Arc<RwLock<HashMap<String, ModelEntry>>>,
+    cache_dir: PathBuf,
 }
 
 impl ModelRegistry {
-    /// Create a new empty registry
+    /// Create a new empty registry backed by the default HF cache directory
     pub fn new() -> Self {
         Self {
             models: Arc::new(RwLock::new(HashMap::new())),
+            cache_dir: get_cache_dir(),
         }
     }
 
+    /// Use a specific HF cache directory instead of the one from the environment
+    pub fn with_cache_dir(mut self, cache_dir: PathBuf) -> Self {
+        self.cache_dir = cache_dir;
+        self
+    }
+
     /// Initialize registry with configured models and discover cached models
     pub async fn init(configured_models: Vec<String>) -> Self {
         let registry = Self::new();
+        registry.sync_known_models(&configured_models).await;
+        registry
+    }
 
-        // Add configured models
-        for model_id in configured_models {
-            registry.add_model(model_id).await;
-        }
-
-        // Discover cached models
-        registry.discover_cached_models().await;
+    /// Register configured and cached models and refresh the cache info of every entry
+    ///
+    /// The cache is scanned on the blocking pool, so models downloaded or removed
+    /// since the last sync flip between Available and Downloaded.
+    pub async fn sync_known_models(&self, configured_models: &[String]) {
+        let mut model_ids: Vec<String> = self.models.read().await.keys().cloned().collect();
+        model_ids.extend_from_slice(configured_models);
+        let cache_dir = self.cache_dir.clone();
+        let scans = tokio::task::spawn_blocking(move || {
+            model_ids.extend(list_cached_models_in(&cache_dir));
+            model_ids.sort();
+            model_ids.dedup();
+            model_ids
+                .into_iter()
+                .map(|model_id| {
+                    let scan = scan_cache_in(&cache_dir, &model_id);
+                    (model_id, scan)
+                })
+                .collect::<Vec<_>>()
+        })
+        .await
+        .unwrap_or_default();
 
-        registry
+        let mut models = self.models.write().await;
+        for (model_id, scan) in scans {
+            models
+                .entry(model_id.clone())
+                .or_insert_with(|| ModelEntry::new(model_id))
+                .apply_scan(scan);
+        }
     }
 
     /// Add a model to the registry
     pub async fn add_model(&self, model_id: String) -> ModelEntry {
         let entry = ModelEntry::new(model_id.clone())
-            .with_cache_info()
+            .with_cache_info_in(&self.cache_dir)
             .with_metadata();
 
         let mut models = self.models.write().await;
@@ -183,7 +258,7 @@ impl ModelRegistry {
         let mut models = self.models.write().await;
 
         if let Some(entry) = models.get_mut(model_id) {
-            entry.refresh();
+            entry.refresh_in(&self.cache_dir);
             return Some(entry.clone());
         }
 
@@ -233,20 +308,14 @@ impl ModelRegistry {
 
     /// Discover and add cached models not already in registry
     pub async fn discover_cached_models(&self) {
-        let cached = list_cached_models();
-
-        for model_id in cached {
-            if !self.contains(&model_id).await {
-                self.add_model(model_id).await;
-            }
-        }
+        self.sync_known_models(&[]).await;
     }
 
     /// Refresh cache info for all models
     pub async fn refresh_all(&self) {
         let mut models = self.models.write().await;
         for entry in models.values_mut() {
-            entry.refresh();
+            entry.refresh_in(&self.cache_dir);
         }
     }
 
@@ -389,13 +458,10 @@ mod tests {
 
     #[test]
     fn test_cache_info_serialize() {
-        use std::path::PathBuf;
-        let cache_info = CacheInfo {
-            path: PathBuf::from("/test/path"),
-            size_bytes: 12345,
-        };
+        let cache_info = CacheInfo::new(PathBuf::from("/test/snapshots/abc123"), 12345);
         let json = serde_json::to_string(&cache_info).unwrap();
         assert!(json.contains("12345"));
+        assert!(json.contains(r#""revision":"abc123""#));
     }
 
     #[test]
@@ -463,4 +529,51 @@ mod tests {
             .await;
         assert!(registry.get("nonexistent/model").await.is_none());
     }
+
+    #[tokio::test]
+    async fn test_sync_known_models_uses_cache_dir() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let snapshot = temp_dir.path().join("models--org--cached/snapshots/abc123");
+        std::fs::create_dir_all(&snapshot).unwrap();
+        std::fs::write(snapshot.join("config.json"), "{}").unwrap();
+
+        let registry = ModelRegistry::new().with_cache_dir(temp_dir.path().to_path_buf());
+        registry
+            .sync_known_models(&["org/configured".to_string()])
+            .await;
+
+        let ids: Vec<_> = registry
+            .list()
+            .await
+            .into_iter()
+            .map(|e| (e.model_id, e.status))
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                ("org/cached".to_string(), ModelStatus::Downloaded),
+                ("org/configured".to_string(), ModelStatus::Available),
+            ]
+        );
+
+        // A model cached after it was registered flips to Downloaded on the next sync
+        let snapshot = temp_dir
+            .path()
+            .join("models--org--configured/snapshots/def456");
+        std::fs::create_dir_all(&snapshot).unwrap();
+        std::fs::write(snapshot.join("config.json"), r#"{"hidden_size": 384}"#).unwrap();
+        registry.set_verified("org/cached").await;
+        registry.sync_known_models(&[]).await;
+
+        let configured = registry.get("org/configured").await.unwrap();
+        assert_eq!(configured.status, ModelStatus::Downloaded);
+        assert_eq!(
+            configured.cache_info.unwrap().revision.as_deref(),
+            Some("def456")
+        );
+        assert_eq!(configured.metadata.unwrap().hidden_size, Some(384));
+        // Statuses past Downloaded are kept
+        let cached = registry.get("org/cached").await.unwrap();
+        assert_eq!(cached.status, ModelStatus::Verified);
+    }
 }
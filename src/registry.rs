@@ -23,16 +23,30 @@
 //! A shared trait would either be too generic to be useful or would force
 //! artificial unification of these different semantics.
 
-use crate::config::InstanceConfig;
-use crate::instance::TeiInstance;
+use crate::config::{InstanceConfig, ModelRoutingStrategy, Pooling};
+use crate::error::TeiError;
+use crate::gpu::GpuInfo;
+use crate::health::{GrpcHealthChecker, HealthChecker};
+use crate::instance::{BindRetry, InstanceStatus, PreStopHook, ProcessExit, TeiInstance};
 use anyhow::{Context, Result};
-use std::collections::HashMap;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::net::TcpListener;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 use tokio::sync::{RwLock, broadcast};
 
+/// Lifecycle events buffered per subscriber before it starts missing some
+const EVENT_CHANNEL_CAPACITY: usize = 100;
+
+/// How often a dependency is polled while dependents wait for it
+const DEPENDENCY_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
 /// Events that occur during instance lifecycle
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum InstanceEvent {
     /// Instance was added to registry
     Added(String),
@@ -42,11 +56,147 @@ pub enum InstanceEvent {
     Started(String),
     /// Instance was stopped
     Stopped(String),
+    /// Status changed outside an API action (e.g. health monitor marked it running or failed)
+    StatusChanged {
+        name: String,
+        from: InstanceStatus,
+        to: InstanceStatus,
+    },
+    /// The process exited without being stopped; the instance is now failed
+    Exited { name: String, exit: ProcessExit },
+}
+
+/// Broadcasts lifecycle events, keeping the most recent for late subscribers
+#[derive(Debug, Clone)]
+pub struct EventSender {
+    tx: broadcast::Sender<InstanceEvent>,
+    history: Arc<std::sync::Mutex<VecDeque<InstanceEvent>>>,
+    history_size: usize,
+}
+
+impl EventSender {
+    /// Channel buffering `capacity` events per subscriber and remembering the last
+    /// `history_size` (0 = none)
+    pub fn new(capacity: usize, history_size: usize) -> Self {
+        Self {
+            tx: broadcast::channel(capacity).0,
+            history: Arc::new(std::sync::Mutex::new(VecDeque::with_capacity(history_size))),
+            history_size,
+        }
+    }
+
+    /// Record `event` and broadcast it to current subscribers
+    pub fn send(&self, event: InstanceEvent) {
+        // Held across the broadcast so a replaying subscriber sees each event exactly once
+        let mut history = self.history.lock().unwrap();
+        if self.history_size > 0 {
+            if history.len() == self.history_size {
+                history.pop_front();
+            }
+            history.push_back(event.clone());
+        }
+        let _ = self.tx.send(event);
+    }
+
+    /// Receive events sent from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<InstanceEvent> {
+        self.tx.subscribe()
+    }
+
+    /// Up to `replay` of the most recent events (oldest first) plus a receiver for later ones
+    pub fn subscribe_with_replay(
+        &self,
+        replay: usize,
+    ) -> (Vec<InstanceEvent>, broadcast::Receiver<InstanceEvent>) {
+        let history = self.history.lock().unwrap();
+        let skip = history.len().saturating_sub(replay);
+        (
+            history.iter().skip(skip).cloned().collect(),
+            self.tx.subscribe(),
+        )
+    }
+}
+
+/// Snapshot of the port allocator, for debugging port leaks
+#[derive(Debug, Clone, Serialize)]
+pub struct PortAllocatorState {
+    /// Auto-allocation range [start, end); empty when auto-allocation is disabled
+    pub instance_port_range: (u16, u16),
+    pub auto_allocation_enabled: bool,
+    /// Where the next instance port search starts
+    pub next_instance_port: u16,
+    /// Where the next Prometheus port search starts
+    pub next_prometheus_port: u16,
+    /// Instance ports held by registered instances, sorted
+    pub allocated_instance_ports: Vec<u16>,
+    /// Prometheus ports held by registered instances, sorted
+    pub allocated_prometheus_ports: Vec<u16>,
+    /// Ports in the range not held by any instance (they may still be bound by other processes)
+    pub unallocated_instance_ports: usize,
+}
+
+/// Which of an instance's ports a [`AllocatedPort`] is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PortKind {
+    Instance,
+    Prometheus,
+}
+
+/// A port held by a registered instance
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AllocatedPort {
+    pub port: u16,
+    pub instance: String,
+    pub kind: PortKind,
+}
+
+/// Ports held by instances and what is left of the allocation range (GET /ports)
+#[derive(Debug, Clone, Serialize)]
+pub struct PortUsage {
+    /// Auto-allocation range [start, end); empty when auto-allocation is disabled
+    pub range: (u16, u16),
+    pub auto_allocation_enabled: bool,
+    /// Instance and Prometheus ports of every instance, in or out of the range, sorted by port
+    pub allocated: Vec<AllocatedPort>,
+    /// Ports in the range held by no instance (they may still be bound by other processes)
+    pub free: usize,
+}
+
+/// One instance's place in the multiplexer routing
+#[derive(Debug, Clone, Serialize)]
+pub struct InstanceRoute {
+    pub instance: String,
+    /// Position used by `instance_index` routing
+    pub index: u32,
+    pub status: InstanceStatus,
+    pub paused: bool,
+    pub restarting: bool,
+    /// Whether `model_id` routing currently picks this instance
+    pub routable: bool,
+    /// Share of the model's `model_id` requests it receives: 0 or 1/n over the
+    /// routable instances, or its share of their `max_concurrent_requests`
+    /// under `weighted` routing. Under `least_loaded` this is the share while
+    /// load is even
+    pub weight: f64,
+}
+
+/// Effective multiplexer routing (GET /routing)
+#[derive(Debug, Clone, Serialize)]
+pub struct RoutingTable {
+    /// How `model_id` requests pick among a model's routable instances
+    pub strategy: ModelRoutingStrategy,
+    /// Instance names in `instance_index` order
+    pub indices: Vec<String>,
+    /// Instances serving each model id, in name order
+    pub models: BTreeMap<String, Vec<InstanceRoute>>,
 }
 
 /// Thread-safe registry for managing TEI instances
 pub struct Registry {
     instances: Arc<RwLock<HashMap<String, Arc<TeiInstance>>>>,
+    /// Instances by model id, sorted by name; updated together with `instances`
+    by_model: Arc<RwLock<HashMap<String, Vec<Arc<TeiInstance>>>>>,
     max_instances: Option<usize>,
     tei_binary_path: Arc<str>,
     next_prometheus_port: Arc<RwLock<u16>>,
@@ -54,7 +204,33 @@ pub struct Registry {
     /// Port range for auto-allocation [start, end)
     /// If start == end, auto-allocation is disabled
     instance_port_range: (u16, u16),
-    event_tx: broadcast::Sender<InstanceEvent>,
+    event_tx: EventSender,
+    /// Checker used when waiting for instances to become ready
+    health_checker: Arc<dyn HealthChecker>,
+    /// Global maintenance mode: the health monitor does not restart any instance
+    maintenance: Arc<AtomicBool>,
+    /// Args prepended to every instance's extra_args at start
+    default_extra_args: Arc<[String]>,
+    /// RUST_LOG for instances without their own log_level
+    instance_log_level: Option<Arc<str>>,
+    /// Directory instance output is captured in
+    log_dir: Arc<Path>,
+    /// Command run before an instance is stopped
+    pre_stop_hook: Arc<PreStopHook>,
+    /// Respawning of instances that fail to bind their port at start
+    bind_retry: BindRetry,
+    /// Startup deadline extension while an instance's model download progresses
+    startup_download_grace: Duration,
+    /// Manager-wide constant labels for instance metrics
+    metric_labels: Arc<BTreeMap<String, String>>,
+    /// Pooling by model family, for instances that don't set one
+    default_pooling: Arc<BTreeMap<String, Pooling>>,
+    /// Model patterns instances may serve (None: any model)
+    allowed_models: Option<Arc<[String]>>,
+    /// GPUs checked for placement (None: detected via nvidia-smi)
+    gpu_info: Option<Arc<GpuInfo>>,
+    /// GPU memory in bytes kept free when placing instances
+    gpu_memory_headroom: u64,
 }
 
 impl Registry {
@@ -73,42 +249,304 @@ impl Registry {
         instance_port_start: u16,
         instance_port_end: u16,
     ) -> Self {
-        // Create broadcast channel for lifecycle events
-        // Capacity of 100 should be sufficient for most use cases
-        let (event_tx, _) = broadcast::channel(100);
-
         Self {
             instances: Arc::new(RwLock::new(HashMap::new())),
+            by_model: Arc::new(RwLock::new(HashMap::new())),
             max_instances,
             tei_binary_path: Arc::from(tei_binary_path),
             next_prometheus_port: Arc::new(RwLock::new(9100)),
             next_instance_port: Arc::new(RwLock::new(instance_port_start)),
             instance_port_range: (instance_port_start, instance_port_end),
-            event_tx,
+            event_tx: EventSender::new(EVENT_CHANNEL_CAPACITY, 0),
+            health_checker: Arc::new(GrpcHealthChecker::default()),
+            maintenance: Arc::new(AtomicBool::new(false)),
+            default_extra_args: Arc::from([]),
+            instance_log_level: None,
+            log_dir: Arc::from(crate::config::default_log_dir()),
+            pre_stop_hook: Arc::new(PreStopHook::default()),
+            bind_retry: BindRetry::default(),
+            startup_download_grace: Duration::ZERO,
+            metric_labels: Arc::new(BTreeMap::new()),
+            default_pooling: Arc::new(BTreeMap::new()),
+            allowed_models: None,
+            gpu_info: None,
+            gpu_memory_headroom: 0,
         }
     }
 
+    /// Args prepended to every instance's `extra_args` when it starts
+    pub fn with_default_extra_args(mut self, args: Vec<String>) -> Self {
+        self.default_extra_args = Arc::from(args);
+        self
+    }
+
+    /// Log level for instances that don't set their own `log_level`
+    pub fn with_instance_log_level(mut self, level: Option<String>) -> Self {
+        self.instance_log_level = level.map(Arc::from);
+        self
+    }
+
+    /// Directory instance logs are written to and read from (default: /data/logs)
+    pub fn with_log_dir(mut self, log_dir: PathBuf) -> Self {
+        self.log_dir = Arc::from(log_dir);
+        self
+    }
+
+    /// Hook run before instances are stopped (instances may override the command)
+    pub fn with_pre_stop_hook(mut self, hook: PreStopHook) -> Self {
+        self.pre_stop_hook = Arc::new(hook);
+        self
+    }
+
+    /// Respawn instances whose process exits at start because the port is in use
+    pub fn with_bind_retry(mut self, bind_retry: BindRetry) -> Self {
+        self.bind_retry = bind_retry;
+        self
+    }
+
+    /// Extend instances' startup deadlines while their model download progresses (zero = off)
+    pub fn with_startup_download_grace(mut self, grace: Duration) -> Self {
+        self.startup_download_grace = grace;
+        self
+    }
+
+    /// Labels added to every instance's metrics; instances' `metric_labels` win
+    pub fn with_metric_labels(mut self, labels: BTreeMap<String, String>) -> Self {
+        self.metric_labels = Arc::new(labels);
+        self
+    }
+
+    /// Pooling by model family (architecture or `model_type`) for instances without one
+    pub fn with_default_pooling(mut self, families: BTreeMap<String, Pooling>) -> Self {
+        self.default_pooling = Arc::new(families);
+        self
+    }
+
+    /// Only admit instances whose model matches one of `patterns` (None = any model)
+    ///
+    /// Enforced by [`add`](Self::add), so restored and imported instances are
+    /// held to the same `allowed_models` as ones created via the API.
+    pub fn with_allowed_models(mut self, patterns: Option<Vec<String>>) -> Self {
+        self.allowed_models = patterns.map(Arc::from);
+        self
+    }
+
+    /// GPU memory in bytes left free on every GPU when placing instances
+    pub fn with_gpu_memory_headroom(mut self, bytes: u64) -> Self {
+        self.gpu_memory_headroom = bytes;
+        self
+    }
+
+    /// Place instances on these GPUs instead of the ones nvidia-smi reports
+    pub fn with_gpu_info(mut self, info: GpuInfo) -> Self {
+        self.gpu_info = Some(Arc::new(info));
+        self
+    }
+
+    /// GPUs gpu_ids are checked against: the injected ones, else what nvidia-smi reports
+    pub fn gpu_info(&self) -> &GpuInfo {
+        match &self.gpu_info {
+            Some(info) => info,
+            None => crate::gpu::get_or_init(),
+        }
+    }
+
+    /// Log file of instance `name`, whether or not it is registered
+    pub fn instance_log_path(&self, name: &str) -> PathBuf {
+        crate::instance::instance_log_path(&self.log_dir, name)
+    }
+
+    /// Use a custom health checker for readiness checks (default: gRPC Info RPC)
+    pub fn with_health_checker(mut self, checker: Arc<dyn HealthChecker>) -> Self {
+        self.health_checker = checker;
+        self
+    }
+
+    /// Health checker used for readiness checks
+    pub fn health_checker(&self) -> Arc<dyn HealthChecker> {
+        self.health_checker.clone()
+    }
+
+    /// Enable or disable global maintenance mode (pauses health-driven restarts)
+    pub fn set_maintenance(&self, enabled: bool) {
+        self.maintenance.store(enabled, Ordering::SeqCst);
+    }
+
+    /// Whether global maintenance mode is enabled
+    pub fn maintenance_enabled(&self) -> bool {
+        self.maintenance.load(Ordering::SeqCst)
+    }
+
+    /// Remember the last `size` lifecycle events for [`Self::subscribe_events_with_replay`]
+    ///
+    /// Must be set before instances are added, which hold on to the event channel.
+    pub fn with_event_history(mut self, size: usize) -> Self {
+        self.event_tx = EventSender::new(EVENT_CHANNEL_CAPACITY, size);
+        self
+    }
+
     /// Subscribe to lifecycle events
     pub fn subscribe_events(&self) -> broadcast::Receiver<InstanceEvent> {
         self.event_tx.subscribe()
     }
 
+    /// Up to `replay` recent lifecycle events, then a subscription to later ones
+    pub fn subscribe_events_with_replay(
+        &self,
+        replay: usize,
+    ) -> (Vec<InstanceEvent>, broadcast::Receiver<InstanceEvent>) {
+        self.event_tx.subscribe_with_replay(replay)
+    }
+
+    /// Broadcast an event as if an instance had sent it
+    #[cfg(test)]
+    pub(crate) fn send_event(&self, event: InstanceEvent) {
+        self.event_tx.send(event);
+    }
+
+    /// Broadcast a status change to event subscribers
+    pub fn notify_status_change(&self, name: &str, from: InstanceStatus, to: InstanceStatus) {
+        self.event_tx.send(InstanceEvent::StatusChanged {
+            name: name.to_string(),
+            from,
+            to,
+        });
+    }
+
     /// Check if port auto-allocation is enabled
     pub fn is_port_auto_allocation_enabled(&self) -> bool {
         self.instance_port_range.0 < self.instance_port_range.1
     }
 
+    /// Fill in the manager's default pooling and validate the launch options
+    ///
+    /// The model family comes from the cached config.json, so an uncached model
+    /// skips the family checks. Reads the model cache.
+    pub fn apply_launch_defaults(&self, config: &mut InstanceConfig) -> Result<()> {
+        Self::apply_launch_defaults_with(&self.default_pooling, config)
+    }
+
+    /// [`apply_launch_defaults`](Self::apply_launch_defaults) without borrowing the
+    /// registry, for use on a blocking thread
+    fn apply_launch_defaults_with(
+        default_pooling: &BTreeMap<String, Pooling>,
+        config: &mut InstanceConfig,
+    ) -> Result<()> {
+        // Model family is only known once config.json is in the cache
+        let cache_path =
+            crate::models::cache::model_cache_path_in(&config.model_cache_dir(), &config.model_id);
+        let metadata = cache_path
+            .as_deref()
+            .and_then(crate::models::parse_model_config);
+        if config.pooling.is_none()
+            && !config.sets_extra_arg("--pooling")
+            && let Some(metadata) = &metadata
+        {
+            let st_pooling = cache_path
+                .as_deref()
+                .and_then(crate::models::parse_pooling_config);
+            config.pooling = crate::models::infer_pooling(metadata, st_pooling, default_pooling);
+            if let Some(pooling) = config.pooling {
+                tracing::info!(
+                    instance = %config.name,
+                    model_id = %config.model_id,
+                    pooling = pooling.as_str(),
+                    "Inferred pooling from model metadata"
+                );
+            }
+        }
+        let architectures = metadata
+            .map(|metadata| metadata.architectures)
+            .unwrap_or_default();
+        config.validate_launch_options(&architectures)
+    }
+
+    /// Check the name, instance count and port of `config` as [`add`](Self::add) would
+    ///
+    /// Lets a caller fail fast before slow preparation such as a model download;
+    /// `add` repeats these checks under its lock.
+    pub async fn check_admission(&self, config: &InstanceConfig) -> Result<()> {
+        let instances = self.instances.read().await;
+        self.check_name_and_capacity(&instances, config)?;
+        if config.port != 0 {
+            Self::check_port_free(&Self::used_ports(&instances), config.port)?;
+        } else if !self.is_port_auto_allocation_enabled() {
+            anyhow::bail!(
+                "Port not specified and auto-allocation is disabled (no port range configured)"
+            );
+        }
+        Ok(())
+    }
+
+    /// Reject a duplicate name or an instance beyond `max_instances`
+    fn check_name_and_capacity(
+        &self,
+        instances: &HashMap<String, Arc<TeiInstance>>,
+        config: &InstanceConfig,
+    ) -> Result<()> {
+        if !crate::config::model_allowed_by(self.allowed_models.as_deref(), &config.model_id) {
+            return Err(TeiError::Forbidden {
+                reason: format!("Model '{}' is not in allowed_models", config.model_id),
+            }
+            .into());
+        }
+        if instances.contains_key(&config.name) {
+            anyhow::bail!("Instance '{}' already exists", config.name);
+        }
+        if let Some(max) = self.max_instances
+            && instances.len() >= max
+        {
+            anyhow::bail!("Maximum instance count ({}) reached", max);
+        }
+        Ok(())
+    }
+
+    /// Ports held by registered instances, HTTP and Prometheus alike
+    fn used_ports(instances: &HashMap<String, Arc<TeiInstance>>) -> HashMap<u16, &str> {
+        instances
+            .values()
+            .flat_map(|i| {
+                let name = i.config.name.as_str();
+                std::iter::once((i.config.port, name))
+                    .chain(i.config.prometheus_port.map(|port| (port, name)))
+            })
+            .collect()
+    }
+
+    /// Reject a port a registered instance already holds
+    fn check_port_free(used_ports: &HashMap<u16, &str>, port: u16) -> Result<()> {
+        if let Some(owner) = used_ports.get(&port) {
+            anyhow::bail!("Port {} already in use by instance '{}'", port, owner);
+        }
+        Ok(())
+    }
+
     /// Add a new instance to the registry
     /// Returns error if name exists, port conflicts, or max instances reached
     ///
     /// If `config.port` is 0, auto-allocates a port from the configured range
+    ///
+    /// Uniqueness checks, port allocation, GPU placement and the insert all happen
+    /// under the registry write lock, so concurrent adds can never be handed the
+    /// same port or overcommit a GPU.
     pub async fn add(&self, mut config: InstanceConfig) -> Result<Arc<TeiInstance>> {
+        // Both read the model's config.json, so they are done before taking the lock
+        let default_pooling = self.default_pooling.clone();
+        let (mut config, footprint_estimate) = tokio::task::spawn_blocking(move || {
+            Self::apply_launch_defaults_with(&default_pooling, &mut config)?;
+            let footprint_estimate = (config.gpu_id.is_some() && !config.cpu_only)
+                .then(|| crate::gpu::estimate_footprint(&config))
+                .flatten();
+            anyhow::Ok((config, footprint_estimate))
+        })
+        .await??;
+
         let mut instances = self.instances.write().await;
 
-        // Validate uniqueness
-        if instances.contains_key(&config.name) {
-            anyhow::bail!("Instance '{}' already exists", config.name);
-        }
+        // Validate uniqueness and check max instances before allocating anything
+        self.check_name_and_capacity(&instances, &config)?;
+
+        let used_ports = Self::used_ports(&instances);
 
         // Auto-assign instance port if not specified (port == 0)
         if config.port == 0 {
@@ -120,10 +558,6 @@ impl Registry {
 
             let mut next_port = self.next_instance_port.write().await;
 
-            // Collect used ports
-            let used_ports: std::collections::HashSet<u16> =
-                instances.values().map(|i| i.config.port).collect();
-
             // Find next available port in range, starting from next_port
             // If next_port is past the end of the range, wrap around to start
             let search_start = if *next_port >= self.instance_port_range.1 {
@@ -136,7 +570,7 @@ impl Registry {
                 search_start,
                 self.instance_port_range.0,
                 self.instance_port_range.1,
-                &used_ports,
+                &used_ports.keys().copied().collect(),
             )?;
             config.port = assigned_port;
 
@@ -146,22 +580,18 @@ impl Registry {
             tracing::info!(port = assigned_port, "Auto-assigned instance port");
         }
 
-        // Check port conflicts
-        for instance in instances.values() {
-            if instance.config.port == config.port {
-                anyhow::bail!(
-                    "Port {} already in use by instance '{}'",
-                    config.port,
-                    instance.config.name
-                );
-            }
-        }
-
-        // Check max instances
-        if let Some(max) = self.max_instances
-            && instances.len() >= max
-        {
-            anyhow::bail!("Maximum instance count ({}) reached", max);
+        Self::check_port_free(&used_ports, config.port)?;
+
+        if config.gpu_id.is_some() {
+            let placed: Vec<InstanceConfig> =
+                instances.values().map(|i| i.config.clone()).collect();
+            let gpu_info = self.gpu_info();
+            // Registered instances keep the estimate taken when they were added
+            gpu_info.check_placement(&config, &placed, self.gpu_memory_headroom, |other| {
+                instances
+                    .get(&other.name)
+                    .map_or(footprint_estimate, |i| i.footprint_estimate())
+            })?;
         }
 
         // Auto-assign Prometheus port if not specified
@@ -169,14 +599,26 @@ impl Registry {
             let mut next_port = self.next_prometheus_port.write().await;
 
             // Find next available port starting from current next_port
-            let assigned_port = Self::find_free_port(*next_port)?;
+            let mut taken: std::collections::HashSet<u16> = used_ports.keys().copied().collect();
+            taken.insert(config.port);
+            let assigned_port = Self::find_free_port(*next_port, &taken)?;
             config.prometheus_port = Some(assigned_port);
 
             // Update next_port for next allocation
             *next_port = assigned_port + 1;
         }
 
-        let instance = Arc::new(TeiInstance::new(config));
+        let instance = Arc::new(
+            TeiInstance::new(config)
+                .with_default_extra_args(self.default_extra_args.clone())
+                .with_default_log_level(self.instance_log_level.clone())
+                .with_log_dir(self.log_dir.clone())
+                .with_pre_stop_hook(self.pre_stop_hook.clone())
+                .with_bind_retry(self.bind_retry)
+                .with_startup_download_grace(self.startup_download_grace)
+                .with_events(self.event_tx.clone())
+                .with_footprint_estimate(footprint_estimate),
+        );
         let instance_name = instance.config.name.clone();
 
         tracing::info!(
@@ -186,10 +628,23 @@ impl Registry {
             "Instance added to registry"
         );
 
+        let mut metric_labels = (*self.metric_labels).clone();
+        metric_labels.extend(instance.config.metric_labels.clone());
+        crate::metrics::set_instance_labels(&instance_name, &metric_labels);
+
         instances.insert(instance_name.clone(), instance.clone());
+        {
+            // Taken while `instances` is still locked, so the two never disagree
+            let mut by_model = self.by_model.write().await;
+            let serving = by_model
+                .entry(instance.config.model_id.clone())
+                .or_default();
+            let at = serving.partition_point(|i| i.config.name < instance_name);
+            serving.insert(at, instance.clone());
+        }
 
         // Notify listeners of the add event
-        let _ = self.event_tx.send(InstanceEvent::Added(instance_name));
+        self.event_tx.send(InstanceEvent::Added(instance_name));
 
         Ok(instance)
     }
@@ -200,6 +655,48 @@ impl Registry {
         instances.get(name).cloned()
     }
 
+    /// Wait until every registered instance in `config.depends_on` is Running
+    ///
+    /// Each dependency gets its own `startup_timeout_secs`, or `default_timeout`.
+    /// Dependencies that are not registered are skipped.
+    pub async fn wait_for_dependencies(
+        &self,
+        config: &InstanceConfig,
+        default_timeout: Duration,
+    ) -> Result<()> {
+        for name in &config.depends_on {
+            let Some(dependency) = self.get(name).await else {
+                continue;
+            };
+            match *dependency.status.read().await {
+                InstanceStatus::Running => continue,
+                InstanceStatus::Starting => {}
+                status => anyhow::bail!(
+                    "Instance '{}' depends on '{}', which is {:?}",
+                    config.name,
+                    name,
+                    status
+                ),
+            }
+
+            tracing::info!(
+                instance = %config.name,
+                dependency = %name,
+                "Waiting for dependency to become ready"
+            );
+            let timeout = dependency.config.startup_timeout(default_timeout);
+            crate::health::wait_for_ready_with(
+                self.health_checker.as_ref(),
+                &dependency,
+                timeout,
+                DEPENDENCY_POLL_INTERVAL,
+            )
+            .await
+            .with_context(|| format!("Dependency of instance '{}' not ready", config.name))?;
+        }
+        Ok(())
+    }
+
     /// Remove instance and stop it
     pub async fn remove(&self, name: &str) -> Result<()> {
         let mut instances = self.instances.write().await;
@@ -207,6 +704,15 @@ impl Registry {
         let instance = instances
             .remove(name)
             .with_context(|| format!("Instance '{}' not found", name))?;
+        {
+            let mut by_model = self.by_model.write().await;
+            if let Some(serving) = by_model.get_mut(&instance.config.model_id) {
+                serving.retain(|i| i.config.name != name);
+                if serving.is_empty() {
+                    by_model.remove(&instance.config.model_id);
+                }
+            }
+        }
 
         // Drop write lock before stopping (stop may take time)
         drop(instances);
@@ -216,7 +722,7 @@ impl Registry {
         tracing::info!(instance = %name, "Instance removed from registry");
 
         // Notify listeners of the removal
-        let _ = self.event_tx.send(InstanceEvent::Removed(name.to_string()));
+        self.event_tx.send(InstanceEvent::Removed(name.to_string()));
 
         Ok(())
     }
@@ -227,12 +733,167 @@ impl Registry {
         instances.values().cloned().collect()
     }
 
+    /// Instances serving `model_id`, sorted by name
+    ///
+    /// With `only_running`, instances that are not Running are left out.
+    pub async fn instances_for_model(
+        &self,
+        model_id: &str,
+        only_running: bool,
+    ) -> Vec<Arc<TeiInstance>> {
+        let serving = self
+            .by_model
+            .read()
+            .await
+            .get(model_id)
+            .cloned()
+            .unwrap_or_default();
+        if !only_running {
+            return serving;
+        }
+
+        let mut running = Vec::with_capacity(serving.len());
+        for instance in serving {
+            if *instance.status.read().await == InstanceStatus::Running {
+                running.push(instance);
+            }
+        }
+        running
+    }
+
+    /// Number of instances in each status (statuses without instances are absent)
+    pub async fn count_by_status(&self) -> HashMap<InstanceStatus, usize> {
+        let mut counts = HashMap::new();
+        for instance in self.list().await {
+            *counts.entry(*instance.status.read().await).or_default() += 1;
+        }
+        counts
+    }
+
     /// Get instance count
     pub async fn count(&self) -> usize {
         let instances = self.instances.read().await;
         instances.len()
     }
 
+    /// Snapshot the port allocator (cursors, range and ports held by instances)
+    pub async fn allocator_state(&self) -> PortAllocatorState {
+        // Same lock order as add(), so the snapshot is consistent
+        let instances = self.instances.read().await;
+        let next_instance_port = *self.next_instance_port.read().await;
+        let next_prometheus_port = *self.next_prometheus_port.read().await;
+
+        let mut allocated_instance_ports: Vec<u16> =
+            instances.values().map(|i| i.config.port).collect();
+        allocated_instance_ports.sort_unstable();
+        let mut allocated_prometheus_ports: Vec<u16> = instances
+            .values()
+            .filter_map(|i| i.config.prometheus_port)
+            .collect();
+        allocated_prometheus_ports.sort_unstable();
+
+        let (start, end) = self.instance_port_range;
+        let in_range = allocated_instance_ports
+            .iter()
+            .filter(|port| (start..end).contains(*port))
+            .count();
+
+        PortAllocatorState {
+            instance_port_range: self.instance_port_range,
+            auto_allocation_enabled: self.is_port_auto_allocation_enabled(),
+            next_instance_port,
+            next_prometheus_port,
+            allocated_instance_ports,
+            allocated_prometheus_ports,
+            unallocated_instance_ports: usize::from(end.saturating_sub(start)) - in_range,
+        }
+    }
+
+    /// Ports held by each instance and how many in the allocation range are free
+    ///
+    /// Prometheus ports count as taken, as they do when add() picks a port.
+    pub async fn port_usage(&self) -> PortUsage {
+        let instances = self.instances.read().await;
+        let mut allocated: Vec<AllocatedPort> = instances
+            .values()
+            .flat_map(|i| {
+                let held = |port, kind| AllocatedPort {
+                    port,
+                    instance: i.config.name.clone(),
+                    kind,
+                };
+                std::iter::once(held(i.config.port, PortKind::Instance)).chain(
+                    i.config
+                        .prometheus_port
+                        .map(|port| held(port, PortKind::Prometheus)),
+                )
+            })
+            .collect();
+        drop(instances);
+        allocated.sort_by(|a, b| (a.port, &a.instance).cmp(&(b.port, &b.instance)));
+
+        let (start, end) = self.instance_port_range;
+        let mut taken: Vec<u16> = allocated
+            .iter()
+            .map(|held| held.port)
+            .filter(|port| (start..end).contains(port))
+            .collect();
+        taken.dedup();
+
+        PortUsage {
+            range: self.instance_port_range,
+            auto_allocation_enabled: self.is_port_auto_allocation_enabled(),
+            allocated,
+            free: usize::from(end.saturating_sub(start)) - taken.len(),
+        }
+    }
+
+    /// Instances in name order, the order `instance_index` routing refers to
+    pub async fn instances_by_index(&self) -> Vec<Arc<TeiInstance>> {
+        let mut instances = self.list().await;
+        instances.sort_unstable_by(|a, b| a.config.name.cmp(&b.config.name));
+        instances
+    }
+
+    /// Where the multiplexer routes requests by index and by model id right now
+    pub async fn routing_table(&self, strategy: ModelRoutingStrategy) -> RoutingTable {
+        let instances = self.instances_by_index().await;
+        let mut models: BTreeMap<String, Vec<InstanceRoute>> = BTreeMap::new();
+        for (index, instance) in instances.iter().enumerate() {
+            let routable = instance.model_routable().await;
+            // Relative weight for now, normalized per model below
+            let weight = match strategy {
+                _ if !routable => 0.0,
+                ModelRoutingStrategy::Weighted => f64::from(instance.config.routing_weight()),
+                ModelRoutingStrategy::RoundRobin | ModelRoutingStrategy::LeastLoaded => 1.0,
+            };
+            models
+                .entry(instance.config.model_id.clone())
+                .or_default()
+                .push(InstanceRoute {
+                    instance: instance.config.name.clone(),
+                    index: index as u32,
+                    status: *instance.status.read().await,
+                    paused: instance.is_paused(),
+                    restarting: instance.is_restarting(),
+                    routable,
+                    weight,
+                });
+        }
+        for routes in models.values_mut() {
+            let total: f64 = routes.iter().map(|route| route.weight).sum();
+            for route in routes.iter_mut().filter(|route| route.routable) {
+                route.weight /= total;
+            }
+        }
+
+        RoutingTable {
+            strategy,
+            indices: instances.iter().map(|i| i.config.name.clone()).collect(),
+            models,
+        }
+    }
+
     /// Get TEI binary path
     pub fn tei_binary_path(&self) -> &str {
         &self.tei_binary_path
@@ -240,14 +901,15 @@ impl Registry {
 
     /// Find next available port starting from the given port
     /// Tries up to 1000 ports to find a free one
-    fn find_free_port(start_port: u16) -> Result<u16> {
+    fn find_free_port(start_port: u16, used_ports: &std::collections::HashSet<u16>) -> Result<u16> {
         const MAX_ATTEMPTS: u16 = 1000;
 
         for offset in 0..MAX_ATTEMPTS {
             let port = start_port.saturating_add(offset);
 
-            // Try to bind to the port to check if it's free
-            if TcpListener::bind(("0.0.0.0", port)).is_ok() {
+            // Skip ports assigned to instances that haven't bound them yet,
+            // then try to bind to the port to check if it's free
+            if !used_ports.contains(&port) && TcpListener::bind(("0.0.0.0", port)).is_ok() {
                 return Ok(port);
             }
         }
@@ -352,6 +1014,35 @@ mod tests {
         assert_eq!(retrieved.config.name, "test");
     }
 
+    #[tokio::test]
+    async fn test_allowed_models_enforced() {
+        let registry = Registry::new(None, "text-embeddings-router".to_string(), 8080, 8180)
+            .with_allowed_models(Some(vec!["BAAI/*".to_string()]));
+
+        let config = InstanceConfig {
+            name: "outside".to_string(),
+            model_id: "other/model".to_string(),
+            port: 8080,
+            ..Default::default()
+        };
+        let Err(err) = registry.add(config.clone()).await else {
+            panic!("model outside allowed_models was added");
+        };
+        assert!(matches!(
+            err.downcast_ref::<TeiError>(),
+            Some(TeiError::Forbidden { .. })
+        ));
+        assert!(registry.check_admission(&config).await.is_err());
+        assert_eq!(registry.count().await, 0);
+
+        let config = InstanceConfig {
+            name: "inside".to_string(),
+            model_id: "BAAI/bge-small-en-v1.5".to_string(),
+            ..config
+        };
+        registry.add(config).await.unwrap();
+    }
+
     #[tokio::test]
     async fn test_duplicate_name_rejection() {
         let registry = Registry::new(None, "text-embeddings-router".to_string(), 8080, 8180);
@@ -632,4 +1323,206 @@ mod tests {
 
         assert_eq!(registry.count().await, 3);
     }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 8)]
+    async fn test_port_auto_allocation_concurrent_adds() {
+        const CREATES: u16 = 40;
+        let base_port =
+            find_consecutive_free_ports(21000, CREATES).expect("Should find free port range");
+        let range_end = base_port + CREATES;
+
+        let registry = Arc::new(Registry::new(
+            None,
+            "text-embeddings-router".to_string(),
+            base_port,
+            range_end,
+        ));
+
+        let handles: Vec<_> = (0..CREATES)
+            .map(|i| {
+                let registry = registry.clone();
+                tokio::spawn(async move {
+                    let config = InstanceConfig {
+                        name: format!("concurrent{}", i),
+                        model_id: "model".to_string(),
+                        port: 0, // Auto-allocate
+                        ..Default::default()
+                    };
+                    registry.add(config).await
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.await.unwrap().unwrap();
+        }
+
+        let instances = registry.list().await;
+        assert_eq!(instances.len(), CREATES as usize);
+
+        // Every allocated port, instance and Prometheus, is handed out once
+        let ports: std::collections::HashSet<_> = instances.iter().map(|i| i.config.port).collect();
+        assert_eq!(ports.len(), CREATES as usize);
+        for port in &ports {
+            assert!((base_port..range_end).contains(port));
+        }
+
+        let prometheus_ports: std::collections::HashSet<_> = instances
+            .iter()
+            .map(|i| i.config.prometheus_port.unwrap())
+            .collect();
+        assert_eq!(prometheus_ports.len(), CREATES as usize);
+        assert!(prometheus_ports.is_disjoint(&ports));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 8)]
+    async fn test_gpu_placement_checked_under_lock() {
+        const GIB: u64 = 1024 * 1024 * 1024;
+        // One 24 GiB GPU with 2 GiB kept free: only two 10 GiB instances fit
+        let registry = Arc::new(
+            Registry::new(None, "text-embeddings-router".to_string(), 8080, 8180)
+                .with_gpu_info(GpuInfo {
+                    indices: vec![0],
+                    memory_total_bytes: vec![Some(24 * GIB)],
+                    ..Default::default()
+                })
+                .with_gpu_memory_headroom(2 * GIB),
+        );
+
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let registry = registry.clone();
+                tokio::spawn(async move {
+                    registry
+                        .add(InstanceConfig {
+                            name: format!("gpu{}", i),
+                            model_id: "model".to_string(),
+                            port: 18300 + i,
+                            gpu_id: Some(0),
+                            gpu_memory_bytes: Some(10 * GIB),
+                            ..Default::default()
+                        })
+                        .await
+                })
+            })
+            .collect();
+
+        let mut added = 0;
+        for handle in handles {
+            match handle.await.unwrap() {
+                Ok(_) => added += 1,
+                Err(e) => assert!(matches!(
+                    e.downcast_ref::<crate::gpu::GpuError>(),
+                    Some(crate::gpu::GpuError::InsufficientMemory { .. })
+                )),
+            }
+        }
+        assert_eq!(added, 2);
+
+        // Instances not pinned to a GPU are not placed
+        registry
+            .add(InstanceConfig {
+                name: "unpinned".to_string(),
+                model_id: "model".to_string(),
+                port: 18310,
+                gpu_memory_bytes: Some(10 * GIB),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_instances_for_model_and_count_by_status() {
+        let registry = Registry::new(None, "text-embeddings-router".to_string(), 8080, 8180);
+        for (name, model_id, port) in [
+            ("bge-b", "bge", 18201),
+            ("bge-a", "bge", 18202),
+            ("e5", "e5", 18203),
+        ] {
+            registry
+                .add(InstanceConfig {
+                    name: name.to_string(),
+                    model_id: model_id.to_string(),
+                    port,
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+        }
+        *registry.get("bge-b").await.unwrap().status.write().await = InstanceStatus::Running;
+        *registry.get("e5").await.unwrap().status.write().await = InstanceStatus::Failed;
+
+        let names = |instances: Vec<Arc<TeiInstance>>| -> Vec<String> {
+            instances.iter().map(|i| i.config.name.clone()).collect()
+        };
+        assert_eq!(
+            names(registry.instances_for_model("bge", false).await),
+            vec!["bge-a", "bge-b"]
+        );
+        assert_eq!(
+            names(registry.instances_for_model("bge", true).await),
+            vec!["bge-b"]
+        );
+        assert!(registry.instances_for_model("e5", true).await.is_empty());
+        assert!(
+            registry
+                .instances_for_model("missing", false)
+                .await
+                .is_empty()
+        );
+
+        let counts = registry.count_by_status().await;
+        assert_eq!(counts.get(&InstanceStatus::Running), Some(&1));
+        assert_eq!(counts.get(&InstanceStatus::Stopped), Some(&1));
+        assert_eq!(counts.get(&InstanceStatus::Failed), Some(&1));
+        assert_eq!(counts.get(&InstanceStatus::Starting), None);
+
+        // Removed instances leave the index
+        registry.remove("bge-a").await.unwrap();
+        registry.remove("e5").await.unwrap();
+        assert_eq!(
+            names(registry.instances_for_model("bge", false).await),
+            vec!["bge-b"]
+        );
+        assert!(registry.instances_for_model("e5", false).await.is_empty());
+        assert_eq!(registry.count_by_status().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_event_history_replayed_before_live_events() {
+        let registry = Registry::new(None, "text-embeddings-router".to_string(), 8080, 8180)
+            .with_event_history(2);
+        for (name, port) in [("a", 18211), ("b", 18212), ("c", 18213)] {
+            registry
+                .add(InstanceConfig {
+                    name: name.to_string(),
+                    model_id: "model".to_string(),
+                    port,
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+        }
+
+        // Only the last two are kept, however many are asked for
+        let (replayed, mut live) = registry.subscribe_events_with_replay(10);
+        let names: Vec<_> = replayed
+            .iter()
+            .map(|event| match event {
+                InstanceEvent::Added(name) => name.as_str(),
+                other => panic!("unexpected event {:?}", other),
+            })
+            .collect();
+        assert_eq!(names, vec!["b", "c"]);
+        assert_eq!(registry.subscribe_events_with_replay(1).0.len(), 1);
+
+        registry.remove("a").await.unwrap();
+        assert!(matches!(live.recv().await.unwrap(), InstanceEvent::Removed(name) if name == "a"));
+
+        // Without history, subscribers only see live events
+        let registry = Registry::new(None, "text-embeddings-router".to_string(), 8080, 8180);
+        registry.notify_status_change("a", InstanceStatus::Stopped, InstanceStatus::Starting);
+        assert!(registry.subscribe_events_with_replay(10).0.is_empty());
+    }
 }
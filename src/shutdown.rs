@@ -0,0 +1,235 @@
+//! Bounded manager shutdown
+//!
+//! Draining the gRPC server and stopping every instance share one budget
+//! (`shutdown_total_timeout_secs`), so a stuck instance can't keep the process
+//! alive forever. Instances still stopping when it runs out are force-killed,
+//! and the final state is saved either way.
+
+use crate::instance::TeiInstance;
+use crate::state::StateManager;
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::Arc;
+use tokio::task::JoinSet;
+use tokio::time::{Duration, Instant, timeout, timeout_at};
+
+/// How long a force-kill may take before the instance is given up on
+pub const FORCE_KILL_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// What did not finish within the shutdown budget
+#[derive(Debug, Default, PartialEq)]
+pub struct ShutdownReport {
+    /// The drain was still running when the budget ran out
+    pub drain_timed_out: bool,
+    /// Instances whose graceful stop was abandoned, by name
+    pub force_killed: Vec<String>,
+}
+
+/// Drain, stop `instances` and save state within `total`
+///
+/// `drain` is typically the gRPC server finishing in-flight requests.
+pub async fn shutdown(
+    drain: impl Future<Output = ()>,
+    instances: Vec<Arc<TeiInstance>>,
+    state_manager: &StateManager,
+    total: Duration,
+) -> Result<ShutdownReport> {
+    let deadline = Instant::now() + total;
+    let mut report = ShutdownReport::default();
+
+    if timeout_at(deadline, drain).await.is_err() {
+        tracing::warn!(
+            budget_secs = total.as_secs_f64(),
+            "Drain did not finish within the shutdown budget"
+        );
+        report.drain_timed_out = true;
+    }
+
+    tracing::info!("Stopping all instances");
+    report.force_killed = stop_all_until(instances, deadline).await;
+
+    tracing::info!("Saving final state");
+    state_manager.save().await?;
+
+    Ok(report)
+}
+
+/// Stop `instances` concurrently, force-killing those still stopping at `deadline`
+///
+/// Returns the names of the force-killed instances.
+pub async fn stop_all_until(instances: Vec<Arc<TeiInstance>>, deadline: Instant) -> Vec<String> {
+    let mut pending: BTreeMap<String, Arc<TeiInstance>> = BTreeMap::new();
+    let mut stops = JoinSet::new();
+    for instance in instances {
+        let name = instance.config.name.clone();
+        pending.insert(name.clone(), instance.clone());
+        stops.spawn(async move { (name, instance.stop().await) });
+    }
+
+    let _ = timeout_at(deadline, async {
+        while let Some(result) = stops.join_next().await {
+            match result {
+                Ok((name, result)) => {
+                    if let Err(e) = result {
+                        tracing::error!(
+                            instance = %name,
+                            error = %e,
+                            "Failed to stop instance during shutdown"
+                        );
+                    }
+                    pending.remove(&name);
+                }
+                // Left in `pending`, so it is force-killed below
+                Err(e) => tracing::error!(error = %e, "Stop task panicked"),
+            }
+        }
+    })
+    .await;
+
+    // Abandoned stops release the handle for force_kill once they are dropped
+    stops.shutdown().await;
+
+    let mut force_killed = Vec::with_capacity(pending.len());
+    for (name, instance) in pending {
+        tracing::warn!(instance = %name, "Instance did not stop in time, force-killing");
+        match timeout(FORCE_KILL_TIMEOUT, instance.force_kill()).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => {
+                tracing::error!(instance = %name, error = %e, "Failed to force-kill instance");
+            }
+            Err(_) => {
+                tracing::error!(instance = %name, "Force-kill did not finish, giving up");
+            }
+        }
+        force_killed.push(name);
+    }
+    force_killed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::InstanceConfig;
+    use crate::instance::mocks::MockProcessManager;
+    use crate::instance::{
+        InstanceStatus, ProcessExit, ProcessHandle, ProcessManager, SpawnConfig,
+    };
+    use crate::registry::Registry;
+    use crate::state::mocks::MockStorage;
+    use async_trait::async_trait;
+    use std::path::PathBuf;
+
+    /// Graceful stops never return; only a kill (zero grace period) does
+    struct HangingStopManager(MockProcessManager);
+
+    #[async_trait]
+    impl ProcessManager for HangingStopManager {
+        async fn spawn(&self, config: SpawnConfig) -> Result<ProcessHandle> {
+            self.0.spawn(config).await
+        }
+
+        async fn stop(&self, handle: ProcessHandle, grace: Duration) -> Result<()> {
+            if grace.is_zero() {
+                self.0.stop(handle, grace).await
+            } else {
+                std::future::pending().await
+            }
+        }
+
+        async fn is_running(&self, handle: &ProcessHandle) -> bool {
+            self.0.is_running(handle).await
+        }
+
+        async fn pid(&self, handle: &ProcessHandle) -> Option<u32> {
+            self.0.pid(handle).await
+        }
+
+        async fn exit_status(&self, handle: &ProcessHandle) -> Option<ProcessExit> {
+            self.0.exit_status(handle).await
+        }
+    }
+
+    async fn started(name: &str, port: u16, manager: Arc<dyn ProcessManager>) -> Arc<TeiInstance> {
+        let instance = Arc::new(TeiInstance::new_with_manager(
+            InstanceConfig {
+                name: name.to_string(),
+                model_id: "model".to_string(),
+                port,
+                ..Default::default()
+            },
+            manager,
+        ));
+        instance.start("tei").await.unwrap();
+        instance
+    }
+
+    #[tokio::test]
+    async fn test_hanging_stop_is_force_killed_within_budget() {
+        let hanging = Arc::new(HangingStopManager(MockProcessManager::new()));
+        let stuck = started("stuck", 8081, hanging.clone()).await;
+        let clean = started("clean", 8082, Arc::new(MockProcessManager::new())).await;
+
+        let storage = Arc::new(MockStorage::new());
+        let state_file = PathBuf::from("/test/shutdown.toml");
+        let state_manager = StateManager::new_with_storage(
+            state_file.clone(),
+            Arc::new(Registry::new(None, "tei".to_string(), 8080, 8180)),
+            "tei".to_string(),
+            storage.clone(),
+        );
+
+        let total = Duration::from_millis(300);
+        let started_at = std::time::Instant::now();
+        let report = shutdown(
+            async {},
+            vec![stuck.clone(), clean.clone()],
+            &state_manager,
+            total,
+        )
+        .await
+        .unwrap();
+        let elapsed = started_at.elapsed();
+
+        assert!(elapsed >= total);
+        assert!(elapsed < total + Duration::from_secs(1), "{:?}", elapsed);
+        assert_eq!(
+            report,
+            ShutdownReport {
+                drain_timed_out: false,
+                force_killed: vec!["stuck".to_string()],
+            }
+        );
+        assert_eq!(*stuck.status.read().await, InstanceStatus::Stopped);
+        assert_eq!(*clean.status.read().await, InstanceStatus::Stopped);
+        assert_eq!(hanging.0.process_count().await, 0);
+        assert!(storage.get_file(&state_file).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_hanging_drain_uses_up_budget() {
+        let hanging = Arc::new(HangingStopManager(MockProcessManager::new()));
+        let instance = started("a", 8081, hanging.clone()).await;
+        let storage = Arc::new(MockStorage::new());
+        let state_manager = StateManager::new_with_storage(
+            PathBuf::from("/test/drain.toml"),
+            Arc::new(Registry::new(None, "tei".to_string(), 8080, 8180)),
+            "tei".to_string(),
+            storage,
+        );
+
+        let report = shutdown(
+            std::future::pending(),
+            vec![instance.clone()],
+            &state_manager,
+            Duration::from_millis(100),
+        )
+        .await
+        .unwrap();
+
+        // No time left to stop gracefully
+        assert!(report.drain_timed_out);
+        assert_eq!(report.force_killed, ["a"]);
+        assert_eq!(hanging.0.process_count().await, 0);
+    }
+}
@@ -1,6 +1,7 @@
 //! State persistence for instance configurations
 
 use crate::config::InstanceConfig;
+use crate::instance::{InstanceStatus, TeiInstance};
 use crate::registry::Registry;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
@@ -8,6 +9,7 @@ use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 use tokio::fs;
 use tokio::io::AsyncWriteExt;
 use tokio::task::JoinSet;
@@ -91,6 +93,12 @@ impl StorageBackend for FileSystemStorage {
 // State Manager with Dependency Injection
 // ============================================================================
 
+/// Schema version of state files written by this build
+///
+/// Bump it when a change to [`SavedState`] or [`InstanceConfig`] would misparse
+/// older files, and add the matching step to [`migrate_state`].
+pub const STATE_VERSION: u32 = 1;
+
 /// State manager for persisting instance configurations
 pub struct StateManager {
     state_file: PathBuf,
@@ -99,6 +107,11 @@ pub struct StateManager {
     storage: Arc<dyn StorageBackend>,
     /// Guard to prevent concurrent restore operations
     restore_in_progress: AtomicBool,
+    /// Extra attempts to start each restored instance
+    start_retries: u32,
+    start_retry_delay: Duration,
+    /// Readiness budget of instances without their own `startup_timeout_secs`
+    startup_timeout: Duration,
 }
 
 impl StateManager {
@@ -115,9 +128,25 @@ impl StateManager {
             tei_binary_path: Arc::from(tei_binary_path),
             storage,
             restore_in_progress: AtomicBool::new(false),
+            start_retries: 0,
+            start_retry_delay: Duration::ZERO,
+            startup_timeout: Duration::from_secs(300),
         }
     }
 
+    /// Readiness budget for restored instances that don't set `startup_timeout_secs`
+    pub fn with_startup_timeout(mut self, timeout: Duration) -> Self {
+        self.startup_timeout = timeout;
+        self
+    }
+
+    /// Retry a failed start of a restored instance up to `retries` times, `delay` apart
+    pub fn with_start_retry(mut self, retries: u32, delay: Duration) -> Self {
+        self.start_retries = retries;
+        self.start_retry_delay = delay;
+        self
+    }
+
     /// Create a new state manager with default filesystem storage
     pub fn new(state_file: PathBuf, registry: Arc<Registry>, tei_binary_path: String) -> Self {
         Self::new_with_storage(
@@ -133,7 +162,14 @@ impl StateManager {
         let instances = self.registry.list().await;
 
         let state = SavedState {
+            version: STATE_VERSION,
             last_updated: chrono::Utc::now(),
+            maintenance: self.registry.maintenance_enabled(),
+            maintenance_instances: instances
+                .iter()
+                .filter(|i| i.in_maintenance())
+                .map(|i| i.config.name.clone())
+                .collect(),
             instances: instances.iter().map(|i| i.config.clone()).collect(),
         };
 
@@ -164,13 +200,17 @@ impl StateManager {
             }
         };
 
-        let state: SavedState = toml::from_str(&content).with_context(|| {
+        let corrupted = || {
             format!(
                 "Failed to parse state file: {:?}. File may be corrupted. \
                 Please delete or fix the file manually.",
                 self.state_file
             )
-        })?;
+        };
+        let table: toml::Table = toml::from_str(&content).with_context(corrupted)?;
+        let table = migrate_state(table)
+            .with_context(|| format!("Cannot load state file {:?}", self.state_file))?;
+        let state: SavedState = table.try_into().with_context(corrupted)?;
 
         tracing::info!(
             instances = state.instances.len(),
@@ -214,6 +254,11 @@ impl StateManager {
 
         let state = self.load().await?;
 
+        if state.maintenance {
+            self.registry.set_maintenance(true);
+            tracing::warn!("Global maintenance mode restored from state");
+        }
+
         if state.instances.is_empty() {
             tracing::info!("No instances to restore");
             return Ok(());
@@ -228,10 +273,25 @@ impl StateManager {
         let mut failed = 0;
         let mut readiness_tasks: JoinSet<(String, Result<(), anyhow::Error>)> = JoinSet::new();
 
-        for config in state.instances {
+        // Dependencies start (and become ready) before their dependents
+        for config in crate::config::dependency_order(&state.instances)? {
             match self.registry.add(config.clone()).await {
                 Ok(instance) => {
-                    if let Err(e) = instance.start(&self.tei_binary_path).await {
+                    if state.maintenance_instances.contains(&config.name) {
+                        instance.set_maintenance(true);
+                    }
+                    if let Err(e) = self
+                        .registry
+                        .wait_for_dependencies(&config, self.startup_timeout)
+                        .await
+                    {
+                        tracing::error!(
+                            instance = %config.name,
+                            error = %e,
+                            "Not starting restored instance"
+                        );
+                        failed += 1;
+                    } else if let Err(e) = self.start_with_retries(&instance).await {
                         tracing::error!(
                             instance = %config.name,
                             error = %e,
@@ -243,13 +303,13 @@ impl StateManager {
                             // Track background task for readiness check
                             let instance_clone = instance.clone();
                             let instance_name = config.name.clone();
+                            let health_checker = self.registry.health_checker();
+                            let timeout = config.startup_timeout(self.startup_timeout);
                             readiness_tasks.spawn(async move {
-                                use crate::health::GrpcHealthChecker;
-                                use std::time::Duration;
-
-                                let result = GrpcHealthChecker::wait_for_ready(
+                                let result = crate::health::wait_for_ready_with(
+                                    health_checker.as_ref(),
                                     &instance_clone,
-                                    Duration::from_secs(300),
+                                    timeout,
                                     Duration::from_millis(500),
                                 )
                                 .await;
@@ -260,8 +320,9 @@ impl StateManager {
                                         error = %e,
                                         "Restored instance failed to become ready"
                                     );
-                                    *instance_clone.status.write().await =
-                                        crate::instance::InstanceStatus::Failed;
+                                    instance_clone
+                                        .set_status(crate::instance::InstanceStatus::Failed)
+                                        .await;
                                 }
 
                                 (instance_name, result)
@@ -308,6 +369,33 @@ impl StateManager {
 
         Ok(())
     }
+
+    /// Start a restored instance, retrying failed starts
+    ///
+    /// Once the retries are used up the instance is marked `Failed`.
+    async fn start_with_retries(&self, instance: &TeiInstance) -> Result<()> {
+        let mut attempt = 0;
+        loop {
+            match instance.start(&self.tei_binary_path).await {
+                Ok(()) => return Ok(()),
+                Err(e) if attempt < self.start_retries => {
+                    attempt += 1;
+                    tracing::warn!(
+                        instance = %instance.config.name,
+                        error = %e,
+                        attempt,
+                        max_retries = self.start_retries,
+                        "Failed to start restored instance, retrying"
+                    );
+                    tokio::time::sleep(self.start_retry_delay).await;
+                }
+                Err(e) => {
+                    instance.set_status(InstanceStatus::Failed).await;
+                    return Err(e);
+                }
+            }
+        }
+    }
 }
 
 /// RAII guard to ensure restore_in_progress flag is cleared on drop
@@ -321,9 +409,96 @@ impl Drop for RestoreGuard<'_> {
     }
 }
 
+/// Upgrade a parsed state file to [`STATE_VERSION`]
+///
+/// Files from before versioning have no `version` and count as version 0.
+/// Files from a newer manager are rejected rather than partially understood.
+pub fn migrate_state(mut state: toml::Table) -> Result<toml::Table> {
+    let version = match state.get("version") {
+        None => 0,
+        Some(value) => value
+            .as_integer()
+            .and_then(|v| u32::try_from(v).ok())
+            .with_context(|| format!("Invalid state file version {}", value))?,
+    };
+    if version > STATE_VERSION {
+        anyhow::bail!(
+            "State file version {} is newer than this tei-manager supports ({}); \
+            upgrade tei-manager or move the file aside",
+            version,
+            STATE_VERSION
+        );
+    }
+
+    if version < STATE_VERSION {
+        tracing::info!(
+            from = version,
+            to = STATE_VERSION,
+            "Migrating state file schema"
+        );
+    }
+
+    // v0 -> v1: the version field is new, and files from before `pooling` was
+    // typed may hold it as a free-form string. Later steps go here in order,
+    // each upgrading the table by one version.
+    if version < 1
+        && let Some(instances) = state.get_mut("instances").and_then(|v| v.as_array_mut())
+    {
+        for instance in instances.iter_mut().filter_map(|v| v.as_table_mut()) {
+            migrate_free_form_pooling(instance);
+        }
+    }
+
+    state.insert("version".to_string(), i64::from(STATE_VERSION).into());
+    Ok(state)
+}
+
+/// Normalise a pre-typed `pooling` string (e.g. "Mean", "last_token")
+///
+/// Values that aren't a known strategy are passed on as `--pooling` in
+/// `extra_args`, so the instance launches exactly as it did before.
+fn migrate_free_form_pooling(instance: &mut toml::Table) {
+    let Some(value) = instance
+        .get("pooling")
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+    else {
+        return;
+    };
+
+    let normalised = value.trim().to_ascii_lowercase().replace('_', "-");
+    if ["mean", "cls", "splade", "last-token"].contains(&normalised.as_str()) {
+        instance.insert("pooling".to_string(), normalised.into());
+        return;
+    }
+
+    tracing::warn!(
+        instance = ?instance.get("name").and_then(|v| v.as_str()),
+        pooling = %value,
+        "Unknown pooling in state file, passing it on via extra_args"
+    );
+    instance.remove("pooling");
+    if let Some(extra_args) = instance
+        .entry("extra_args")
+        .or_insert_with(|| toml::Value::Array(Vec::new()))
+        .as_array_mut()
+    {
+        extra_args.push(format!("--pooling={}", value).into());
+    }
+}
+
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct SavedState {
+    /// Schema version, see [`STATE_VERSION`] (0 for files written before versioning)
+    #[serde(default)]
+    pub version: u32,
     pub last_updated: chrono::DateTime<chrono::Utc>,
+    /// Global maintenance mode
+    #[serde(default)]
+    pub maintenance: bool,
+    /// Instances in per-instance maintenance mode
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub maintenance_instances: Vec<String>,
     pub instances: Vec<InstanceConfig>,
 }
 
@@ -466,6 +641,8 @@ mod tests {
             model_id: "model".to_string(),
             port: 8080,
             gpu_id: Some(1),
+            revision: Some("abc123".to_string()),
+            log_level: Some("debug".to_string()),
             created_at: Some(chrono::Utc::now()),
             ..Default::default()
         };
@@ -484,6 +661,162 @@ mod tests {
         assert_eq!(loaded.instances.len(), 1);
         assert_eq!(loaded.instances[0].name, "test");
         assert_eq!(loaded.instances[0].gpu_id, Some(1));
+        assert_eq!(loaded.instances[0].revision.as_deref(), Some("abc123"));
+        assert_eq!(loaded.instances[0].log_level.as_deref(), Some("debug"));
+    }
+
+    #[tokio::test]
+    async fn test_cpu_only_persisted() {
+        let state_file = PathBuf::from("/test/state.toml");
+        let storage = Arc::new(MockStorage::new());
+        let registry = Arc::new(Registry::new(
+            None,
+            "text-embeddings-router".to_string(),
+            8080,
+            8180,
+        ));
+        let state_manager = StateManager::new_with_storage(
+            state_file.clone(),
+            registry.clone(),
+            "text-embeddings-router".to_string(),
+            storage.clone(),
+        );
+
+        for (name, port, cpu_only) in [("cpu", 8080, true), ("gpu", 8081, false)] {
+            registry
+                .add(InstanceConfig {
+                    name: name.to_string(),
+                    model_id: "model".to_string(),
+                    port,
+                    cpu_only,
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+        }
+        state_manager.save().await.unwrap();
+
+        // Only set flags are written
+        let saved = storage.get_file(&state_file).await.unwrap();
+        assert_eq!(saved.matches("cpu_only = true").count(), 1);
+        assert!(!saved.contains("cpu_only = false"));
+
+        let mut loaded = state_manager.load().await.unwrap().instances;
+        loaded.sort_by(|a, b| a.name.cmp(&b.name));
+        assert!(loaded[0].cpu_only);
+        assert!(!loaded[1].cpu_only);
+    }
+
+    /// Reports every instance healthy after a delay
+    struct SlowHealthyChecker(Duration);
+
+    #[async_trait]
+    impl crate::health::HealthChecker for SlowHealthyChecker {
+        async fn check(
+            &self,
+            _instance: &crate::instance::TeiInstance,
+        ) -> crate::health::HealthCheckResult {
+            tokio::time::sleep(self.0).await;
+            crate::health::HealthCheckResult::healthy()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_restore_starts_dependencies_first() {
+        let log_dir = TempDir::new().unwrap();
+        let state_file = PathBuf::from("/test/deps.toml");
+        let storage = Arc::new(MockStorage::new());
+        let delay = Duration::from_millis(50);
+        let registry = Arc::new(
+            Registry::new(None, "/bin/sleep".to_string(), 8080, 8180)
+                .with_log_dir(log_dir.path().to_path_buf())
+                .with_health_checker(Arc::new(SlowHealthyChecker(delay))),
+        );
+
+        // Saved dependents-first
+        let state_content = r#"
+last_updated = "2025-01-01T00:00:00Z"
+
+[[instances]]
+name = "reranker"
+model_id = "model"
+port = 8082
+depends_on = ["embedder"]
+
+[[instances]]
+name = "embedder"
+model_id = "model"
+port = 8081
+depends_on = ["tokenizer"]
+
+[[instances]]
+name = "tokenizer"
+model_id = "model"
+port = 8080
+"#;
+        storage.save(&state_file, state_content).await.unwrap();
+
+        let state_manager = StateManager::new_with_storage(
+            state_file,
+            registry.clone(),
+            "/bin/sleep".to_string(),
+            storage,
+        );
+        state_manager.restore_with_options(false).await.unwrap();
+
+        let mut started = Vec::new();
+        for name in ["tokenizer", "embedder", "reranker"] {
+            let instance = registry.get(name).await.unwrap();
+            started.push(instance.stats.read().await.started_at.unwrap());
+        }
+        // Each dependent started only after its dependency passed a (slow) readiness check
+        for pair in started.windows(2) {
+            assert!(pair[1] - pair[0] >= chrono::Duration::from_std(delay).unwrap());
+        }
+        for name in ["tokenizer", "embedder"] {
+            let instance = registry.get(name).await.unwrap();
+            assert_eq!(
+                *instance.status.read().await,
+                crate::instance::InstanceStatus::Running
+            );
+        }
+        assert_eq!(
+            registry.get("reranker").await.unwrap().config.depends_on,
+            ["embedder"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_restore_rejects_dependency_cycle() {
+        let state_file = PathBuf::from("/test/cycle.toml");
+        let storage = Arc::new(MockStorage::new());
+        let registry = Arc::new(Registry::new(None, "/bin/sleep".to_string(), 8080, 8180));
+        let state_content = r#"
+last_updated = "2025-01-01T00:00:00Z"
+
+[[instances]]
+name = "a"
+model_id = "model"
+port = 8080
+depends_on = ["b"]
+
+[[instances]]
+name = "b"
+model_id = "model"
+port = 8081
+depends_on = ["a"]
+"#;
+        storage.save(&state_file, state_content).await.unwrap();
+
+        let state_manager = StateManager::new_with_storage(
+            state_file,
+            registry.clone(),
+            "/bin/sleep".to_string(),
+            storage,
+        );
+        let err = state_manager.restore_with_options(false).await.unwrap_err();
+        assert!(err.to_string().contains("cycle"), "{}", err);
+        assert!(registry.list().await.is_empty());
     }
 
     #[tokio::test]
@@ -537,6 +870,103 @@ mod tests {
         assert!(state_manager.load().await.is_err());
     }
 
+    #[tokio::test]
+    async fn test_versionless_state_migrates() {
+        let state_file = PathBuf::from("/test/v0.toml");
+        let storage = Arc::new(MockStorage::new());
+        let registry = Arc::new(Registry::new(
+            None,
+            "text-embeddings-router".to_string(),
+            8080,
+            8180,
+        ));
+
+        // As written by managers before state files were versioned
+        let v0 = r#"
+last_updated = "2025-01-01T00:00:00Z"
+
+[[instances]]
+name = "legacy"
+model_id = "BAAI/bge-small-en-v1.5"
+port = 8081
+max_batch_tokens = 16384
+max_concurrent_requests = 512
+gpu_id = 0
+extra_args = []
+"#;
+        storage.save(&state_file, v0).await.unwrap();
+
+        let state_manager = StateManager::new_with_storage(
+            state_file,
+            registry,
+            "text-embeddings-router".to_string(),
+            storage,
+        );
+        let loaded = state_manager.load().await.unwrap();
+        assert_eq!(loaded.version, STATE_VERSION);
+        assert_eq!(loaded.instances.len(), 1);
+        assert_eq!(loaded.instances[0].name, "legacy");
+        assert_eq!(loaded.instances[0].gpu_id, Some(0));
+    }
+
+    #[test]
+    fn test_versionless_free_form_pooling_migrates() {
+        let v0: toml::Table = toml::from_str(
+            r#"
+last_updated = "2025-01-01T00:00:00Z"
+
+[[instances]]
+name = "upper"
+model_id = "m"
+port = 8081
+pooling = "Last_Token"
+
+[[instances]]
+name = "custom"
+model_id = "m"
+port = 8082
+pooling = "weighted-mean"
+extra_args = ["--auto-truncate"]
+"#,
+        )
+        .unwrap();
+
+        let state: SavedState = migrate_state(v0).unwrap().try_into().unwrap();
+        assert_eq!(
+            state.instances[0].pooling,
+            Some(crate::config::Pooling::LastToken)
+        );
+        assert_eq!(state.instances[1].pooling, None);
+        assert_eq!(
+            state.instances[1].extra_args,
+            vec!["--auto-truncate", "--pooling=weighted-mean"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_newer_state_version_rejected() {
+        let state_file = PathBuf::from("/test/future.toml");
+        let storage = Arc::new(MockStorage::new());
+        let future = format!(
+            "version = {}\nlast_updated = \"2030-01-01T00:00:00Z\"\ninstances = []\n",
+            STATE_VERSION + 1
+        );
+        storage.save(&state_file, &future).await.unwrap();
+
+        let state_manager = StateManager::new_with_storage(
+            state_file,
+            Arc::new(Registry::new(None, "tei".to_string(), 8080, 8180)),
+            "tei".to_string(),
+            storage,
+        );
+        let err = format!("{:#}", state_manager.load().await.unwrap_err());
+        assert!(
+            err.contains("is newer than this tei-manager supports"),
+            "{}",
+            err
+        );
+    }
+
     #[tokio::test]
     async fn test_save_multiple_instances() {
         let state_file = PathBuf::from("/test/multi.toml");
@@ -720,7 +1150,7 @@ mod tests {
             port: 9090,
             max_batch_tokens: 2048,
             max_concurrent_requests: 20,
-            pooling: Some("mean".to_string()),
+            pooling: Some(crate::config::Pooling::Mean),
             gpu_id: Some(1),
             prometheus_port: Some(9091),
             extra_args: vec!["--arg1".to_string()],
@@ -737,6 +1167,7 @@ mod tests {
         assert!(content.contains("model_id = \"bert-base\""));
         assert!(content.contains("port = 9090"));
         assert!(content.contains("pooling = \"mean\""));
+        assert!(content.contains(&format!("version = {}", STATE_VERSION)));
     }
 
     #[tokio::test]
@@ -991,6 +1422,181 @@ max_concurrent_requests = 10
         assert_eq!(instances.len(), 1);
     }
 
+    /// Stand-in TEI binary that stays up, since exited processes are marked failed
+    const FAKE_TEI: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/fake-tei");
+
+    #[tokio::test]
+    async fn test_restore_retries_failed_start() {
+        let dir = TempDir::new().unwrap();
+        let env_file = dir.path().join("secrets.env");
+        let state_file = PathBuf::from("/test/retry.toml");
+        let storage = Arc::new(MockStorage::new());
+        let registry = Arc::new(
+            Registry::new(None, FAKE_TEI.to_string(), 8080, 8180)
+                .with_log_dir(dir.path().to_path_buf()),
+        );
+
+        let state_content = format!(
+            r#"
+last_updated = "2025-01-01T00:00:00Z"
+
+[[instances]]
+name = "late-mount"
+model_id = "model"
+port = 8080
+env_file = "{}"
+
+[[instances]]
+name = "never-mounted"
+model_id = "model"
+port = 8081
+env_file = "{}"
+"#,
+            env_file.display(),
+            dir.path().join("missing.env").display()
+        );
+        storage.save(&state_file, &state_content).await.unwrap();
+
+        let state_manager = StateManager::new_with_storage(
+            state_file,
+            registry.clone(),
+            FAKE_TEI.to_string(),
+            storage,
+        )
+        .with_start_retry(2, Duration::from_millis(200));
+
+        // The first start fails on the missing env_file, which appears before the retry
+        let mount = tokio::spawn({
+            let env_file = env_file.clone();
+            async move {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                std::fs::write(env_file, "HF_TOKEN=hf_test\n").unwrap();
+            }
+        });
+        state_manager.restore_with_options(false).await.unwrap();
+        mount.await.unwrap();
+
+        let instance = registry.get("late-mount").await.unwrap();
+        assert_eq!(*instance.status.read().await, InstanceStatus::Starting);
+        assert!(instance.stats.read().await.started_at.is_some());
+        instance.stop().await.unwrap();
+
+        // Retries used up: registered but failed, and the restore still completed
+        let instance = registry.get("never-mounted").await.unwrap();
+        assert_eq!(*instance.status.read().await, InstanceStatus::Failed);
+        assert!(instance.stats.read().await.started_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_restore_waits_per_instance_startup_timeout() {
+        use crate::health::mocks::MockHealthChecker;
+
+        let dir = TempDir::new().unwrap();
+        let checker = Arc::new(MockHealthChecker::new());
+        checker.set_unhealthy("still loading".to_string());
+        let registry = Arc::new(
+            Registry::new(None, FAKE_TEI.to_string(), 8080, 8180)
+                .with_log_dir(dir.path().to_path_buf())
+                .with_health_checker(checker),
+        );
+
+        let state_file = PathBuf::from("/test/startup_timeout.toml");
+        let storage = Arc::new(MockStorage::new());
+        let state_content = r#"
+last_updated = "2025-01-01T00:00:00Z"
+
+[[instances]]
+name = "small-model"
+model_id = "model"
+port = 8080
+startup_timeout_secs = 1
+"#;
+        storage.save(&state_file, state_content).await.unwrap();
+
+        // The global budget alone would keep the restore waiting for minutes
+        let state_manager = StateManager::new_with_storage(
+            state_file,
+            registry.clone(),
+            FAKE_TEI.to_string(),
+            storage,
+        )
+        .with_startup_timeout(Duration::from_secs(300));
+
+        let started = std::time::Instant::now();
+        tokio::time::timeout(
+            Duration::from_secs(10),
+            state_manager.restore_with_options(true),
+        )
+        .await
+        .expect("restore waited for the global startup timeout")
+        .unwrap();
+        assert!(started.elapsed() >= Duration::from_secs(1));
+
+        let instance = registry.get("small-model").await.unwrap();
+        assert_eq!(*instance.status.read().await, InstanceStatus::Failed);
+        instance.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_maintenance_persisted_and_restored() {
+        let state_file = PathBuf::from("/test/maintenance.toml");
+        let storage = Arc::new(MockStorage::new());
+        let registry = Arc::new(Registry::new(None, "/bin/sleep".to_string(), 8080, 8180));
+        let state_manager = StateManager::new_with_storage(
+            state_file.clone(),
+            registry.clone(),
+            "/bin/sleep".to_string(),
+            storage.clone(),
+        );
+
+        for (name, port) in [("held", 8080), ("normal", 8081)] {
+            registry
+                .add(InstanceConfig {
+                    name: name.to_string(),
+                    model_id: "model".to_string(),
+                    port,
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+        }
+        registry.set_maintenance(true);
+        registry.get("held").await.unwrap().set_maintenance(true);
+        state_manager.save().await.unwrap();
+
+        let loaded = state_manager.load().await.unwrap();
+        assert!(loaded.maintenance);
+        assert_eq!(loaded.maintenance_instances, vec!["held".to_string()]);
+
+        let restored_registry = Arc::new(Registry::new(None, "/bin/sleep".to_string(), 8080, 8180));
+        let restored = StateManager::new_with_storage(
+            state_file,
+            restored_registry.clone(),
+            "/bin/sleep".to_string(),
+            storage,
+        );
+        restored.restore_with_options(false).await.unwrap();
+
+        assert!(restored_registry.maintenance_enabled());
+        assert!(
+            restored_registry
+                .get("held")
+                .await
+                .unwrap()
+                .in_maintenance()
+        );
+        assert!(
+            !restored_registry
+                .get("normal")
+                .await
+                .unwrap()
+                .in_maintenance()
+        );
+        for instance in restored_registry.list().await {
+            instance.stop().await.unwrap();
+        }
+    }
+
     #[tokio::test]
     async fn test_restore_without_waiting_for_ready() {
         let state_file = PathBuf::from("/test/no_wait.toml");
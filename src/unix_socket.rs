@@ -0,0 +1,111 @@
+//! Unix domain socket listeners for the HTTP API and gRPC multiplexer
+//!
+//! Used for sidecar deployments where the manager should not expose a TCP port.
+
+use anyhow::{Context, Result};
+use std::future::Future;
+use std::os::unix::fs::FileTypeExt;
+use std::path::{Path, PathBuf};
+use tokio::net::UnixListener;
+
+/// Socket file owned by a listener, removed when dropped
+#[derive(Debug)]
+pub struct SocketFile {
+    path: PathBuf,
+}
+
+impl Drop for SocketFile {
+    fn drop(&mut self) {
+        match std::fs::remove_file(&self.path) {
+            Ok(()) => tracing::debug!(path = %self.path.display(), "Removed Unix socket"),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => tracing::warn!(
+                path = %self.path.display(),
+                error = %e,
+                "Failed to remove Unix socket"
+            ),
+        }
+    }
+}
+
+/// Bind a listener at `path`, replacing a stale socket left by a previous run
+///
+/// Anything at `path` that is not a socket is left alone and reported as an error.
+pub fn bind(path: &Path) -> Result<(UnixListener, SocketFile)> {
+    match std::fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_socket() => std::fs::remove_file(path)
+            .with_context(|| format!("Failed to remove stale socket {}", path.display()))?,
+        Ok(_) => anyhow::bail!("{} exists and is not a socket", path.display()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => {
+            return Err(e)
+                .with_context(|| format!("Cannot inspect socket path {}", path.display()));
+        }
+    }
+
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Cannot create socket directory {}", parent.display()))?;
+    }
+
+    let listener = UnixListener::bind(path)
+        .with_context(|| format!("Failed to bind Unix socket {}", path.display()))?;
+    Ok((
+        listener,
+        SocketFile {
+            path: path.to_path_buf(),
+        },
+    ))
+}
+
+/// Serve the HTTP API on a Unix socket until `shutdown` resolves
+///
+/// The socket file is removed once the server stops, including on error.
+pub async fn serve_api<F>(path: &Path, app: axum::Router, shutdown: F) -> Result<()>
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    let (listener, _socket_file) = bind(path)?;
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown)
+        .await
+        .context("HTTP API server error")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_bind_replaces_stale_socket_and_cleans_up() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("run/api.sock");
+
+        // A socket left behind by a previous run
+        let stale = std::os::unix::net::UnixListener::bind({
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            &path
+        })
+        .unwrap();
+        drop(stale);
+        assert!(path.exists());
+
+        let (_listener, socket_file) = bind(&path).unwrap();
+        assert!(path.exists());
+        drop(socket_file);
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_bind_refuses_to_replace_regular_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("not-a-socket");
+        std::fs::write(&path, "data").unwrap();
+
+        let err = bind(&path).unwrap_err();
+        assert!(err.to_string().contains("not a socket"), "{}", err);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "data");
+    }
+}
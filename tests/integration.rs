@@ -13,14 +13,27 @@ use axum_test::TestServer;
 use serde_json::json;
 use std::sync::{Arc, OnceLock};
 use tei_manager::{
-    ModelLoader, ModelRegistry,
+    ModelLoader, ModelRegistry, PreloadTracker,
     api::routes::{AppState, create_router},
-    config::ManagerConfig,
+    audit::{AuditSink, FileAuditSink},
+    auth::Principal,
+    config::{HealthCheckMode, InstanceConfig, ManagerConfig},
+    gpu::GpuInfo,
+    grpc::{
+        channel::BackendChannelConfig,
+        pool::BackendPool,
+        proto::{multiplexer::v1 as mux, tei::v1 as tei},
+        server::GrpcServerOptions,
+    },
+    health,
+    instance::{BindRetry, PreStopHook, RESTART_DELAY},
     metrics,
+    models::preload::ModelDownloader,
     registry::Registry,
     state::StateManager,
 };
 use tempfile::TempDir;
+use tokio_stream::StreamExt;
 
 // Global metrics handle - only initialize once per test process
 static METRICS_HANDLE: OnceLock<metrics_exporter_prometheus::PrometheusHandle> = OnceLock::new();
@@ -32,33 +45,113 @@ fn get_metrics_handle() -> metrics_exporter_prometheus::PrometheusHandle {
 }
 
 /// Stub binary for integration tests.
-/// On Unix, we use /bin/sleep which exists on all systems and can be spawned.
-/// The sleep command will just run until killed, simulating a running process.
+/// On Unix, a script that ignores the TEI arguments and sleeps until killed,
+/// simulating a running process (an exited one is marked failed at once).
 #[cfg(unix)]
-const STUB_BINARY: &str = "/bin/sleep";
+const STUB_BINARY: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/fake-tei");
 #[cfg(not(unix))]
 const STUB_BINARY: &str = "timeout"; // Windows equivalent
 
 /// Helper to create a test server with the API
 async fn create_test_server() -> (TestServer, TempDir) {
+    create_test_server_with_config(ManagerConfig {
+        max_instances: Some(10),
+        ..Default::default()
+    })
+    .await
+}
+
+/// Helper to create a test server from a custom config
+///
+/// `state_file` is overridden with a temp path, a default `log_dir` with a temp
+/// `logs` directory, and a default `tei_binary_path` with a stub binary. The model
+/// registry reads an (initially empty) HF cache under the temp `hf-cache` directory.
+async fn create_test_server_with_config(config: ManagerConfig) -> (TestServer, TempDir) {
+    let (app, temp_dir) = create_test_app(config);
+    let server = TestServer::new(app).expect("Failed to create test server");
+
+    (server, temp_dir)
+}
+
+/// Helper to build the API router from a custom config, for tests that add layers
+fn create_test_app(config: ManagerConfig) -> (axum::Router, TempDir) {
+    create_test_app_with_preload(config, Arc::new(PreloadTracker::new()))
+}
+
+/// Helper to build the API router with a custom preload tracker (e.g. a stub downloader)
+fn create_test_app_with_preload(
+    config: ManagerConfig,
+    preload_tracker: Arc<PreloadTracker>,
+) -> (axum::Router, TempDir) {
+    build_test_app(config, preload_tracker, None)
+}
+
+/// Helper to build the API router with fixed GPU detection results instead of nvidia-smi's
+fn create_test_app_with_gpu_info(
+    config: ManagerConfig,
+    gpu_info: GpuInfo,
+) -> (axum::Router, TempDir) {
+    build_test_app(config, Arc::new(PreloadTracker::new()), Some(gpu_info))
+}
+
+fn build_test_app(
+    config: ManagerConfig,
+    preload_tracker: Arc<PreloadTracker>,
+    gpu_info: Option<GpuInfo>,
+) -> (axum::Router, TempDir) {
     let temp_dir = TempDir::new().expect("Failed to create temp dir");
     let state_file = temp_dir.path().join("state.toml");
 
-    // Use a stub binary for integration tests.
+    // Use a stub binary for integration tests unless one was set explicitly.
     // The actual process spawning logic is tested in src/instance.rs unit tests.
+    let tei_binary_path = if config.tei_binary_path == ManagerConfig::default().tei_binary_path {
+        STUB_BINARY.to_string()
+    } else {
+        config.tei_binary_path.clone()
+    };
+    let log_dir = if config.log_dir == ManagerConfig::default().log_dir {
+        temp_dir.path().join("logs")
+    } else {
+        config.log_dir.clone()
+    };
     let config = ManagerConfig {
         state_file: state_file.clone(),
-        tei_binary_path: STUB_BINARY.to_string(),
-        max_instances: Some(10),
-        ..Default::default()
+        log_dir,
+        tei_binary_path,
+        ..config
     };
 
-    let registry = Arc::new(Registry::new(
+    // Tests opt into the fake checker via health_check_mode, no env var needed
+    let health_checker = health::checker_for_mode(
+        config.health_check_mode,
+        BackendChannelConfig::from_config(&config),
+        true,
+    )
+    .expect("Failed to select health checker");
+
+    let registry = Registry::new(
         config.max_instances,
         config.tei_binary_path.clone(),
         config.instance_port_start,
         config.instance_port_end,
-    ));
+    )
+    .with_health_checker(health_checker)
+    .with_default_extra_args(config.default_extra_args.clone())
+    .with_instance_log_level(config.instance_log_level.clone())
+    .with_log_dir(config.log_dir.clone())
+    .with_pre_stop_hook(PreStopHook::from_config(&config))
+    .with_bind_retry(BindRetry::from_config(&config))
+    .with_startup_download_grace(std::time::Duration::from_secs(
+        config.startup_download_grace_secs,
+    ))
+    .with_metric_labels(config.metric_labels.clone())
+    .with_default_pooling(config.default_pooling.clone())
+    .with_allowed_models(config.allowed_models.clone())
+    .with_event_history(config.event_history_size);
+    let registry = Arc::new(match gpu_info {
+        Some(info) => registry.with_gpu_info(info),
+        None => registry,
+    });
 
     let state_manager = Arc::new(StateManager::new(
         state_file,
@@ -66,10 +159,18 @@ async fn create_test_server() -> (TestServer, TempDir) {
         config.tei_binary_path.clone(),
     ));
 
-    let model_registry = Arc::new(ModelRegistry::new());
+    let model_registry =
+        Arc::new(ModelRegistry::new().with_cache_dir(temp_dir.path().join("hf-cache")));
     let model_loader = Arc::new(ModelLoader::new());
 
     let state = AppState {
+        backend_pool: BackendPool::new(registry.clone()),
+        audit_sink: config
+            .audit_log_file
+            .clone()
+            .map(|path| Arc::new(FileAuditSink::new(path)) as Arc<dyn AuditSink>),
+        access_log: None,
+        embedding_dimensions: Arc::new(tei_manager::models::CachedEmbeddingDimensions::default()),
         registry,
         state_manager,
         prometheus_handle: get_metrics_handle(),
@@ -77,12 +178,11 @@ async fn create_test_server() -> (TestServer, TempDir) {
         require_cert_headers: false,
         model_registry,
         model_loader,
+        preload_tracker,
+        config: Arc::new(config),
     };
 
-    let app = create_router(state);
-    let server = TestServer::new(app).expect("Failed to create test server");
-
-    (server, temp_dir)
+    (create_router(state), temp_dir)
 }
 
 #[tokio::test]
@@ -98,6 +198,72 @@ async fn test_health_endpoint() {
     assert!(body["timestamp"].is_string());
 }
 
+#[tokio::test]
+async fn test_health_endpoint_over_unix_socket() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let (app, temp_dir) = create_test_app(ManagerConfig::default());
+    let socket_path = temp_dir.path().join("api.sock");
+
+    let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+    let server = tokio::spawn({
+        let socket_path = socket_path.clone();
+        async move {
+            tei_manager::unix_socket::serve_api(&socket_path, app, async {
+                let _ = shutdown_rx.await;
+            })
+            .await
+        }
+    });
+
+    let mut stream = None;
+    for _ in 0..50 {
+        if let Ok(connected) = tokio::net::UnixStream::connect(&socket_path).await {
+            stream = Some(connected);
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    let mut stream = stream.expect("API socket not up");
+    stream
+        .write_all(b"GET /health HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+        .await
+        .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+
+    assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+    assert!(response.contains(r#""status":"healthy""#), "{}", response);
+
+    // The socket file goes away with the server
+    shutdown_tx.send(()).unwrap();
+    server.await.unwrap().unwrap();
+    assert!(!socket_path.exists());
+}
+
+#[tokio::test]
+async fn test_version_endpoint() {
+    let (server, _temp_dir) = create_test_server().await;
+
+    let response = server.get("/version").await;
+    assert_eq!(response.status_code(), 200);
+
+    let body: serde_json::Value = response.json();
+    let version = body["version"].as_str().unwrap();
+    let core = version.split(['-', '+']).next().unwrap();
+    let parts: Vec<&str> = core.split('.').collect();
+    assert_eq!(parts.len(), 3, "not semver: {}", version);
+    assert!(
+        parts.iter().all(|p| p.parse::<u64>().is_ok()),
+        "not semver: {}",
+        version
+    );
+
+    assert!(!body["git_commit"].as_str().unwrap().is_empty());
+    assert!(body["build_timestamp"].is_string());
+    assert!(body["gpu_count"].is_u64());
+}
+
 #[tokio::test]
 async fn test_metrics_endpoint() {
     let (server, _temp_dir) = create_test_server().await;
@@ -109,6 +275,176 @@ async fn test_metrics_endpoint() {
     let _text = response.text(); // Verify we can read the body
 }
 
+#[tokio::test]
+async fn test_metrics_grpc_requests_have_model_label() {
+    let (server, _temp_dir) = create_test_server().await;
+
+    metrics::record_grpc_request(
+        "model-label-test",
+        "BAAI/bge-small-en-v1.5",
+        "embed",
+        "ok",
+        0.01,
+    );
+
+    let response = server.get("/metrics").await;
+    assert_eq!(response.status_code(), 200);
+
+    let text = response.text();
+    let line = text
+        .lines()
+        .find(|l| {
+            l.starts_with("tei_manager_grpc_requests_total")
+                && l.contains(r#"instance="model-label-test""#)
+        })
+        .expect("grpc request counter should be exported");
+    assert!(line.contains(r#"model="BAAI/bge-small-en-v1.5""#));
+    assert!(line.contains(r#"method="embed""#));
+    assert!(line.contains(r#"status="ok""#));
+}
+
+#[tokio::test]
+async fn test_metric_labels_on_instance_metrics() {
+    let (server, _temp_dir) = create_test_server_with_config(ManagerConfig {
+        max_instances: Some(10),
+        metric_labels: [("team".to_string(), "search".to_string())].into(),
+        ..Default::default()
+    })
+    .await;
+
+    let response = server
+        .post("/instances")
+        .json(&json!({
+            "name": "labelled",
+            "model_id": "BAAI/bge-small-en-v1.5",
+            "port": 0,
+            "metric_labels": {"tier": "gold"}
+        }))
+        .await;
+    assert_eq!(response.status_code(), 201);
+    metrics::record_instance_restart("labelled");
+
+    let text = server.get("/metrics").await.text();
+    for metric in [
+        "tei_manager_instances_created_total",
+        "tei_manager_instance_restarts_total",
+    ] {
+        let line = text
+            .lines()
+            .find(|l| l.starts_with(metric) && l.contains(r#"instance="labelled""#))
+            .unwrap_or_else(|| panic!("{} should be exported", metric));
+        assert!(line.contains(r#"team="search""#), "{}", line);
+        assert!(line.contains(r#"tier="gold""#), "{}", line);
+    }
+
+    // Label names are validated like the config file's
+    let response = server
+        .post("/instances")
+        .json(&json!({
+            "name": "bad-labels",
+            "model_id": "BAAI/bge-small-en-v1.5",
+            "port": 0,
+            "metric_labels": {"model": "x"}
+        }))
+        .await;
+    assert_eq!(response.status_code(), 400);
+}
+
+#[tokio::test]
+async fn test_admin_healthcheck_reports_every_instance() {
+    let (server, _temp_dir) = create_test_server().await;
+
+    // Nothing serves gRPC on these ports, so running instances fail softly
+    for (name, port) in [("sweep-a", 18461), ("sweep-b", 18462), ("sweep-c", 18463)] {
+        let response = server
+            .post("/instances")
+            .json(&json!({
+                "name": name,
+                "model_id": "BAAI/bge-small-en-v1.5",
+                "port": port
+            }))
+            .await;
+        assert_eq!(response.status_code(), 201);
+    }
+    server.post("/instances/sweep-c/stop").await;
+
+    let response = server.post("/admin/healthcheck").await;
+    assert_eq!(response.status_code(), 200);
+    let report: Vec<serde_json::Value> = response.json();
+    let names: Vec<_> = report.iter().map(|r| r["name"].as_str().unwrap()).collect();
+    assert_eq!(names, ["sweep-a", "sweep-b", "sweep-c"]);
+    assert!(report.iter().all(|r| r["healthy"] == false));
+    assert!(
+        report[0]["reason"]
+            .as_str()
+            .unwrap()
+            .contains("gRPC connect failed")
+    );
+    assert_eq!(report[2]["reason"], "Process not running");
+
+    // The sweep does not count toward restart thresholds
+    let health: serde_json::Value = server.get("/health/instances").await.json();
+    for instance in health["instances"].as_array().unwrap() {
+        assert_eq!(instance["consecutive_failures"], 0);
+    }
+
+    for name in ["sweep-a", "sweep-b", "sweep-c"] {
+        let _ = server.delete(&format!("/instances/{}", name)).await;
+    }
+}
+
+#[tokio::test]
+async fn test_instances_health_summary() {
+    // Fake health checker lets stub instances reach Running
+    let (server, _temp_dir) = create_test_server_with_config(ManagerConfig {
+        health_check_mode: HealthCheckMode::AlwaysHealthy,
+        ..Default::default()
+    })
+    .await;
+
+    for (name, port) in [("health-a", 8080), ("health-b", 8081)] {
+        let response = server
+            .post("/instances")
+            .json(&json!({
+                "name": name,
+                "model_id": "BAAI/bge-small-en-v1.5",
+                "port": port
+            }))
+            .await;
+        assert_eq!(response.status_code(), 201);
+    }
+
+    // Readiness is checked in the background after create
+    let mut body = serde_json::Value::Null;
+    for _ in 0..50 {
+        body = server.get("/health/instances").await.json();
+        if body["summary"]["running"] == 2 {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    assert_eq!(body["summary"]["running"], 2);
+
+    server.post("/instances/health-b/stop").await;
+
+    let response = server.get("/health/instances").await;
+    assert_eq!(response.status_code(), 200);
+
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["summary"]["total"], 2);
+    assert_eq!(body["summary"]["running"], 1);
+    assert_eq!(body["summary"]["starting"], 0);
+    assert_eq!(body["summary"]["failed"], 0);
+
+    let instances = body["instances"].as_array().unwrap();
+    assert_eq!(instances[0]["name"], "health-a");
+    assert_eq!(instances[0]["healthy"], true);
+    assert_eq!(instances[0]["consecutive_failures"], 0);
+    assert_eq!(instances[1]["name"], "health-b");
+    assert_eq!(instances[1]["status"], "stopped");
+    assert_eq!(instances[1]["healthy"], false);
+}
+
 #[tokio::test]
 async fn test_list_instances_empty() {
     let (server, _temp_dir) = create_test_server().await;
@@ -148,7 +484,7 @@ async fn test_create_instance() {
 async fn test_create_instance_with_invalid_gpu() {
     // Tests that invalid GPU IDs are rejected
     // GPU validation uses nvidia-smi to detect available GPUs
-    // On machines without GPUs, any gpu_id is invalid
+    // Without nvidia-smi the id can't be validated, which is rejected too
     let (server, _temp_dir) = create_test_server().await;
 
     let create_req = json!({
@@ -164,7 +500,43 @@ async fn test_create_instance_with_invalid_gpu() {
     assert_eq!(response.status_code(), 400);
 
     let body: serde_json::Value = response.json();
-    assert!(body["error"].as_str().unwrap().contains("Invalid GPU ID"));
+    let gpu_info = tei_manager::gpu::get_or_init();
+    if gpu_info.detection_error.is_some() {
+        assert_eq!(body["code"], "GPU_DETECTION_UNAVAILABLE");
+    } else {
+        assert!(body["error"].as_str().unwrap().contains("Invalid GPU ID"));
+    }
+}
+
+#[tokio::test]
+async fn test_create_instance_gpu_trusted_without_smi() {
+    // Detection fails here whether or not the host has GPUs
+    let (app, _temp_dir) = create_test_app_with_gpu_info(
+        ManagerConfig {
+            allow_gpu_without_smi: true,
+            ..Default::default()
+        },
+        GpuInfo {
+            detection_error: Some("nvidia-smi not found".to_string()),
+            ..Default::default()
+        },
+    );
+    let server = TestServer::new(app).expect("Failed to create test server");
+
+    let response = server
+        .post("/instances")
+        .json(&json!({
+            "name": "trusted-gpu",
+            "model_id": "BAAI/bge-small-en-v1.5",
+            "port": 8081,
+            "gpu_id": 3
+        }))
+        .await;
+
+    assert_eq!(response.status_code(), 201);
+    let response = server.get("/instances/trusted-gpu/command").await;
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["cuda_visible_devices"], "3");
 }
 
 #[tokio::test]
@@ -186,6 +558,156 @@ async fn test_create_instance_with_prometheus_port() {
     assert_eq!(instance["prometheus_port"], 9100);
 }
 
+#[tokio::test]
+async fn test_create_instance_depends_on() {
+    let (server, _temp_dir) = create_test_server().await;
+
+    let response = server
+        .post("/instances")
+        .json(&json!({
+            "name": "reranker",
+            "model_id": "BAAI/bge-reranker-base",
+            "port": 8081,
+            "depends_on": ["embedder"]
+        }))
+        .await;
+    assert_eq!(response.status_code(), 400);
+    let body: serde_json::Value = response.json();
+    assert!(body["error"].as_str().unwrap().contains("unknown instance"));
+
+    let response = server
+        .post("/instances")
+        .json(&json!({
+            "name": "embedder",
+            "model_id": "BAAI/bge-small-en-v1.5",
+            "port": 8080
+        }))
+        .await;
+    assert_eq!(response.status_code(), 201);
+
+    let response = server
+        .post("/instances")
+        .json(&json!({
+            "name": "reranker",
+            "model_id": "BAAI/bge-reranker-base",
+            "port": 8081,
+            "depends_on": ["embedder"]
+        }))
+        .await;
+    assert_eq!(response.status_code(), 201);
+    let instance: serde_json::Value = response.json();
+    assert_eq!(instance["depends_on"], json!(["embedder"]));
+}
+
+#[tokio::test]
+async fn test_export_import_round_trip() {
+    let config = ManagerConfig {
+        max_instances: Some(10),
+        health_check_mode: HealthCheckMode::AlwaysHealthy,
+        ..Default::default()
+    };
+    let (source, _source_dir) = create_test_server_with_config(config.clone()).await;
+    for body in [
+        json!({"name": "embedder", "model_id": "BAAI/bge-small-en-v1.5", "port": 8080, "revision": "main"}),
+        json!({
+            "name": "reranker",
+            "model_id": "BAAI/bge-reranker-base",
+            "port": 8081,
+            "depends_on": ["embedder"],
+            "env": {"EMBED_MODE": "rerank", "HF_TOKEN": "${HOME}"}
+        }),
+    ] {
+        let response = source.post("/instances").json(&body).await;
+        assert_eq!(response.status_code(), 201);
+    }
+
+    let response = source.get("/instances/export").await;
+    assert_eq!(response.status_code(), 200);
+    let export: serde_json::Value = response.json();
+    assert_eq!(export["instances"][0]["name"], "embedder");
+    assert_eq!(export["instances"][1]["name"], "reranker");
+    // References are not secrets and survive the export
+    assert_eq!(export["instances"][1]["env"]["HF_TOKEN"], "${HOME}");
+
+    let (target, _target_dir) = create_test_server_with_config(config).await;
+    let response = target.post("/instances/import").json(&export).await;
+    assert_eq!(response.status_code(), 201);
+    let imported: Vec<serde_json::Value> = response.json();
+    assert_eq!(imported.len(), 2);
+
+    let round_trip: serde_json::Value = target.get("/instances/export").await.json();
+    assert_eq!(round_trip, export);
+
+    // Existing names conflict unless overwriting
+    let response = target.post("/instances/import").json(&export).await;
+    assert_eq!(response.status_code(), 409);
+    let response = target
+        .post("/instances/import")
+        .add_query_param("overwrite", true)
+        .json(&export)
+        .await;
+    assert_eq!(response.status_code(), 201);
+    let round_trip: serde_json::Value = target.get("/instances/export").await.json();
+    assert_eq!(round_trip, export);
+}
+
+#[tokio::test]
+async fn test_import_rejected_without_changes() {
+    let (server, _temp_dir) = create_test_server_with_config(ManagerConfig {
+        max_instances: Some(2),
+        ..Default::default()
+    })
+    .await;
+    let response = server
+        .post("/instances")
+        .json(&json!({"name": "existing", "model_id": "BAAI/bge-small-en-v1.5", "port": 8080}))
+        .await;
+    assert_eq!(response.status_code(), 201);
+
+    // The second instance takes a port that is already in use
+    let export = json!({"instances": [
+        {"name": "first", "model_id": "BAAI/bge-small-en-v1.5", "port": 8090},
+        {"name": "second", "model_id": "BAAI/bge-small-en-v1.5", "port": 8080},
+    ]});
+    let response = server.post("/instances/import").json(&export).await;
+    assert_eq!(response.status_code(), 409);
+
+    let export = json!({"instances": [
+        {"name": "orphan", "model_id": "BAAI/bge-small-en-v1.5", "port": 8091, "depends_on": ["missing"]},
+    ]});
+    let response = server.post("/instances/import").json(&export).await;
+    assert_eq!(response.status_code(), 400);
+
+    // Masked secrets from another manager's /config are not imported as values
+    let export = json!({"instances": [
+        {"name": "masked", "model_id": "BAAI/bge-small-en-v1.5", "port": 8091,
+         "env": {"HF_TOKEN": "***REDACTED***"}},
+    ]});
+    let response = server.post("/instances/import").json(&export).await;
+    assert_eq!(response.status_code(), 400);
+    let body: serde_json::Value = response.json();
+    assert!(body["error"].as_str().unwrap().contains("HF_TOKEN"));
+
+    // Passes the checks, but the registry is full after the first: rolled back
+    let export = json!({"instances": [
+        {"name": "first", "model_id": "BAAI/bge-small-en-v1.5", "port": 8090},
+        {"name": "second", "model_id": "BAAI/bge-small-en-v1.5", "port": 8091},
+    ]});
+    let response = server.post("/instances/import").json(&export).await;
+    assert_eq!(response.status_code(), 400);
+    let body: serde_json::Value = response.json();
+    assert!(
+        body["error"]
+            .as_str()
+            .unwrap()
+            .contains("Maximum instance count")
+    );
+
+    let instances: Vec<serde_json::Value> = server.get("/instances").await.json();
+    assert_eq!(instances.len(), 1);
+    assert_eq!(instances[0]["name"], "existing");
+}
+
 #[tokio::test]
 async fn test_get_instance() {
     let (server, _temp_dir) = create_test_server().await;
@@ -289,7 +811,12 @@ async fn test_start_instance() {
 
 #[tokio::test]
 async fn test_restart_instance() {
-    let (server, _temp_dir) = create_test_server().await;
+    // Stub binary plus fake health checker acts as a mock router that becomes ready
+    let (server, _temp_dir) = create_test_server_with_config(ManagerConfig {
+        health_check_mode: HealthCheckMode::AlwaysHealthy,
+        ..Default::default()
+    })
+    .await;
 
     // Create instance
     let create_req = json!({
@@ -301,12 +828,40 @@ async fn test_restart_instance() {
     server.post("/instances").json(&create_req).await;
 
     // Restart instance
+    let started = std::time::Instant::now();
     let response = server.post("/instances/restart-test/restart").await;
 
     assert_eq!(response.status_code(), 200);
+    // The old process gets the same pause as in a health-driven restart
+    assert!(started.elapsed() >= RESTART_DELAY);
 
     let instance: serde_json::Value = response.json();
     assert_eq!(instance["name"], "restart-test");
+    assert_eq!(instance["status"], "running");
+    assert_eq!(instance["manual_restart_count"], 1);
+    // Manual restarts are not counted as health-driven restarts
+    assert_eq!(instance["restarts"], 0);
+}
+
+#[tokio::test]
+async fn test_restart_instance_not_ready_times_out() {
+    // Default gRPC checker never sees the stub binary as ready
+    let (server, _temp_dir) = create_test_server().await;
+
+    let create_req = json!({
+        "name": "restart-timeout",
+        "model_id": "BAAI/bge-small-en-v1.5",
+        "port": 8081,
+        "startup_timeout_secs": 1
+    });
+
+    server.post("/instances").json(&create_req).await;
+
+    let response = server.post("/instances/restart-timeout/restart").await;
+
+    assert_eq!(response.status_code(), 504);
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["code"], "TIMEOUT");
 }
 
 #[tokio::test]
@@ -413,8 +968,13 @@ async fn test_max_instances_limit() {
 
     let model_registry = Arc::new(ModelRegistry::new());
     let model_loader = Arc::new(ModelLoader::new());
+    let preload_tracker = Arc::new(PreloadTracker::new());
 
     let state = AppState {
+        backend_pool: BackendPool::new(registry.clone()),
+        audit_sink: None,
+        access_log: None,
+        embedding_dimensions: Arc::new(tei_manager::models::CachedEmbeddingDimensions::default()),
         registry,
         state_manager,
         prometheus_handle: get_metrics_handle(),
@@ -422,6 +982,8 @@ async fn test_max_instances_limit() {
         require_cert_headers: false,
         model_registry,
         model_loader,
+        preload_tracker,
+        config: Arc::new(config),
     };
 
     let app = create_router(state);
@@ -498,7 +1060,7 @@ async fn test_state_persistence() {
         port: 9090,
         max_batch_tokens: 1024,
         max_concurrent_requests: 10,
-        pooling: Some("mean".to_string()),
+        pooling: Some(tei_manager::config::Pooling::Mean),
         gpu_id: Some(1),
         prometheus_port: Some(9200),
         extra_args: vec!["--arg1".to_string()],
@@ -524,7 +1086,10 @@ async fn test_state_persistence() {
     assert_eq!(loaded_state.instances[0].name, "persist-test");
     assert_eq!(loaded_state.instances[0].model_id, "test/model");
     assert_eq!(loaded_state.instances[0].port, 9090);
-    assert_eq!(loaded_state.instances[0].pooling, Some("mean".to_string()));
+    assert_eq!(
+        loaded_state.instances[0].pooling,
+        Some(tei_manager::config::Pooling::Mean)
+    );
     assert_eq!(loaded_state.instances[0].gpu_id, Some(1));
     assert_eq!(loaded_state.instances[0].prometheus_port, Some(9200));
 }
@@ -1132,11 +1697,11 @@ async fn test_get_logs_instance_not_found() {
 
 #[tokio::test]
 async fn test_get_logs_with_log_file() {
-    let (server, _temp_dir) = create_test_server().await;
+    let (server, temp_dir) = create_test_server().await;
 
-    // Use the fallback log directory that the handler checks
-    let log_dir = std::path::Path::new("/tmp/tei-manager/logs");
-    std::fs::create_dir_all(log_dir).unwrap();
+    // The handler reads from the configured log_dir (a temp dir in tests)
+    let log_dir = temp_dir.path().join("logs");
+    std::fs::create_dir_all(&log_dir).unwrap();
 
     let log_content = "line 1\nline 2\nline 3\nline 4\nline 5\n";
     std::fs::write(log_dir.join("test-logs.log"), log_content).unwrap();
@@ -1149,18 +1714,14 @@ async fn test_get_logs_with_log_file() {
     assert_eq!(logs["total_lines"], 5);
     assert_eq!(logs["start"], 0);
     assert_eq!(logs["end"], 5);
-
-    // Clean up
-    let _ = std::fs::remove_file(log_dir.join("test-logs.log"));
 }
 
 #[tokio::test]
 async fn test_get_logs_with_slicing() {
-    let (server, _temp_dir) = create_test_server().await;
+    let (server, temp_dir) = create_test_server().await;
 
-    // Use the fallback log directory
-    let log_dir = std::path::Path::new("/tmp/tei-manager/logs");
-    std::fs::create_dir_all(log_dir).unwrap();
+    let log_dir = temp_dir.path().join("logs");
+    std::fs::create_dir_all(&log_dir).unwrap();
 
     let log_content = "line 1\nline 2\nline 3\nline 4\nline 5\n";
     std::fs::write(log_dir.join("sliced-logs.log"), log_content).unwrap();
@@ -1184,18 +1745,14 @@ async fn test_get_logs_with_slicing() {
     assert_eq!(logs["lines"].as_array().unwrap().len(), 2);
     assert_eq!(logs["start"], 3);
     assert_eq!(logs["end"], 5);
-
-    // Clean up
-    let _ = std::fs::remove_file(log_dir.join("sliced-logs.log"));
 }
 
 #[tokio::test]
 async fn test_get_logs_empty_slice() {
-    let (server, _temp_dir) = create_test_server().await;
+    let (server, temp_dir) = create_test_server().await;
 
-    // Use the fallback log directory
-    let log_dir = std::path::Path::new("/tmp/tei-manager/logs");
-    std::fs::create_dir_all(log_dir).unwrap();
+    let log_dir = temp_dir.path().join("logs");
+    std::fs::create_dir_all(&log_dir).unwrap();
 
     let log_content = "line 1\nline 2\nline 3\n";
     std::fs::write(log_dir.join("empty-slice.log"), log_content).unwrap();
@@ -1208,9 +1765,94 @@ async fn test_get_logs_empty_slice() {
 
     let logs: serde_json::Value = response.json();
     assert_eq!(logs["lines"].as_array().unwrap().len(), 0);
+}
 
-    // Clean up
-    let _ = std::fs::remove_file(log_dir.join("empty-slice.log"));
+#[tokio::test]
+async fn test_clear_logs_instance_not_found() {
+    let (server, _temp_dir) = create_test_server().await;
+
+    let response = server.post("/instances/nonexistent/logs/clear").await;
+    assert_eq!(response.status_code(), 404);
+}
+
+#[tokio::test]
+async fn test_clear_logs_truncates_in_place() {
+    use std::io::Write;
+
+    let (server, temp_dir) = create_test_server().await;
+
+    let response = server
+        .post("/instances")
+        .json(&json!({
+            "name": "clear-logs",
+            "model_id": "BAAI/bge-small-en-v1.5",
+            "port": 8391
+        }))
+        .await;
+    assert_eq!(response.status_code(), 201);
+
+    // Let the stub process exit so it doesn't write after we do
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+
+    // Created at spawn in the configured log_dir
+    let log_path = temp_dir.path().join("logs").join("clear-logs.log");
+
+    // Hold an append-mode handle like the child process does
+    let mut writer = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&log_path)
+        .unwrap();
+    writer.write_all(b"line 1\nline 2\nline 3\n").unwrap();
+    assert!(std::fs::metadata(&log_path).unwrap().len() > 0);
+
+    let response = server.post("/instances/clear-logs/logs/clear").await;
+    assert_eq!(response.status_code(), 200);
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["instance"], "clear-logs");
+    assert_eq!(body["size_bytes"], 0);
+    assert_eq!(std::fs::read(&log_path).unwrap().len(), 0);
+
+    // The open descriptor keeps working and writes land at the start of the file
+    writer.write_all(b"after clear\n").unwrap();
+    assert_eq!(std::fs::read_to_string(&log_path).unwrap(), "after clear\n");
+
+    let _ = server.delete("/instances/clear-logs").await;
+}
+
+#[tokio::test]
+async fn test_log_dir_used_for_spawn_and_logs_endpoint() {
+    let log_dir = TempDir::new().unwrap();
+    let config = ManagerConfig {
+        log_dir: log_dir.path().join("instance-logs"),
+        // Rejects TEI's arguments, so there is output to capture
+        tei_binary_path: "/bin/sleep".to_string(),
+        ..Default::default()
+    };
+    let (server, _temp_dir) = create_test_server_with_config(config).await;
+
+    let response = server
+        .post("/instances")
+        .json(&json!({
+            "name": "custom-log-dir",
+            "model_id": "BAAI/bge-small-en-v1.5",
+            "port": 8392
+        }))
+        .await;
+    assert_eq!(response.status_code(), 201);
+
+    // The usage error lands in the log
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    let log_path = log_dir.path().join("instance-logs/custom-log-dir.log");
+    let written = std::fs::read_to_string(&log_path).unwrap();
+    assert!(!written.is_empty());
+
+    let response = server.get("/instances/custom-log-dir/logs").await;
+    assert_eq!(response.status_code(), 200);
+    let logs: serde_json::Value = response.json();
+    assert_eq!(logs["total_lines"], written.lines().count());
+
+    let _ = server.delete("/instances/custom-log-dir").await;
 }
 
 // ========================================
@@ -1301,20 +1943,167 @@ async fn test_list_instances_after_operations() {
     assert_eq!(instances[0]["name"], "list-test-1");
 }
 
-// ========================================
-// Model Management API Tests
-// ========================================
+/// Serve one canned HTTP response per connection on a random localhost port
+async fn spawn_mock_metrics_server(body: &'static str) -> u16 {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let mut request = [0u8; 1024];
+            let _ = stream.read(&mut request).await;
+            let response = format!(
+                "HTTP/1.0 200 OK\r\nContent-Type: text/plain\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(response.as_bytes()).await;
+        }
+    });
+    port
+}
 
 #[tokio::test]
-async fn test_list_models_empty() {
+async fn test_instance_metrics_proxies_single_instance() {
+    let metrics_port = spawn_mock_metrics_server(
+        "# TYPE te_request_count counter\nte_request_count{method=\"embed\"} 7\nte_queue_size 0\n",
+    )
+    .await;
     let (server, _temp_dir) = create_test_server().await;
 
-    let response = server.get("/models").await;
+    let response = server
+        .post("/instances")
+        .json(&json!({
+            "name": "scraped",
+            "model_id": "BAAI/bge-small-en-v1.5",
+            "port": 18881,
+            "prometheus_port": metrics_port
+        }))
+        .await;
+    assert_eq!(response.status_code(), 201);
+    let response = server
+        .post("/instances")
+        .json(&json!({
+            "name": "no-metrics",
+            "model_id": "BAAI/bge-small-en-v1.5",
+            "port": 18882,
+            "prometheus_port": 0
+        }))
+        .await;
+    assert_eq!(response.status_code(), 201);
+
+    let response = server.get("/instances/scraped/metrics").await;
     assert_eq!(response.status_code(), 200);
+    assert!(
+        response.headers()["content-type"]
+            .to_str()
+            .unwrap()
+            .starts_with("text/plain")
+    );
+    assert_eq!(
+        response.text(),
+        "# TYPE te_request_count counter\n\
+         te_request_count{instance=\"scraped\",method=\"embed\"} 7\n\
+         te_queue_size{instance=\"scraped\"} 0\n"
+    );
 
-    let models: Vec<serde_json::Value> = response.json();
-    // May have discovered models from HF cache, but should be empty list if no cache
-    assert!(models.is_empty() || models.iter().all(|m| m.get("model_id").is_some()));
+    let response = server.get("/instances/no-metrics/metrics").await;
+    assert_eq!(response.status_code(), 503);
+    let body: serde_json::Value = response.json();
+    assert!(body["error"].as_str().unwrap().contains("disabled"));
+
+    let response = server.get("/instances/unknown/metrics").await;
+    assert_eq!(response.status_code(), 404);
+
+    server.delete("/instances/scraped").await;
+    server.delete("/instances/no-metrics").await;
+}
+
+// ========================================
+// Model Management API Tests
+// ========================================
+
+#[tokio::test]
+async fn test_list_models_empty() {
+    let (server, _temp_dir) = create_test_server().await;
+
+    let response = server.get("/models").await;
+    assert_eq!(response.status_code(), 200);
+
+    let models: Vec<serde_json::Value> = response.json();
+    // May have discovered models from HF cache, but should be empty list if no cache
+    assert!(models.is_empty() || models.iter().all(|m| m.get("model_id").is_some()));
+}
+
+#[tokio::test]
+async fn test_list_models_merges_config_cache_and_instances() {
+    let config = ManagerConfig {
+        models: Some(vec![
+            "BAAI/bge-small-en-v1.5".to_string(),
+            "sentence-transformers/all-MiniLM-L6-v2".to_string(),
+        ]),
+        ..Default::default()
+    };
+    let (server, temp_dir) = create_test_server_with_config(config).await;
+
+    // Fake HF cache entry for one configured model and one unconfigured model
+    for (cache_name, bytes) in [
+        ("models--BAAI--bge-small-en-v1.5", 11),
+        ("models--org--cached-only", 3),
+    ] {
+        let snapshot = temp_dir
+            .path()
+            .join("hf-cache")
+            .join(cache_name)
+            .join("snapshots/abc123");
+        std::fs::create_dir_all(&snapshot).unwrap();
+        std::fs::write(snapshot.join("config.json"), "x".repeat(bytes)).unwrap();
+    }
+
+    let create_req = json!({
+        "name": "models-user",
+        "model_id": "BAAI/bge-small-en-v1.5",
+        "port": 18876
+    });
+    let response = server.post("/instances").json(&create_req).await;
+    assert_eq!(response.status_code(), 201);
+
+    let response = server.get("/models").await;
+    assert_eq!(response.status_code(), 200);
+    let models: Vec<serde_json::Value> = response.json();
+
+    let ids: Vec<_> = models
+        .iter()
+        .map(|m| m["model_id"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        ids,
+        vec![
+            "BAAI/bge-small-en-v1.5",
+            "org/cached-only",
+            "sentence-transformers/all-MiniLM-L6-v2"
+        ]
+    );
+
+    let bge = &models[0];
+    assert_eq!(bge["configured"], true);
+    assert_eq!(bge["downloaded"], true);
+    assert_eq!(bge["cache_size_bytes"], 11);
+    assert_eq!(bge["instances"], json!(["models-user"]));
+
+    let cached_only = &models[1];
+    assert_eq!(cached_only["configured"], false);
+    assert_eq!(cached_only["downloaded"], true);
+    assert_eq!(cached_only["instances"], json!([]));
+
+    let minilm = &models[2];
+    assert_eq!(minilm["configured"], true);
+    assert_eq!(minilm["downloaded"], false);
+    assert!(minilm.get("cache_size_bytes").is_none());
+    assert_eq!(minilm["instances"], json!([]));
+
+    server.delete("/instances/models-user").await;
 }
 
 #[tokio::test]
@@ -1416,3 +2205,2416 @@ async fn test_list_models_after_add() {
     assert!(model_ids.contains(&"BAAI/bge-small-en-v1.5"));
     assert!(model_ids.contains(&"sentence-transformers/all-MiniLM-L6-v2"));
 }
+
+#[tokio::test]
+async fn test_preload_models_empty_rejected() {
+    let (server, _temp_dir) = create_test_server().await;
+
+    let response = server
+        .post("/models/preload")
+        .json(&json!({ "model_ids": [] }))
+        .await;
+
+    assert_eq!(response.status_code(), 400);
+}
+
+#[tokio::test]
+async fn test_get_preload_job_not_found() {
+    let (server, _temp_dir) = create_test_server().await;
+
+    let response = server.get("/models/preload/preload-999").await;
+
+    assert_eq!(response.status_code(), 404);
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["code"], "PRELOAD_JOB_NOT_FOUND");
+}
+
+/// Downloader with a fixed cache that fails for one model ID
+struct StubDownloader {
+    cached: std::sync::Mutex<std::collections::HashSet<String>>,
+    failing: &'static str,
+    downloads: std::sync::Mutex<Vec<String>>,
+}
+
+impl StubDownloader {
+    fn new(cached: &[&str], failing: &'static str) -> Self {
+        Self {
+            cached: std::sync::Mutex::new(cached.iter().map(|s| s.to_string()).collect()),
+            failing,
+            downloads: std::sync::Mutex::new(Vec::new()),
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl ModelDownloader for StubDownloader {
+    fn is_cached(&self, model_id: &str, revision: Option<&str>) -> bool {
+        revision.is_none() && self.cached.lock().unwrap().contains(model_id)
+    }
+
+    async fn download(
+        &self,
+        model_id: &str,
+        revision: Option<&str>,
+    ) -> Result<std::path::PathBuf, String> {
+        let key = match revision {
+            Some(revision) => format!("{}@{}", model_id, revision),
+            None => model_id.to_string(),
+        };
+        self.downloads.lock().unwrap().push(key);
+        if model_id == self.failing {
+            return Err("404 Not Found".to_string());
+        }
+        self.cached.lock().unwrap().insert(model_id.to_string());
+        Ok(std::path::PathBuf::from("/tmp/stub-cache").join(model_id))
+    }
+}
+
+#[tokio::test]
+async fn test_create_instance_download_if_missing() {
+    let downloader = Arc::new(StubDownloader::new(&["org/cached"], "org/broken"));
+    let (app, _temp_dir) = create_test_app_with_preload(
+        ManagerConfig {
+            max_instances: Some(10),
+            ..Default::default()
+        },
+        Arc::new(PreloadTracker::new_with_downloader(downloader.clone())),
+    );
+    let server = TestServer::new(app).unwrap();
+
+    // Cached model: no download
+    let response = server
+        .post("/instances")
+        .json(&json!({
+            "name": "from-cache",
+            "model_id": "org/cached",
+            "port": 8095,
+            "download_if_missing": true
+        }))
+        .await;
+    assert_eq!(response.status_code(), 201);
+    assert!(downloader.downloads.lock().unwrap().is_empty());
+
+    // Missing model: downloaded before the instance is created
+    let response = server
+        .post("/instances")
+        .json(&json!({
+            "name": "downloaded",
+            "model_id": "org/fresh",
+            "port": 8096,
+            "download_if_missing": true
+        }))
+        .await;
+    assert_eq!(response.status_code(), 201);
+    assert_eq!(*downloader.downloads.lock().unwrap(), vec!["org/fresh"]);
+
+    // The requested revision is the one downloaded
+    let response = server
+        .post("/instances")
+        .json(&json!({
+            "name": "pinned",
+            "model_id": "org/fresh",
+            "revision": "v2",
+            "port": 8099,
+            "download_if_missing": true
+        }))
+        .await;
+    assert_eq!(response.status_code(), 201);
+    assert_eq!(
+        downloader.downloads.lock().unwrap().last().unwrap(),
+        "org/fresh@v2"
+    );
+
+    // A taken name or port is rejected before anything is downloaded
+    for (name, port) in [("downloaded", 8100), ("port-clash", 8096)] {
+        let response = server
+            .post("/instances")
+            .json(&json!({
+                "name": name,
+                "model_id": "org/never-fetched",
+                "port": port,
+                "download_if_missing": true
+            }))
+            .await;
+        assert_eq!(response.status_code(), 400);
+    }
+    assert_eq!(downloader.downloads.lock().unwrap().len(), 2);
+
+    // Failed download: clear error and no instance left behind
+    let response = server
+        .post("/instances")
+        .json(&json!({
+            "name": "broken",
+            "model_id": "org/broken",
+            "port": 8097,
+            "download_if_missing": true
+        }))
+        .await;
+    assert_eq!(response.status_code(), 500);
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["code"], "MODEL_DOWNLOAD_FAILED");
+    assert!(body["error"].as_str().unwrap().contains("404 Not Found"));
+    assert_eq!(server.get("/instances/broken").await.status_code(), 404);
+
+    // Without the flag nothing is downloaded
+    let response = server
+        .post("/instances")
+        .json(&json!({
+            "name": "no-download",
+            "model_id": "org/other",
+            "port": 8098
+        }))
+        .await;
+    assert_eq!(response.status_code(), 201);
+    assert_eq!(downloader.downloads.lock().unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_get_config_redacts_secrets() {
+    let config = ManagerConfig {
+        api_port: 9555,
+        instances: vec![InstanceConfig {
+            name: "private-model".to_string(),
+            model_id: "org/private".to_string(),
+            port: 8085,
+            extra_args: vec!["--hf-api-token".to_string(), "hf_supersecret".to_string()],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let (server, _temp_dir) = create_test_server_with_config(config).await;
+
+    let response = server.get("/config").await;
+    assert_eq!(response.status_code(), 200);
+
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["api_port"], 9555);
+    assert_eq!(body["instances"][0]["extra_args"][0], "--hf-api-token");
+    assert_eq!(body["instances"][0]["extra_args"][1], "***REDACTED***");
+    assert!(!response.text().contains("hf_supersecret"));
+}
+
+#[tokio::test]
+async fn test_debug_registry_disabled_by_default() {
+    let (server, _temp_dir) = create_test_server().await;
+
+    let response = server.get("/debug/registry").await;
+    assert_eq!(response.status_code(), 404);
+}
+
+#[tokio::test]
+async fn test_debug_registry_dumps_allocator_state() {
+    let config = ManagerConfig {
+        debug_endpoints: true,
+        instance_port_start: 18500,
+        instance_port_end: 18600,
+        ..Default::default()
+    };
+    let (server, _temp_dir) = create_test_server_with_config(config).await;
+
+    let response = server
+        .post("/instances")
+        .json(&json!({
+            "name": "debug-test",
+            "model_id": "BAAI/bge-small-en-v1.5",
+            "port": 18550,
+            "prometheus_port": 19550,
+            "extra_args": ["--hf-api-token", "hf_supersecret"]
+        }))
+        .await;
+    assert_eq!(response.status_code(), 201);
+
+    let response = server.get("/debug/registry").await;
+    assert_eq!(response.status_code(), 200);
+
+    let body: serde_json::Value = response.json();
+    let allocator = &body["allocator"];
+    assert_eq!(allocator["instance_port_range"], json!([18500, 18600]));
+    assert_eq!(allocator["auto_allocation_enabled"], true);
+    assert_eq!(allocator["next_instance_port"], 18500);
+    assert_eq!(allocator["allocated_instance_ports"], json!([18550]));
+    assert_eq!(allocator["allocated_prometheus_ports"], json!([19550]));
+    assert_eq!(allocator["unallocated_instance_ports"], 99);
+
+    let instance = &body["instances"][0];
+    assert_eq!(instance["config"]["name"], "debug-test");
+    assert!(instance["stats"].is_object());
+    assert!(!response.text().contains("hf_supersecret"));
+}
+
+#[tokio::test]
+async fn test_ports_track_created_and_deleted_instances() {
+    let (server, _temp_dir) = create_test_server_with_config(ManagerConfig {
+        max_instances: Some(10),
+        instance_port_start: 18700,
+        instance_port_end: 18710,
+        ..Default::default()
+    })
+    .await;
+
+    let body: serde_json::Value = server.get("/ports").await.json();
+    assert_eq!(body["range"], json!([18700, 18710]));
+    assert_eq!(body["auto_allocation_enabled"], true);
+    assert_eq!(body["allocated"], json!([]));
+    assert_eq!(body["free"], 10);
+
+    for (name, port, prometheus_port) in [("first", 18701, 18702), ("second", 18705, 9300)] {
+        let response = server
+            .post("/instances")
+            .json(&json!({
+                "name": name,
+                "model_id": "BAAI/bge-small-en-v1.5",
+                "port": port,
+                "prometheus_port": prometheus_port
+            }))
+            .await;
+        assert_eq!(response.status_code(), 201);
+    }
+
+    // The Prometheus port inside the range is taken too; the one outside doesn't count
+    let body: serde_json::Value = server.get("/ports").await.json();
+    assert_eq!(
+        body["allocated"],
+        json!([
+            {"port": 9300, "instance": "second", "kind": "prometheus"},
+            {"port": 18701, "instance": "first", "kind": "instance"},
+            {"port": 18702, "instance": "first", "kind": "prometheus"},
+            {"port": 18705, "instance": "second", "kind": "instance"},
+        ])
+    );
+    assert_eq!(body["free"], 7);
+
+    server.delete("/instances/first").await;
+    let body: serde_json::Value = server.get("/ports").await.json();
+    assert_eq!(
+        body["allocated"],
+        json!([
+            {"port": 9300, "instance": "second", "kind": "prometheus"},
+            {"port": 18705, "instance": "second", "kind": "instance"},
+        ])
+    );
+    assert_eq!(body["free"], 9);
+}
+
+/// Minimal Embed backend: the embedding is `[text length]`, and shorter
+/// texts respond later so completion order differs from input order
+#[derive(Default)]
+struct MockEmbedBackend {
+    /// Reject requests that did not arrive gzip-compressed
+    require_gzip: bool,
+    /// Metadata of every embed request received
+    seen_metadata: Arc<std::sync::Mutex<Vec<tonic::metadata::MetadataMap>>>,
+    /// `truncate` flag of every unary embed request received
+    seen_truncate: Arc<std::sync::Mutex<Vec<bool>>>,
+    /// Answer unary embeds with zeros of this length instead of `[text length]`
+    /// (repeated `dimensions` times when the request sets it)
+    dimension: Option<Arc<std::sync::atomic::AtomicUsize>>,
+    /// Answer embed streams in reverse request order, once the request stream ends
+    reverse_stream: bool,
+    /// Leave the last request of every embed stream unanswered
+    drop_last_stream_response: bool,
+    /// Unary embeds currently running and the most seen at once
+    unary_in_flight: Arc<(
+        std::sync::atomic::AtomicUsize,
+        std::sync::atomic::AtomicUsize,
+    )>,
+    /// Requests received on each embed stream, in call order
+    stream_requests: Arc<std::sync::Mutex<Vec<usize>>>,
+}
+
+#[tonic::async_trait]
+impl tei::embed_server::Embed for MockEmbedBackend {
+    async fn embed(
+        &self,
+        request: tonic::Request<tei::EmbedRequest>,
+    ) -> Result<tonic::Response<tei::EmbedResponse>, tonic::Status> {
+        if self.require_gzip
+            && request
+                .metadata()
+                .get("grpc-encoding")
+                .is_none_or(|encoding| encoding != "gzip")
+        {
+            return Err(tonic::Status::failed_precondition("request not compressed"));
+        }
+        self.seen_metadata
+            .lock()
+            .unwrap()
+            .push(request.metadata().clone());
+        if let Some(dimension) = &self.dimension {
+            let dimension = dimension.load(std::sync::atomic::Ordering::SeqCst);
+            return Ok(tonic::Response::new(tei::EmbedResponse {
+                embeddings: vec![0.0; dimension],
+                metadata: None,
+            }));
+        }
+        let request = request.into_inner();
+        self.seen_truncate.lock().unwrap().push(request.truncate);
+        let len = request.inputs.len();
+        let (current, peak) = &*self.unary_in_flight;
+        let running = current.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+        peak.fetch_max(running, std::sync::atomic::Ordering::SeqCst);
+        tokio::time::sleep(std::time::Duration::from_millis(
+            100u64.saturating_sub(len as u64 * 20),
+        ))
+        .await;
+        current.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
+        Ok(tonic::Response::new(tei::EmbedResponse {
+            embeddings: vec![len as f32; request.dimensions.unwrap_or(1) as usize],
+            metadata: None,
+        }))
+    }
+
+    type EmbedStreamStream = MockStream<tei::EmbedResponse>;
+
+    async fn embed_stream(
+        &self,
+        request: tonic::Request<tonic::Streaming<tei::EmbedRequest>>,
+    ) -> Result<tonic::Response<Self::EmbedStreamStream>, tonic::Status> {
+        let stream_requests = self.stream_requests.clone();
+        let call = {
+            let mut calls = stream_requests.lock().unwrap();
+            calls.push(0);
+            calls.len() - 1
+        };
+        // Stays open until the caller closes its request stream
+        let responses = request.into_inner().map(move |result| {
+            result.map(|req| {
+                stream_requests.lock().unwrap()[call] += 1;
+                tei::EmbedResponse {
+                    embeddings: vec![req.inputs.len() as f32; req.dimensions.unwrap_or(1) as usize],
+                    metadata: None,
+                }
+            })
+        });
+        if self.drop_last_stream_response {
+            let mut collected: Vec<_> = responses.collect().await;
+            collected.pop();
+            return Ok(tonic::Response::new(Box::pin(tokio_stream::iter(
+                collected,
+            ))));
+        }
+        if self.reverse_stream {
+            let mut collected: Vec<_> = responses.collect().await;
+            collected.reverse();
+            return Ok(tonic::Response::new(Box::pin(tokio_stream::iter(
+                collected,
+            ))));
+        }
+        Ok(tonic::Response::new(Box::pin(responses)))
+    }
+
+    async fn embed_sparse(
+        &self,
+        _request: tonic::Request<tei::EmbedSparseRequest>,
+    ) -> Result<tonic::Response<tei::EmbedSparseResponse>, tonic::Status> {
+        Err(tonic::Status::unimplemented("mock"))
+    }
+
+    type EmbedSparseStreamStream = MockStream<tei::EmbedSparseResponse>;
+
+    async fn embed_sparse_stream(
+        &self,
+        _request: tonic::Request<tonic::Streaming<tei::EmbedSparseRequest>>,
+    ) -> Result<tonic::Response<Self::EmbedSparseStreamStream>, tonic::Status> {
+        Err(tonic::Status::unimplemented("mock"))
+    }
+
+    async fn embed_all(
+        &self,
+        _request: tonic::Request<tei::EmbedAllRequest>,
+    ) -> Result<tonic::Response<tei::EmbedAllResponse>, tonic::Status> {
+        Err(tonic::Status::unimplemented("mock"))
+    }
+
+    type EmbedAllStreamStream = MockStream<tei::EmbedAllResponse>;
+
+    async fn embed_all_stream(
+        &self,
+        _request: tonic::Request<tonic::Streaming<tei::EmbedAllRequest>>,
+    ) -> Result<tonic::Response<Self::EmbedAllStreamStream>, tonic::Status> {
+        Err(tonic::Status::unimplemented("mock"))
+    }
+}
+
+type MockStream<T> =
+    std::pin::Pin<Box<dyn tokio_stream::Stream<Item = Result<T, tonic::Status>> + Send + 'static>>;
+
+/// Serve the mock Embed backend on an ephemeral local port
+async fn start_mock_embed_backend() -> u16 {
+    serve_mock_embed_backend(tei::embed_server::EmbedServer::new(
+        MockEmbedBackend::default(),
+    ))
+    .await
+}
+
+/// Serve a mock Embed backend that only accepts and returns gzip-compressed messages
+async fn start_gzip_mock_embed_backend() -> u16 {
+    use tonic::codec::CompressionEncoding;
+
+    serve_mock_embed_backend(
+        tei::embed_server::EmbedServer::new(MockEmbedBackend {
+            require_gzip: true,
+            ..Default::default()
+        })
+        .accept_compressed(CompressionEncoding::Gzip)
+        .send_compressed(CompressionEncoding::Gzip),
+    )
+    .await
+}
+
+async fn serve_mock_embed_backend(
+    service: tei::embed_server::EmbedServer<MockEmbedBackend>,
+) -> u16 {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(
+        tonic::transport::Server::builder()
+            .add_service(service)
+            .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener)),
+    );
+    port
+}
+
+/// Register a running instance `name` backed by the mock on `backend_port`
+async fn registry_with_mock_backend(name: &str, backend_port: u16) -> Arc<Registry> {
+    use tei_manager::instance::InstanceStatus;
+
+    let registry = Arc::new(Registry::new(None, STUB_BINARY.to_string(), 8080, 8180));
+    let instance = registry
+        .add(InstanceConfig {
+            name: name.to_string(),
+            model_id: "BAAI/bge-small-en-v1.5".to_string(),
+            port: backend_port,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    *instance.status.write().await = InstanceStatus::Running;
+    registry
+}
+
+/// Start the gRPC multiplexer on a free port and return a connected channel
+async fn start_test_grpc_server(
+    registry: Arc<Registry>,
+    config: &ManagerConfig,
+) -> tonic::transport::Channel {
+    start_test_grpc_server_with_options(registry, GrpcServerOptions::from_config(config)).await
+}
+
+/// Start the gRPC multiplexer with explicit options (e.g. an access log)
+async fn start_test_grpc_server_with_options(
+    registry: Arc<Registry>,
+    options: GrpcServerOptions,
+) -> tonic::transport::Channel {
+    use tei_manager::grpc::server::start_grpc_server;
+
+    let addr = {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.local_addr().unwrap()
+    };
+    tokio::spawn(start_grpc_server(addr, registry, None, options));
+
+    let endpoint = tonic::transport::Endpoint::from_shared(format!("http://{}", addr)).unwrap();
+    let mut channel = None;
+    for _ in 0..50 {
+        if let Ok(connected) = endpoint.connect().await {
+            channel = Some(connected);
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    channel.expect("server not up")
+}
+
+fn mux_embed_request(instance: &str, inputs: &str) -> mux::EmbedRequest {
+    mux::EmbedRequest {
+        target: Some(mux::Target {
+            routing: Some(mux::target::Routing::InstanceName(instance.to_string())),
+        }),
+        request: Some(tei::EmbedRequest {
+            inputs: inputs.to_string(),
+            ..Default::default()
+        }),
+    }
+}
+
+#[tokio::test]
+async fn test_access_log_records_resolved_instance() {
+    use tei_manager::grpc::access_log::{AccessLog, AccessRouting};
+
+    let backend_port = serve_mock_embed_backend(tei::embed_server::EmbedServer::new(
+        MockEmbedBackend::default(),
+    ))
+    .await;
+    let registry = registry_with_mock_backend("logged", backend_port).await;
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("access.log");
+    let access_log = Arc::new(AccessLog::new().with_file(&path).unwrap());
+    let mut entries = access_log.subscribe();
+    let channel = start_test_grpc_server_with_options(
+        registry,
+        GrpcServerOptions {
+            access_log: Some(access_log),
+            ..Default::default()
+        },
+    )
+    .await;
+    let mut client = mux::tei_multiplexer_client::TeiMultiplexerClient::new(channel);
+
+    let mut request = mux_embed_request("logged", "abcd");
+    request.target = Some(mux::Target {
+        routing: Some(mux::target::Routing::ModelId(
+            "BAAI/bge-small-en-v1.5".to_string(),
+        )),
+    });
+    client.embed(request).await.unwrap();
+    client
+        .embed(mux_embed_request("missing", "abcd"))
+        .await
+        .unwrap_err();
+
+    let timeout = std::time::Duration::from_secs(5);
+    let routed = tokio::time::timeout(timeout, entries.recv())
+        .await
+        .expect("no access log entry")
+        .unwrap();
+    assert_eq!(routed.rpc, "Embed");
+    assert_eq!(
+        routed.routing,
+        Some(AccessRouting::Model("BAAI/bge-small-en-v1.5".to_string()))
+    );
+    assert_eq!(routed.resolved_instance.as_deref(), Some("logged"));
+    assert_eq!(routed.status, "Ok");
+    assert_eq!(routed.principal, None);
+
+    let failed = tokio::time::timeout(timeout, entries.recv())
+        .await
+        .expect("no access log entry")
+        .unwrap();
+    assert_eq!(failed.resolved_instance.as_deref(), Some("missing"));
+    assert_eq!(failed.status, "NotFound");
+
+    // The same entries are appended to the file
+    let written = std::fs::read_to_string(&path).unwrap();
+    let first: serde_json::Value = serde_json::from_str(written.lines().next().unwrap()).unwrap();
+    assert_eq!(first["resolved_instance"], "logged");
+}
+
+#[tokio::test]
+async fn test_forward_headers_reach_backend() {
+    let backend = MockEmbedBackend::default();
+    let seen = backend.seen_metadata.clone();
+    let backend_port = serve_mock_embed_backend(tei::embed_server::EmbedServer::new(backend)).await;
+    let registry = registry_with_mock_backend("tenant", backend_port).await;
+    let config = ManagerConfig {
+        forward_headers: vec!["x-tenant-id".to_string()],
+        ..Default::default()
+    };
+    let channel = start_test_grpc_server(registry, &config).await;
+    let mut client = mux::tei_multiplexer_client::TeiMultiplexerClient::new(channel);
+
+    let mut request = tonic::Request::new(mux_embed_request("tenant", "abcd"));
+    request
+        .metadata_mut()
+        .insert("x-tenant-id", "acme".parse().unwrap());
+    request
+        .metadata_mut()
+        .insert("authorization", "Bearer secret".parse().unwrap());
+    client.embed(request).await.unwrap();
+
+    // Same allowlist on the HTTP data plane
+    let (server, _temp_dir) = create_test_server_with_config(config).await;
+    let response = server
+        .post("/instances")
+        .json(&json!({
+            "name": "tenant-http",
+            "model_id": "BAAI/bge-small-en-v1.5",
+            "port": backend_port
+        }))
+        .await;
+    assert_eq!(response.status_code(), 201);
+    let response = server
+        .post("/instances/tenant-http/embed/jsonl")
+        .add_header("x-tenant-id", "globex")
+        .add_header("authorization", "Bearer secret")
+        .text(r#"{"id": 1, "text": "a"}"#)
+        .await;
+    assert_eq!(response.status_code(), 200);
+    assert!(response.text().contains("embedding"));
+
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.len(), 2);
+    assert_eq!(seen[0].get("x-tenant-id").unwrap(), "acme");
+    assert_eq!(seen[1].get("x-tenant-id").unwrap(), "globex");
+    assert!(seen.iter().all(|m| m.get("authorization").is_none()));
+}
+
+#[tokio::test]
+async fn test_instance_load_metadata_on_responses() {
+    use tei_manager::grpc::multiplexer::INSTANCE_LOAD_METADATA;
+    use tei_manager::instance::InstanceStatus;
+
+    let backend_port = serve_mock_embed_backend(tei::embed_server::EmbedServer::new(
+        MockEmbedBackend::default(),
+    ))
+    .await;
+    let registry = Arc::new(Registry::new(None, STUB_BINARY.to_string(), 8080, 8180));
+    let instance = registry
+        .add(InstanceConfig {
+            name: "loaded".to_string(),
+            model_id: "BAAI/bge-small-en-v1.5".to_string(),
+            port: backend_port,
+            max_concurrent_requests: 4,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    *instance.status.write().await = InstanceStatus::Running;
+
+    let config = ManagerConfig {
+        grpc_instance_load_metadata: true,
+        ..Default::default()
+    };
+    let channel = start_test_grpc_server(registry.clone(), &config).await;
+    let mut client = mux::tei_multiplexer_client::TeiMultiplexerClient::new(channel);
+
+    // Only this request is in flight
+    let response = client
+        .embed(mux_embed_request("loaded", "abcd"))
+        .await
+        .unwrap();
+    assert_eq!(
+        response.metadata().get(INSTANCE_LOAD_METADATA).unwrap(),
+        "0.250"
+    );
+
+    // The mock answers shorter inputs more slowly, so "a" is still in flight
+    // when "abcd" returns
+    let (mut slow, mut fast) = (client.clone(), client.clone());
+    let (slow, fast) = tokio::join!(
+        slow.embed(mux_embed_request("loaded", "a")),
+        fast.embed(mux_embed_request("loaded", "abcd")),
+    );
+    assert_eq!(
+        fast.unwrap()
+            .metadata()
+            .get(INSTANCE_LOAD_METADATA)
+            .unwrap(),
+        "0.500"
+    );
+    assert_eq!(
+        slow.unwrap()
+            .metadata()
+            .get(INSTANCE_LOAD_METADATA)
+            .unwrap(),
+        "0.250"
+    );
+
+    // Off by default
+    let channel = start_test_grpc_server(registry, &ManagerConfig::default()).await;
+    let response = mux::tei_multiplexer_client::TeiMultiplexerClient::new(channel)
+        .embed(mux_embed_request("loaded", "abcd"))
+        .await
+        .unwrap();
+    assert!(response.metadata().get(INSTANCE_LOAD_METADATA).is_none());
+}
+
+#[tokio::test]
+async fn test_dimension_probe_flags_changed_dimension() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tei_manager::health::{AlwaysHealthyChecker, GrpcDimensionProbe, HealthMonitor};
+
+    let dimension = Arc::new(AtomicUsize::new(384));
+    let backend_port =
+        serve_mock_embed_backend(tei::embed_server::EmbedServer::new(MockEmbedBackend {
+            dimension: Some(dimension.clone()),
+            ..Default::default()
+        }))
+        .await;
+    let registry = registry_with_mock_backend("drifting", backend_port).await;
+    let instance = registry.get("drifting").await.unwrap();
+
+    let monitor = HealthMonitor::builder(registry)
+        .health_checker(Arc::new(AlwaysHealthyChecker))
+        .dimension_probe(Arc::new(GrpcDimensionProbe::default()))
+        .build(STUB_BINARY.to_string());
+
+    // The first dimension seen becomes the expected one
+    monitor.check_single_instance(&instance).await;
+    monitor.check_single_instance(&instance).await;
+    {
+        let stats = instance.stats.read().await;
+        assert_eq!(stats.embedding_dimension, Some(384));
+        assert_eq!(stats.health_check_failures, 0);
+    }
+
+    // e.g. a restart picked up a different model
+    dimension.store(768, Ordering::SeqCst);
+    monitor.check_single_instance(&instance).await;
+    let stats = instance.stats.read().await;
+    assert_eq!(stats.health_check_failures, 1);
+    assert_eq!(
+        stats.last_health_error.as_deref(),
+        Some("Embedding dimension changed from 384 to 768")
+    );
+    assert!(!stats.health_history.back().unwrap().healthy);
+    assert_eq!(stats.embedding_dimension, Some(384));
+}
+
+#[tokio::test]
+async fn test_grpc_compression_round_trip() {
+    use tonic::codec::CompressionEncoding;
+
+    let backend_port = start_gzip_mock_embed_backend().await;
+    let registry = registry_with_mock_backend("gzip", backend_port).await;
+    let config = ManagerConfig {
+        grpc_compression: true,
+        ..Default::default()
+    };
+    let channel = start_test_grpc_server(registry, &config).await;
+    let mut client = mux::tei_multiplexer_client::TeiMultiplexerClient::new(channel)
+        .send_compressed(CompressionEncoding::Gzip)
+        .accept_compressed(CompressionEncoding::Gzip);
+
+    let response = client
+        .embed(mux_embed_request("gzip", "abcd"))
+        .await
+        .unwrap();
+
+    assert_eq!(response.metadata().get("grpc-encoding").unwrap(), "gzip");
+    assert_eq!(response.into_inner().embeddings, vec![4.0]);
+}
+
+#[tokio::test]
+async fn test_grpc_max_total_inflight_rejects_when_saturated() {
+    let backend_port = start_mock_embed_backend().await;
+    let registry = registry_with_mock_backend("capped", backend_port).await;
+    let config = ManagerConfig {
+        grpc_max_total_inflight: 1,
+        ..Default::default()
+    };
+    let channel = start_test_grpc_server(registry, &config).await;
+    let mut client = mux::tei_multiplexer_client::TeiMultiplexerClient::new(channel);
+
+    // An open stream holds the only slot until its request side is closed
+    let (tx, rx) = tokio::sync::mpsc::channel(4);
+    tx.send(mux_embed_request("capped", "ab")).await.unwrap();
+    let mut responses = client
+        .embed_stream(tokio_stream::wrappers::ReceiverStream::new(rx))
+        .await
+        .unwrap()
+        .into_inner();
+    let first = responses.next().await.unwrap().unwrap();
+    assert_eq!(first.embeddings, vec![2.0]);
+
+    let status = client
+        .embed(mux_embed_request("capped", "abcd"))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::ResourceExhausted);
+
+    // Closing the stream releases the slot
+    drop(tx);
+    assert!(responses.next().await.is_none());
+    let mut released = false;
+    for _ in 0..50 {
+        if client
+            .embed(mux_embed_request("capped", "abcd"))
+            .await
+            .is_ok()
+        {
+            released = true;
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    assert!(released, "slot was not released after the stream closed");
+}
+
+#[tokio::test]
+async fn test_instance_inflight_tracks_open_stream() {
+    let backend_port = start_mock_embed_backend().await;
+    let registry = registry_with_mock_backend("busy", backend_port).await;
+    let instance = registry.get("busy").await.unwrap();
+    let channel = start_test_grpc_server(registry, &ManagerConfig::default()).await;
+    let mut client = mux::tei_multiplexer_client::TeiMultiplexerClient::new(channel);
+
+    assert_eq!(instance.inflight().get(), 0);
+
+    // An open stream is one forward in flight until its request side is closed
+    let (tx, rx) = tokio::sync::mpsc::channel(4);
+    tx.send(mux_embed_request("busy", "ab")).await.unwrap();
+    let mut responses = client
+        .embed_stream(tokio_stream::wrappers::ReceiverStream::new(rx))
+        .await
+        .unwrap()
+        .into_inner();
+    responses.next().await.unwrap().unwrap();
+    assert_eq!(instance.inflight().get(), 1);
+
+    drop(tx);
+    assert!(responses.next().await.is_none());
+    let mut released = false;
+    for _ in 0..50 {
+        if instance.inflight().get() == 0 {
+            released = true;
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    assert!(
+        released,
+        "inflight count not released after the stream closed"
+    );
+}
+
+#[tokio::test]
+async fn test_get_instance_reports_inflight() {
+    let (app, _temp_dir) = create_test_app(ManagerConfig::default());
+    let server = TestServer::builder()
+        .http_transport()
+        .build(app)
+        .expect("Failed to create test server");
+    let port = start_mock_embed_backend().await;
+
+    let response = server
+        .post("/instances")
+        .json(&json!({
+            "name": "ws-busy",
+            "model_id": "BAAI/bge-small-en-v1.5",
+            "port": port
+        }))
+        .await;
+    assert_eq!(response.status_code(), 201);
+    let body: serde_json::Value = server.get("/instances/ws-busy").await.json();
+    assert_eq!(body["inflight"], 0);
+
+    // A WebSocket session holds one forward open for its lifetime
+    let mut websocket = server
+        .get_websocket("/instances/ws-busy/embed/ws")
+        .await
+        .into_websocket()
+        .await;
+    websocket.send_json(&json!({"text": "ab"})).await;
+    websocket
+        .assert_receive_json(&json!({"embedding": [2.0]}))
+        .await;
+
+    let body: serde_json::Value = server.get("/instances/ws-busy").await.json();
+    assert_eq!(body["inflight"], 1);
+}
+
+#[tokio::test]
+async fn test_embed_jsonl_streams_results_in_order() {
+    let (server, _temp_dir) = create_test_server().await;
+    let port = start_mock_embed_backend().await;
+
+    let response = server
+        .post("/instances")
+        .json(&json!({
+            "name": "jsonl-test",
+            "model_id": "BAAI/bge-small-en-v1.5",
+            "port": port
+        }))
+        .await;
+    assert_eq!(response.status_code(), 201);
+
+    let body = [
+        r#"{"id": "a", "text": "a"}"#,
+        r#"{"id": 2, "text": "bbbb"}"#,
+        "",
+        "not json",
+        r#"{"id": "c", "text": "ccc"}"#,
+    ]
+    .join("\n");
+
+    let response = server
+        .post("/instances/jsonl-test/embed/jsonl")
+        .content_type("application/x-ndjson")
+        .text(body)
+        .await;
+    assert_eq!(response.status_code(), 200);
+    assert_eq!(
+        response.header("content-type").to_str().unwrap(),
+        "application/x-ndjson"
+    );
+
+    let lines: Vec<serde_json::Value> = response
+        .text()
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[0], json!({"id": "a", "embedding": [1.0]}));
+    assert_eq!(lines[1], json!({"id": 2, "embedding": [4.0]}));
+    assert_eq!(lines[2]["id"], serde_json::Value::Null);
+    assert!(lines[2]["error"].as_str().unwrap().contains("line 4"));
+    assert_eq!(lines[3], json!({"id": "c", "embedding": [3.0]}));
+}
+
+/// Create an instance of `model_id` on the mock backend and wait until it is routable
+async fn running_mock_instance(server: &TestServer, name: &str, model_id: &str) {
+    let port = start_mock_embed_backend().await;
+    let response = server
+        .post("/instances")
+        .json(&json!({"name": name, "model_id": model_id, "port": port}))
+        .await;
+    assert_eq!(response.status_code(), 201);
+
+    // Readiness is checked in the background after create
+    for _ in 0..50 {
+        let body: serde_json::Value = server.get(&format!("/instances/{}", name)).await.json();
+        if body["status"] == "running" {
+            return;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    panic!("instance '{}' never became running", name);
+}
+
+#[tokio::test]
+async fn test_openai_embeddings() {
+    let (server, _temp_dir) = create_test_server_with_config(ManagerConfig {
+        health_check_mode: HealthCheckMode::AlwaysHealthy,
+        ..Default::default()
+    })
+    .await;
+    running_mock_instance(&server, "openai", "BAAI/bge-small-en-v1.5").await;
+
+    // Single string input
+    let response = server
+        .post("/v1/embeddings")
+        .json(&json!({"model": "BAAI/bge-small-en-v1.5", "input": "abc"}))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    assert_eq!(
+        response.json::<serde_json::Value>(),
+        json!({
+            "object": "list",
+            "data": [{"object": "embedding", "embedding": [3.0], "index": 0}],
+            "model": "BAAI/bge-small-en-v1.5",
+            "usage": {"prompt_tokens": 0, "total_tokens": 0}
+        })
+    );
+
+    // Array input keeps input order (the mock answers shorter texts later)
+    let response = server
+        .post("/v1/embeddings")
+        .json(&json!({"model": "BAAI/bge-small-en-v1.5", "input": ["a", "bbbb", "cc"]}))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let body: serde_json::Value = response.json();
+    let data = body["data"].as_array().unwrap();
+    assert_eq!(data.len(), 3);
+    for (i, expected) in [1.0, 4.0, 2.0].into_iter().enumerate() {
+        assert_eq!(data[i]["index"], i);
+        assert_eq!(data[i]["embedding"], json!([expected]));
+    }
+
+    // base64 is what the official OpenAI clients request by default
+    let response = server
+        .post("/v1/embeddings")
+        .json(&json!({
+            "model": "BAAI/bge-small-en-v1.5",
+            "input": ["ab"],
+            "encoding_format": "base64"
+        }))
+        .await;
+    let body: serde_json::Value = response.json();
+    // 2.0f32 little-endian
+    assert_eq!(body["data"][0]["embedding"], "AAAAQA==");
+}
+
+#[tokio::test]
+async fn test_openai_embeddings_zero_request_timeout_disables_timeout() {
+    let (server, _temp_dir) = create_test_server_with_config(ManagerConfig {
+        health_check_mode: HealthCheckMode::AlwaysHealthy,
+        grpc_request_timeout_secs: 0,
+        ..Default::default()
+    })
+    .await;
+    running_mock_instance(&server, "openai-no-timeout", "BAAI/bge-small-en-v1.5").await;
+
+    let response = server
+        .post("/v1/embeddings")
+        .json(&json!({"model": "BAAI/bge-small-en-v1.5", "input": "ab"}))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["data"][0]["embedding"], json!([2.0]));
+}
+
+#[tokio::test]
+async fn test_openai_embeddings_rejections() {
+    let (server, _temp_dir) = create_test_server().await;
+
+    let response = server
+        .post("/v1/embeddings")
+        .json(&json!({"model": "unknown/model", "input": "hi"}))
+        .await;
+    assert_eq!(response.status_code(), 404);
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["code"], "MODEL_NOT_FOUND");
+
+    let response = server
+        .post("/v1/embeddings")
+        .json(&json!({"model": "unknown/model", "input": []}))
+        .await;
+    assert_eq!(response.status_code(), 400);
+
+    let response = server
+        .post("/v1/embeddings")
+        .json(&json!({"model": "unknown/model", "input": "hi", "encoding_format": "int8"}))
+        .await;
+    assert_eq!(response.status_code(), 400);
+}
+
+#[tokio::test]
+async fn test_embed_jsonl_unknown_instance() {
+    let (server, _temp_dir) = create_test_server().await;
+
+    let response = server
+        .post("/instances/missing/embed/jsonl")
+        .text(r#"{"id": 1, "text": "hi"}"#)
+        .await;
+
+    assert_eq!(response.status_code(), 404);
+}
+
+#[tokio::test]
+async fn test_embed_ws_round_trip() {
+    let (app, _temp_dir) = create_test_app(ManagerConfig::default());
+    // WebSockets need a real connection rather than the mock transport
+    let server = TestServer::builder()
+        .http_transport()
+        .build(app)
+        .expect("Failed to create test server");
+    let port = start_mock_embed_backend().await;
+
+    let response = server
+        .post("/instances")
+        .json(&json!({
+            "name": "ws-test",
+            "model_id": "BAAI/bge-small-en-v1.5",
+            "port": port
+        }))
+        .await;
+    assert_eq!(response.status_code(), 201);
+
+    let mut websocket = server
+        .get_websocket("/instances/ws-test/embed/ws")
+        .await
+        .into_websocket()
+        .await;
+
+    websocket.send_json(&json!({"text": "ab"})).await;
+    websocket.send_json(&json!({"text": "abcd"})).await;
+    websocket
+        .assert_receive_json(&json!({"embedding": [2.0]}))
+        .await;
+    websocket
+        .assert_receive_json(&json!({"embedding": [4.0]}))
+        .await;
+    // Requested dimensions reach the backend
+    websocket
+        .send_json(&json!({"text": "abc", "dimensions": 2}))
+        .await;
+    websocket
+        .assert_receive_json(&json!({"embedding": [3.0, 3.0]}))
+        .await;
+
+    // An invalid message ends the session with a close frame
+    websocket.send_text("not json").await;
+    match websocket.receive_message().await {
+        axum_test::WsMessage::Close(Some(frame)) => {
+            assert_eq!(u16::from(frame.code), 1007);
+            assert!(frame.reason.contains("Invalid message"));
+        }
+        other => panic!("expected close frame, got {:?}", other),
+    }
+
+    let mut websocket = server
+        .get_websocket("/instances/ws-test/embed/ws")
+        .await
+        .into_websocket()
+        .await;
+    websocket
+        .send_json(&json!({"text": "ab", "dimensions": 0}))
+        .await;
+    match websocket.receive_message().await {
+        axum_test::WsMessage::Close(Some(frame)) => {
+            assert_eq!(u16::from(frame.code), 1007);
+            assert!(frame.reason.contains("dimensions must be > 0"));
+        }
+        other => panic!("expected close frame, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_embed_ws_idle_timeout() {
+    let (app, _temp_dir) = create_test_app(ManagerConfig {
+        http_ws_idle_timeout_secs: 1,
+        ..Default::default()
+    });
+    let server = TestServer::builder()
+        .http_transport()
+        .build(app)
+        .expect("Failed to create test server");
+    let port = start_mock_embed_backend().await;
+
+    let response = server
+        .post("/instances")
+        .json(&json!({
+            "name": "ws-idle",
+            "model_id": "BAAI/bge-small-en-v1.5",
+            "port": port
+        }))
+        .await;
+    assert_eq!(response.status_code(), 201);
+
+    let mut websocket = server
+        .get_websocket("/instances/ws-idle/embed/ws")
+        .await
+        .into_websocket()
+        .await;
+    websocket.send_json(&json!({"text": "ab"})).await;
+    websocket
+        .assert_receive_json(&json!({"embedding": [2.0]}))
+        .await;
+
+    // Nothing more is sent, so the socket is closed after the idle timeout
+    match websocket.receive_message().await {
+        axum_test::WsMessage::Close(Some(frame)) => {
+            assert_eq!(u16::from(frame.code), 1001);
+            assert!(frame.reason.contains("No message for 1s"));
+        }
+        other => panic!("expected close frame, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_embed_ws_unknown_instance() {
+    let (app, _temp_dir) = create_test_app(ManagerConfig::default());
+    let server = TestServer::builder()
+        .http_transport()
+        .build(app)
+        .expect("Failed to create test server");
+
+    let response = server.get_websocket("/instances/missing/embed/ws").await;
+
+    assert_eq!(response.status_code(), 404);
+}
+
+#[tokio::test]
+async fn test_create_instance_writes_audit_entry() {
+    let audit_dir = TempDir::new().unwrap();
+    let audit_path = audit_dir.path().join("audit.log");
+    let (app, _temp_dir) = create_test_app(ManagerConfig {
+        audit_log_file: Some(audit_path.clone()),
+        ..Default::default()
+    });
+    // Stand in for the auth middleware, which sets the principal on success
+    let app = app.layer(axum::Extension(Principal("CN=test-client".to_string())));
+    let server = TestServer::new(app).expect("Failed to create test server");
+
+    let response = server
+        .post("/instances")
+        .json(&json!({
+            "name": "audited",
+            "model_id": "BAAI/bge-small-en-v1.5",
+            "port": 8090
+        }))
+        .await;
+    assert_eq!(response.status_code(), 201);
+
+    let content = std::fs::read_to_string(&audit_path).unwrap();
+    let entries: Vec<serde_json::Value> = content
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["action"], "create");
+    assert_eq!(entries[0]["instance"], "audited");
+    assert_eq!(entries[0]["principal"], "CN=test-client");
+    assert!(
+        chrono::DateTime::parse_from_rfc3339(entries[0]["timestamp"].as_str().unwrap()).is_ok()
+    );
+
+    // Failed actions are not audited
+    server.delete("/instances/missing").await;
+    server.delete("/instances/audited").await;
+    let content = std::fs::read_to_string(&audit_path).unwrap();
+    let actions: Vec<serde_json::Value> = content
+        .lines()
+        .map(|l| serde_json::from_str::<serde_json::Value>(l).unwrap()["action"].clone())
+        .collect();
+    assert_eq!(actions, vec![json!("create"), json!("delete")]);
+}
+
+#[tokio::test]
+async fn test_describe_instance() {
+    let (server, _temp_dir) = create_test_server().await;
+
+    let response = server
+        .post("/instances")
+        .json(&json!({
+            "name": "described",
+            "model_id": "BAAI/bge-small-en-v1.5",
+            "port": 8092,
+            "extra_args": ["--hf-api-token", "hf_supersecret"],
+            "env": {"HF_TOKEN": "hf_envsecret", "RUST_LOG": "info"}
+        }))
+        .await;
+    assert_eq!(response.status_code(), 201);
+
+    let response = server
+        .get("/instances/described/describe?log_lines=5")
+        .await;
+    assert_eq!(response.status_code(), 200);
+    assert!(!response.text().contains("hf_supersecret"));
+    assert!(!response.text().contains("hf_envsecret"));
+
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["config"]["name"], "described");
+    assert_eq!(body["config"]["env"]["HF_TOKEN"], "***REDACTED***");
+    assert_eq!(body["config"]["env"]["RUST_LOG"], "info");
+    assert!(body["status"].is_string());
+    assert!(body.get("pid").is_some());
+    assert_eq!(body["maintenance"], false);
+    assert!(body["stats"]["started_at"].is_string());
+    assert!(body["stats"].get("last_health_error").is_some());
+    assert!(body["cuda_visible_devices"].is_null());
+    assert!(body["gpu_count"].is_u64());
+    assert!(body["log_tail"].as_array().unwrap().len() <= 5);
+
+    let command: Vec<&str> = body["command"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|arg| arg.as_str().unwrap())
+        .collect();
+    assert_eq!(command[0], STUB_BINARY);
+    assert!(command.windows(2).any(|w| w == ["--port", "8092"]));
+    assert!(
+        command
+            .windows(2)
+            .any(|w| w == ["--hf-api-token", "***REDACTED***"])
+    );
+
+    let response = server.get("/instances/missing/describe").await;
+    assert_eq!(response.status_code(), 404);
+}
+
+#[tokio::test]
+async fn test_get_instance_command() {
+    let (server, _temp_dir) = create_test_server().await;
+
+    let response = server
+        .post("/instances")
+        .json(&json!({
+            "name": "commanded",
+            "model_id": "BAAI/bge-small-en-v1.5",
+            "port": 8094,
+            "pooling": "mean",
+            "dtype": "float16",
+            "extra_args": ["--hf-api-token=hf_supersecret"],
+            "env": {"HF_TOKEN": "hf_envsecret"}
+        }))
+        .await;
+    assert_eq!(response.status_code(), 201);
+
+    let response = server.get("/instances/commanded/command").await;
+    assert_eq!(response.status_code(), 200);
+    assert!(!response.text().contains("supersecret"));
+    assert!(!response.text().contains("envsecret"));
+
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["name"], "commanded");
+    let command: Vec<&str> = body["command"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|arg| arg.as_str().unwrap())
+        .collect();
+    assert_eq!(command[0], STUB_BINARY);
+    assert!(
+        command
+            .windows(2)
+            .any(|w| w == ["--model-id", "BAAI/bge-small-en-v1.5"])
+    );
+    assert!(command.windows(2).any(|w| w == ["--port", "8094"]));
+    assert!(command.windows(2).any(|w| w == ["--pooling", "mean"]));
+    assert!(command.windows(2).any(|w| w == ["--dtype", "float16"]));
+    assert!(command.contains(&"--max-batch-tokens"));
+    assert!(command.contains(&"--hf-api-token=***REDACTED***"));
+    assert_eq!(body["env"]["HF_TOKEN"], "***REDACTED***");
+    assert!(body["cuda_visible_devices"].is_null());
+
+    let response = server.get("/instances/missing/command").await;
+    assert_eq!(response.status_code(), 404);
+}
+
+#[tokio::test]
+async fn test_default_extra_args_in_launch_command() {
+    let (server, _temp_dir) = create_test_server_with_config(ManagerConfig {
+        default_extra_args: vec!["--dtype".to_string(), "float16".to_string()],
+        ..Default::default()
+    })
+    .await;
+
+    let response = server
+        .post("/instances")
+        .json(&json!({
+            "name": "with-defaults",
+            "model_id": "BAAI/bge-small-en-v1.5",
+            "port": 8093,
+            "extra_args": ["--auto-truncate"]
+        }))
+        .await;
+    assert_eq!(response.status_code(), 201);
+
+    let body: serde_json::Value = server.get("/instances/with-defaults/describe").await.json();
+    let command: Vec<&str> = body["command"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|arg| arg.as_str().unwrap())
+        .collect();
+    assert!(command.ends_with(&["--dtype", "float16", "--auto-truncate"]));
+
+    // Defaults are runtime-only: the stored (and persisted) config keeps its own args
+    assert_eq!(body["config"]["extra_args"], json!(["--auto-truncate"]));
+}
+
+#[tokio::test]
+async fn test_create_instance_with_missing_tei_binary() {
+    let (server, _temp_dir) = create_test_server_with_config(ManagerConfig {
+        tei_binary_path: "/nonexistent/text-embeddings-router".to_string(),
+        ..Default::default()
+    })
+    .await;
+
+    let response = server
+        .post("/instances")
+        .json(&json!({
+            "name": "no-binary",
+            "model_id": "BAAI/bge-small-en-v1.5",
+            "port": 8091
+        }))
+        .await;
+
+    assert_eq!(response.status_code(), 500);
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["code"], "TEI_BINARY_NOT_FOUND");
+    let message = body["error"].as_str().unwrap();
+    assert!(
+        message.contains("/nonexistent/text-embeddings-router"),
+        "{}",
+        message
+    );
+    assert!(message.contains("TEI_BINARY_PATH"), "{}", message);
+}
+
+#[tokio::test]
+async fn test_oversized_body_rejected_with_413() {
+    let (server, _temp_dir) = create_test_server_with_config(ManagerConfig {
+        http_max_body_bytes: 1024,
+        http_max_embed_body_bytes: 4096,
+        ..Default::default()
+    })
+    .await;
+
+    let response = server
+        .post("/instances")
+        .json(&json!({
+            "name": "too-big",
+            "model_id": "x".repeat(2048),
+        }))
+        .await;
+    assert_eq!(response.status_code(), 413);
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["code"], "PAYLOAD_TOO_LARGE");
+    let error = body["error"].as_str().unwrap();
+    assert!(error.contains("1024"));
+    assert!(error.contains("http_max_body_bytes"));
+
+    // The embed endpoint has its own, higher limit
+    let line = format!("{}\n", json!({"id": 1, "text": "x".repeat(100)}));
+    let response = server
+        .post("/instances/missing/embed/jsonl")
+        .text(line.repeat(20))
+        .await;
+    assert_eq!(response.status_code(), 404);
+
+    let response = server
+        .post("/instances/missing/embed/jsonl")
+        .text(line.repeat(50))
+        .await;
+    assert_eq!(response.status_code(), 413);
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["code"], "PAYLOAD_TOO_LARGE");
+    assert!(body["error"].as_str().unwrap().contains("4096"));
+}
+
+#[tokio::test]
+async fn test_maintenance_mode_endpoints() {
+    let (server, _temp_dir) = create_test_server().await;
+
+    let body: serde_json::Value = server.get("/admin/maintenance").await.json();
+    assert_eq!(body["enabled"], false);
+    assert_eq!(body["instances"], json!([]));
+
+    let response = server
+        .post("/admin/maintenance")
+        .json(&json!({"enabled": true}))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    assert_eq!(response.json::<serde_json::Value>()["enabled"], true);
+
+    server
+        .post("/instances")
+        .json(&json!({
+            "name": "maint-test",
+            "model_id": "BAAI/bge-small-en-v1.5",
+            "port": 8091
+        }))
+        .await;
+
+    let response = server
+        .post("/instances/maint-test/maintenance")
+        .json(&json!({"enabled": true}))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    assert_eq!(response.json::<serde_json::Value>()["maintenance"], true);
+
+    let body: serde_json::Value = server.get("/admin/maintenance").await.json();
+    assert_eq!(body["enabled"], true);
+    assert_eq!(body["instances"], json!(["maint-test"]));
+
+    let response = server
+        .post("/instances/missing/maintenance")
+        .json(&json!({"enabled": true}))
+        .await;
+    assert_eq!(response.status_code(), 404);
+}
+
+#[tokio::test]
+async fn test_maintenance_changes_audited() {
+    let audit_dir = TempDir::new().unwrap();
+    let audit_path = audit_dir.path().join("audit.log");
+    let (app, _temp_dir) = create_test_app(ManagerConfig {
+        audit_log_file: Some(audit_path.clone()),
+        ..Default::default()
+    });
+    let app = app.layer(axum::Extension(Principal("CN=operator".to_string())));
+    let server = TestServer::new(app).expect("Failed to create test server");
+
+    server
+        .post("/instances")
+        .json(&json!({
+            "name": "maint-audit",
+            "model_id": "BAAI/bge-small-en-v1.5",
+            "port": 8092
+        }))
+        .await;
+    server
+        .post("/instances/maint-audit/maintenance")
+        .json(&json!({"enabled": true}))
+        .await;
+    server
+        .post("/admin/maintenance")
+        .json(&json!({"enabled": false}))
+        .await;
+
+    let content = std::fs::read_to_string(&audit_path).unwrap();
+    let entries: Vec<(serde_json::Value, serde_json::Value)> = content
+        .lines()
+        .map(|l| {
+            let entry: serde_json::Value = serde_json::from_str(l).unwrap();
+            (entry["action"].clone(), entry["instance"].clone())
+        })
+        .collect();
+    assert_eq!(
+        entries,
+        vec![
+            (json!("create"), json!("maint-audit")),
+            (json!("maintenance_on"), json!("maint-audit")),
+            (json!("maintenance_off"), json!("*")),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_pause_resume_endpoints() {
+    let (server, _temp_dir) = create_test_server().await;
+
+    let created: serde_json::Value = server
+        .post("/instances")
+        .json(&json!({
+            "name": "pause-test",
+            "model_id": "BAAI/bge-small-en-v1.5",
+            "port": 8392
+        }))
+        .await
+        .json();
+    assert_eq!(created["paused"], false);
+
+    let response = server.post("/instances/pause-test/pause").await;
+    assert_eq!(response.status_code(), 200);
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["paused"], true);
+    // Pausing leaves the process alone
+    assert_eq!(body["pid"], created["pid"]);
+
+    // Still listed while paused
+    let list: serde_json::Value = server.get("/instances").await.json();
+    let listed = list
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|i| i["name"] == "pause-test")
+        .expect("paused instance should still be listed");
+    assert_eq!(listed["paused"], true);
+
+    let response = server.post("/instances/pause-test/resume").await;
+    assert_eq!(response.status_code(), 200);
+    assert_eq!(response.json::<serde_json::Value>()["paused"], false);
+
+    let response = server.post("/instances/missing/pause").await;
+    assert_eq!(response.status_code(), 404);
+    let response = server.post("/instances/missing/resume").await;
+    assert_eq!(response.status_code(), 404);
+}
+
+#[tokio::test]
+async fn test_unquarantine_requires_quarantined_instance() {
+    let (server, _temp_dir) = create_test_server().await;
+
+    server
+        .post("/instances")
+        .json(&json!({
+            "name": "healthy",
+            "model_id": "BAAI/bge-small-en-v1.5",
+            "port": 8393
+        }))
+        .await;
+
+    let response = server.post("/instances/healthy/unquarantine").await;
+    assert_eq!(response.status_code(), 400);
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["code"], "INVALID_INSTANCE_STATE");
+
+    let response = server.post("/instances/missing/unquarantine").await;
+    assert_eq!(response.status_code(), 404);
+}
+
+#[tokio::test]
+async fn test_model_hub_check_rejects_missing_model() {
+    // Hub that only knows one model
+    let hub = axum::Router::new().route(
+        "/api/models/{org}/{model}",
+        axum::routing::get(
+            |axum::extract::Path((org, model)): axum::extract::Path<(String, String)>| async move {
+                if org == "BAAI" && model == "bge-small-en-v1.5" {
+                    axum::http::StatusCode::OK
+                } else {
+                    axum::http::StatusCode::NOT_FOUND
+                }
+            },
+        ),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let endpoint = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, hub).await });
+
+    let (server, _temp_dir) = create_test_server_with_config(ManagerConfig {
+        max_instances: Some(10),
+        model_hub_check: true,
+        model_hub_endpoint: Some(endpoint),
+        ..Default::default()
+    })
+    .await;
+
+    let response = server
+        .post("/instances")
+        .json(&json!({
+            "name": "typo",
+            "model_id": "BAAI/bge-smol-en-v1.5",
+            "port": 0
+        }))
+        .await;
+    assert_eq!(response.status_code(), 404);
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["code"], "MODEL_NOT_FOUND");
+    server
+        .get("/instances/typo")
+        .await
+        .assert_status_not_found();
+
+    // Uncached but on the Hub: TEI downloads it on start
+    let response = server
+        .post("/instances")
+        .json(&json!({
+            "name": "valid",
+            "model_id": "BAAI/bge-small-en-v1.5",
+            "port": 0
+        }))
+        .await;
+    assert_eq!(response.status_code(), 201);
+}
+
+#[tokio::test]
+async fn test_model_hub_check_uses_instance_hf_home() {
+    // Hub that knows no models, so only a cache hit lets an instance through
+    let hub = axum::Router::new().route(
+        "/api/models/{org}/{model}",
+        axum::routing::get(|| async { axum::http::StatusCode::NOT_FOUND }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let endpoint = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, hub).await });
+
+    let (server, temp_dir) = create_test_server_with_config(ManagerConfig {
+        max_instances: Some(10),
+        model_hub_check: true,
+        model_hub_endpoint: Some(endpoint),
+        ..Default::default()
+    })
+    .await;
+
+    let hf_home = temp_dir.path().join("hf-team-a");
+    let snapshot = hf_home.join("hub/models--team-a--private-embedder/snapshots/abc123");
+    std::fs::create_dir_all(&snapshot).unwrap();
+    std::fs::write(snapshot.join("config.json"), "{}").unwrap();
+
+    // Not in the manager's cache
+    server
+        .post("/instances")
+        .json(&json!({
+            "name": "global",
+            "model_id": "team-a/private-embedder",
+            "port": 0
+        }))
+        .await
+        .assert_status_not_found();
+
+    let response = server
+        .post("/instances")
+        .json(&json!({
+            "name": "isolated",
+            "model_id": "team-a/private-embedder",
+            "port": 0,
+            "hf_home": hf_home
+        }))
+        .await;
+    assert_eq!(response.status_code(), 201);
+
+    // The launch preview shows the cache handed to TEI
+    let response = server.get("/instances/isolated/command").await;
+    assert_eq!(response.status_code(), 200);
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["env"]["HF_HOME"], hf_home.display().to_string());
+    assert_eq!(
+        body["env"]["HUGGINGFACE_HUB_CACHE"],
+        hf_home.join("hub").display().to_string()
+    );
+}
+
+#[tokio::test]
+async fn test_embed_arrow_dimensions_shape_output() {
+    use arrow::array::{Array, FixedSizeListArray, Float32Array, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::ipc::{reader::StreamReader, writer::StreamWriter};
+    use arrow::record_batch::RecordBatch;
+
+    let backend_port = start_mock_embed_backend().await;
+    let registry = registry_with_mock_backend("matryoshka", backend_port).await;
+    let channel = start_test_grpc_server(registry, &ManagerConfig::default()).await;
+    let mut client = mux::tei_multiplexer_client::TeiMultiplexerClient::new(channel);
+
+    let schema = Arc::new(Schema::new(vec![Field::new("text", DataType::Utf8, false)]));
+    let batch = RecordBatch::try_new(
+        schema.clone(),
+        vec![Arc::new(StringArray::from(vec!["ab", "abcd"]))],
+    )
+    .unwrap();
+    let mut arrow_ipc = Vec::new();
+    {
+        let mut writer = StreamWriter::try_new(&mut arrow_ipc, &schema).unwrap();
+        writer.write(&batch).unwrap();
+        writer.finish().unwrap();
+    }
+    let request = |dimensions| mux::EmbedArrowRequest {
+        target: Some(mux::Target {
+            routing: Some(mux::target::Routing::InstanceName("matryoshka".to_string())),
+        }),
+        arrow_ipc: arrow_ipc.clone(),
+        dimensions,
+        ..Default::default()
+    };
+
+    let response = client.embed_arrow(request(Some(3))).await.unwrap();
+    let mut reader =
+        StreamReader::try_new(std::io::Cursor::new(response.into_inner().arrow_ipc), None).unwrap();
+    let result = reader.next().unwrap().unwrap();
+    let embeddings = result
+        .column(0)
+        .as_any()
+        .downcast_ref::<FixedSizeListArray>()
+        .unwrap();
+    assert_eq!(embeddings.value_length(), 3);
+    assert_eq!(embeddings.len(), 2);
+    // The mock repeats the text length `dimensions` times
+    let second = embeddings.value(1);
+    let second = second.as_any().downcast_ref::<Float32Array>().unwrap();
+    assert_eq!(second.values().to_vec(), vec![4.0, 4.0, 4.0]);
+
+    let status = client.embed_arrow(request(Some(0))).await.unwrap_err();
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn test_embed_arrow_order_survives_reordering_backend() {
+    use arrow::array::{Array, FixedSizeListArray, Float32Array, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::ipc::{reader::StreamReader, writer::StreamWriter};
+    use arrow::record_batch::RecordBatch;
+    use tei_manager::config::ArrowEmbedOrdering;
+
+    // Unary calls finish shortest-text-last and streams answer back to front
+    let backend_port =
+        serve_mock_embed_backend(tei::embed_server::EmbedServer::new(MockEmbedBackend {
+            reverse_stream: true,
+            ..Default::default()
+        }))
+        .await;
+
+    let schema = Arc::new(Schema::new(vec![Field::new("text", DataType::Utf8, false)]));
+    let batch = RecordBatch::try_new(
+        schema.clone(),
+        vec![Arc::new(StringArray::from(vec!["a", "abc", "abcd", "ab"]))],
+    )
+    .unwrap();
+    let mut arrow_ipc = Vec::new();
+    {
+        let mut writer = StreamWriter::try_new(&mut arrow_ipc, &schema).unwrap();
+        writer.write(&batch).unwrap();
+        writer.finish().unwrap();
+    }
+
+    async fn embed_lengths(
+        backend_port: u16,
+        ordering: ArrowEmbedOrdering,
+        arrow_ipc: Vec<u8>,
+    ) -> Vec<f32> {
+        let registry = registry_with_mock_backend("reorder", backend_port).await;
+        let config = ManagerConfig {
+            arrow_embed_ordering: ordering,
+            ..Default::default()
+        };
+        let channel = start_test_grpc_server(registry, &config).await;
+        let mut client = mux::tei_multiplexer_client::TeiMultiplexerClient::new(channel);
+        let response = client
+            .embed_arrow(mux::EmbedArrowRequest {
+                target: Some(mux::Target {
+                    routing: Some(mux::target::Routing::InstanceName("reorder".to_string())),
+                }),
+                arrow_ipc,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let mut reader =
+            StreamReader::try_new(std::io::Cursor::new(response.into_inner().arrow_ipc), None)
+                .unwrap();
+        let result = reader.next().unwrap().unwrap();
+        let embeddings = result
+            .column(0)
+            .as_any()
+            .downcast_ref::<FixedSizeListArray>()
+            .unwrap();
+        (0..embeddings.len())
+            .map(|i| {
+                let row = embeddings.value(i);
+                row.as_any()
+                    .downcast_ref::<Float32Array>()
+                    .unwrap()
+                    .value(0)
+            })
+            .collect()
+    }
+
+    // Indexed mode places every embedding on its own row
+    let lengths = embed_lengths(backend_port, ArrowEmbedOrdering::Indexed, arrow_ipc.clone()).await;
+    assert_eq!(lengths, vec![1.0, 3.0, 4.0, 2.0]);
+
+    // Stream mode takes the backend's order as given
+    let lengths = embed_lengths(backend_port, ArrowEmbedOrdering::Stream, arrow_ipc).await;
+    assert_eq!(lengths, vec![2.0, 4.0, 3.0, 1.0]);
+}
+
+/// Arrow IPC stream with one `text` column holding `texts`
+fn arrow_text_batch(texts: Vec<&str>) -> Vec<u8> {
+    use arrow::array::StringArray;
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::ipc::writer::StreamWriter;
+    use arrow::record_batch::RecordBatch;
+
+    let schema = Arc::new(Schema::new(vec![Field::new("text", DataType::Utf8, false)]));
+    let batch =
+        RecordBatch::try_new(schema.clone(), vec![Arc::new(StringArray::from(texts))]).unwrap();
+    let mut arrow_ipc = Vec::new();
+    {
+        let mut writer = StreamWriter::try_new(&mut arrow_ipc, &schema).unwrap();
+        writer.write(&batch).unwrap();
+        writer.finish().unwrap();
+    }
+    arrow_ipc
+}
+
+#[tokio::test]
+async fn test_embed_arrow_stream_rejects_missing_responses() {
+    let backend_port =
+        serve_mock_embed_backend(tei::embed_server::EmbedServer::new(MockEmbedBackend {
+            drop_last_stream_response: true,
+            ..Default::default()
+        }))
+        .await;
+    let registry = registry_with_mock_backend("short", backend_port).await;
+    // Streaming is the default ordering
+    let channel = start_test_grpc_server(registry, &ManagerConfig::default()).await;
+    let mut client = mux::tei_multiplexer_client::TeiMultiplexerClient::new(channel);
+
+    let status = client
+        .embed_arrow(mux::EmbedArrowRequest {
+            target: Some(mux::Target {
+                routing: Some(mux::target::Routing::InstanceName("short".to_string())),
+            }),
+            arrow_ipc: arrow_text_batch(vec!["a", "ab", "abc"]),
+            ..Default::default()
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::Internal);
+    assert!(
+        status.message().contains("2 embeddings for 3 rows"),
+        "{}",
+        status.message()
+    );
+}
+
+#[tokio::test]
+async fn test_embed_arrow_chunks_sent_as_separate_streams() {
+    use arrow::array::{Array, FixedSizeListArray, Float32Array};
+    use arrow::ipc::reader::StreamReader;
+
+    let backend = MockEmbedBackend::default();
+    let stream_requests = backend.stream_requests.clone();
+    let backend_port = serve_mock_embed_backend(tei::embed_server::EmbedServer::new(backend)).await;
+    let registry = registry_with_mock_backend("chunked", backend_port).await;
+    let config = ManagerConfig {
+        arrow_max_rows_per_chunk: 3,
+        ..Default::default()
+    };
+    let channel = start_test_grpc_server(registry, &config).await;
+    let mut client = mux::tei_multiplexer_client::TeiMultiplexerClient::new(channel);
+
+    // Text i is i + 1 characters long, and the mock embeds a text as its length
+    let texts: Vec<String> = (1..=10).map(|len| "x".repeat(len)).collect();
+    let response = client
+        .embed_arrow(mux::EmbedArrowRequest {
+            target: Some(mux::Target {
+                routing: Some(mux::target::Routing::InstanceName("chunked".to_string())),
+            }),
+            arrow_ipc: arrow_text_batch(texts.iter().map(String::as_str).collect()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    // 10 rows with a chunk size of 3: one backend stream per sub-batch
+    assert_eq!(*stream_requests.lock().unwrap(), vec![3, 3, 3, 1]);
+
+    // ... concatenated back into one output batch, in input order
+    let mut reader =
+        StreamReader::try_new(std::io::Cursor::new(response.into_inner().arrow_ipc), None).unwrap();
+    let result = reader.next().unwrap().unwrap();
+    assert!(reader.next().is_none());
+    let embeddings = result
+        .column(0)
+        .as_any()
+        .downcast_ref::<FixedSizeListArray>()
+        .unwrap();
+    let lengths: Vec<f32> = (0..embeddings.len())
+        .map(|i| {
+            let row = embeddings.value(i);
+            row.as_any()
+                .downcast_ref::<Float32Array>()
+                .unwrap()
+                .value(0)
+        })
+        .collect();
+    assert_eq!(lengths, (1..=10).map(|len| len as f32).collect::<Vec<_>>());
+}
+
+#[tokio::test]
+async fn test_embed_arrow_indexed_capped_at_instance_capacity() {
+    use tei_manager::config::ArrowEmbedOrdering;
+    use tei_manager::instance::InstanceStatus;
+
+    let backend = MockEmbedBackend::default();
+    let in_flight = backend.unary_in_flight.clone();
+    let backend_port = serve_mock_embed_backend(tei::embed_server::EmbedServer::new(backend)).await;
+
+    let registry = Arc::new(Registry::new(None, STUB_BINARY.to_string(), 8080, 8180));
+    let instance = registry
+        .add(InstanceConfig {
+            name: "small".to_string(),
+            model_id: "BAAI/bge-small-en-v1.5".to_string(),
+            port: backend_port,
+            max_concurrent_requests: 2,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    *instance.status.write().await = InstanceStatus::Running;
+    let config = ManagerConfig {
+        arrow_embed_ordering: ArrowEmbedOrdering::Indexed,
+        ..Default::default()
+    };
+    let channel = start_test_grpc_server(registry, &config).await;
+    let mut client = mux::tei_multiplexer_client::TeiMultiplexerClient::new(channel);
+
+    client
+        .embed_arrow(mux::EmbedArrowRequest {
+            target: Some(mux::Target {
+                routing: Some(mux::target::Routing::InstanceName("small".to_string())),
+            }),
+            arrow_ipc: arrow_text_batch(vec!["a"; 8]),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    // grpc_max_parallel_streams (1024) would allow all 8 at once
+    assert_eq!(in_flight.1.load(std::sync::atomic::Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_embed_jsonl_dimensions_flow_to_backend() {
+    let (server, _temp_dir) = create_test_server().await;
+    let port = start_mock_embed_backend().await;
+
+    let response = server
+        .post("/instances")
+        .json(&json!({
+            "name": "jsonl-dims",
+            "model_id": "BAAI/bge-small-en-v1.5",
+            "port": port
+        }))
+        .await;
+    assert_eq!(response.status_code(), 201);
+
+    let body = [
+        r#"{"id": "a", "text": "ab", "dimensions": 3}"#,
+        r#"{"id": "b", "text": "abc"}"#,
+        r#"{"id": "c", "text": "abc", "dimensions": 0}"#,
+    ]
+    .join("\n");
+    let response = server
+        .post("/instances/jsonl-dims/embed/jsonl")
+        .content_type("application/x-ndjson")
+        .text(body)
+        .await;
+    assert_eq!(response.status_code(), 200);
+
+    let lines: Vec<serde_json::Value> = response
+        .text()
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(lines[0], json!({"id": "a", "embedding": [2.0, 2.0, 2.0]}));
+    assert_eq!(lines[1], json!({"id": "b", "embedding": [3.0]}));
+    assert_eq!(lines[2]["id"], "c");
+    assert!(lines[2]["error"].as_str().unwrap().contains("dimensions"));
+}
+
+#[tokio::test]
+async fn test_embed_jsonl_zero_request_timeout_disables_timeout() {
+    let (server, _temp_dir) = create_test_server_with_config(ManagerConfig {
+        grpc_request_timeout_secs: 0,
+        ..Default::default()
+    })
+    .await;
+    let port = start_mock_embed_backend().await;
+
+    let response = server
+        .post("/instances")
+        .json(&json!({
+            "name": "jsonl-no-timeout",
+            "model_id": "BAAI/bge-small-en-v1.5",
+            "port": port
+        }))
+        .await;
+    assert_eq!(response.status_code(), 201);
+
+    let response = server
+        .post("/instances/jsonl-no-timeout/embed/jsonl")
+        .content_type("application/x-ndjson")
+        .text(r#"{"id": "a", "text": "ab"}"#)
+        .await;
+    assert_eq!(response.status_code(), 200);
+
+    let line: serde_json::Value = serde_json::from_str(response.text().trim()).unwrap();
+    assert_eq!(line, json!({"id": "a", "embedding": [2.0]}));
+}
+
+#[tokio::test]
+async fn test_embed_jsonl_truncate_flows_to_backend() {
+    let (server, _temp_dir) = create_test_server().await;
+    let backend = MockEmbedBackend::default();
+    let seen = backend.seen_truncate.clone();
+    let port = serve_mock_embed_backend(tei::embed_server::EmbedServer::new(backend)).await;
+
+    let response = server
+        .post("/instances")
+        .json(&json!({
+            "name": "jsonl-truncate",
+            "model_id": "BAAI/bge-small-en-v1.5",
+            "port": port
+        }))
+        .await;
+    assert_eq!(response.status_code(), 201);
+
+    let body = [
+        r#"{"id": "a", "text": "a", "truncate": true}"#,
+        r#"{"id": "b", "text": "b"}"#,
+    ]
+    .join("\n");
+    let response = server
+        .post("/instances/jsonl-truncate/embed/jsonl")
+        .content_type("application/x-ndjson")
+        .text(body)
+        .await;
+    assert_eq!(response.status_code(), 200);
+    assert_eq!(response.text().lines().count(), 2);
+
+    let mut seen = seen.lock().unwrap().clone();
+    seen.sort();
+    assert_eq!(seen, vec![false, true]);
+}
+
+#[tokio::test]
+async fn test_events_replay_history_then_live() {
+    let (app, _temp_dir) = create_test_app(ManagerConfig {
+        max_instances: Some(10),
+        ..Default::default()
+    });
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await });
+    let client = reqwest::Client::new();
+
+    let create = |name: &'static str| {
+        client
+            .post(format!("{}/instances", base))
+            .json(&json!({"name": name, "model_id": "BAAI/bge-small-en-v1.5", "port": 0}))
+            .send()
+    };
+    assert_eq!(create("early").await.unwrap().status(), 201);
+
+    // Connects after "early" was added
+    let response = client
+        .get(format!("{}/events?replay=100", base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let mut body = response.bytes_stream();
+    let mut buffer = String::new();
+    let mut next_event = async || -> serde_json::Value {
+        loop {
+            if let Some(end) = buffer.find("\n\n") {
+                let frame: String = buffer.drain(..end + 2).collect();
+                if let Some(data) = frame.lines().find_map(|l| l.strip_prefix("data: ")) {
+                    return serde_json::from_str(data).unwrap();
+                }
+                continue;
+            }
+            let chunk = tokio::time::timeout(std::time::Duration::from_secs(5), body.next())
+                .await
+                .expect("no event received")
+                .unwrap()
+                .unwrap();
+            buffer.push_str(std::str::from_utf8(&chunk).unwrap());
+        }
+    };
+
+    assert_eq!(next_event().await, json!({"added": "early"}));
+
+    assert_eq!(create("late").await.unwrap().status(), 201);
+    loop {
+        if next_event().await == json!({"added": "late"}) {
+            break;
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_grpc_oversized_message_reports_limit() {
+    use tei_manager::grpc::status::error_info;
+
+    let backend_port = start_mock_embed_backend().await;
+    let registry = registry_with_mock_backend("bge", backend_port).await;
+    let channel = start_test_grpc_server(
+        registry,
+        &ManagerConfig {
+            grpc_max_message_size_mb: 1,
+            ..Default::default()
+        },
+    )
+    .await;
+    let mut client = mux::tei_multiplexer_client::TeiMultiplexerClient::new(channel);
+    let target = Some(mux::Target {
+        routing: Some(mux::target::Routing::InstanceName("bge".to_string())),
+    });
+
+    // An Arrow batch over the limit is rejected with the limit spelled out
+    let status = client
+        .embed_arrow(mux::EmbedArrowRequest {
+            target: target.clone(),
+            arrow_ipc: vec![0; 2 * 1024 * 1024],
+            ..Default::default()
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::ResourceExhausted);
+    assert!(status.message().contains("1MB"), "{}", status.message());
+    assert!(status.message().contains("grpc_max_message_size_mb"));
+    let info = error_info(&status).expect("ErrorInfo detail");
+    assert_eq!(info.reason, "PAYLOAD_TOO_LARGE");
+    assert_eq!(info.metadata["limit_mb"], "1");
+
+    // Same for the first message of a client stream
+    let request = mux::EmbedRequest {
+        target,
+        request: Some(tei::EmbedRequest {
+            inputs: "x".repeat(2 * 1024 * 1024),
+            ..Default::default()
+        }),
+    };
+    let status = client
+        .embed_stream(tokio_stream::iter(vec![request.clone()]))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::ResourceExhausted);
+    assert_eq!(error_info(&status).unwrap().reason, "PAYLOAD_TOO_LARGE");
+
+    // And for a later message: earlier messages are answered, then the stream fails
+    let small = mux::EmbedRequest {
+        request: Some(tei::EmbedRequest {
+            inputs: "abc".to_string(),
+            ..Default::default()
+        }),
+        ..request.clone()
+    };
+    let mut responses = client
+        .embed_stream(tokio_stream::iter(vec![small, request]))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(
+        responses.message().await.unwrap().unwrap().embeddings,
+        [3.0]
+    );
+    let status = responses.message().await.unwrap_err();
+    assert_eq!(status.code(), tonic::Code::ResourceExhausted);
+    assert!(status.message().contains("1MB"), "{}", status.message());
+    assert_eq!(error_info(&status).unwrap().reason, "PAYLOAD_TOO_LARGE");
+}
+
+/// Pins the wording of tonic's own decoding limit error, which the message
+/// size translation matches on
+#[tokio::test]
+async fn test_tonic_decode_limit_status_is_recognized() {
+    use tei_manager::grpc::message_size::{is_message_too_large, translate};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(
+        tonic::transport::Server::builder()
+            .add_service(
+                tei::embed_server::EmbedServer::new(MockEmbedBackend::default())
+                    .max_decoding_message_size(1024),
+            )
+            .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener)),
+    );
+    let mut client = tei::embed_client::EmbedClient::connect(format!("http://127.0.0.1:{port}"))
+        .await
+        .unwrap();
+
+    let status = client
+        .embed(tei::EmbedRequest {
+            inputs: "x".repeat(4096),
+            ..Default::default()
+        })
+        .await
+        .unwrap_err();
+    assert!(
+        is_message_too_large(&status),
+        "tonic changed its decode limit error: {:?}",
+        status
+    );
+    let translated = translate(status, 1);
+    assert_eq!(translated.code(), tonic::Code::ResourceExhausted);
+    assert!(
+        translated.message().starts_with("Request of 40"),
+        "{}",
+        translated.message()
+    );
+}
+
+#[tokio::test]
+async fn test_grpc_errors_carry_error_details() {
+    use tei_manager::grpc::status::{bad_request, error_info};
+
+    let backend_port = start_mock_embed_backend().await;
+    let registry = registry_with_mock_backend("bge", backend_port).await;
+    let channel = start_test_grpc_server(registry, &ManagerConfig::default()).await;
+    let mut client = mux::tei_multiplexer_client::TeiMultiplexerClient::new(channel);
+    let target = |name: &str| {
+        Some(mux::Target {
+            routing: Some(mux::target::Routing::InstanceName(name.to_string())),
+        })
+    };
+
+    // Unknown instance: ErrorInfo names it
+    let status = client
+        .embed(mux::EmbedRequest {
+            target: target("missing"),
+            request: Some(tei::EmbedRequest {
+                inputs: "hello".to_string(),
+                ..Default::default()
+            }),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::NotFound);
+    let info = error_info(&status).expect("ErrorInfo detail");
+    assert_eq!(info.reason, "INSTANCE_NOT_FOUND");
+    assert_eq!(info.domain, "tei-manager");
+    assert_eq!(info.metadata["instance"], "missing");
+
+    // Invalid argument: BadRequest points at the field
+    let status = client
+        .embed_arrow(mux::EmbedArrowRequest {
+            target: target("bge"),
+            arrow_ipc: vec![1, 2, 3],
+            ..Default::default()
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    assert_eq!(error_info(&status).unwrap().reason, "VALIDATION_ERROR");
+    let violations = bad_request(&status)
+        .expect("BadRequest detail")
+        .field_violations;
+    assert_eq!(violations[0].field, "arrow_ipc");
+    assert!(violations[0].description.starts_with("Invalid Arrow IPC"));
+
+    // Missing request body
+    let status = client
+        .embed(mux::EmbedRequest {
+            target: target("bge"),
+            request: None,
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(error_info(&status).unwrap().reason, "MISSING_FIELD");
+    assert_eq!(
+        bad_request(&status).unwrap().field_violations[0].field,
+        "request"
+    );
+}
+
+#[tokio::test]
+async fn test_allowed_models_restricts_creation() {
+    let (server, _temp_dir) = create_test_server_with_config(ManagerConfig {
+        max_instances: Some(10),
+        allowed_models: Some(vec!["BAAI/*".to_string(), "intfloat/e5-small".to_string()]),
+        ..Default::default()
+    })
+    .await;
+
+    // Glob and exact matches are accepted
+    for (name, model_id, port) in [
+        ("bge", "BAAI/bge-small-en-v1.5", 8080),
+        ("e5", "intfloat/e5-small", 8081),
+    ] {
+        let response = server
+            .post("/instances")
+            .json(&json!({"name": name, "model_id": model_id, "port": port}))
+            .await;
+        assert_eq!(response.status_code(), 201, "{}", model_id);
+    }
+
+    let response = server
+        .post("/instances")
+        .json(&json!({"name": "mini", "model_id": "sentence-transformers/all-MiniLM-L6-v2", "port": 8082}))
+        .await;
+    assert_eq!(response.status_code(), 403);
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["code"], "FORBIDDEN");
+    assert!(body["error"].as_str().unwrap().contains("allowed_models"));
+
+    // Imports are held to the same list, before anything is changed
+    let export = json!({"instances": [
+        {"name": "bge-2", "model_id": "BAAI/bge-base-en-v1.5", "port": 8090},
+        {"name": "e5-large", "model_id": "intfloat/e5-large", "port": 8091},
+    ]});
+    let response = server.post("/instances/import").json(&export).await;
+    assert_eq!(response.status_code(), 403);
+
+    let instances: Vec<serde_json::Value> = server.get("/instances").await.json();
+    assert_eq!(instances.len(), 2);
+}
+
+#[tokio::test]
+async fn test_routing_table_lists_model_instances() {
+    let (server, _temp_dir) = create_test_server_with_config(ManagerConfig {
+        max_instances: Some(10),
+        health_check_mode: HealthCheckMode::AlwaysHealthy,
+        ..Default::default()
+    })
+    .await;
+    running_mock_instance(&server, "bge-b", "BAAI/bge-small-en-v1.5").await;
+    running_mock_instance(&server, "bge-a", "BAAI/bge-small-en-v1.5").await;
+    running_mock_instance(&server, "e5", "intfloat/e5-small").await;
+
+    let body: serde_json::Value = server.get("/routing").await.json();
+    assert_eq!(body["strategy"], "round_robin");
+    assert_eq!(body["indices"], json!(["bge-a", "bge-b", "e5"]));
+
+    let bge = &body["models"]["BAAI/bge-small-en-v1.5"];
+    assert_eq!(bge.as_array().unwrap().len(), 2);
+    assert_eq!(bge[0]["instance"], "bge-a");
+    assert_eq!(bge[0]["index"], 0);
+    assert_eq!(bge[0]["status"], "running");
+    assert_eq!(bge[0]["routable"], true);
+    assert_eq!(bge[0]["weight"], 0.5);
+    assert_eq!(bge[1]["instance"], "bge-b");
+    assert_eq!(bge[1]["index"], 1);
+    assert_eq!(bge[1]["weight"], 0.5);
+    assert_eq!(body["models"]["intfloat/e5-small"][0]["index"], 2);
+
+    // A paused instance keeps its index but drops out of model routing
+    let response = server.post("/instances/bge-b/pause").await;
+    assert_eq!(response.status_code(), 200);
+    let body: serde_json::Value = server.get("/routing").await.json();
+    let bge = &body["models"]["BAAI/bge-small-en-v1.5"];
+    assert_eq!(bge[1]["paused"], true);
+    assert_eq!(bge[1]["routable"], false);
+    assert_eq!(bge[1]["weight"], 0.0);
+    assert_eq!(bge[0]["weight"], 1.0);
+    assert_eq!(body["indices"][1], "bge-b");
+}
@@ -9,8 +9,10 @@ use serde_json::json;
 use std::sync::Arc;
 use std::sync::OnceLock;
 use tei_manager::{
-    ModelLoader, ModelRegistry,
+    ModelLoader, ModelRegistry, PreloadTracker,
     api::routes::{AppState, create_router},
+    config::ManagerConfig,
+    grpc::pool::BackendPool,
     metrics,
     models::{get_model_cache_path, is_model_cached},
     registry::Registry,
@@ -57,8 +59,13 @@ async fn create_test_server() -> (TestServer, TempDir) {
 
     let model_registry = Arc::new(ModelRegistry::new());
     let model_loader = Arc::new(ModelLoader::new());
+    let preload_tracker = Arc::new(PreloadTracker::new());
 
     let state = AppState {
+        backend_pool: BackendPool::new(registry.clone()),
+        audit_sink: None,
+        access_log: None,
+        embedding_dimensions: Arc::new(tei_manager::models::CachedEmbeddingDimensions::default()),
         registry,
         state_manager,
         prometheus_handle: get_metrics_handle(),
@@ -66,6 +73,8 @@ async fn create_test_server() -> (TestServer, TempDir) {
         require_cert_headers: false,
         model_registry,
         model_loader,
+        preload_tracker,
+        config: Arc::new(ManagerConfig::default()),
     };
 
     let app = create_router(state);
@@ -329,11 +338,16 @@ async fn test_downloading_state_visible() {
     ));
     let model_registry = Arc::new(ModelRegistry::new());
     let model_loader = Arc::new(ModelLoader::new());
+    let preload_tracker = Arc::new(PreloadTracker::new());
 
     // Clone references for checking status
     let model_registry_check = model_registry.clone();
 
     let state = AppState {
+        backend_pool: BackendPool::new(registry.clone()),
+        audit_sink: None,
+        access_log: None,
+        embedding_dimensions: Arc::new(tei_manager::models::CachedEmbeddingDimensions::default()),
         registry,
         state_manager,
         prometheus_handle: get_metrics_handle(),
@@ -341,6 +355,8 @@ async fn test_downloading_state_visible() {
         require_cert_headers: false,
         model_registry,
         model_loader,
+        preload_tracker,
+        config: Arc::new(ManagerConfig::default()),
     };
 
     let app = create_router(state);
@@ -406,8 +422,13 @@ async fn test_concurrent_download_rejected() {
     let model_registry = Arc::new(ModelRegistry::new());
     let model_registry_ref = model_registry.clone();
     let model_loader = Arc::new(ModelLoader::new());
+    let preload_tracker = Arc::new(PreloadTracker::new());
 
     let state = AppState {
+        backend_pool: BackendPool::new(registry.clone()),
+        audit_sink: None,
+        access_log: None,
+        embedding_dimensions: Arc::new(tei_manager::models::CachedEmbeddingDimensions::default()),
         registry,
         state_manager,
         prometheus_handle: get_metrics_handle(),
@@ -415,6 +436,8 @@ async fn test_concurrent_download_rejected() {
         require_cert_headers: false,
         model_registry,
         model_loader,
+        preload_tracker,
+        config: Arc::new(ManagerConfig::default()),
     };
 
     let app = create_router(state);
@@ -4,25 +4,56 @@
 //! edge cases that might be missed by example-based testing.
 
 use proptest::prelude::*;
-use tei_manager::config::{InstanceConfig, ManagerConfig};
+use tei_manager::config::{Dtype, InstanceConfig, ManagerConfig, Pooling};
 
 // =============================================================================
 // Arbitrary Implementations
 // =============================================================================
 
+fn arb_pooling() -> impl Strategy<Value = Pooling> {
+    prop_oneof![
+        Just(Pooling::Mean),
+        Just(Pooling::Cls),
+        Just(Pooling::Splade),
+        Just(Pooling::LastToken),
+    ]
+}
+
+fn arb_dtype() -> impl Strategy<Value = Dtype> {
+    prop_oneof![
+        Just(Dtype::Float16),
+        Just(Dtype::Float32),
+        Just(Dtype::Bfloat16),
+    ]
+}
+
 /// Generate arbitrary InstanceConfig values
 fn arb_instance_config() -> impl Strategy<Value = InstanceConfig> {
     (
-        "[a-zA-Z][a-zA-Z0-9_-]{0,30}", // valid instance name
-        "[a-zA-Z0-9/-]{3,50}",         // model_id like "BAAI/bge-small"
-        1024u16..60000,                // port (valid range)
-        1024u32..65536,                // max_batch_tokens
-        1u32..1024,                    // max_concurrent_requests
-        prop::option::of("[a-z]+"),    // pooling
-        prop::option::of(0u32..8),     // gpu_id
+        "[a-zA-Z][a-zA-Z0-9_-]{0,30}",   // valid instance name
+        "[a-zA-Z0-9/-]{3,50}",           // model_id like "BAAI/bge-small"
+        1024u16..60000,                  // port (valid range)
+        1024u32..65536,                  // max_batch_tokens
+        1u32..1024,                      // max_concurrent_requests
+        prop::option::of(arb_pooling()), // pooling
+        prop::option::of(arb_dtype()),   // dtype
+        prop::option::of(0u32..8),       // gpu_id
+        prop::option::of("[0-9a-f]{7}"), // revision
+        prop::option::of("(error|warn|info|debug|trace)"), // log_level
     )
         .prop_map(
-            |(name, model_id, port, max_batch_tokens, max_concurrent_requests, pooling, gpu_id)| {
+            |(
+                name,
+                model_id,
+                port,
+                max_batch_tokens,
+                max_concurrent_requests,
+                pooling,
+                dtype,
+                gpu_id,
+                revision,
+                log_level,
+            )| {
                 InstanceConfig {
                     name,
                     model_id,
@@ -30,10 +61,23 @@ fn arb_instance_config() -> impl Strategy<Value = InstanceConfig> {
                     max_batch_tokens,
                     max_concurrent_requests,
                     pooling,
+                    dtype,
+                    revision,
                     gpu_id,
+                    cpu_only: false,
+                    gpu_memory_fraction: None,
+                    gpu_memory_bytes: None,
                     prometheus_port: None,
                     startup_timeout_secs: None,
+                    grpc_max_message_size_mb: None,
+                    pre_stop_command: None,
+                    log_level,
                     extra_args: Vec::new(),
+                    env: Default::default(),
+                    env_file: None,
+                    hf_home: None,
+                    depends_on: Vec::new(),
+                    metric_labels: Default::default(),
                     created_at: None,
                 }
             },